@@ -21,10 +21,38 @@ impl RuntimeFlavor {
     }
 }
 
+#[derive(Clone, Copy, PartialEq)]
+enum UnhandledPanic {
+    Ignore,
+    ShutdownRuntime,
+}
+
+impl UnhandledPanic {
+    fn from_str(s: &str) -> Result<UnhandledPanic, String> {
+        match s {
+            "ignore" => Ok(UnhandledPanic::Ignore),
+            "shutdown_runtime" => Ok(UnhandledPanic::ShutdownRuntime),
+            _ => Err(format!("No such unhandled panic behavior `{}`. The unhandled panic behaviors are `ignore` and `shutdown_runtime`.", s)),
+        }
+    }
+
+    fn into_tokens(self) -> proc_macro2::TokenStream {
+        match self {
+            UnhandledPanic::Ignore => quote! { tokio::runtime::UnhandledPanic::Ignore },
+            UnhandledPanic::ShutdownRuntime => {
+                quote! { tokio::runtime::UnhandledPanic::ShutdownRuntime }
+            }
+        }
+    }
+}
+
 struct FinalConfig {
     flavor: RuntimeFlavor,
     worker_threads: Option<usize>,
     start_paused: Option<bool>,
+    max_blocking_threads: Option<usize>,
+    unhandled_panic: Option<UnhandledPanic>,
+    builder_fn: Option<syn::Path>,
 }
 
 struct Configuration {
@@ -33,6 +61,9 @@ struct Configuration {
     flavor: Option<RuntimeFlavor>,
     worker_threads: Option<(usize, Span)>,
     start_paused: Option<(bool, Span)>,
+    max_blocking_threads: Option<(usize, Span)>,
+    unhandled_panic: Option<(UnhandledPanic, Span)>,
+    builder_fn: Option<(syn::Path, Span)>,
     is_test: bool,
 }
 
@@ -47,6 +78,9 @@ impl Configuration {
             flavor: None,
             worker_threads: None,
             start_paused: None,
+            max_blocking_threads: None,
+            unhandled_panic: None,
+            builder_fn: None,
             is_test,
         }
     }
@@ -93,6 +127,58 @@ impl Configuration {
         Ok(())
     }
 
+    fn set_max_blocking_threads(
+        &mut self,
+        max_blocking_threads: syn::Lit,
+        span: Span,
+    ) -> Result<(), syn::Error> {
+        if self.max_blocking_threads.is_some() {
+            return Err(syn::Error::new(
+                span,
+                "`max_blocking_threads` set multiple times.",
+            ));
+        }
+
+        let max_blocking_threads = parse_int(max_blocking_threads, span, "max_blocking_threads")?;
+        if max_blocking_threads == 0 {
+            return Err(syn::Error::new(span, "`max_blocking_threads` may not be 0."));
+        }
+        self.max_blocking_threads = Some((max_blocking_threads, span));
+        Ok(())
+    }
+
+    fn set_unhandled_panic(
+        &mut self,
+        unhandled_panic: syn::Lit,
+        span: Span,
+    ) -> Result<(), syn::Error> {
+        if self.unhandled_panic.is_some() {
+            return Err(syn::Error::new(span, "`unhandled_panic` set multiple times."));
+        }
+
+        let unhandled_panic_str = parse_string(unhandled_panic, span, "unhandled_panic")?;
+        let unhandled_panic = UnhandledPanic::from_str(&unhandled_panic_str)
+            .map_err(|err| syn::Error::new(span, err))?;
+        self.unhandled_panic = Some((unhandled_panic, span));
+        Ok(())
+    }
+
+    fn set_builder_fn(&mut self, builder_fn: syn::Lit, span: Span) -> Result<(), syn::Error> {
+        if self.builder_fn.is_some() {
+            return Err(syn::Error::new(span, "`builder` set multiple times."));
+        }
+
+        let builder_fn_str = parse_string(builder_fn, span, "builder")?;
+        let builder_fn = syn::parse_str::<syn::Path>(&builder_fn_str).map_err(|err| {
+            syn::Error::new(
+                span,
+                format!("Failed to parse value of `builder` as path: {}", err),
+            )
+        })?;
+        self.builder_fn = Some((builder_fn, span));
+        Ok(())
+    }
+
     fn macro_name(&self) -> &'static str {
         if self.is_test {
             "tokio::test"
@@ -139,10 +225,25 @@ impl Configuration {
             (_, None) => None,
         };
 
+        let unhandled_panic = match (flavor, self.unhandled_panic) {
+            (Threaded, Some((_, unhandled_panic_span))) => {
+                let msg = format!(
+                    "The `unhandled_panic` option requires the `current_thread` runtime flavor. Use `#[{}(flavor = \"current_thread\")]`",
+                    self.macro_name(),
+                );
+                return Err(syn::Error::new(unhandled_panic_span, msg));
+            }
+            (CurrentThread, Some((unhandled_panic, _))) => Some(unhandled_panic),
+            (_, None) => None,
+        };
+
         Ok(FinalConfig {
             flavor,
             worker_threads,
             start_paused,
+            max_blocking_threads: self.max_blocking_threads.map(|(val, _span)| val),
+            unhandled_panic,
+            builder_fn: self.builder_fn.clone().map(|(path, _span)| path),
         })
     }
 }
@@ -225,13 +326,31 @@ fn parse_knobs(
                             syn::spanned::Spanned::span(&namevalue.lit),
                         )?;
                     }
+                    "max_blocking_threads" => {
+                        config.set_max_blocking_threads(
+                            namevalue.lit.clone(),
+                            syn::spanned::Spanned::span(&namevalue.lit),
+                        )?;
+                    }
+                    "unhandled_panic" => {
+                        config.set_unhandled_panic(
+                            namevalue.lit.clone(),
+                            syn::spanned::Spanned::span(&namevalue.lit),
+                        )?;
+                    }
+                    "builder" => {
+                        config.set_builder_fn(
+                            namevalue.lit.clone(),
+                            syn::spanned::Spanned::span(&namevalue.lit),
+                        )?;
+                    }
                     "core_threads" => {
                         let msg = "Attribute `core_threads` is renamed to `worker_threads`";
                         return Err(syn::Error::new_spanned(namevalue, msg));
                     }
                     name => {
                         let msg = format!(
-                            "Unknown attribute {} is specified; expected one of: `flavor`, `worker_threads`, `start_paused`",
+                            "Unknown attribute {} is specified; expected one of: `flavor`, `worker_threads`, `start_paused`, `max_blocking_threads`, `unhandled_panic`, `builder`",
                             name,
                         );
                         return Err(syn::Error::new_spanned(namevalue, msg));
@@ -258,11 +377,12 @@ fn parse_knobs(
                             macro_name
                         )
                     }
-                    "flavor" | "worker_threads" | "start_paused" => {
+                    "flavor" | "worker_threads" | "start_paused" | "max_blocking_threads"
+                    | "unhandled_panic" | "builder" => {
                         format!("The `{}` attribute requires an argument.", name)
                     }
                     name => {
-                        format!("Unknown attribute {} is specified; expected one of: `flavor`, `worker_threads`, `start_paused`", name)
+                        format!("Unknown attribute {} is specified; expected one of: `flavor`, `worker_threads`, `start_paused`, `max_blocking_threads`, `unhandled_panic`, `builder`", name)
                     }
                 };
                 return Err(syn::Error::new_spanned(path, msg));
@@ -296,7 +416,7 @@ fn parse_knobs(
         (start, end)
     };
 
-    let mut rt = match config.flavor {
+    let rt = match config.flavor {
         RuntimeFlavor::CurrentThread => quote_spanned! {last_stmt_start_span=>
             tokio::runtime::Builder::new_current_thread()
         },
@@ -304,11 +424,25 @@ fn parse_knobs(
             tokio::runtime::Builder::new_multi_thread()
         },
     };
+
+    let mut cfg = quote! {};
     if let Some(v) = config.worker_threads {
-        rt = quote! { #rt.worker_threads(#v) };
+        cfg = quote! { #cfg builder.worker_threads(#v); };
     }
     if let Some(v) = config.start_paused {
-        rt = quote! { #rt.start_paused(#v) };
+        cfg = quote! { #cfg builder.start_paused(#v); };
+    }
+    if let Some(v) = config.max_blocking_threads {
+        cfg = quote! { #cfg builder.max_blocking_threads(#v); };
+    }
+    if let Some(v) = config.unhandled_panic {
+        let v = v.into_tokens();
+        cfg = quote! { #cfg builder.unhandled_panic(#v); };
+    }
+    // The user-provided customization function runs last so it can override
+    // any of the attribute-driven configuration.
+    if let Some(builder_fn) = &config.builder_fn {
+        cfg = quote! { #cfg #builder_fn(&mut builder); };
     }
 
     let header = if is_test {
@@ -323,9 +457,10 @@ fn parse_knobs(
     let brace_token = input.block.brace_token;
     input.block = syn::parse2(quote_spanned! {last_stmt_end_span=>
         {
-            #rt
-                .enable_all()
-                .build()
+            let mut builder = #rt;
+            builder.enable_all();
+            #cfg
+            builder.build()
                 .unwrap()
                 .block_on(async #body)
         }
@@ -169,6 +169,92 @@ use proc_macro::TokenStream;
 ///
 /// Note that `start_paused` requires the `test-util` feature to be enabled.
 ///
+/// ### Set the limit on blocking threads
+///
+/// ```rust
+/// #[tokio::main(max_blocking_threads = 16)]
+/// async fn main() {
+///     println!("Hello world");
+/// }
+/// ```
+///
+/// Equivalent code not using `#[tokio::main]`
+///
+/// ```rust
+/// fn main() {
+///     let mut builder = tokio::runtime::Builder::new_multi_thread();
+///     builder.enable_all();
+///     builder.max_blocking_threads(16);
+///     builder.build()
+///         .unwrap()
+///         .block_on(async {
+///             println!("Hello world");
+///         })
+/// }
+/// ```
+///
+/// ### Shut the runtime down when a task panics
+///
+/// ```rust
+/// #[tokio::main(flavor = "current_thread", unhandled_panic = "shutdown_runtime")]
+/// async fn main() {
+///     println!("Hello world");
+/// }
+/// ```
+///
+/// Equivalent code not using `#[tokio::main]`
+///
+/// ```rust
+/// fn main() {
+///     let mut builder = tokio::runtime::Builder::new_current_thread();
+///     builder.enable_all();
+///     builder.unhandled_panic(tokio::runtime::UnhandledPanic::ShutdownRuntime);
+///     builder.build()
+///         .unwrap()
+///         .block_on(async {
+///             println!("Hello world");
+///         })
+/// }
+/// ```
+///
+/// Note that `unhandled_panic` requires the `current_thread` runtime flavor.
+///
+/// ### Customize the builder with a function
+///
+/// For configuration that has no dedicated attribute, a function taking
+/// `&mut tokio::runtime::Builder` can be named with the `builder` option. It
+/// runs after all other attributes have been applied, just before the runtime
+/// is built.
+///
+/// ```rust
+/// fn customize(builder: &mut tokio::runtime::Builder) {
+///     builder.thread_name("my-custom-name");
+/// }
+///
+/// #[tokio::main(builder = "customize")]
+/// async fn main() {
+///     println!("Hello world");
+/// }
+/// ```
+///
+/// Equivalent code not using `#[tokio::main]`
+///
+/// ```rust
+/// # fn customize(builder: &mut tokio::runtime::Builder) {
+/// #     builder.thread_name("my-custom-name");
+/// # }
+/// fn main() {
+///     let mut builder = tokio::runtime::Builder::new_multi_thread();
+///     builder.enable_all();
+///     customize(&mut builder);
+///     builder.build()
+///         .unwrap()
+///         .block_on(async {
+///             println!("Hello world");
+///         })
+/// }
+/// ```
+///
 /// ### NOTE:
 ///
 /// If you rename the Tokio crate in your dependencies this macro will not work.
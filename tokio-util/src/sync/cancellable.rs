@@ -0,0 +1,175 @@
+//! Cancellation-aware acquisition of Tokio's synchronization primitives.
+//!
+//! These functions race an acquire operation against a
+//! [`CancellationToken`], completing with a distinct error the moment the
+//! token is cancelled. Compared to wrapping every acquire in `select!`, the
+//! waker plumbing and drop behavior live in one place: when the token fires,
+//! the pending acquire future is dropped, which removes its waiter from the
+//! primitive's queue without leaking permits.
+
+use crate::sync::CancellationToken;
+
+use tokio::sync::{
+    Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard, Semaphore, SemaphorePermit,
+};
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::error::Error;
+use std::fmt;
+
+/// Error returned when a cancellation-aware operation is interrupted by its
+/// [`CancellationToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled(pub(super) ());
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "operation cancelled")
+    }
+}
+
+impl Error for Cancelled {}
+
+/// Error returned by [`acquire_cancellable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AcquireCancellableError {
+    /// The [`CancellationToken`] was cancelled before a permit was acquired.
+    Cancelled,
+
+    /// The semaphore was closed while waiting for a permit.
+    Closed,
+}
+
+impl fmt::Display for AcquireCancellableError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AcquireCancellableError::Cancelled => write!(fmt, "operation cancelled"),
+            AcquireCancellableError::Closed => write!(fmt, "semaphore closed"),
+        }
+    }
+}
+
+impl Error for AcquireCancellableError {}
+
+/// Acquires `n` permits from the semaphore, unless the token is cancelled
+/// first.
+///
+/// Completes with [`AcquireCancellableError::Cancelled`] the moment `token`
+/// fires; the waiter is removed from the semaphore's queue and no permits are
+/// leaked. If the token is already cancelled, no permits are acquired.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::Semaphore;
+/// use tokio_util::sync::{cancellable, CancellationToken};
+///
+/// # #[tokio::main(flavor = "current_thread")] async fn main() {
+/// let semaphore = Semaphore::new(2);
+/// let token = CancellationToken::new();
+///
+/// let permit = cancellable::acquire_cancellable(&semaphore, 2, &token)
+///     .await
+///     .unwrap();
+///
+/// // All permits are held; a second acquire waits until the token fires.
+/// token.cancel();
+///
+/// let err = cancellable::acquire_cancellable(&semaphore, 1, &token)
+///     .await
+///     .unwrap_err();
+/// assert_eq!(err, cancellable::AcquireCancellableError::Cancelled);
+///
+/// drop(permit);
+/// # }
+/// ```
+pub async fn acquire_cancellable<'a>(
+    semaphore: &'a Semaphore,
+    n: u32,
+    token: &CancellationToken,
+) -> Result<SemaphorePermit<'a>, AcquireCancellableError> {
+    match with_cancellation(semaphore.acquire_many(n), token).await {
+        Ok(Ok(permit)) => Ok(permit),
+        Ok(Err(_)) => Err(AcquireCancellableError::Closed),
+        Err(Cancelled(())) => Err(AcquireCancellableError::Cancelled),
+    }
+}
+
+/// Locks the mutex, unless the token is cancelled first.
+///
+/// Completes with [`Cancelled`] the moment `token` fires; the waiter is
+/// removed from the mutex's queue. If the token is already cancelled, the
+/// lock is not acquired.
+pub async fn lock_cancellable<'a, T>(
+    mutex: &'a Mutex<T>,
+    token: &CancellationToken,
+) -> Result<MutexGuard<'a, T>, Cancelled> {
+    with_cancellation(mutex.lock(), token).await
+}
+
+/// Acquires a read lock, unless the token is cancelled first.
+///
+/// Completes with [`Cancelled`] the moment `token` fires; the waiter is
+/// removed from the lock's queue.
+pub async fn read_cancellable<'a, T>(
+    rwlock: &'a RwLock<T>,
+    token: &CancellationToken,
+) -> Result<RwLockReadGuard<'a, T>, Cancelled> {
+    with_cancellation(rwlock.read(), token).await
+}
+
+/// Acquires a write lock, unless the token is cancelled first.
+///
+/// Completes with [`Cancelled`] the moment `token` fires; the waiter is
+/// removed from the lock's queue.
+pub async fn write_cancellable<'a, T>(
+    rwlock: &'a RwLock<T>,
+    token: &CancellationToken,
+) -> Result<RwLockWriteGuard<'a, T>, Cancelled> {
+    with_cancellation(rwlock.write(), token).await
+}
+
+fn with_cancellation<'a, F: Future>(
+    future: F,
+    token: &'a CancellationToken,
+) -> WithCancellation<'a, F> {
+    WithCancellation {
+        future,
+        cancelled: token.cancelled(),
+    }
+}
+
+/// Future racing an acquire operation against cancellation.
+///
+/// Cancellation wins ties: the token is polled first, so an acquire that
+/// becomes ready in the same wakeup as the cancellation is dropped and its
+/// resources released.
+struct WithCancellation<'a, F> {
+    future: F,
+    cancelled: crate::sync::WaitForCancellationFuture<'a>,
+}
+
+impl<'a, F: Future> Future for WithCancellation<'a, F> {
+    type Output = Result<F::Output, Cancelled>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Safety: both fields are structurally pinned. Neither is moved out
+        // of `self`, and `WithCancellation` has no `Drop` implementation of
+        // its own.
+        let (future, cancelled) = unsafe {
+            let this = self.get_unchecked_mut();
+            (
+                Pin::new_unchecked(&mut this.future),
+                Pin::new_unchecked(&mut this.cancelled),
+            )
+        };
+
+        if cancelled.poll(cx).is_ready() {
+            return Poll::Ready(Err(Cancelled(())));
+        }
+
+        future.poll(cx).map(Ok)
+    }
+}
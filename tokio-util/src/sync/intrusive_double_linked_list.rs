@@ -137,6 +137,7 @@ impl<T> LinkedList<T> {
     /// the linked list.
     /// The returned pointer is only guaranteed to be valid as long as the list
     /// is not mutated
+    #[allow(clippy::mut_from_ref)]
     pub fn peek_first(&self) -> Option<&mut ListNode<T>> {
         // Safety: When the node was inserted it was promised that it is alive
         // until it gets removed from the list.
@@ -154,6 +155,7 @@ impl<T> LinkedList<T> {
     /// the linked list.
     /// The returned pointer is only guaranteed to be valid as long as the list
     /// is not mutated
+    #[allow(clippy::mut_from_ref)]
     pub fn peek_last(&self) -> Option<&mut ListNode<T>> {
         // Safety: When the node was inserted it was promised that it is alive
         // until it gets removed from the list.
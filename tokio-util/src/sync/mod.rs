@@ -1,5 +1,7 @@
 //! Synchronization primitives
 
+pub mod cancellable;
+
 mod cancellation_token;
 pub use cancellation_token::{CancellationToken, WaitForCancellationFuture};
 
@@ -0,0 +1,138 @@
+#![warn(rust_2018_idioms)]
+
+use tokio::sync::{Mutex, RwLock, Semaphore};
+use tokio_test::task::spawn;
+use tokio_test::{assert_pending, assert_ready, assert_ready_err, assert_ready_ok};
+use tokio_util::sync::cancellable::{
+    acquire_cancellable, lock_cancellable, read_cancellable, write_cancellable,
+    AcquireCancellableError,
+};
+use tokio_util::sync::CancellationToken;
+
+#[test]
+fn acquire_uncontended() {
+    let semaphore = Semaphore::new(2);
+    let token = CancellationToken::new();
+
+    let mut acquire = spawn(acquire_cancellable(&semaphore, 2, &token));
+    let permit = assert_ready_ok!(acquire.poll());
+
+    assert_eq!(semaphore.available_permits(), 0);
+    drop(permit);
+    assert_eq!(semaphore.available_permits(), 2);
+}
+
+#[test]
+fn acquire_cancelled_while_waiting() {
+    let semaphore = Semaphore::new(1);
+    let token = CancellationToken::new();
+
+    let permit = semaphore.try_acquire().unwrap();
+
+    let mut acquire = spawn(acquire_cancellable(&semaphore, 1, &token));
+    assert_pending!(acquire.poll());
+
+    token.cancel();
+
+    assert!(acquire.is_woken());
+    let err = assert_ready_err!(acquire.poll());
+    assert_eq!(err, AcquireCancellableError::Cancelled);
+    drop(acquire);
+
+    // The cancelled waiter released its queue slot: the permit comes back in
+    // full once the holder drops it.
+    drop(permit);
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[test]
+fn acquire_already_cancelled() {
+    let semaphore = Semaphore::new(1);
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let mut acquire = spawn(acquire_cancellable(&semaphore, 1, &token));
+    let err = assert_ready_err!(acquire.poll());
+    assert_eq!(err, AcquireCancellableError::Cancelled);
+
+    // No permit was consumed.
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[test]
+fn acquire_closed() {
+    let semaphore = Semaphore::new(0);
+    let token = CancellationToken::new();
+
+    let mut acquire = spawn(acquire_cancellable(&semaphore, 1, &token));
+    assert_pending!(acquire.poll());
+
+    semaphore.close();
+
+    let err = assert_ready_err!(acquire.poll());
+    assert_eq!(err, AcquireCancellableError::Closed);
+}
+
+#[test]
+fn lock_cancelled_while_waiting() {
+    let mutex = Mutex::new(0);
+    let token = CancellationToken::new();
+
+    let guard = mutex.try_lock().unwrap();
+
+    let mut lock = spawn(lock_cancellable(&mutex, &token));
+    assert_pending!(lock.poll());
+
+    token.cancel();
+
+    assert!(lock.is_woken());
+    assert_ready_err!(lock.poll());
+    drop(lock);
+
+    // The waiter was dequeued; the mutex is free again after the holder
+    // releases it.
+    drop(guard);
+    assert!(mutex.try_lock().is_ok());
+}
+
+#[tokio::test]
+async fn rwlock_cancellable() {
+    let rwlock = RwLock::new(0);
+    let token = CancellationToken::new();
+
+    {
+        let read = read_cancellable(&rwlock, &token).await.unwrap();
+        assert_eq!(*read, 0);
+    }
+
+    {
+        let mut write = write_cancellable(&rwlock, &token).await.unwrap();
+        *write = 1;
+    }
+
+    let read = rwlock.read().await;
+
+    let mut write = spawn(write_cancellable(&rwlock, &token));
+    assert_pending!(write.poll());
+
+    token.cancel();
+    assert!(write.is_woken());
+    assert_ready_err!(write.poll());
+    drop(write);
+
+    assert_eq!(*read, 1);
+}
+
+#[test]
+fn cancellation_wins_ties() {
+    let semaphore = Semaphore::new(1);
+    let token = CancellationToken::new();
+    token.cancel();
+
+    // Even though a permit is immediately available, a cancelled token takes
+    // priority and no permit is consumed.
+    let mut acquire = spawn(acquire_cancellable(&semaphore, 1, &token));
+    let err = assert_ready!(acquire.poll()).unwrap_err();
+    assert_eq!(err, AcquireCancellableError::Cancelled);
+    assert_eq!(semaphore.available_permits(), 1);
+}
@@ -61,11 +61,13 @@ impl Budget {
     }
 }
 
-cfg_rt_multi_thread! {
-    impl Budget {
-        fn has_remaining(self) -> bool {
-            self.0.map(|budget| budget > 0).unwrap_or(true)
-        }
+impl Budget {
+    fn has_remaining(self) -> bool {
+        self.0.map(|budget| budget > 0).unwrap_or(true)
+    }
+
+    fn remaining(self) -> Option<u8> {
+        self.0
     }
 }
 
@@ -112,11 +114,33 @@ cfg_rt_multi_thread! {
     pub(crate) fn set(budget: Budget) {
         CURRENT.with(|cell| cell.set(budget))
     }
+}
 
-    #[inline(always)]
-    pub(crate) fn has_budget_remaining() -> bool {
-        CURRENT.with(|cell| cell.get().has_remaining())
-    }
+/// Returns `true` if the current task may still perform work before yielding
+/// back to the scheduler.
+///
+/// This is `true` whenever the budget is unconstrained, such as when called
+/// from outside of a task running on a Tokio runtime. Libraries implementing
+/// their own leaf futures can use this to participate in cooperative
+/// scheduling: when no budget remains, the future should register the current
+/// waker, wake it, and return `Poll::Pending` to yield back to the scheduler.
+///
+/// See also [`budget_remaining`], which exposes the remaining budget itself.
+#[inline(always)]
+pub fn has_budget_remaining() -> bool {
+    CURRENT.with(|cell| cell.get().has_remaining())
+}
+
+/// Returns the number of budget units the current task has left, or `None` if
+/// the budget is unconstrained.
+///
+/// The budget is decremented each time a Tokio resource makes progress, and
+/// the task is expected to yield back to the scheduler once it reaches zero.
+/// The absolute value is an implementation detail and may change; it should
+/// only be used for comparisons or diagnostics.
+#[inline(always)]
+pub fn budget_remaining() -> Option<u8> {
+    CURRENT.with(|cell| cell.get().remaining())
 }
 
 cfg_rt! {
@@ -215,6 +239,17 @@ mod test {
         CURRENT.with(|cell| cell.get())
     }
 
+    #[test]
+    fn budget_introspection() {
+        assert!(has_budget_remaining());
+        assert_eq!(budget_remaining(), None);
+
+        budget(|| {
+            assert!(has_budget_remaining());
+            assert_eq!(budget_remaining(), Budget::initial().0);
+        });
+    }
+
     #[test]
     fn bugeting() {
         use futures::future::poll_fn;
@@ -4,6 +4,8 @@
 
 use self::State::*;
 use crate::fs::{asyncify, sys};
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+use crate::fs::uring;
 use crate::io::blocking::Buf;
 use crate::io::{AsyncRead, AsyncSeek, AsyncWrite, ReadBuf};
 use crate::sync::Mutex;
@@ -96,7 +98,66 @@ struct Inner {
 #[derive(Debug)]
 enum State {
     Idle(Option<Buf>),
-    Busy(sys::Blocking<(Operation, Buf)>),
+    Busy(OpFuture),
+}
+
+/// An in-flight file operation: a closure on the blocking pool, or an
+/// operation submitted to io_uring when that backend is available.
+enum OpFuture {
+    Blocking(sys::Blocking<(Operation, Buf)>),
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    UringRead(uring::Op<uring::FileData>),
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    UringWrite(uring::Op<uring::FileData>),
+}
+
+impl Future for OpFuture {
+    type Output = io::Result<(Operation, Buf)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.get_mut() {
+            OpFuture::Blocking(rx) => Pin::new(rx).poll(cx).map_err(io::Error::from),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            OpFuture::UringRead(op) => {
+                let (res, (mut buf, _std)) = ready!(Pin::new(op).poll(cx));
+                buf.complete_read(&res);
+                Ready(Ok((Operation::Read(res.map(|n| n as usize)), buf)))
+            }
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            OpFuture::UringWrite(op) => loop {
+                let (res, (mut buf, std)) = ready!(Pin::new(&mut *op).poll(cx));
+
+                match res {
+                    // A short write; submit the remainder. Polling the new
+                    // operation registers the waker again.
+                    Ok(n) if (n as usize) < buf.len() => {
+                        buf.advance(n as usize);
+                        *op = uring::write(std, buf);
+                    }
+                    Ok(_) => {
+                        buf.reset();
+                        return Ready(Ok((Operation::Write(Ok(())), buf)));
+                    }
+                    Err(e) => {
+                        buf.reset();
+                        return Ready(Ok((Operation::Write(Err(e)), buf)));
+                    }
+                }
+            },
+        }
+    }
+}
+
+impl fmt::Debug for OpFuture {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpFuture::Blocking(rx) => fmt.debug_tuple("Blocking").field(rx).finish(),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            OpFuture::UringRead(_) => fmt.debug_tuple("UringRead").finish(),
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            OpFuture::UringWrite(_) => fmt.debug_tuple("UringWrite").finish(),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -142,6 +203,13 @@ impl File {
     /// [`AsyncReadExt`]: trait@crate::io::AsyncReadExt
     pub async fn open(path: impl AsRef<Path>) -> io::Result<File> {
         let path = path.as_ref().to_owned();
+
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        if uring::driver().is_some() {
+            let std = uring::open(&path, libc::O_RDONLY | libc::O_CLOEXEC, 0).await?;
+            return Ok(File::from_std(std));
+        }
+
         let std = asyncify(|| sys::File::open(path)).await?;
 
         Ok(File::from_std(std))
@@ -182,6 +250,14 @@ impl File {
     /// [`AsyncWriteExt`]: trait@crate::io::AsyncWriteExt
     pub async fn create(path: impl AsRef<Path>) -> io::Result<File> {
         let path = path.as_ref().to_owned();
+
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        if uring::driver().is_some() {
+            let flags = libc::O_WRONLY | libc::O_CREAT | libc::O_TRUNC | libc::O_CLOEXEC;
+            let std = uring::open(&path, flags, 0o666).await?;
+            return Ok(File::from_std(std));
+        }
+
         let std_file = asyncify(move || sys::File::create(path)).await?;
         Ok(File::from_std(std_file))
     }
@@ -238,6 +314,12 @@ impl File {
         inner.complete_inflight().await;
 
         let std = self.std.clone();
+
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        if uring::driver().is_some() {
+            return uring::fsync(std, false).await;
+        }
+
         asyncify(move || std.sync_all()).await
     }
 
@@ -273,6 +355,12 @@ impl File {
         inner.complete_inflight().await;
 
         let std = self.std.clone();
+
+        #[cfg(all(target_os = "linux", feature = "io-uring"))]
+        if uring::driver().is_some() {
+            return uring::fsync(std, true).await;
+        }
+
         asyncify(move || std.sync_data()).await
     }
 
@@ -323,7 +411,7 @@ impl File {
 
         let std = self.std.clone();
 
-        inner.state = Busy(sys::run(move || {
+        inner.state = Busy(OpFuture::Blocking(sys::run(move || {
             let res = if let Some(seek) = seek {
                 (&*std).seek(seek).and_then(|_| std.set_len(size))
             } else {
@@ -333,7 +421,7 @@ impl File {
 
             // Return the result as a seek
             (Operation::Seek(res), buf)
-        }));
+        })));
 
         let (op, buf) = match inner.state {
             Idle(_) => unreachable!(),
@@ -350,6 +438,96 @@ impl File {
         }
     }
 
+    /// Reads bytes at the given offset, without using or altering the seek
+    /// position.
+    ///
+    /// Returns the number of bytes read, which may be less than the length of
+    /// `buf` if the offset is near the end of the file. This uses `pread` on
+    /// Unix and positioned (overlapped) reads on Windows, so multiple tasks
+    /// can read from one file handle concurrently without serializing on the
+    /// seek position.
+    ///
+    /// Any operation in flight on the file's internal buffer is completed
+    /// first, as with [`sync_all`](Self::sync_all).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio::fs::File;
+    ///
+    /// # async fn dox() -> std::io::Result<()> {
+    /// let file = File::open("foo.txt").await?;
+    ///
+    /// let mut buf = [0; 16];
+    /// let n = file.read_at(&mut buf, 1024).await?;
+    ///
+    /// println!("read {} bytes", n);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(unix, windows))]
+    pub async fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        {
+            let mut inner = self.inner.lock().await;
+            inner.complete_inflight().await;
+        }
+
+        let std = self.std.clone();
+        let len = buf.len();
+
+        let tmp = asyncify(move || {
+            let mut tmp = vec![0; len];
+            let n = read_at_blocking(&std, &mut tmp, offset)?;
+            tmp.truncate(n);
+            Ok(tmp)
+        })
+        .await?;
+
+        buf[..tmp.len()].copy_from_slice(&tmp);
+        Ok(tmp.len())
+    }
+
+    /// Writes bytes at the given offset, without using or altering the seek
+    /// position.
+    ///
+    /// Returns the number of bytes written, which may be less than the length
+    /// of `buf`. This uses `pwrite` on Unix and positioned (overlapped)
+    /// writes on Windows, so multiple tasks can write to one file handle
+    /// concurrently without serializing on the seek position.
+    ///
+    /// Any operation in flight on the file's internal buffer is completed
+    /// first, as with [`sync_all`](Self::sync_all).
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the file is not opened for
+    /// writing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio::fs::OpenOptions;
+    ///
+    /// # async fn dox() -> std::io::Result<()> {
+    /// let file = OpenOptions::new().write(true).open("foo.txt").await?;
+    ///
+    /// file.write_at(b"hello, world!", 1024).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(any(unix, windows))]
+    pub async fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+        {
+            let mut inner = self.inner.lock().await;
+            inner.complete_inflight().await;
+        }
+
+        let std = self.std.clone();
+        let buf = buf.to_vec();
+
+        asyncify(move || write_at_blocking(&std, &buf, offset)).await
+    }
+
     /// Queries metadata about the underlying file.
     ///
     /// # Examples
@@ -506,10 +684,16 @@ impl AsyncRead for File {
                     buf.ensure_capacity_for(dst);
                     let std = me.std.clone();
 
-                    inner.state = Busy(sys::run(move || {
+                    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+                    if uring::driver().is_some() {
+                        inner.state = Busy(OpFuture::UringRead(uring::read(std, buf)));
+                        continue;
+                    }
+
+                    inner.state = Busy(OpFuture::Blocking(sys::run(move || {
                         let res = buf.read_from(&mut &*std);
                         (Operation::Read(res), buf)
-                    }));
+                    })));
                 }
                 Busy(ref mut rx) => {
                     let (op, mut buf) = ready!(Pin::new(rx).poll(cx))?;
@@ -552,6 +736,7 @@ impl AsyncRead for File {
 }
 
 impl AsyncSeek for File {
+    #[allow(clippy::never_loop)]
     fn start_seek(self: Pin<&mut Self>, mut pos: SeekFrom) -> io::Result<()> {
         let me = self.get_mut();
         let inner = me.inner.get_mut();
@@ -573,10 +758,10 @@ impl AsyncSeek for File {
 
                     let std = me.std.clone();
 
-                    inner.state = Busy(sys::run(move || {
+                    inner.state = Busy(OpFuture::Blocking(sys::run(move || {
                         let res = (&*std).seek(pos);
                         (Operation::Seek(res), buf)
-                    }));
+                    })));
                     return Ok(());
                 }
             }
@@ -640,7 +825,13 @@ impl AsyncWrite for File {
                     let n = buf.copy_from(src);
                     let std = me.std.clone();
 
-                    inner.state = Busy(sys::run(move || {
+                    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+                    if seek.is_none() && uring::driver().is_some() {
+                        inner.state = Busy(OpFuture::UringWrite(uring::write(std, buf)));
+                        return Ready(Ok(n));
+                    }
+
+                    inner.state = Busy(OpFuture::Blocking(sys::run(move || {
                         let res = if let Some(seek) = seek {
                             (&*std).seek(seek).and_then(|_| buf.write_to(&mut &*std))
                         } else {
@@ -648,7 +839,7 @@ impl AsyncWrite for File {
                         };
 
                         (Operation::Write(res), buf)
-                    }));
+                    })));
 
                     return Ready(Ok(n));
                 }
@@ -761,6 +952,30 @@ impl Inner {
     }
 }
 
+#[cfg(unix)]
+fn read_at_blocking(file: &sys::File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    use std::os::unix::fs::FileExt;
+    file.read_at(buf, offset)
+}
+
+#[cfg(windows)]
+fn read_at_blocking(file: &sys::File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    use std::os::windows::fs::FileExt;
+    file.seek_read(buf, offset)
+}
+
+#[cfg(unix)]
+fn write_at_blocking(file: &sys::File, buf: &[u8], offset: u64) -> io::Result<usize> {
+    use std::os::unix::fs::FileExt;
+    file.write_at(buf, offset)
+}
+
+#[cfg(windows)]
+fn write_at_blocking(file: &sys::File, buf: &[u8], offset: u64) -> io::Result<usize> {
+    use std::os::windows::fs::FileExt;
+    file.seek_write(buf, offset)
+}
+
 #[cfg(all(target_os = "linux", not(test)))]
 pub(crate) fn try_nonblocking_read(
     file: &crate::fs::sys::File,
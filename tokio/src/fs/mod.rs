@@ -39,6 +39,10 @@ pub use self::dir_builder::DirBuilder;
 mod file;
 pub use self::file::File;
 
+cfg_io_uring! {
+    pub(crate) mod uring;
+}
+
 mod hard_link;
 pub use self::hard_link::hard_link;
 
@@ -275,3 +275,16 @@ impl DirEntry {
         &self.0
     }
 }
+
+cfg_stream! {
+    impl futures_core::Stream for ReadDir {
+        type Item = io::Result<DirEntry>;
+
+        fn poll_next(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<io::Result<DirEntry>>> {
+            self.poll_next_entry(cx).map(Result::transpose)
+        }
+    }
+}
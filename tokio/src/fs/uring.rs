@@ -0,0 +1,340 @@
+//! io_uring-backed file operations.
+//!
+//! When the `io-uring` feature is enabled and the running kernel supports
+//! the required operations, the reads, writes, opens and fsyncs that
+//! [`File`](crate::fs::File) would otherwise run on the blocking pool are
+//! submitted to a shared io_uring instead, leaving the pool free for work
+//! that genuinely needs a thread.
+//!
+//! A single ring is shared by all files. Submissions are serialized by a
+//! mutex; completions are reaped by one dedicated thread that parks in
+//! `io_uring_enter` and wakes the tasks owning the finished operations.
+//! If the ring cannot be created — old kernel, seccomp — [`driver`] returns
+//! `None` and callers fall back to the blocking pool.
+
+use crate::io::blocking::Buf;
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::fs::File as StdFile;
+use std::future::Future;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use iou::{opcode, squeue, types, IoUring, Probe};
+use once_cell::sync::OnceCell;
+
+/// Number of submission queue entries; in-flight operations beyond this are
+/// flushed to the kernel eagerly rather than queued.
+const NUM_ENTRIES: u32 = 256;
+
+static DRIVER: OnceCell<Option<Driver>> = OnceCell::new();
+
+/// Returns the shared io_uring driver, or `None` if the kernel does not
+/// support the operations the file implementation needs.
+pub(crate) fn driver() -> Option<&'static Driver> {
+    DRIVER.get_or_init(|| Driver::new().ok()).as_ref()
+}
+
+/// The buffer and file handle owned by an in-flight file operation.
+///
+/// Both must stay alive until the kernel completes the operation: the
+/// kernel writes into the buffer and the file descriptor must not be
+/// closed under it.
+pub(crate) type FileData = (Buf, Arc<StdFile>);
+
+pub(crate) struct Driver {
+    ring: &'static IoUring,
+    inner: Arc<Mutex<Inner>>,
+}
+
+struct Inner {
+    next_id: u64,
+    ops: HashMap<u64, Lifecycle>,
+}
+
+enum Lifecycle {
+    /// The operation is in flight and has not been polled yet.
+    Submitted,
+    /// The operation is in flight and this task is waiting for it.
+    Waiting(Waker),
+    /// The operation completed with this raw `cqe` result.
+    Completed(i32),
+    /// The future was dropped mid-flight; the resources the kernel may
+    /// still touch are parked here until the completion arrives.
+    Ignored(Box<dyn Any + Send>),
+}
+
+impl Driver {
+    fn new() -> io::Result<Driver> {
+        let ring = IoUring::new(NUM_ENTRIES)?;
+
+        // Setup succeeding does not imply the opcodes we need are there;
+        // probe for them so old kernels fall back to the blocking pool.
+        let mut probe = Probe::new();
+        ring.submitter().register_probe(&mut probe)?;
+
+        for &code in &[
+            opcode::OpenAt::CODE,
+            opcode::Read::CODE,
+            opcode::Write::CODE,
+            opcode::Fsync::CODE,
+        ] {
+            if !probe.is_supported(code) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "kernel does not support the required io_uring operations",
+                ));
+            }
+        }
+
+        let ring: &'static IoUring = Box::leak(Box::new(ring));
+
+        let inner = Arc::new(Mutex::new(Inner {
+            next_id: 0,
+            ops: HashMap::new(),
+        }));
+
+        let reaper_inner = inner.clone();
+        thread::Builder::new()
+            .name("tokio-io-uring".to_string())
+            .spawn(move || reap(ring, reaper_inner))?;
+
+        Ok(Driver { ring, inner })
+    }
+
+    /// Queues an operation with the kernel, transferring ownership of the
+    /// resources it touches to the returned future.
+    fn submit<D: Send + 'static>(&'static self, entry: squeue::Entry, data: D) -> Op<D> {
+        let mut inner = self.inner.lock().unwrap();
+
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.ops.insert(id, Lifecycle::Submitted);
+
+        let entry = entry.user_data(id);
+        let mut failed = None;
+
+        // Safety: the `inner` lock serializes all submission queue access;
+        // the reaper thread only touches the completion side.
+        unsafe {
+            let mut sq = self.ring.submission_shared();
+
+            while sq.push(&entry).is_err() {
+                // The queue is full; flush it to the kernel and retry.
+                sq.sync();
+
+                if let Err(e) = self.ring.submit() {
+                    failed = Some(e);
+                    break;
+                }
+
+                sq.sync();
+            }
+        }
+
+        if failed.is_none() {
+            if let Err(e) = self.ring.submit() {
+                failed = Some(e);
+            }
+        }
+
+        // If the entry never reached the kernel, complete the operation
+        // here so the future resolves with the error instead of hanging.
+        if let Some(e) = failed {
+            let res = -e.raw_os_error().unwrap_or(libc::EIO);
+            inner.ops.insert(id, Lifecycle::Completed(res));
+        }
+
+        Op {
+            driver: self,
+            id,
+            data: Some(data),
+        }
+    }
+}
+
+/// Drains completions, parking in `io_uring_enter` while none are pending.
+fn reap(ring: &'static IoUring, inner: Arc<Mutex<Inner>>) {
+    loop {
+        match ring.submitter().submit_and_wait(1) {
+            Ok(_) => {}
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(ref e) if e.raw_os_error() == Some(libc::EBUSY) => {}
+            // The ring is broken; in-flight operations cannot complete.
+            Err(_) => return,
+        }
+
+        // Safety: this thread is the only consumer of the completion queue.
+        let cq = unsafe { ring.completion_shared() };
+
+        for cqe in cq {
+            let id = cqe.user_data();
+            let res = cqe.result();
+
+            let waker = {
+                let mut inner = inner.lock().unwrap();
+
+                match inner.ops.remove(&id) {
+                    Some(Lifecycle::Submitted) => {
+                        inner.ops.insert(id, Lifecycle::Completed(res));
+                        None
+                    }
+                    Some(Lifecycle::Waiting(waker)) => {
+                        inner.ops.insert(id, Lifecycle::Completed(res));
+                        Some(waker)
+                    }
+                    // The future was dropped; release its resources now
+                    // that the kernel is done with them.
+                    Some(Lifecycle::Ignored(data)) => {
+                        drop(data);
+                        None
+                    }
+                    Some(Lifecycle::Completed(_)) | None => None,
+                }
+            };
+
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// An operation submitted to the ring.
+///
+/// Resolves to the raw completion result paired with the resources the
+/// operation owned. Dropping the future before completion parks those
+/// resources with the driver instead of freeing them, since the kernel may
+/// still write to them.
+pub(crate) struct Op<D: Send + 'static> {
+    driver: &'static Driver,
+    id: u64,
+    data: Option<D>,
+}
+
+// `data` is never pinned; the kernel refers to it by raw pointer.
+impl<D: Send + 'static> Unpin for Op<D> {}
+
+impl<D: Send + 'static> Future for Op<D> {
+    type Output = (io::Result<u32>, D);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.get_mut();
+        let mut inner = me.driver.inner.lock().unwrap();
+
+        match inner.ops.get_mut(&me.id) {
+            Some(Lifecycle::Completed(res)) => {
+                let res = *res;
+                inner.ops.remove(&me.id);
+                drop(inner);
+
+                let result = if res < 0 {
+                    Err(io::Error::from_raw_os_error(-res))
+                } else {
+                    Ok(res as u32)
+                };
+
+                Poll::Ready((result, me.data.take().expect("polled after completion")))
+            }
+            Some(lifecycle) => {
+                *lifecycle = Lifecycle::Waiting(cx.waker().clone());
+                Poll::Pending
+            }
+            None => unreachable!("operation missing from the driver table"),
+        }
+    }
+}
+
+impl<D: Send + 'static> Drop for Op<D> {
+    fn drop(&mut self) {
+        let data = match self.data.take() {
+            Some(data) => data,
+            // Already completed and handed back by `poll`.
+            None => return,
+        };
+
+        let mut inner = self.driver.inner.lock().unwrap();
+
+        match inner.ops.remove(&self.id) {
+            Some(Lifecycle::Completed(_)) | None => {}
+            Some(_) => {
+                inner.ops.insert(self.id, Lifecycle::Ignored(Box::new(data)));
+            }
+        }
+    }
+}
+
+/// Submits a read into the buffer at the file's current position.
+///
+/// The buffer must have been prepared with
+/// [`ensure_capacity_for`](Buf::ensure_capacity_for).
+pub(crate) fn read(std: Arc<StdFile>, mut buf: Buf) -> Op<FileData> {
+    let (ptr, len) = buf.unread_parts();
+
+    let entry = opcode::Read::new(types::Fd(std.as_raw_fd()), ptr, len)
+        .offset(-1)
+        .build();
+
+    driver().expect("io_uring driver not available").submit(entry, (buf, std))
+}
+
+/// Submits a write of the buffer's unread contents at the file's current
+/// position.
+pub(crate) fn write(std: Arc<StdFile>, mut buf: Buf) -> Op<FileData> {
+    let (ptr, len) = buf.unread_parts();
+
+    let entry = opcode::Write::new(types::Fd(std.as_raw_fd()), ptr as *const u8, len)
+        .offset(-1)
+        .build();
+
+    driver().expect("io_uring driver not available").submit(entry, (buf, std))
+}
+
+/// Opens a file relative to the current working directory.
+pub(crate) async fn open(path: &Path, flags: i32, mode: libc::mode_t) -> io::Result<StdFile> {
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "path contained a nul byte"))?;
+
+    let entry = opcode::OpenAt::new(types::Fd(libc::AT_FDCWD), path.as_ptr())
+        .flags(flags)
+        .mode(mode)
+        .build();
+
+    let op = driver()
+        .expect("io_uring driver not available")
+        .submit(entry, path);
+    let (res, _path) = op.await;
+
+    let fd = res? as RawFd;
+
+    // Safety: the kernel just handed us this descriptor.
+    Ok(unsafe { StdFile::from_raw_fd(fd) })
+}
+
+/// Flushes file data — and unless `datasync` is set, metadata — to disk.
+pub(crate) async fn fsync(std: Arc<StdFile>, datasync: bool) -> io::Result<()> {
+    let flags = if datasync {
+        types::FsyncFlags::DATASYNC
+    } else {
+        types::FsyncFlags::empty()
+    };
+
+    let entry = opcode::Fsync::new(types::Fd(std.as_raw_fd()))
+        .flags(flags)
+        .build();
+
+    let op = driver()
+        .expect("io_uring driver not available")
+        .submit(entry, std);
+    let (res, _std) = op.await;
+
+    res.map(|_| ())
+}
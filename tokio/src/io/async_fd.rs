@@ -1,4 +1,4 @@
-use crate::io::driver::{Handle, Interest, ReadyEvent, Registration};
+use crate::io::driver::{Handle, Interest, Ready, ReadyEvent, Registration};
 
 use mio::unix::SourceFd;
 use std::io;
@@ -410,6 +410,108 @@ impl<T: AsRawFd> AsyncFd<T> {
         })
     }
 
+    /// Waits for any of the requested readiness states, returning a
+    /// [`AsyncFdReadyGuard`] that must be dropped to resume polling for the
+    /// requested readiness states.
+    ///
+    /// The returned guard reports, via [`AsyncFdReadyGuard::ready`], which of
+    /// the requested states were actually observed; when waiting on both
+    /// directions at once, use [`AsyncFdReadyGuard::clear_ready_matching`] to
+    /// clear only the direction that was observed to block, so readiness in
+    /// the other direction is not lost.
+    ///
+    /// This method takes `&self`, so it is possible to call this method
+    /// concurrently with other methods on this struct. This method only
+    /// provides shared access to the inner IO resource when handling the
+    /// [`AsyncFdReadyGuard`].
+    ///
+    /// # Examples
+    ///
+    /// Concurrently read and write to a [`std::net::TcpStream`] on the same task without
+    /// splitting.
+    ///
+    /// ```no_run
+    /// use std::io;
+    /// use std::io::{Read, Write};
+    /// use std::net::TcpStream;
+    /// use tokio::io::unix::AsyncFd;
+    /// use tokio::io::{Interest, Ready};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> io::Result<()> {
+    ///     let stream = TcpStream::connect("127.0.0.1:8080")?;
+    ///     stream.set_nonblocking(true)?;
+    ///     let stream = AsyncFd::new(stream)?;
+    ///
+    ///     loop {
+    ///         let mut guard = stream
+    ///             .ready(Interest::READABLE | Interest::WRITABLE)
+    ///             .await?;
+    ///
+    ///         if guard.ready().is_readable() {
+    ///             let mut data = vec![0; 1024];
+    ///             // Try to read data, this may still fail with `WouldBlock`
+    ///             // if the readiness event is a false positive.
+    ///             match stream.get_ref().read(&mut data) {
+    ///                 Ok(n) => {
+    ///                     println!("read {} bytes", n);
+    ///                 }
+    ///                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+    ///                     // a read has blocked, but a write might still succeed.
+    ///                     // clear only the read readiness.
+    ///                     guard.clear_ready_matching(Ready::READABLE);
+    ///                     continue;
+    ///                 }
+    ///                 Err(e) => {
+    ///                     return Err(e);
+    ///                 }
+    ///             }
+    ///         }
+    ///
+    ///         if guard.ready().is_writable() {
+    ///             // Try to write data, this may still fail with `WouldBlock`
+    ///             // if the readiness event is a false positive.
+    ///             match stream.get_ref().write(b"hello world") {
+    ///                 Ok(n) => {
+    ///                     println!("write {} bytes", n);
+    ///                 }
+    ///                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => {
+    ///                     // a write has blocked, but a read might still succeed.
+    ///                     // clear only the write readiness.
+    ///                     guard.clear_ready_matching(Ready::WRITABLE);
+    ///                     continue;
+    ///                 }
+    ///                 Err(e) => {
+    ///                     return Err(e);
+    ///                 }
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub async fn ready(&self, interest: Interest) -> io::Result<AsyncFdReadyGuard<'_, T>> {
+        self.readiness(interest).await
+    }
+
+    /// Waits for any of the requested readiness states, returning a
+    /// [`AsyncFdReadyMutGuard`] that must be dropped to resume polling for the
+    /// requested readiness states.
+    ///
+    /// The returned guard reports, via [`AsyncFdReadyMutGuard::ready`], which
+    /// of the requested states were actually observed; when waiting on both
+    /// directions at once, use [`AsyncFdReadyMutGuard::clear_ready_matching`]
+    /// to clear only the direction that was observed to block, so readiness in
+    /// the other direction is not lost.
+    ///
+    /// This method takes `&mut self`, so it is possible to access the inner IO
+    /// resource mutably when handling the [`AsyncFdReadyMutGuard`].
+    pub async fn ready_mut(
+        &mut self,
+        interest: Interest,
+    ) -> io::Result<AsyncFdReadyMutGuard<'_, T>> {
+        self.readiness_mut(interest).await
+    }
+
     /// Waits for the file descriptor to become readable, returning a
     /// [`AsyncFdReadyGuard`] that must be dropped to resume read-readiness
     /// polling.
@@ -505,6 +607,51 @@ impl<'a, Inner: AsRawFd> AsyncFdReadyGuard<'a, Inner> {
         // no-op
     }
 
+    /// Clears only the readiness states in `ready` from this guard.
+    ///
+    /// Readiness states not in `ready` remain asserted, both in this guard and
+    /// in the internal readiness flag, so a subsequent call to a readiness
+    /// checking function for those states completes immediately.
+    ///
+    /// This is intended for guards obtained from [`AsyncFd::ready`] with a
+    /// combined interest: when only one of the observed operations blocks,
+    /// clear that direction alone instead of discarding the other.
+    ///
+    /// As with [`clear_ready`], it is critical that a state only be cleared
+    /// when the corresponding operation was _actually observed_ to block.
+    ///
+    /// [`clear_ready`]: method@Self::clear_ready
+    pub fn clear_ready_matching(&mut self, ready: Ready) {
+        if let Some(mut event) = self.event.take() {
+            self.async_fd
+                .registration
+                .clear_readiness(event.with_ready(ready));
+
+            // The guard only tracks readiness that has not been cleared yet.
+            event.ready = event.ready - ready;
+
+            if !event.ready.is_empty() {
+                self.event = Some(event);
+            }
+        }
+    }
+
+    /// Returns the readiness states this guard observed and has not yet
+    /// cleared.
+    ///
+    /// When the guard was obtained by waiting on a single direction, for
+    /// example via [`readable`], this is at most that direction. Guards
+    /// obtained from [`AsyncFd::ready`] may report any combination of the
+    /// requested states.
+    ///
+    /// [`readable`]: method@AsyncFd::readable
+    pub fn ready(&self) -> Ready {
+        match &self.event {
+            Some(event) => event.ready,
+            None => Ready::EMPTY,
+        }
+    }
+
     /// Performs the provided IO operation.
     ///
     /// If `f` returns a [`WouldBlock`] error, the readiness state associated
@@ -568,6 +715,51 @@ impl<'a, Inner: AsRawFd> AsyncFdReadyMutGuard<'a, Inner> {
         // no-op
     }
 
+    /// Clears only the readiness states in `ready` from this guard.
+    ///
+    /// Readiness states not in `ready` remain asserted, both in this guard and
+    /// in the internal readiness flag, so a subsequent call to a readiness
+    /// checking function for those states completes immediately.
+    ///
+    /// This is intended for guards obtained from [`AsyncFd::ready_mut`] with a
+    /// combined interest: when only one of the observed operations blocks,
+    /// clear that direction alone instead of discarding the other.
+    ///
+    /// As with [`clear_ready`], it is critical that a state only be cleared
+    /// when the corresponding operation was _actually observed_ to block.
+    ///
+    /// [`clear_ready`]: method@Self::clear_ready
+    pub fn clear_ready_matching(&mut self, ready: Ready) {
+        if let Some(mut event) = self.event.take() {
+            self.async_fd
+                .registration
+                .clear_readiness(event.with_ready(ready));
+
+            // The guard only tracks readiness that has not been cleared yet.
+            event.ready = event.ready - ready;
+
+            if !event.ready.is_empty() {
+                self.event = Some(event);
+            }
+        }
+    }
+
+    /// Returns the readiness states this guard observed and has not yet
+    /// cleared.
+    ///
+    /// When the guard was obtained by waiting on a single direction, for
+    /// example via [`readable_mut`], this is at most that direction. Guards
+    /// obtained from [`AsyncFd::ready_mut`] may report any combination of the
+    /// requested states.
+    ///
+    /// [`readable_mut`]: method@AsyncFd::readable_mut
+    pub fn ready(&self) -> Ready {
+        match &self.event {
+            Some(event) => event.ready,
+            None => Ready::EMPTY,
+        }
+    }
+
     /// Performs the provided IO operation.
     ///
     /// If `f` returns a [`WouldBlock`] error, the readiness state associated
@@ -277,3 +277,40 @@ cfg_fs! {
         }
     }
 }
+
+cfg_io_uring! {
+    impl Buf {
+        /// The unread contents as raw parts for an io_uring submission.
+        ///
+        /// For reads this is the region prepared by `ensure_capacity_for`;
+        /// the kernel fills it and `complete_read` trims it to size.
+        pub(crate) fn unread_parts(&mut self) -> (*mut u8, u32) {
+            let len = self.buf.len() - self.pos;
+            let ptr = unsafe { self.buf.as_mut_ptr().add(self.pos) };
+            (ptr, len as u32)
+        }
+
+        /// Marks `n` bytes as consumed by a short io_uring write.
+        pub(crate) fn advance(&mut self, n: usize) {
+            assert!(self.pos + n <= self.buf.len());
+            self.pos += n;
+        }
+
+        /// Applies the result of an io_uring read; mirrors `read_from`.
+        pub(crate) fn complete_read(&mut self, res: &io::Result<u32>) {
+            assert_eq!(self.pos, 0);
+
+            match res {
+                Ok(n) => self.buf.truncate(*n as usize),
+                Err(_) => self.buf.clear(),
+            }
+        }
+
+        /// Resets the buffer once an io_uring write is done; mirrors
+        /// `write_to`.
+        pub(crate) fn reset(&mut self) {
+            self.pos = 0;
+            self.buf.clear();
+        }
+    }
+}
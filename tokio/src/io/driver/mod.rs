@@ -56,6 +56,19 @@ pub(crate) struct ReadyEvent {
     pub(crate) ready: Ready,
 }
 
+impl ReadyEvent {
+    // Returns the same event but with the given readiness, preserving the
+    // tick so the driver still recognizes it as the original notification.
+    cfg_net_unix! {
+        pub(crate) fn with_ready(&self, ready: Ready) -> Self {
+            Self {
+                tick: self.tick,
+                ready,
+            }
+        }
+    }
+}
+
 pub(super) struct Inner {
     /// Primary slab handle containing the state for each resource registered
     /// with this driver.
@@ -196,6 +209,9 @@ impl Driver {
             return;
         }
 
+        #[cfg(all(tokio_unstable, feature = "rt"))]
+        crate::runtime::console::io_ready_event();
+
         io.wake(ready);
     }
 }
@@ -297,6 +313,16 @@ impl Handle {
     pub(super) fn inner(&self) -> Option<Arc<Inner>> {
         self.inner.upgrade()
     }
+
+    /// Returns `true` if both handles refer to the same driver instance, or
+    /// if either driver has shut down and no comparison can be made.
+    #[cfg(all(debug_assertions, feature = "rt"))]
+    pub(super) fn ptr_eq(&self, other: &Handle) -> bool {
+        match (self.inner(), other.inner()) {
+            (Some(a), Some(b)) => Arc::ptr_eq(&a, &b),
+            _ => true,
+        }
+    }
 }
 
 impl Unpark for Handle {
@@ -153,6 +153,15 @@ impl Registration {
         cx: &mut Context<'_>,
         direction: Direction,
     ) -> Poll<io::Result<ReadyEvent>> {
+        // In debug builds, catch I/O resources that were moved to a different
+        // runtime, which otherwise shows up as lost wakeups.
+        #[cfg(all(debug_assertions, feature = "rt"))]
+        if let Some(Some(current)) = crate::runtime::context::try_io_handle() {
+            if !self.handle.ptr_eq(&current) {
+                return Poll::Ready(Err(cross_runtime()));
+            }
+        }
+
         // Keep track of task budget
         let coop = ready!(crate::coop::poll_proceed(cx));
         let ev = ready!(self.shared.poll_readiness(cx, direction));
@@ -225,6 +234,14 @@ fn gone() -> io::Error {
     io::Error::new(io::ErrorKind::Other, "IO driver has terminated")
 }
 
+#[cfg(all(debug_assertions, feature = "rt"))]
+fn cross_runtime() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "IO resource polled on a runtime other than the one it was registered with",
+    )
+}
+
 cfg_io_readiness! {
     impl Registration {
         pub(crate) async fn readiness(&self, interest: Interest) -> io::Result<ReadyEvent> {
@@ -235,6 +252,15 @@ cfg_io_readiness! {
             pin!(fut);
 
             crate::future::poll_fn(|cx| {
+                // In debug builds, catch I/O resources that were moved to a
+                // different runtime, which otherwise shows up as lost wakeups.
+                #[cfg(all(debug_assertions, feature = "rt"))]
+                if let Some(Some(current)) = crate::runtime::context::try_io_handle() {
+                    if !self.handle.ptr_eq(&current) {
+                        return Poll::Ready(Err(cross_runtime()));
+                    }
+                }
+
                 if self.handle.inner().is_none() {
                     return Poll::Ready(Err(io::Error::new(
                         io::ErrorKind::Other,
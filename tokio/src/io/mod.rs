@@ -229,6 +229,9 @@ cfg_net_unix! {
 cfg_io_std! {
     mod stdio_common;
 
+    #[cfg(unix)]
+    pub mod tty;
+
     mod stderr;
     pub use stderr::{stderr, Stderr};
 
@@ -247,8 +250,13 @@ cfg_io_util! {
     pub(crate) mod util;
     pub use util::{
         copy, copy_bidirectional, copy_buf, duplex, empty, repeat, sink, AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, AsyncWriteExt,
-        BufReader, BufStream, BufWriter, DuplexStream, Empty, Lines, Repeat, Sink, Split, Take,
+        BufReader, BufStream, BufWriter, DuplexStream, Empty, Lines, ReadLineState, Repeat, Sink,
+        Split, Take,
     };
+
+    cfg_time! {
+        pub use util::{RateLimitedReader, RateLimitedWriter};
+    }
 }
 
 cfg_not_io_util! {
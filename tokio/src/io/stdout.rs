@@ -79,6 +79,55 @@ impl std::os::unix::io::AsRawFd for Stdout {
     }
 }
 
+#[cfg(unix)]
+impl Stdout {
+    /// Returns `true` if standard output refers to a terminal.
+    ///
+    /// Returns `false` when standard output is redirected, for example to a
+    /// file or a pipe.
+    pub fn is_tty(&self) -> bool {
+        use std::os::unix::io::AsRawFd;
+        crate::io::tty::is_tty(self.as_raw_fd())
+    }
+
+    /// Returns the window size of the terminal attached to standard output.
+    ///
+    /// Fails if standard output does not refer to a terminal.
+    pub fn window_size(&self) -> io::Result<crate::io::tty::WindowSize> {
+        use std::os::unix::io::AsRawFd;
+        crate::io::tty::window_size(self.as_raw_fd())
+    }
+
+    /// Switches the terminal attached to standard output into raw mode.
+    ///
+    /// In raw mode, output is not post-processed: newlines are not translated
+    /// to carriage-return line-feed pairs. The previous terminal attributes
+    /// are restored when the returned guard is dropped.
+    ///
+    /// Fails if standard output does not refer to a terminal.
+    pub fn enter_raw_mode(&self) -> io::Result<crate::io::tty::RawModeGuard> {
+        use std::os::unix::io::AsRawFd;
+        crate::io::tty::enter_raw_mode(self.as_raw_fd())
+    }
+
+    cfg_signal! {
+        /// Returns a stream of window-resize notifications for the terminal
+        /// attached to standard output.
+        ///
+        /// Each notification carries the new window size. See
+        /// [`tty::WindowResize`](crate::io::tty::WindowResize).
+        ///
+        /// # Errors
+        ///
+        /// Fails if the `SIGWINCH` handler cannot be registered, for example
+        /// outside a runtime enabled for signals.
+        pub fn resize_events(&self) -> io::Result<crate::io::tty::WindowResize> {
+            use std::os::unix::io::AsRawFd;
+            crate::io::tty::resize_events(self.as_raw_fd())
+        }
+    }
+}
+
 #[cfg(windows)]
 impl std::os::windows::io::AsRawHandle for Stdout {
     fn as_raw_handle(&self) -> std::os::windows::io::RawHandle {
@@ -0,0 +1,166 @@
+//! Terminal capabilities for the standard streams.
+//!
+//! This module is only defined on Unix platforms. It backs the terminal
+//! methods on [`Stdin`] and [`Stdout`]: querying whether the stream is
+//! attached to a terminal, reading the window size, switching the terminal
+//! into raw mode, and receiving window-resize notifications without a second
+//! event loop.
+//!
+//! [`Stdin`]: crate::io::Stdin
+//! [`Stdout`]: crate::io::Stdout
+
+#![cfg(unix)]
+
+use std::io;
+use std::mem::MaybeUninit;
+use std::os::unix::io::RawFd;
+
+/// The dimensions of the terminal attached to a stream.
+///
+/// Returned by [`Stdin::window_size`] and [`Stdout::window_size`].
+///
+/// [`Stdin::window_size`]: crate::io::Stdin::window_size
+/// [`Stdout::window_size`]: crate::io::Stdout::window_size
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowSize {
+    /// Number of rows of characters.
+    pub rows: u16,
+
+    /// Number of columns of characters.
+    pub cols: u16,
+}
+
+/// Keeps the terminal in raw mode for as long as it is held.
+///
+/// Returned by [`Stdin::enter_raw_mode`] and [`Stdout::enter_raw_mode`]. The
+/// previous terminal attributes are restored when the guard is dropped,
+/// including when the task holding it panics.
+///
+/// [`Stdin::enter_raw_mode`]: crate::io::Stdin::enter_raw_mode
+/// [`Stdout::enter_raw_mode`]: crate::io::Stdout::enter_raw_mode
+pub struct RawModeGuard {
+    fd: RawFd,
+    saved: libc::termios,
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        // Restoring the attributes of a closed or hung-up terminal can fail;
+        // there is nothing useful to do about it while dropping.
+        unsafe {
+            let _ = libc::tcsetattr(self.fd, libc::TCSADRAIN, &self.saved);
+        }
+    }
+}
+
+impl std::fmt::Debug for RawModeGuard {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("RawModeGuard").field("fd", &self.fd).finish()
+    }
+}
+
+/// Returns `true` if the file descriptor refers to a terminal.
+pub(crate) fn is_tty(fd: RawFd) -> bool {
+    unsafe { libc::isatty(fd) == 1 }
+}
+
+/// Queries the kernel for the window size of the terminal behind `fd`.
+pub(crate) fn window_size(fd: RawFd) -> io::Result<WindowSize> {
+    let mut size: MaybeUninit<libc::winsize> = MaybeUninit::uninit();
+
+    // Safety: `TIOCGWINSZ` writes a `winsize` through the provided pointer.
+    let size = unsafe {
+        if libc::ioctl(fd, libc::TIOCGWINSZ, size.as_mut_ptr()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        size.assume_init()
+    };
+
+    Ok(WindowSize {
+        rows: size.ws_row,
+        cols: size.ws_col,
+    })
+}
+
+/// Switches the terminal behind `fd` into raw mode, returning a guard that
+/// restores the previous attributes on drop.
+pub(crate) fn enter_raw_mode(fd: RawFd) -> io::Result<RawModeGuard> {
+    unsafe {
+        let mut attrs: MaybeUninit<libc::termios> = MaybeUninit::uninit();
+
+        if libc::tcgetattr(fd, attrs.as_mut_ptr()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let saved = attrs.assume_init();
+        let mut raw = saved;
+        libc::cfmakeraw(&mut raw);
+
+        if libc::tcsetattr(fd, libc::TCSANOW, &raw) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(RawModeGuard { fd, saved })
+    }
+}
+
+cfg_signal! {
+    /// A stream of window-resize notifications for a terminal.
+    ///
+    /// Created by [`Stdin::resize_events`] and [`Stdout::resize_events`].
+    /// Each `SIGWINCH` delivered to the process produces the new window size.
+    /// Notifications are delivered through the signal driver, so no extra
+    /// event loop or thread is involved.
+    ///
+    /// As with [`signal::unix::Signal`], notifications that arrive in quick
+    /// succession may be coalesced into one.
+    ///
+    /// [`Stdin::resize_events`]: crate::io::Stdin::resize_events
+    /// [`Stdout::resize_events`]: crate::io::Stdout::resize_events
+    /// [`signal::unix::Signal`]: crate::signal::unix::Signal
+    #[derive(Debug)]
+    pub struct WindowResize {
+        fd: RawFd,
+        signal: crate::signal::unix::Signal,
+    }
+
+    pub(crate) fn resize_events(fd: RawFd) -> io::Result<WindowResize> {
+        use crate::signal::unix::{signal, SignalKind};
+
+        let signal = signal(SignalKind::window_change())?;
+        Ok(WindowResize { fd, signal })
+    }
+
+    impl WindowResize {
+        /// Receives the next window-resize notification, returning the new
+        /// window size.
+        ///
+        /// `None` is returned if no more notifications can be received, and
+        /// an error if the window size cannot be queried after a resize.
+        pub async fn recv(&mut self) -> Option<io::Result<WindowSize>> {
+            self.signal.recv().await?;
+            Some(window_size(self.fd))
+        }
+
+        /// Polls to receive the next window-resize notification.
+        ///
+        /// This method returns:
+        ///
+        ///  * `Poll::Pending` if no resize has happened but the terminal is
+        ///    still open.
+        ///  * `Poll::Ready(Some(size))` if a resize was received.
+        ///  * `Poll::Ready(None)` if no more notifications can be received.
+        pub fn poll_recv(
+            &mut self,
+            cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Option<io::Result<WindowSize>>> {
+            match self.signal.poll_recv(cx) {
+                std::task::Poll::Pending => std::task::Poll::Pending,
+                std::task::Poll::Ready(None) => std::task::Poll::Ready(None),
+                std::task::Poll::Ready(Some(())) => {
+                    std::task::Poll::Ready(Some(window_size(self.fd)))
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::io::util::lines::{lines, Lines};
 use crate::io::util::read_line::{read_line, ReadLine};
+use crate::io::util::read_line_resumable::{read_line_resumable, ReadLineResumable, ReadLineState};
 use crate::io::util::read_until::{read_until, ReadUntil};
 use crate::io::util::split::{split, Split};
 use crate::io::AsyncBufRead;
@@ -166,6 +167,78 @@ cfg_io_util! {
             read_line(self, buf)
         }
 
+        /// Reads a line, keeping partial progress in `state` if the returned
+        /// future is dropped before a newline is reached.
+        ///
+        /// Equivalent to:
+        ///
+        /// ```ignore
+        /// async fn read_line_resumable(
+        ///     &mut self,
+        ///     state: &mut ReadLineState,
+        /// ) -> io::Result<Option<String>>;
+        /// ```
+        ///
+        /// Unlike [`read_line`], this method is cancellation safe: bytes read
+        /// before the newline delimiter (the 0xA byte) live in the
+        /// [`ReadLineState`] rather than in the future, so dropping the future
+        /// — for example when it loses a [`select!`] race or a timeout fires —
+        /// does not discard them. Calling this method again with the same
+        /// state resumes the read where it left off.
+        ///
+        /// On success, returns the line including the trailing newline, the
+        /// final unterminated line before EOF, or `None` once the stream has
+        /// reached EOF. After a complete line is returned, the state is empty
+        /// and can be reused for the next line.
+        ///
+        /// # Errors
+        ///
+        /// This function has the same error semantics as [`read_until`] and
+        /// will also return an error if the read bytes are not valid UTF-8. In
+        /// both cases the bytes read so far remain buffered in `state`.
+        ///
+        /// [`read_line`]: AsyncBufReadExt::read_line
+        /// [`read_until`]: AsyncBufReadExt::read_until
+        /// [`select!`]: crate::select
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use tokio::io::{AsyncBufReadExt, ReadLineState};
+        ///
+        /// use std::io::Cursor;
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mut cursor = Cursor::new(b"foo\nbar");
+        ///     let mut state = ReadLineState::new();
+        ///
+        ///     let line = cursor.read_line_resumable(&mut state)
+        ///         .await
+        ///         .expect("reading from cursor won't fail");
+        ///     assert_eq!(line.as_deref(), Some("foo\n"));
+        ///
+        ///     let line = cursor.read_line_resumable(&mut state)
+        ///         .await
+        ///         .expect("reading from cursor won't fail");
+        ///     assert_eq!(line.as_deref(), Some("bar"));
+        ///
+        ///     let line = cursor.read_line_resumable(&mut state)
+        ///         .await
+        ///         .expect("reading from cursor won't fail");
+        ///     assert_eq!(line, None);
+        /// }
+        /// ```
+        fn read_line_resumable<'a>(
+            &'a mut self,
+            state: &'a mut ReadLineState,
+        ) -> ReadLineResumable<'a, Self>
+        where
+            Self: Unpin,
+        {
+            read_line_resumable(self, state)
+        }
+
         /// Returns a stream of the contents of this reader split on the byte
         /// `byte`.
         ///
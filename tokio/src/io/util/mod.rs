@@ -44,12 +44,20 @@ cfg_io_util! {
     mod mem;
     pub use mem::{duplex, DuplexStream};
 
+    cfg_time! {
+        mod rate_limit;
+        pub use rate_limit::{RateLimitedReader, RateLimitedWriter};
+    }
+
     mod read;
     mod read_buf;
     mod read_exact;
     mod read_int;
     mod read_line;
 
+    mod read_line_resumable;
+    pub use read_line_resumable::ReadLineState;
+
     mod read_to_end;
     mod vec_with_initialized;
     cfg_process! {
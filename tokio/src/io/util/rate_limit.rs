@@ -0,0 +1,369 @@
+use crate::io::{AsyncRead, AsyncWrite, ReadBuf};
+use crate::time::{sleep_until, Instant, Sleep};
+
+use pin_project_lite::pin_project;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use std::{cmp, fmt};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// A token bucket: `rate` tokens (bytes) accrue per second, up to `burst`
+/// stored tokens. Time is tracked against the runtime timer's `Instant`.
+struct TokenBucket {
+    rate: u64,
+    burst: u64,
+    tokens: u64,
+    /// The point in time up to which accrual has been accounted for. Kept
+    /// exact so fractional tokens are not lost between polls.
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: u64, burst: u64) -> TokenBucket {
+        assert!(rate > 0, "rate must be greater than zero");
+        assert!(burst > 0, "burst must be greater than zero");
+
+        TokenBucket {
+            rate,
+            burst,
+            // Start with a full bucket so short transfers are not delayed.
+            tokens: burst,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        if self.tokens == self.burst {
+            // A full bucket does not accrue.
+            self.last_refill = now;
+            return;
+        }
+
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let earned = (elapsed.as_nanos() * self.rate as u128 / NANOS_PER_SEC) as u64;
+
+        if earned == 0 {
+            return;
+        }
+
+        if self.tokens.saturating_add(earned) >= self.burst {
+            self.tokens = self.burst;
+            self.last_refill = now;
+        } else {
+            self.tokens += earned;
+
+            // Only advance by the time the earned tokens account for, so the
+            // fractional remainder keeps accruing.
+            let accounted = earned as u128 * NANOS_PER_SEC / self.rate as u128;
+            self.last_refill += Duration::from_nanos(accounted as u64);
+        }
+    }
+
+    /// Returns when at least `want` tokens will be available.
+    fn deadline_for(&self, want: u64) -> Instant {
+        let missing = (want - self.tokens) as u128;
+
+        // Round up so the deadline is never short of a whole token.
+        let nanos = (missing * NANOS_PER_SEC + self.rate as u128 - 1) / self.rate as u128;
+
+        self.last_refill + Duration::from_nanos(nanos as u64)
+    }
+}
+
+impl fmt::Debug for TokenBucket {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TokenBucket")
+            .field("rate", &self.rate)
+            .field("burst", &self.burst)
+            .field("tokens", &self.tokens)
+            .finish()
+    }
+}
+
+/// Waits, if necessary, until the bucket holds tokens for an operation that
+/// wants up to `want` bytes. Returns the number of bytes the caller may move
+/// right now.
+fn poll_tokens(
+    bucket: &mut TokenBucket,
+    delay: &mut Pin<Box<Sleep>>,
+    cx: &mut Context<'_>,
+    want: u64,
+) -> Poll<u64> {
+    loop {
+        bucket.refill(Instant::now());
+
+        if bucket.tokens > 0 {
+            return Poll::Ready(cmp::min(bucket.tokens, want));
+        }
+
+        // Out of tokens: wait until a whole operation's worth (capped at the
+        // burst size) has accrued rather than dribbling single bytes.
+        let deadline = bucket.deadline_for(cmp::min(want, bucket.burst));
+        delay.as_mut().reset(deadline);
+        ready!(delay.as_mut().poll(cx));
+    }
+}
+
+pin_project! {
+    /// An [`AsyncRead`] adapter that caps the rate at which bytes are read
+    /// from the wrapped stream.
+    ///
+    /// Consumption is governed by a token bucket holding one token per byte:
+    /// tokens accrue at the configured rate up to the configured burst size,
+    /// and each byte read takes one token. When the bucket is empty, reads
+    /// wait on the runtime timer — no thread is blocked — until enough
+    /// tokens have accrued for the pending read (or the burst size,
+    /// whichever is smaller). The bucket starts full, so a fresh adapter can
+    /// serve up to one burst immediately.
+    ///
+    /// This requires the runtime timer, so reads must be performed from
+    /// within a runtime with time enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio::io::{AsyncReadExt, RateLimitedReader};
+    /// use tokio::net::TcpStream;
+    ///
+    /// # async fn dox() -> std::io::Result<()> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    ///
+    /// // At most 64 KB/s, with bursts of up to 16 KB.
+    /// let mut stream = RateLimitedReader::with_burst(stream, 64 * 1024, 16 * 1024);
+    ///
+    /// let mut buf = vec![0; 4096];
+    /// let n = stream.read(&mut buf).await?;
+    /// # let _ = n;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[derive(Debug)]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "io-util", feature = "time"))))]
+    pub struct RateLimitedReader<R> {
+        #[pin]
+        inner: R,
+        bucket: TokenBucket,
+        // Boxed so the adapter stays `Unpin` regardless of the timer future.
+        delay: Pin<Box<Sleep>>,
+    }
+}
+
+impl<R: AsyncRead> RateLimitedReader<R> {
+    /// Creates a new `RateLimitedReader` reading at most `rate` bytes per
+    /// second, with a burst size equal to one second's worth of tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero, or when called from outside of a runtime
+    /// with time enabled.
+    pub fn new(inner: R, rate: u64) -> Self {
+        Self::with_burst(inner, rate, rate)
+    }
+
+    /// Creates a new `RateLimitedReader` reading at most `rate` bytes per
+    /// second in bursts of at most `burst` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` or `burst` is zero, or when called from outside of a
+    /// runtime with time enabled.
+    pub fn with_burst(inner: R, rate: u64, burst: u64) -> Self {
+        Self {
+            inner,
+            bucket: TokenBucket::new(rate, burst),
+            delay: Box::pin(sleep_until(Instant::now())),
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying reader.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut R> {
+        self.project().inner
+    }
+
+    /// Consumes this `RateLimitedReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for RateLimitedReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if buf.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let me = self.project();
+
+        let allowed = ready!(poll_tokens(
+            me.bucket,
+            me.delay,
+            cx,
+            buf.remaining() as u64
+        ));
+
+        let mut b = buf.take(allowed as usize);
+        ready!(me.inner.poll_read(cx, &mut b))?;
+        let n = b.filled().len();
+
+        // We need to update the original ReadBuf
+        unsafe {
+            buf.assume_init(n);
+        }
+        buf.advance(n);
+        me.bucket.tokens -= n as u64;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+// Writes pass straight through so the adapter can wrap a full duplex stream
+// while limiting only the read side.
+impl<R: AsyncRead + AsyncWrite> AsyncWrite for RateLimitedReader<R> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_pin_mut().poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_pin_mut().poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_pin_mut().poll_shutdown(cx)
+    }
+}
+
+pin_project! {
+    /// An [`AsyncWrite`] adapter that caps the rate at which bytes are
+    /// written to the wrapped stream.
+    ///
+    /// Works exactly like [`RateLimitedReader`], but on the write side: each
+    /// written byte takes one token, and writes wait on the runtime timer
+    /// while the bucket is empty. A large write is split into rate-limited
+    /// chunks, so callers using [`write_all`] get pacing without interleaving
+    /// manual sleeps.
+    ///
+    /// This requires the runtime timer, so writes must be performed from
+    /// within a runtime with time enabled.
+    ///
+    /// [`write_all`]: crate::io::AsyncWriteExt::write_all
+    #[derive(Debug)]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "io-util", feature = "time"))))]
+    pub struct RateLimitedWriter<W> {
+        #[pin]
+        inner: W,
+        bucket: TokenBucket,
+        // Boxed so the adapter stays `Unpin` regardless of the timer future.
+        delay: Pin<Box<Sleep>>,
+    }
+}
+
+impl<W: AsyncWrite> RateLimitedWriter<W> {
+    /// Creates a new `RateLimitedWriter` writing at most `rate` bytes per
+    /// second, with a burst size equal to one second's worth of tokens.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero, or when called from outside of a runtime
+    /// with time enabled.
+    pub fn new(inner: W, rate: u64) -> Self {
+        Self::with_burst(inner, rate, rate)
+    }
+
+    /// Creates a new `RateLimitedWriter` writing at most `rate` bytes per
+    /// second in bursts of at most `burst` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` or `burst` is zero, or when called from outside of a
+    /// runtime with time enabled.
+    pub fn with_burst(inner: W, rate: u64, burst: u64) -> Self {
+        Self {
+            inner,
+            bucket: TokenBucket::new(rate, burst),
+            delay: Box::pin(sleep_until(Instant::now())),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying writer.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut W> {
+        self.project().inner
+    }
+
+    /// Consumes this `RateLimitedWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for RateLimitedWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if buf.is_empty() {
+            return self.get_pin_mut().poll_write(cx, buf);
+        }
+
+        let me = self.project();
+
+        let allowed = ready!(poll_tokens(me.bucket, me.delay, cx, buf.len() as u64));
+
+        let n = ready!(me.inner.poll_write(cx, &buf[..allowed as usize]))?;
+        me.bucket.tokens -= n as u64;
+
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+}
+
+// Reads pass straight through so the adapter can wrap a full duplex stream
+// while limiting only the write side.
+impl<W: AsyncRead + AsyncWrite> AsyncRead for RateLimitedWriter<W> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.get_pin_mut().poll_read(cx, buf)
+    }
+}
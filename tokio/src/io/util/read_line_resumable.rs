@@ -0,0 +1,117 @@
+use crate::io::util::read_until::read_until_internal;
+use crate::io::AsyncBufRead;
+
+use pin_project_lite::pin_project;
+use std::future::Future;
+use std::io;
+use std::marker::PhantomPinned;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Buffered progress of a resumable line read.
+///
+/// Bytes read by [`read_line_resumable`] before a newline is found are stored
+/// here rather than in the future, so dropping the future (for example when it
+/// loses a `select!` race or a [`timeout`] expires) does not lose them. Passing
+/// the same state to the next call picks up where the previous one left off.
+///
+/// The state can be reused for subsequent lines; it is emptied every time a
+/// complete line is returned.
+///
+/// [`read_line_resumable`]: crate::io::AsyncBufReadExt::read_line_resumable
+/// [`timeout`]: crate::time::timeout
+#[derive(Debug, Default)]
+pub struct ReadLineState {
+    /// Bytes accumulated so far, without a trailing newline.
+    buf: Vec<u8>,
+
+    /// The number of bytes appended to buf by the in-progress read.
+    read: usize,
+}
+
+impl ReadLineState {
+    /// Creates an empty state with no buffered partial line.
+    pub fn new() -> ReadLineState {
+        ReadLineState::default()
+    }
+
+    /// Returns the bytes buffered from an incomplete line.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns `true` if no partial line is buffered.
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Consumes the state, returning any buffered partial line.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+pin_project! {
+    /// Future for the [`read_line_resumable`](crate::io::AsyncBufReadExt::read_line_resumable) method.
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct ReadLineResumable<'a, R: ?Sized> {
+        reader: &'a mut R,
+        state: &'a mut ReadLineState,
+        // Make this future `!Unpin` for compatibility with async trait methods.
+        #[pin]
+        _pin: PhantomPinned,
+    }
+}
+
+pub(crate) fn read_line_resumable<'a, R>(
+    reader: &'a mut R,
+    state: &'a mut ReadLineState,
+) -> ReadLineResumable<'a, R>
+where
+    R: AsyncBufRead + ?Sized + Unpin,
+{
+    ReadLineResumable {
+        reader,
+        state,
+        _pin: PhantomPinned,
+    }
+}
+
+impl<R: AsyncBufRead + ?Sized + Unpin> Future for ReadLineResumable<'_, R> {
+    type Output = io::Result<Option<String>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.project();
+        let state = &mut **me.state;
+
+        // Any bytes appended before a pending or failed read stay in the
+        // state, which is what makes the operation resumable.
+        let num_bytes = ready!(read_until_internal(
+            Pin::new(*me.reader),
+            cx,
+            b'\n',
+            &mut state.buf,
+            &mut state.read,
+        ))?;
+
+        if num_bytes == 0 && state.buf.is_empty() {
+            return Poll::Ready(Ok(None));
+        }
+
+        match String::from_utf8(mem::take(&mut state.buf)) {
+            Ok(string) => Poll::Ready(Ok(Some(string))),
+            Err(utf8_err) => {
+                // Keep the bytes around so the caller can inspect or discard
+                // them.
+                state.buf = utf8_err.into_bytes();
+
+                Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "stream did not contain valid UTF-8",
+                )))
+            }
+        }
+    }
+}
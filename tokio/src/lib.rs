@@ -313,6 +313,8 @@
 //! - `process`: Enables `tokio::process` types.
 //! - `macros`: Enables `#[tokio::main]` and `#[tokio::test]` macros.
 //! - `sync`: Enables all `tokio::sync` types.
+//! - `stream`: Enables `Stream` implementations for types such as `Interval`
+//!             and the mpsc receivers.
 //! - `signal`: Enables all `tokio::signal` types.
 //! - `fs`: Enables `tokio::fs` types.
 //! - `test-util`: Enables testing based infrastructure for the Tokio runtime.
@@ -329,6 +331,9 @@
 //! - `parking_lot`: As a potential optimization, use the _parking_lot_ crate's
 //! synchronization primitives internally. MSRV may increase according to the
 //! _parking_lot_ release in use.
+//! - `io-uring`: On Linux, serve `tokio::fs` file operations through a shared
+//! io_uring instance instead of the blocking thread pool when the kernel
+//! supports it. Implies `fs`.
 //!
 //! ### Unstable features
 //!
@@ -340,6 +345,57 @@
 //! - `tracing`: Enables tracing events.
 //!
 //! [feature flags]: https://doc.rust-lang.org/cargo/reference/manifest.html#the-features-section
+//!
+//! ## WASM support
+//!
+//! Tokio has some limited support for the WASM platform. The `sync`,
+//! `macros`, `io-util`, `rt`, and `time` features compile for
+//! `wasm32-unknown-unknown` and `wasm32-wasi`; the remaining features are
+//! rejected at compile time because the underlying OS facilities do not
+//! exist there.
+//!
+//! On `wasm32-unknown-unknown` there is no OS clock and no way to block a
+//! thread, so the clock and the timer are routed through two functions that
+//! the embedder must supply in the `tokio` WASM import module:
+//!
+//! - `now_millis() -> f64`: milliseconds elapsed since an arbitrary but
+//!   fixed origin, e.g. `performance.now()`.
+//! - `park_millis(millis: f64)`: return after approximately `millis`
+//!   milliseconds (which may be infinite, meaning "until woken"), or
+//!   earlier if the host wants the runtime to wake up. In a browser worker
+//!   this is typically implemented with `Atomics.wait`; a non-browser host
+//!   can simply sleep.
+//!
+//! With those imports provided, a `current_thread` runtime and the timer
+//! work on that target. `spawn_blocking` still panics at runtime, as the
+//! target cannot spawn threads, and the `test-util` feature is not
+//! supported there.
+
+// WASM support: only a subset of Tokio works on `wasm32` targets. The
+// unsupported features are rejected at compile time rather than failing with
+// confusing errors deep inside `mio` or `libc`.
+#[cfg(all(
+    target_arch = "wasm32",
+    any(
+        feature = "fs",
+        feature = "io-std",
+        feature = "net",
+        feature = "process",
+        feature = "signal",
+        feature = "rt-multi-thread",
+    )
+))]
+compile_error!(
+    "the `fs`, `io-std`, `net`, `process`, `signal`, and `rt-multi-thread` \
+     features are not supported on wasm32 targets; \
+     only `sync`, `macros`, `io-util`, `rt`, and `time` may be enabled"
+);
+
+// The test-util clock is built on `std::time::Instant`, which does not exist
+// on `wasm32-unknown-unknown` (the real clock there is provided by the host;
+// see the "WASM support" section above).
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "test-util"))]
+compile_error!("the `test-util` feature is not supported on `wasm32-unknown-unknown`");
 
 // Includes re-exports used by macros.
 //
@@ -401,8 +457,12 @@ cfg_time! {
     pub mod time;
 }
 
+#[cfg(not(tokio_unstable))]
 mod util;
 
+#[cfg(tokio_unstable)]
+pub mod util;
+
 /// Due to the `Stream` trait's inclusion in `std` landing later than Tokio's 1.0
 /// release, most of the Tokio stream utilities have been moved into the [`tokio-stream`]
 /// crate.
@@ -55,6 +55,16 @@ macro_rules! cfg_fs {
     }
 }
 
+macro_rules! cfg_io_uring {
+    ($($item:item)*) => {
+        $(
+            #[cfg(all(target_os = "linux", feature = "io-uring"))]
+            #[cfg_attr(docsrs, doc(cfg(all(target_os = "linux", feature = "io-uring"))))]
+            $item
+        )*
+    }
+}
+
 macro_rules! cfg_io_blocking {
     ($($item:item)*) => {
         $( #[cfg(any(feature = "io-std", feature = "fs"))] $item )*
@@ -241,6 +251,16 @@ macro_rules! cfg_not_signal_internal {
     }
 }
 
+macro_rules! cfg_stream {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "stream")]
+            #[cfg_attr(docsrs, doc(cfg(feature = "stream")))]
+            $item
+        )*
+    }
+}
+
 macro_rules! cfg_sync {
     ($($item:item)*) => {
         $(
@@ -149,6 +149,43 @@
 /// always polled, and will not be ignored due to the stream being constantly
 /// ready.
 ///
+/// ### Branch index
+///
+/// Adding `index;` to the beginning of the macro usage makes the `select!`
+/// expression evaluate to a `(u32, _)` tuple pairing the index of the branch
+/// that completed — counting from zero, top to bottom — with the result of its
+/// handler. This makes it possible to match exhaustively on *which* branch
+/// completed, independently of the values the branches produce.
+///
+/// `index;` may be combined with `biased;` by writing `index; biased;`. Note
+/// that when an `else` branch is present, its expression is **not** paired
+/// with an index; it must evaluate to the tuple type itself.
+///
+/// ```
+/// async fn do_stuff_async() {
+///     // async work
+/// }
+///
+/// async fn more_async_work() {
+///     // more here
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (index, ()) = tokio::select! {
+///         index;
+///         _ = do_stuff_async() => {}
+///         _ = more_async_work() => {}
+///     };
+///
+///     match index {
+///         0 => println!("do_stuff_async() completed first"),
+///         1 => println!("more_async_work() completed first"),
+///         _ => unreachable!(),
+///     }
+/// }
+/// ```
+///
 /// # Panics
 ///
 /// `select!` panics if all branches are disabled **and** there is no provided
@@ -359,6 +396,12 @@ macro_rules! select {
         // expression to use to pick a future to poll first.
         start=$start:expr;
 
+        // How to shape the output once a branch completes. `value` (the
+        // default) evaluates to the handler result alone; `index` (the
+        // `index;` mode) pairs it with the index of the completed branch. See
+        // `select_wrap!` below.
+        wrap=$wrap:ident;
+
         // One `_` for each branch in the `select!` macro. Passing this to
         // `count!` converts $skip to an integer.
         ( $($count:tt)* )
@@ -493,7 +536,8 @@ macro_rules! select {
 
         match output {
             $(
-                $crate::select_variant!(util::Out, ($($skip)*) ($bind)) => $handle,
+                $crate::select_variant!(util::Out, ($($skip)*) ($bind)) =>
+                    $crate::select_wrap!($wrap, $crate::count!( $($skip)* ), $handle),
             )*
             util::Out::Disabled => $else,
             _ => unreachable!("failed to match bind"),
@@ -505,54 +549,76 @@ macro_rules! select {
     // These rules match a single `select!` branch and normalize it for
     // processing by the first rule.
 
-    (@ { start=$start:expr; $($t:tt)* } ) => {
+    (@ { start=$start:expr; wrap=$wrap:ident; $($t:tt)* } ) => {
         // No `else` branch
-        $crate::select!(@{ start=$start; $($t)*; panic!("all branches are disabled and there is no else branch") })
+        $crate::select!(@{ start=$start; wrap=$wrap; $($t)*; panic!("all branches are disabled and there is no else branch") })
     };
-    (@ { start=$start:expr; $($t:tt)* } else => $else:expr $(,)?) => {
-        $crate::select!(@{ start=$start; $($t)*; $else })
+    (@ { start=$start:expr; wrap=$wrap:ident; $($t:tt)* } else => $else:expr $(,)?) => {
+        $crate::select!(@{ start=$start; wrap=$wrap; $($t)*; $else })
     };
-    (@ { start=$start:expr; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr, if $c:expr => $h:block, $($r:tt)* ) => {
-        $crate::select!(@{ start=$start; ($($s)* _) $($t)* ($($s)*) $p = $f, if $c => $h, } $($r)*)
+    (@ { start=$start:expr; wrap=$wrap:ident; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr, if $c:expr => $h:block, $($r:tt)* ) => {
+        $crate::select!(@{ start=$start; wrap=$wrap; ($($s)* _) $($t)* ($($s)*) $p = $f, if $c => $h, } $($r)*)
     };
-    (@ { start=$start:expr; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr => $h:block, $($r:tt)* ) => {
-        $crate::select!(@{ start=$start; ($($s)* _) $($t)* ($($s)*) $p = $f, if true => $h, } $($r)*)
+    (@ { start=$start:expr; wrap=$wrap:ident; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr => $h:block, $($r:tt)* ) => {
+        $crate::select!(@{ start=$start; wrap=$wrap; ($($s)* _) $($t)* ($($s)*) $p = $f, if true => $h, } $($r)*)
     };
-    (@ { start=$start:expr; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr, if $c:expr => $h:block $($r:tt)* ) => {
-        $crate::select!(@{ start=$start; ($($s)* _) $($t)* ($($s)*) $p = $f, if $c => $h, } $($r)*)
+    (@ { start=$start:expr; wrap=$wrap:ident; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr, if $c:expr => $h:block $($r:tt)* ) => {
+        $crate::select!(@{ start=$start; wrap=$wrap; ($($s)* _) $($t)* ($($s)*) $p = $f, if $c => $h, } $($r)*)
     };
-    (@ { start=$start:expr; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr => $h:block $($r:tt)* ) => {
-        $crate::select!(@{ start=$start; ($($s)* _) $($t)* ($($s)*) $p = $f, if true => $h, } $($r)*)
+    (@ { start=$start:expr; wrap=$wrap:ident; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr => $h:block $($r:tt)* ) => {
+        $crate::select!(@{ start=$start; wrap=$wrap; ($($s)* _) $($t)* ($($s)*) $p = $f, if true => $h, } $($r)*)
     };
-    (@ { start=$start:expr; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr, if $c:expr => $h:expr ) => {
-        $crate::select!(@{ start=$start; ($($s)* _) $($t)* ($($s)*) $p = $f, if $c => $h, })
+    (@ { start=$start:expr; wrap=$wrap:ident; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr, if $c:expr => $h:expr ) => {
+        $crate::select!(@{ start=$start; wrap=$wrap; ($($s)* _) $($t)* ($($s)*) $p = $f, if $c => $h, })
     };
-    (@ { start=$start:expr; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr => $h:expr ) => {
-        $crate::select!(@{ start=$start; ($($s)* _) $($t)* ($($s)*) $p = $f, if true => $h, })
+    (@ { start=$start:expr; wrap=$wrap:ident; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr => $h:expr ) => {
+        $crate::select!(@{ start=$start; wrap=$wrap; ($($s)* _) $($t)* ($($s)*) $p = $f, if true => $h, })
     };
-    (@ { start=$start:expr; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr, if $c:expr => $h:expr, $($r:tt)* ) => {
-        $crate::select!(@{ start=$start; ($($s)* _) $($t)* ($($s)*) $p = $f, if $c => $h, } $($r)*)
+    (@ { start=$start:expr; wrap=$wrap:ident; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr, if $c:expr => $h:expr, $($r:tt)* ) => {
+        $crate::select!(@{ start=$start; wrap=$wrap; ($($s)* _) $($t)* ($($s)*) $p = $f, if $c => $h, } $($r)*)
     };
-    (@ { start=$start:expr; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr => $h:expr, $($r:tt)* ) => {
-        $crate::select!(@{ start=$start; ($($s)* _) $($t)* ($($s)*) $p = $f, if true => $h, } $($r)*)
+    (@ { start=$start:expr; wrap=$wrap:ident; ( $($s:tt)* ) $($t:tt)* } $p:pat = $f:expr => $h:expr, $($r:tt)* ) => {
+        $crate::select!(@{ start=$start; wrap=$wrap; ($($s)* _) $($t)* ($($s)*) $p = $f, if true => $h, } $($r)*)
     };
 
     // ===== Entry point =====
 
+    (index; biased; $p:pat = $($t:tt)* ) => {
+        $crate::select!(@{ start=0; wrap=index; () } $p = $($t)*)
+    };
+
+    (index; $p:pat = $($t:tt)* ) => {
+        $crate::select!(@{ start={ $crate::macros::support::thread_rng_n(BRANCHES) }; wrap=index; () } $p = $($t)*)
+    };
+
     (biased; $p:pat = $($t:tt)* ) => {
-        $crate::select!(@{ start=0; () } $p = $($t)*)
+        $crate::select!(@{ start=0; wrap=value; () } $p = $($t)*)
     };
 
     ( $p:pat = $($t:tt)* ) => {
         // Randomly generate a starting point. This makes `select!` a bit more
         // fair and avoids always polling the first future.
-        $crate::select!(@{ start={ $crate::macros::support::thread_rng_n(BRANCHES) }; () } $p = $($t)*)
+        $crate::select!(@{ start={ $crate::macros::support::thread_rng_n(BRANCHES) }; wrap=value; () } $p = $($t)*)
     };
     () => {
         compile_error!("select! requires at least one branch.")
     };
 }
 
+// Shapes the output of a completed `select!` branch. In the default (`value`)
+// mode, the handler result is returned as-is. In the `index;` mode, the
+// handler result is paired with the index of the branch that completed.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! select_wrap {
+    (value, $index:expr, $out:expr) => {
+        $out
+    };
+    (index, $index:expr, $out:expr) => {
+        (($index) as u32, $out)
+    };
+}
+
 // And here... we manually list out matches for up to 64 branches... I'm not
 // happy about it either, but this is how we manage to use a declarative macro!
 
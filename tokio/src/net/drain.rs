@@ -0,0 +1,238 @@
+//! Connection tracking for graceful server shutdown.
+
+use crate::io::{AsyncRead, AsyncWrite, ReadBuf};
+use crate::sync::notify::Notify;
+
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::io;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Tracks accepted connections so a server can shut down gracefully.
+///
+/// Each connection accepted from a listener is registered with
+/// [`track`](Self::track), which wraps it in a [`Tracked`] guard. Once the
+/// listener has been dropped so that no new connections arrive,
+/// [`drain`](Self::drain) resolves when every tracked connection has
+/// finished.
+///
+/// `Drain` is cheap to clone; all clones observe the same set of
+/// connections.
+///
+/// # Examples
+///
+/// ```no_run
+/// use tokio::net::{Drain, TcpListener};
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let listener = TcpListener::bind("127.0.0.1:8080").await?;
+///     let drain = Drain::new();
+///
+///     loop {
+///         let (socket, _) = tokio::select! {
+///             res = listener.accept() => res?,
+///             _ = tokio::signal::ctrl_c() => break,
+///         };
+///
+///         let socket = drain.track(socket);
+///         tokio::spawn(async move {
+///             // serve the connection, dropping `socket` when done
+///             drop(socket);
+///         });
+///     }
+///
+///     // Stop accepting, then wait for in-flight connections to finish.
+///     drop(listener);
+///     if !drain.drain_timeout(Duration::from_secs(30)).await {
+///         eprintln!("shut down with {} connections still open", drain.tracked());
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Drain {
+    shared: Arc<Shared>,
+}
+
+#[derive(Default)]
+struct Shared {
+    /// Number of live `Tracked` guards.
+    count: AtomicUsize,
+
+    /// Notified when `count` drops to zero.
+    notify: Notify,
+}
+
+impl Drain {
+    /// Creates a new tracker with no tracked connections.
+    pub fn new() -> Drain {
+        Drain::default()
+    }
+
+    /// Registers a connection with the tracker.
+    ///
+    /// The connection counts as in flight until the returned [`Tracked`]
+    /// wrapper is dropped. The wrapper forwards [`AsyncRead`] and
+    /// [`AsyncWrite`], so it can be used wherever the connection itself
+    /// could.
+    pub fn track<T>(&self, io: T) -> Tracked<T> {
+        self.shared.count.fetch_add(1, Ordering::AcqRel);
+
+        Tracked {
+            io,
+            _guard: TrackGuard {
+                shared: self.shared.clone(),
+            },
+        }
+    }
+
+    /// Returns the number of connections currently tracked.
+    pub fn tracked(&self) -> usize {
+        self.shared.count.load(Ordering::Acquire)
+    }
+
+    /// Waits until every tracked connection has finished.
+    ///
+    /// This does not prevent new connections from being
+    /// [`track`](Self::track)ed while waiting; close the listener first.
+    pub async fn drain(&self) {
+        loop {
+            let notified = self.shared.notify.notified();
+
+            if self.shared.count.load(Ordering::Acquire) == 0 {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+}
+
+cfg_time! {
+    impl Drain {
+        /// Waits until every tracked connection has finished or the timeout
+        /// elapses, whichever comes first.
+        ///
+        /// Returns `true` if all connections finished, and `false` if the
+        /// deadline passed with connections still in flight.
+        pub async fn drain_timeout(&self, timeout: std::time::Duration) -> bool {
+            crate::time::timeout(timeout, self.drain()).await.is_ok()
+        }
+    }
+}
+
+impl fmt::Debug for Drain {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Drain")
+            .field("tracked", &self.tracked())
+            .finish()
+    }
+}
+
+struct TrackGuard {
+    shared: Arc<Shared>,
+}
+
+impl Drop for TrackGuard {
+    fn drop(&mut self) {
+        if self.shared.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.notify.notify_waiters();
+        }
+    }
+}
+
+pin_project! {
+    /// A connection registered with a [`Drain`] tracker.
+    ///
+    /// Returned by [`Drain::track`]. The connection counts as in flight until
+    /// this wrapper is dropped. I/O is forwarded to the inner connection.
+    pub struct Tracked<T> {
+        #[pin]
+        io: T,
+        _guard: TrackGuard,
+    }
+}
+
+impl<T> Tracked<T> {
+    /// Returns a reference to the inner connection.
+    pub fn get_ref(&self) -> &T {
+        &self.io
+    }
+
+    /// Returns a mutable reference to the inner connection.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.io
+    }
+
+    /// Consumes the wrapper, returning the inner connection and removing it
+    /// from the tracker.
+    pub fn into_inner(self) -> T {
+        self.io
+    }
+}
+
+impl<T> Deref for Tracked<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.io
+    }
+}
+
+impl<T> DerefMut for Tracked<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.io
+    }
+}
+
+impl<T: AsyncRead> AsyncRead for Tracked<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.project().io.poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncWrite> AsyncWrite for Tracked<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.project().io.poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.project().io.poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.io.is_write_vectored()
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().io.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().io.poll_shutdown(cx)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Tracked<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Tracked").field("io", &self.io).finish()
+    }
+}
@@ -28,16 +28,60 @@ pub(crate) use addr::to_socket_addrs;
 pub use addr::ToSocketAddrs;
 
 cfg_net! {
+    mod drain;
+    pub use drain::{Drain, Tracked};
+
     mod lookup_host;
     pub use lookup_host::lookup_host;
 
     pub mod tcp;
+    pub use tcp::dual_stack::{DualStackConfig, DualStackListener};
     pub use tcp::listener::TcpListener;
     pub use tcp::socket::TcpSocket;
     pub use tcp::stream::TcpStream;
 
     mod udp;
     pub use udp::UdpSocket;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    mod udp_msg;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub use udp_msg::{EcnCodepoint, RecvMsg, SendMsg};
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    mod reuseport;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub use reuseport::SockFilter;
+
+    /// Verifies that a socket handed over by the user is in non-blocking
+    /// mode before registering it with the reactor.
+    ///
+    /// Only Unix exposes the flag; on other platforms the caller's word is
+    /// taken for it.
+    #[cfg(unix)]
+    pub(crate) fn check_nonblocking<T: std::os::unix::io::AsRawFd>(
+        socket: &T,
+    ) -> std::io::Result<()> {
+        let flags = unsafe { libc::fcntl(socket.as_raw_fd(), libc::F_GETFL) };
+
+        if flags == -1 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        if flags & libc::O_NONBLOCK == 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "socket must be set to non-blocking mode before registering with the reactor",
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn check_nonblocking<T>(_socket: &T) -> std::io::Result<()> {
+        Ok(())
+    }
 }
 
 cfg_net_unix! {
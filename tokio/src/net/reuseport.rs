@@ -0,0 +1,106 @@
+//! BPF filters for `SO_REUSEPORT` listener groups.
+//!
+//! When several sockets share a port with `SO_REUSEPORT`, the kernel picks
+//! which socket of the group receives each incoming connection or datagram
+//! by hashing the four-tuple. Attaching a classic or extended BPF program
+//! with `SO_ATTACH_REUSEPORT_CBPF` / `SO_ATTACH_REUSEPORT_EBPF` replaces
+//! that hash with the program's verdict, letting shard-per-core servers
+//! steer traffic to the socket owned by the current CPU.
+//!
+//! This module is only available on Linux and Android.
+
+use std::io;
+use std::mem;
+use std::os::unix::io::RawFd;
+
+/// A single classic BPF instruction, matching the kernel's `sock_filter`
+/// layout.
+///
+/// Programs are built from the opcode constants in `<linux/bpf_common.h>`;
+/// see [`attach_reuseport_cbpf`](crate::net::TcpSocket::attach_reuseport_cbpf)
+/// for an example.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct SockFilter {
+    /// The opcode.
+    pub code: u16,
+    /// The jump offset when the condition holds.
+    pub jt: u8,
+    /// The jump offset when the condition does not hold.
+    pub jf: u8,
+    /// The immediate operand.
+    pub k: u32,
+}
+
+impl SockFilter {
+    /// Creates a statement instruction (`BPF_STMT`).
+    pub fn stmt(code: u16, k: u32) -> SockFilter {
+        SockFilter {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        }
+    }
+
+    /// Creates a jump instruction (`BPF_JUMP`).
+    pub fn jump(code: u16, k: u32, jt: u8, jf: u8) -> SockFilter {
+        SockFilter { code, jt, jf, k }
+    }
+}
+
+/// Attaches a classic BPF program with `SO_ATTACH_REUSEPORT_CBPF`.
+pub(super) fn attach_cbpf(fd: RawFd, program: &[SockFilter]) -> io::Result<()> {
+    if program.is_empty() || program.len() > u16::MAX as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "invalid BPF program length",
+        ));
+    }
+
+    let prog = libc::sock_fprog {
+        len: program.len() as u16,
+        // `SockFilter` is `repr(C)` with the same layout as `sock_filter`.
+        filter: program.as_ptr() as *mut libc::sock_filter,
+    };
+
+    setsockopt(
+        fd,
+        libc::SO_ATTACH_REUSEPORT_CBPF,
+        &prog as *const libc::sock_fprog as *const libc::c_void,
+        mem::size_of::<libc::sock_fprog>(),
+    )
+}
+
+/// Attaches an extended BPF program with `SO_ATTACH_REUSEPORT_EBPF`.
+pub(super) fn attach_ebpf(fd: RawFd, program_fd: RawFd) -> io::Result<()> {
+    setsockopt(
+        fd,
+        libc::SO_ATTACH_REUSEPORT_EBPF,
+        &program_fd as *const RawFd as *const libc::c_void,
+        mem::size_of::<RawFd>(),
+    )
+}
+
+fn setsockopt(
+    fd: RawFd,
+    name: libc::c_int,
+    value: *const libc::c_void,
+    len: usize,
+) -> io::Result<()> {
+    let res = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            name,
+            value,
+            len as libc::socklen_t,
+        )
+    };
+
+    if res < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,238 @@
+use crate::net::tcp::TcpStream;
+use crate::net::TcpListener;
+
+use std::fmt;
+use std::io;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+
+/// Configuration for [`TcpListener::bind_dual_stack`].
+///
+/// The defaults prefer a single IPv6 socket with the `IPV6_V6ONLY` option
+/// disabled, falling back to a pair of sockets on platforms where a single
+/// socket cannot accept both address families.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::net::DualStackConfig;
+///
+/// // Force separate v4 and v6 sockets regardless of platform support.
+/// let config = DualStackConfig::new().single_socket(false);
+/// # drop(config);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DualStackConfig {
+    single_socket: bool,
+    backlog: u32,
+}
+
+impl DualStackConfig {
+    /// Returns the default configuration.
+    pub fn new() -> DualStackConfig {
+        DualStackConfig {
+            single_socket: true,
+            backlog: 1024,
+        }
+    }
+
+    /// Sets whether to attempt a single IPv6 socket with `IPV6_V6ONLY`
+    /// disabled before falling back to separate v4 and v6 sockets.
+    ///
+    /// Defaults to `true`. When disabled, separate sockets are always bound;
+    /// this keeps v4 peer addresses un-mapped (no `::ffff:a.b.c.d`
+    /// addresses).
+    pub fn single_socket(mut self, single_socket: bool) -> DualStackConfig {
+        self.single_socket = single_socket;
+        self
+    }
+
+    /// Sets the listen backlog applied to each bound socket.
+    ///
+    /// Defaults to 1024.
+    pub fn backlog(mut self, backlog: u32) -> DualStackConfig {
+        self.backlog = backlog;
+        self
+    }
+}
+
+impl Default for DualStackConfig {
+    fn default() -> DualStackConfig {
+        DualStackConfig::new()
+    }
+}
+
+/// A TCP listener accepting connections over both IPv4 and IPv6.
+///
+/// Returned by [`TcpListener::bind_dual_stack`]. Depending on the
+/// configuration and platform support this wraps either a single socket with
+/// `IPV6_V6ONLY` disabled or a pair of per-family sockets;
+/// [`accept`](Self::accept) multiplexes over whichever sockets were bound so
+/// callers do not have to care which form was used.
+pub struct DualStackListener {
+    v4: Option<TcpListener>,
+    v6: Option<TcpListener>,
+
+    /// Alternates which socket is polled first so one busy family cannot
+    /// starve the other.
+    flip: AtomicBool,
+}
+
+impl TcpListener {
+    /// Creates a listener accepting both IPv4 and IPv6 connections on `port`.
+    ///
+    /// On platforms where the `IPV6_V6ONLY` socket option can be disabled, a
+    /// single IPv6 socket bound to `[::]:port` accepts connections from both
+    /// families. Elsewhere, or when [`single_socket`] is disabled, separate
+    /// sockets are bound to `0.0.0.0:port` and `[::]:port` and
+    /// [`accept`](DualStackListener::accept) multiplexes them.
+    ///
+    /// A `port` of 0 requests an ephemeral port. In two-socket mode the v4
+    /// socket picks the port and the v6 socket binds the same number, which
+    /// can fail if another process grabs it first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio::net::{DualStackConfig, TcpListener};
+    ///
+    /// use std::io;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> io::Result<()> {
+    ///     let listener = TcpListener::bind_dual_stack(8080, DualStackConfig::new())?;
+    ///
+    ///     loop {
+    ///         let (socket, peer) = listener.accept().await?;
+    ///         println!("accepted {}", peer);
+    ///         # drop(socket); break Ok(());
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`single_socket`]: DualStackConfig::single_socket
+    pub fn bind_dual_stack(port: u16, config: DualStackConfig) -> io::Result<DualStackListener> {
+        if config.single_socket {
+            if let Ok(listener) = bind_one(
+                SocketAddr::from((Ipv6Addr::UNSPECIFIED, port)),
+                Some(false),
+                config.backlog,
+            ) {
+                return Ok(DualStackListener {
+                    v4: None,
+                    v6: Some(listener),
+                    flip: AtomicBool::new(false),
+                });
+            }
+        }
+
+        let v4 = bind_one(
+            SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)),
+            None,
+            config.backlog,
+        )?;
+
+        // If an ephemeral port was requested, reuse the one the v4 socket
+        // picked so both sockets share a port number.
+        let port = v4.local_addr()?.port();
+
+        let v6 = bind_one(
+            SocketAddr::from((Ipv6Addr::UNSPECIFIED, port)),
+            Some(true),
+            config.backlog,
+        )?;
+
+        Ok(DualStackListener {
+            v4: Some(v4),
+            v6: Some(v6),
+            flip: AtomicBool::new(false),
+        })
+    }
+}
+
+/// Binds a single non-blocking listening socket, optionally forcing the
+/// `IPV6_V6ONLY` option.
+fn bind_one(addr: SocketAddr, only_v6: Option<bool>, backlog: u32) -> io::Result<TcpListener> {
+    let domain = if addr.is_ipv6() {
+        socket2::Domain::IPV6
+    } else {
+        socket2::Domain::IPV4
+    };
+
+    let socket = socket2::Socket::new(domain, socket2::Type::STREAM, Some(socket2::Protocol::TCP))?;
+
+    if let Some(only_v6) = only_v6 {
+        socket.set_only_v6(only_v6)?;
+    }
+
+    // Match the platform behavior of `TcpSocket`: without `SO_REUSEADDR`,
+    // rebinding after a restart fails while old connections sit in TIME_WAIT.
+    #[cfg(not(windows))]
+    socket.set_reuse_address(true)?;
+
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(backlog as i32)?;
+
+    TcpListener::from_socket2(socket)
+}
+
+impl DualStackListener {
+    /// Accepts a new incoming connection from either address family.
+    ///
+    /// When both a v4 and a v6 socket are bound, the listener alternates
+    /// which is polled first so neither family is starved.
+    pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        crate::future::poll_fn(|cx| self.poll_accept(cx)).await
+    }
+
+    /// Polls to accept a new incoming connection to this listener.
+    ///
+    /// If there is no connection to accept on any of the bound sockets,
+    /// `Poll::Pending` is returned and the current task will be notified by a
+    /// waker.
+    pub fn poll_accept(&self, cx: &mut Context<'_>) -> Poll<io::Result<(TcpStream, SocketAddr)>> {
+        let flip = self.flip.fetch_xor(true, Ordering::Relaxed);
+
+        let (first, second) = if flip {
+            (&self.v6, &self.v4)
+        } else {
+            (&self.v4, &self.v6)
+        };
+
+        for listener in [first, second].iter().filter_map(|l| l.as_ref()) {
+            if let Poll::Ready(res) = listener.poll_accept(cx) {
+                return Poll::Ready(res);
+            }
+        }
+
+        Poll::Pending
+    }
+
+    /// Returns the local addresses the listener is bound to.
+    ///
+    /// This is one address in single-socket mode and two in two-socket mode.
+    pub fn local_addrs(&self) -> io::Result<Vec<SocketAddr>> {
+        let mut addrs = Vec::with_capacity(2);
+
+        if let Some(v4) = &self.v4 {
+            addrs.push(v4.local_addr()?);
+        }
+
+        if let Some(v6) = &self.v6 {
+            addrs.push(v6.local_addr()?);
+        }
+
+        Ok(addrs)
+    }
+}
+
+impl fmt::Debug for DualStackListener {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("DualStackListener")
+            .field("v4", &self.v4)
+            .field("v6", &self.v6)
+            .finish()
+    }
+}
@@ -220,6 +220,45 @@ impl TcpListener {
         Ok(TcpListener { io })
     }
 
+    /// Creates new `TcpListener` from a fully configured [`socket2::Socket`].
+    ///
+    /// Unlike [`from_std`](Self::from_std), this verifies on Unix that the
+    /// socket is in non-blocking mode before registering it with the reactor,
+    /// returning an [`InvalidInput`](std::io::ErrorKind::InvalidInput) error
+    /// otherwise. The socket must already be bound and listening.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use std::error::Error;
+    /// use socket2::{Domain, Socket, Type};
+    /// use tokio::net::TcpListener;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let socket = Socket::new(Domain::IPV4, Type::STREAM, None)?;
+    ///     socket.set_reuse_address(true)?;
+    ///     socket.bind(&"127.0.0.1:8080".parse::<std::net::SocketAddr>()?.into())?;
+    ///     socket.listen(1024)?;
+    ///     socket.set_nonblocking(true)?;
+    ///
+    ///     let listener = TcpListener::from_socket2(socket)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// This function panics if thread-local runtime is not set.
+    ///
+    /// The runtime is usually set implicitly when this function is called
+    /// from a future driven by a tokio runtime, otherwise runtime can be set
+    /// explicitly with [`Runtime::enter`](crate::runtime::Runtime::enter) function.
+    pub fn from_socket2(socket: socket2::Socket) -> io::Result<TcpListener> {
+        crate::net::check_nonblocking(&socket)?;
+        TcpListener::from_std(socket.into())
+    }
+
     /// Turn a [`tokio::net::TcpListener`] into a [`std::net::TcpListener`].
     ///
     /// The returned [`std::net::TcpListener`] will have nonblocking mode set as
@@ -1,5 +1,7 @@
 //! TCP utility types
 
+pub(crate) mod dual_stack;
+
 pub(crate) mod listener;
 
 pub(crate) mod socket;
@@ -280,6 +280,78 @@ impl TcpSocket {
         self.inner.get_reuseport()
     }
 
+    /// Attaches a classic BPF program steering incoming connections within a
+    /// `SO_REUSEPORT` listener group.
+    ///
+    /// The program runs for each new connection on the shared port and
+    /// returns the index of the group socket that should accept it,
+    /// replacing the kernel's default four-tuple hash. [`set_reuseport`]
+    /// must be enabled before attaching a program, and only one socket of
+    /// the group needs to carry it.
+    ///
+    /// This sets the `SO_ATTACH_REUSEPORT_CBPF` option and is supported on
+    /// Linux and Android only.
+    ///
+    /// [`set_reuseport`]: method@Self::set_reuseport
+    ///
+    /// # Examples
+    ///
+    /// Steer each connection to the socket of the CPU that accepted it:
+    ///
+    /// ```no_run
+    /// use tokio::net::{SockFilter, TcpSocket};
+    ///
+    /// use std::io;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> io::Result<()> {
+    ///     let addr = "127.0.0.1:8080".parse().unwrap();
+    ///
+    ///     let socket = TcpSocket::new_v4()?;
+    ///     socket.set_reuseport(true)?;
+    ///     socket.attach_reuseport_cbpf(&[
+    ///         // A = raw_smp_processor_id()
+    ///         SockFilter::stmt(0x20, 0xffff_f000 + 36),
+    ///         // return A
+    ///         SockFilter::stmt(0x16, 0),
+    ///     ])?;
+    ///     socket.bind(addr)?;
+    ///
+    ///     let listener = socket.listen(1024)?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn attach_reuseport_cbpf(&self, program: &[crate::net::SockFilter]) -> io::Result<()> {
+        use std::os::unix::io::AsRawFd;
+
+        crate::net::reuseport::attach_cbpf(self.inner.as_raw_fd(), program)
+    }
+
+    /// Attaches an extended BPF program steering incoming connections within
+    /// a `SO_REUSEPORT` listener group.
+    ///
+    /// `program_fd` is the file descriptor of a loaded
+    /// `BPF_PROG_TYPE_SK_REUSEPORT` program, obtained from the `bpf(2)`
+    /// system call. Like [`attach_reuseport_cbpf`], the program returns the
+    /// index of the group socket that should accept each connection.
+    ///
+    /// This sets the `SO_ATTACH_REUSEPORT_EBPF` option and is supported on
+    /// Linux and Android only.
+    ///
+    /// [`attach_reuseport_cbpf`]: method@Self::attach_reuseport_cbpf
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn attach_reuseport_ebpf(
+        &self,
+        program_fd: std::os::unix::io::RawFd,
+    ) -> io::Result<()> {
+        use std::os::unix::io::AsRawFd;
+
+        crate::net::reuseport::attach_ebpf(self.inner.as_raw_fd(), program_fd)
+    }
+
     /// Sets the size of the TCP send buffer on this socket.
     ///
     /// On most operating systems, this sets the `SO_SNDBUF` socket option.
@@ -9,6 +9,7 @@ use std::fmt;
 use std::io;
 use std::net::{Shutdown, SocketAddr};
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::task::{Context, Poll};
 use std::time::Duration;
 
@@ -66,6 +67,12 @@ cfg_net! {
     /// [`shutdown()`]: fn@crate::io::AsyncWriteExt::shutdown
     pub struct TcpStream {
         io: PollEvented<mio::net::TcpStream>,
+
+        /// Tracks halves explicitly shut down so that later operations on
+        /// them fail eagerly with a clear error instead of relying on what
+        /// the operating system reports.
+        read_closed: AtomicBool,
+        write_closed: AtomicBool,
     }
 }
 
@@ -154,7 +161,11 @@ impl TcpStream {
 
     pub(crate) fn new(connected: mio::net::TcpStream) -> io::Result<TcpStream> {
         let io = PollEvented::new(connected)?;
-        Ok(TcpStream { io })
+        Ok(TcpStream {
+            io,
+            read_closed: AtomicBool::new(false),
+            write_closed: AtomicBool::new(false),
+        })
     }
 
     /// Creates new `TcpStream` from a `std::net::TcpStream`.
@@ -189,7 +200,31 @@ impl TcpStream {
     pub fn from_std(stream: std::net::TcpStream) -> io::Result<TcpStream> {
         let io = mio::net::TcpStream::from_std(stream);
         let io = PollEvented::new(io)?;
-        Ok(TcpStream { io })
+        Ok(TcpStream {
+            io,
+            read_closed: AtomicBool::new(false),
+            write_closed: AtomicBool::new(false),
+        })
+    }
+
+    /// Creates new `TcpStream` from a fully configured [`socket2::Socket`].
+    ///
+    /// Unlike [`from_std`](Self::from_std), this verifies on Unix that the
+    /// socket is in non-blocking mode before registering it with the reactor,
+    /// returning an [`InvalidInput`](std::io::ErrorKind::InvalidInput) error
+    /// otherwise. The socket must already be connected, or have a connection
+    /// in progress.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if thread-local runtime is not set.
+    ///
+    /// The runtime is usually set implicitly when this function is called
+    /// from a future driven by a tokio runtime, otherwise runtime can be set
+    /// explicitly with [`Runtime::enter`](crate::runtime::Runtime::enter) function.
+    pub fn from_socket2(socket: socket2::Socket) -> io::Result<TcpStream> {
+        crate::net::check_nonblocking(&socket)?;
+        TcpStream::from_std(socket.into())
     }
 
     /// Turn a [`tokio::net::TcpStream`] into a [`std::net::TcpStream`].
@@ -558,6 +593,10 @@ impl TcpStream {
     pub fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
         use std::io::Read;
 
+        if self.is_read_shutdown() {
+            return Err(read_shutdown_err());
+        }
+
         self.io
             .registration()
             .try_io(Interest::READABLE, || (&*self.io).read(buf))
@@ -622,6 +661,10 @@ impl TcpStream {
         /// }
         /// ```
         pub fn try_read_buf<B: BufMut>(&self, buf: &mut B) -> io::Result<usize> {
+            if self.is_read_shutdown() {
+                return Err(read_shutdown_err());
+            }
+
             self.io.registration().try_io(Interest::READABLE, || {
                 use std::io::Read;
 
@@ -768,6 +811,10 @@ impl TcpStream {
     /// }
     /// ```
     pub fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        if self.is_write_shutdown() {
+            return Err(write_shutdown_err());
+        }
+
         use std::io::Write;
 
         self.io
@@ -825,7 +872,111 @@ impl TcpStream {
     /// portions to return immediately with an appropriate value (see the
     /// documentation of `Shutdown`).
     pub(super) fn shutdown_std(&self, how: Shutdown) -> io::Result<()> {
-        self.io.shutdown(how)
+        self.io.shutdown(how)?;
+
+        match how {
+            Shutdown::Read => self.read_closed.store(true, Ordering::Relaxed),
+            Shutdown::Write => self.write_closed.store(true, Ordering::Relaxed),
+            Shutdown::Both => {
+                self.read_closed.store(true, Ordering::Relaxed);
+                self.write_closed.store(true, Ordering::Relaxed);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Shuts down the read half of this connection.
+    ///
+    /// Data already received from the peer but not yet read is discarded.
+    /// Subsequent reads on this stream fail with a [`NotConnected`] error
+    /// rather than reporting end-of-file, so a locally initiated shutdown
+    /// can be told apart from the peer closing the connection.
+    ///
+    /// Calling this method more than once has no further effect.
+    ///
+    /// [`NotConnected`]: std::io::ErrorKind::NotConnected
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use tokio::net::TcpStream;
+    ///
+    /// # async fn dox() -> Result<(), Box<dyn std::error::Error>> {
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    ///
+    /// // Done receiving; the write half remains usable.
+    /// stream.shutdown_read()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn shutdown_read(&self) -> io::Result<()> {
+        if self.is_read_shutdown() {
+            return Ok(());
+        }
+
+        self.shutdown_std(Shutdown::Read)
+    }
+
+    /// Polling version of [`shutdown_read`].
+    ///
+    /// The shutdown takes effect immediately; this never returns
+    /// `Poll::Pending` and is provided for use inside manually implemented
+    /// futures.
+    ///
+    /// [`shutdown_read`]: method@Self::shutdown_read
+    pub fn poll_shutdown_read(&self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.shutdown_read())
+    }
+
+    /// Shuts down the write half of this connection.
+    ///
+    /// The peer receives an end-of-file once the data already queued has
+    /// been transmitted; this is how protocols using half-close signal the
+    /// end of a request. Subsequent writes on this stream fail with a
+    /// [`NotConnected`] error. The read half is unaffected.
+    ///
+    /// This is the same shutdown performed by
+    /// [`AsyncWriteExt::shutdown`](crate::io::AsyncWriteExt::shutdown), but
+    /// usable through a shared reference.
+    ///
+    /// Calling this method more than once has no further effect.
+    ///
+    /// [`NotConnected`]: std::io::ErrorKind::NotConnected
+    pub fn shutdown_write(&self) -> io::Result<()> {
+        if self.is_write_shutdown() {
+            return Ok(());
+        }
+
+        self.shutdown_std(Shutdown::Write)
+    }
+
+    /// Polling version of [`shutdown_write`].
+    ///
+    /// The shutdown takes effect immediately; this never returns
+    /// `Poll::Pending` and is provided for use inside manually implemented
+    /// futures.
+    ///
+    /// [`shutdown_write`]: method@Self::shutdown_write
+    pub fn poll_shutdown_write(&self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.shutdown_write())
+    }
+
+    /// Returns `true` if the read half of this stream has been shut down.
+    ///
+    /// Only shutdowns performed through this stream are tracked; the peer
+    /// closing its write half does not set this flag.
+    pub fn is_read_shutdown(&self) -> bool {
+        self.read_closed.load(Ordering::Relaxed)
+    }
+
+    /// Returns `true` if the write half of this stream has been shut down,
+    /// either with [`shutdown_write`] or through [`AsyncWrite`].
+    ///
+    /// [`shutdown_write`]: method@Self::shutdown_write
+    /// [`AsyncWrite`]: crate::io::AsyncWrite
+    pub fn is_write_shutdown(&self) -> bool {
+        self.write_closed.load(Ordering::Relaxed)
     }
 
     /// Gets the value of the `TCP_NODELAY` option on this socket.
@@ -1017,6 +1168,10 @@ impl TcpStream {
         cx: &mut Context<'_>,
         buf: &mut ReadBuf<'_>,
     ) -> Poll<io::Result<()>> {
+        if self.is_read_shutdown() {
+            return Poll::Ready(Err(read_shutdown_err()));
+        }
+
         // Safety: `TcpStream::read` correctly handles reads into uninitialized memory
         unsafe { self.io.poll_read(cx, buf) }
     }
@@ -1026,6 +1181,10 @@ impl TcpStream {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
+        if self.is_write_shutdown() {
+            return Poll::Ready(Err(write_shutdown_err()));
+        }
+
         self.io.poll_write(cx, buf)
     }
 
@@ -1034,10 +1193,28 @@ impl TcpStream {
         cx: &mut Context<'_>,
         bufs: &[io::IoSlice<'_>],
     ) -> Poll<io::Result<usize>> {
+        if self.is_write_shutdown() {
+            return Poll::Ready(Err(write_shutdown_err()));
+        }
+
         self.io.poll_write_vectored(cx, bufs)
     }
 }
 
+fn read_shutdown_err() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotConnected,
+        "the read half of this stream has been shut down",
+    )
+}
+
+fn write_shutdown_err() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotConnected,
+        "the write half of this stream has been shut down",
+    )
+}
+
 impl TryFrom<std::net::TcpStream> for TcpStream {
     type Error = io::Error;
 
@@ -211,6 +211,46 @@ impl UdpSocket {
         UdpSocket::new(io)
     }
 
+    /// Creates a new `UdpSocket` from a fully configured [`socket2::Socket`].
+    ///
+    /// Unlike [`from_std`](Self::from_std), this verifies on Unix that the
+    /// socket is in non-blocking mode before registering it with the reactor,
+    /// returning an [`InvalidInput`](std::io::ErrorKind::InvalidInput) error
+    /// otherwise. The socket must already be bound.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if thread-local runtime is not set.
+    ///
+    /// The runtime is usually set implicitly when this function is called
+    /// from a future driven by a tokio runtime, otherwise runtime can be set
+    /// explicitly with [`Runtime::enter`](crate::runtime::Runtime::enter) function.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use socket2::{Domain, Socket, Type};
+    /// use tokio::net::UdpSocket;
+    /// # use std::{io, net::SocketAddr};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> io::Result<()> {
+    /// let addr = "0.0.0.0:8080".parse::<SocketAddr>().unwrap();
+    /// let socket = Socket::new(Domain::IPV4, Type::DGRAM, None)?;
+    /// socket.set_reuse_address(true)?;
+    /// socket.bind(&addr.into())?;
+    /// socket.set_nonblocking(true)?;
+    ///
+    /// let sock = UdpSocket::from_socket2(socket)?;
+    /// // use `sock`
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_socket2(socket: socket2::Socket) -> io::Result<UdpSocket> {
+        crate::net::check_nonblocking(&socket)?;
+        UdpSocket::from_std(socket.into())
+    }
+
     /// Turn a [`tokio::net::UdpSocket`] into a [`std::net::UdpSocket`].
     ///
     /// The returned [`std::net::UdpSocket`] will have nonblocking mode set as
@@ -1208,6 +1248,221 @@ impl UdpSocket {
         Poll::Ready(Ok(addr))
     }
 
+    /// Receives a single datagram together with its control messages.
+    ///
+    /// The returned [`RecvMsg`] carries the remote address plus whichever of
+    /// the ECN codepoint, destination address and kernel receive timestamp
+    /// have been enabled with [`set_recv_ecn`], [`set_recv_pktinfo`] and
+    /// [`set_recv_timestamp`].
+    ///
+    /// If the datagram is larger than the buffer, the excess is discarded
+    /// and [`RecvMsg::truncated`] is set.
+    ///
+    /// This is supported on Linux and Android only.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If `recv_msg` is used as the event in a
+    /// [`tokio::select!`](crate::select) statement and some other branch
+    /// completes first, it is guaranteed that no messages were received on
+    /// this socket.
+    ///
+    /// [`set_recv_ecn`]: method@Self::set_recv_ecn
+    /// [`set_recv_pktinfo`]: method@Self::set_recv_pktinfo
+    /// [`set_recv_timestamp`]: method@Self::set_recv_timestamp
+    /// [`RecvMsg`]: crate::net::RecvMsg
+    /// [`RecvMsg::truncated`]: crate::net::RecvMsg#structfield.truncated
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub async fn recv_msg(&self, buf: &mut [u8]) -> io::Result<crate::net::RecvMsg> {
+        use std::os::unix::io::AsRawFd;
+
+        self.io
+            .registration()
+            .async_io(Interest::READABLE, || {
+                super::udp_msg::recv_msg(self.io.as_raw_fd(), buf)
+            })
+            .await
+    }
+
+    /// Tries to receive a single datagram together with its control
+    /// messages.
+    ///
+    /// This is supported on Linux and Android only.
+    ///
+    /// # Errors
+    ///
+    /// If there is no datagram to read, `Err(io::ErrorKind::WouldBlock)` is
+    /// returned.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn try_recv_msg(&self, buf: &mut [u8]) -> io::Result<crate::net::RecvMsg> {
+        use std::os::unix::io::AsRawFd;
+
+        self.io.registration().try_io(Interest::READABLE, || {
+            super::udp_msg::recv_msg(self.io.as_raw_fd(), buf)
+        })
+    }
+
+    /// Sends a datagram together with control messages.
+    ///
+    /// The [`SendMsg`] value selects the destination address, the source
+    /// address (`IP_PKTINFO` / `IPV6_PKTINFO`) and the ECN codepoint to mark
+    /// the packet with. When no target is set, the datagram is sent to the
+    /// connected peer.
+    ///
+    /// This is supported on Linux and Android only.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If `send_msg` is used as the event in a
+    /// [`tokio::select!`](crate::select) statement and some other branch
+    /// completes first, it is guaranteed that the message was not sent.
+    ///
+    /// [`SendMsg`]: crate::net::SendMsg
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub async fn send_msg(&self, buf: &[u8], msg: &crate::net::SendMsg) -> io::Result<usize> {
+        use std::os::unix::io::AsRawFd;
+
+        self.io
+            .registration()
+            .async_io(Interest::WRITABLE, || {
+                super::udp_msg::send_msg(self.io.as_raw_fd(), buf, msg)
+            })
+            .await
+    }
+
+    /// Tries to send a datagram together with control messages.
+    ///
+    /// This is supported on Linux and Android only.
+    ///
+    /// # Errors
+    ///
+    /// If the socket is not writable, `Err(io::ErrorKind::WouldBlock)` is
+    /// returned.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn try_send_msg(&self, buf: &[u8], msg: &crate::net::SendMsg) -> io::Result<usize> {
+        use std::os::unix::io::AsRawFd;
+
+        self.io.registration().try_io(Interest::WRITABLE, || {
+            super::udp_msg::send_msg(self.io.as_raw_fd(), buf, msg)
+        })
+    }
+
+    /// Sets whether received datagrams report their ECN codepoint.
+    ///
+    /// Enables the `IP_RECVTOS` option on IPv4 sockets and `IPV6_RECVTCLASS`
+    /// on IPv6 sockets, populating [`RecvMsg::ecn`] for datagrams received
+    /// with [`recv_msg`].
+    ///
+    /// This is supported on Linux and Android only.
+    ///
+    /// [`RecvMsg::ecn`]: crate::net::RecvMsg#structfield.ecn
+    /// [`recv_msg`]: method@Self::recv_msg
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn set_recv_ecn(&self, enabled: bool) -> io::Result<()> {
+        use std::os::unix::io::AsRawFd;
+
+        let fd = self.io.as_raw_fd();
+
+        if self.local_addr()?.is_ipv4() {
+            super::udp_msg::set_opt(fd, libc::IPPROTO_IP, libc::IP_RECVTOS, enabled)
+        } else {
+            super::udp_msg::set_opt(fd, libc::IPPROTO_IPV6, libc::IPV6_RECVTCLASS, enabled)
+        }
+    }
+
+    /// Sets whether received datagrams report their destination address.
+    ///
+    /// Enables the `IP_PKTINFO` option on IPv4 sockets and
+    /// `IPV6_RECVPKTINFO` on IPv6 sockets, populating [`RecvMsg::dst_ip`]
+    /// for datagrams received with [`recv_msg`].
+    ///
+    /// This is supported on Linux and Android only.
+    ///
+    /// [`RecvMsg::dst_ip`]: crate::net::RecvMsg#structfield.dst_ip
+    /// [`recv_msg`]: method@Self::recv_msg
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn set_recv_pktinfo(&self, enabled: bool) -> io::Result<()> {
+        use std::os::unix::io::AsRawFd;
+
+        let fd = self.io.as_raw_fd();
+
+        if self.local_addr()?.is_ipv4() {
+            super::udp_msg::set_opt(fd, libc::IPPROTO_IP, libc::IP_PKTINFO, enabled)
+        } else {
+            super::udp_msg::set_opt(fd, libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO, enabled)
+        }
+    }
+
+    /// Sets whether received datagrams report a kernel receive timestamp.
+    ///
+    /// Enables the `SO_TIMESTAMPNS` option, populating
+    /// [`RecvMsg::timestamp`] for datagrams received with [`recv_msg`].
+    ///
+    /// This is supported on Linux and Android only.
+    ///
+    /// [`RecvMsg::timestamp`]: crate::net::RecvMsg#structfield.timestamp
+    /// [`recv_msg`]: method@Self::recv_msg
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn set_recv_timestamp(&self, enabled: bool) -> io::Result<()> {
+        use std::os::unix::io::AsRawFd;
+
+        super::udp_msg::set_opt(
+            self.io.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_TIMESTAMPNS,
+            enabled,
+        )
+    }
+
+    /// Attaches a classic BPF program steering incoming datagrams within a
+    /// `SO_REUSEPORT` socket group.
+    ///
+    /// The program runs for each datagram arriving on the shared port and
+    /// returns the index of the group socket that should receive it,
+    /// replacing the kernel's default four-tuple hash. The socket must have
+    /// been bound with `SO_REUSEPORT` enabled, and only one socket of the
+    /// group needs to carry the program.
+    ///
+    /// This sets the `SO_ATTACH_REUSEPORT_CBPF` option and is supported on
+    /// Linux and Android only.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn attach_reuseport_cbpf(&self, program: &[crate::net::SockFilter]) -> io::Result<()> {
+        use std::os::unix::io::AsRawFd;
+
+        super::reuseport::attach_cbpf(self.io.as_raw_fd(), program)
+    }
+
+    /// Attaches an extended BPF program steering incoming datagrams within a
+    /// `SO_REUSEPORT` socket group.
+    ///
+    /// `program_fd` is the file descriptor of a loaded
+    /// `BPF_PROG_TYPE_SK_REUSEPORT` program, obtained from the `bpf(2)`
+    /// system call. Like [`attach_reuseport_cbpf`], the program returns the
+    /// index of the group socket that should receive each datagram.
+    ///
+    /// This sets the `SO_ATTACH_REUSEPORT_EBPF` option and is supported on
+    /// Linux and Android only.
+    ///
+    /// [`attach_reuseport_cbpf`]: method@Self::attach_reuseport_cbpf
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[cfg_attr(docsrs, doc(cfg(any(target_os = "linux", target_os = "android"))))]
+    pub fn attach_reuseport_ebpf(
+        &self,
+        program_fd: std::os::unix::io::RawFd,
+    ) -> io::Result<()> {
+        use std::os::unix::io::AsRawFd;
+
+        super::reuseport::attach_ebpf(self.io.as_raw_fd(), program_fd)
+    }
+
     /// Gets the value of the `SO_BROADCAST` option for this socket.
     ///
     /// For more information about this option, see [`set_broadcast`].
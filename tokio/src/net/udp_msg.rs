@@ -0,0 +1,422 @@
+//! Control message support for [`UdpSocket`](crate::net::UdpSocket).
+//!
+//! The `recv_msg` and `send_msg` socket methods exchange datagrams together
+//! with ancillary data: the ECN bits of the IP header, the destination
+//! address a packet was received on (`IP_PKTINFO` / `IPV6_PKTINFO`), and
+//! kernel receive timestamps. This is the information QUIC implementations
+//! need and which plain `recv_from` cannot surface.
+//!
+//! This module is only available on Linux and Android.
+
+use std::io;
+use std::mem::{self, MaybeUninit};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::os::unix::io::RawFd;
+use std::time::{Duration, SystemTime};
+
+/// An Explicit Congestion Notification codepoint, carried in the two low
+/// bits of the IP TOS / traffic-class byte.
+///
+/// The "not ECN-capable" codepoint (`0b00`) is represented as `None` in the
+/// APIs that use this type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcnCodepoint {
+    /// ECN-capable transport, codepoint `0b10`.
+    Ect0,
+    /// ECN-capable transport, codepoint `0b01`.
+    Ect1,
+    /// Congestion experienced, codepoint `0b11`.
+    Ce,
+}
+
+impl EcnCodepoint {
+    /// Extracts the codepoint from a TOS / traffic-class byte.
+    pub(crate) fn from_bits(bits: u8) -> Option<EcnCodepoint> {
+        match bits & 0b11 {
+            0b10 => Some(EcnCodepoint::Ect0),
+            0b01 => Some(EcnCodepoint::Ect1),
+            0b11 => Some(EcnCodepoint::Ce),
+            _ => None,
+        }
+    }
+
+    /// Returns the two-bit codepoint value.
+    pub(crate) fn bits(self) -> u8 {
+        match self {
+            EcnCodepoint::Ect0 => 0b10,
+            EcnCodepoint::Ect1 => 0b01,
+            EcnCodepoint::Ce => 0b11,
+        }
+    }
+}
+
+/// Metadata describing a datagram received with
+/// [`recv_msg`](crate::net::UdpSocket::recv_msg).
+///
+/// Which of the optional fields are populated depends on the socket options
+/// enabled beforehand; see [`set_recv_ecn`], [`set_recv_pktinfo`] and
+/// [`set_recv_timestamp`].
+///
+/// [`set_recv_ecn`]: crate::net::UdpSocket::set_recv_ecn
+/// [`set_recv_pktinfo`]: crate::net::UdpSocket::set_recv_pktinfo
+/// [`set_recv_timestamp`]: crate::net::UdpSocket::set_recv_timestamp
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RecvMsg {
+    /// The number of bytes copied into the buffer.
+    pub len: usize,
+
+    /// Whether the datagram was larger than the buffer and had its tail
+    /// discarded.
+    pub truncated: bool,
+
+    /// The remote address the datagram was sent from.
+    pub addr: SocketAddr,
+
+    /// The local address the datagram was sent to, if
+    /// [`set_recv_pktinfo`](crate::net::UdpSocket::set_recv_pktinfo) is
+    /// enabled.
+    pub dst_ip: Option<IpAddr>,
+
+    /// The ECN codepoint of the IP header, if
+    /// [`set_recv_ecn`](crate::net::UdpSocket::set_recv_ecn) is enabled and
+    /// the sender marked the packet.
+    pub ecn: Option<EcnCodepoint>,
+
+    /// The kernel receive timestamp, if
+    /// [`set_recv_timestamp`](crate::net::UdpSocket::set_recv_timestamp) is
+    /// enabled.
+    pub timestamp: Option<SystemTime>,
+}
+
+/// Ancillary data attached to a datagram sent with
+/// [`send_msg`](crate::net::UdpSocket::send_msg).
+///
+/// The default value sends to the connected peer with no control messages,
+/// making `send_msg` equivalent to [`send`](crate::net::UdpSocket::send).
+#[derive(Debug, Clone, Default)]
+pub struct SendMsg {
+    pub(crate) target: Option<SocketAddr>,
+    pub(crate) src_ip: Option<IpAddr>,
+    pub(crate) ecn: Option<EcnCodepoint>,
+}
+
+impl SendMsg {
+    /// Creates an empty `SendMsg`.
+    pub fn new() -> SendMsg {
+        SendMsg::default()
+    }
+
+    /// Sets the destination address of the datagram.
+    ///
+    /// When not set, the datagram is sent to the address the socket is
+    /// connected to.
+    pub fn target(mut self, target: SocketAddr) -> SendMsg {
+        self.target = Some(target);
+        self
+    }
+
+    /// Sets the source address of the datagram via an `IP_PKTINFO` /
+    /// `IPV6_PKTINFO` control message.
+    ///
+    /// This selects which local address the datagram is sent from, which
+    /// matters for sockets bound to a wildcard address on multihomed hosts.
+    pub fn src_ip(mut self, src_ip: IpAddr) -> SendMsg {
+        self.src_ip = Some(src_ip);
+        self
+    }
+
+    /// Sets the ECN codepoint to mark the datagram with.
+    ///
+    /// The control message family follows the target address when one is
+    /// set, otherwise the source address, otherwise IPv4.
+    pub fn ecn(mut self, ecn: EcnCodepoint) -> SendMsg {
+        self.ecn = Some(ecn);
+        self
+    }
+}
+
+/// Room for the pktinfo, TOS and timestamp control messages, with slack for
+/// ancillary data this module does not decode.
+const CMSG_LEN: usize = 128;
+
+/// Storage for the control message buffer.
+///
+/// `CMSG_FIRSTHDR` / `CMSG_NXTHDR` return pointers into the buffer that are
+/// dereferenced as `cmsghdr`, so the storage must be at least as aligned as
+/// that type; a plain `[u8; CMSG_LEN]` (alignment 1) would make those
+/// dereferences undefined behavior. `u64` alignment is sufficient for
+/// `cmsghdr` on every libc target.
+type CmsgBuf = [u64; CMSG_LEN / 8];
+
+/// Performs a single non-blocking `recvmsg(2)` call, decoding the control
+/// messages this module understands.
+pub(super) fn recv_msg(fd: RawFd, buf: &mut [u8]) -> io::Result<RecvMsg> {
+    let mut addr: MaybeUninit<libc::sockaddr_storage> = MaybeUninit::zeroed();
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    let mut control: CmsgBuf = [0; CMSG_LEN / 8];
+
+    let mut hdr: libc::msghdr = unsafe { mem::zeroed() };
+    hdr.msg_name = addr.as_mut_ptr() as *mut libc::c_void;
+    hdr.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    hdr.msg_iov = &mut iov;
+    hdr.msg_iovlen = 1;
+    hdr.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    hdr.msg_controllen = CMSG_LEN as _;
+
+    let n = unsafe { libc::recvmsg(fd, &mut hdr, 0) };
+
+    if n < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut msg = RecvMsg {
+        len: n as usize,
+        truncated: hdr.msg_flags & libc::MSG_TRUNC != 0,
+        addr: decode_addr(unsafe { &*addr.as_ptr() })?,
+        dst_ip: None,
+        ecn: None,
+        timestamp: None,
+    };
+
+    // Walk the control messages the kernel attached, ignoring any this
+    // module does not understand.
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&hdr);
+
+        while !cmsg.is_null() {
+            match ((*cmsg).cmsg_level, (*cmsg).cmsg_type) {
+                (libc::IPPROTO_IP, libc::IP_TOS) => {
+                    let tos = *(libc::CMSG_DATA(cmsg) as *const u8);
+                    msg.ecn = EcnCodepoint::from_bits(tos);
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_TCLASS) => {
+                    let tclass = *(libc::CMSG_DATA(cmsg) as *const libc::c_int);
+                    msg.ecn = EcnCodepoint::from_bits(tclass as u8);
+                }
+                (libc::IPPROTO_IP, libc::IP_PKTINFO) => {
+                    let pktinfo = &*(libc::CMSG_DATA(cmsg) as *const libc::in_pktinfo);
+                    let ip = Ipv4Addr::from(u32::from_be(pktinfo.ipi_addr.s_addr));
+                    msg.dst_ip = Some(IpAddr::V4(ip));
+                }
+                (libc::IPPROTO_IPV6, libc::IPV6_PKTINFO) => {
+                    let pktinfo = &*(libc::CMSG_DATA(cmsg) as *const libc::in6_pktinfo);
+                    msg.dst_ip = Some(IpAddr::V6(Ipv6Addr::from(pktinfo.ipi6_addr.s6_addr)));
+                }
+                (libc::SOL_SOCKET, libc::SCM_TIMESTAMPNS) => {
+                    let ts = &*(libc::CMSG_DATA(cmsg) as *const libc::timespec);
+                    let elapsed = Duration::new(ts.tv_sec as u64, ts.tv_nsec as u32);
+                    msg.timestamp = Some(SystemTime::UNIX_EPOCH + elapsed);
+                }
+                _ => {}
+            }
+
+            cmsg = libc::CMSG_NXTHDR(&hdr, cmsg);
+        }
+    }
+
+    Ok(msg)
+}
+
+/// Performs a single non-blocking `sendmsg(2)` call, encoding the requested
+/// control messages.
+pub(super) fn send_msg(fd: RawFd, buf: &[u8], msg: &SendMsg) -> io::Result<usize> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+    let mut control: CmsgBuf = [0; CMSG_LEN / 8];
+
+    let mut hdr: libc::msghdr = unsafe { mem::zeroed() };
+    hdr.msg_iov = &mut iov;
+    hdr.msg_iovlen = 1;
+
+    let mut v4 = MaybeUninit::<libc::sockaddr_in>::uninit();
+    let mut v6 = MaybeUninit::<libc::sockaddr_in6>::uninit();
+
+    if let Some(target) = msg.target {
+        let (name, namelen) = encode_addr(target, &mut v4, &mut v6);
+        hdr.msg_name = name;
+        hdr.msg_namelen = namelen;
+    }
+
+    // The ECN control message family follows the target, then the source,
+    // defaulting to IPv4.
+    let is_ipv6 = match (msg.target, msg.src_ip) {
+        (Some(target), _) => target.is_ipv6(),
+        (None, Some(src_ip)) => src_ip.is_ipv6(),
+        (None, None) => false,
+    };
+
+    hdr.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+    hdr.msg_controllen = CMSG_LEN as _;
+
+    let mut len = 0;
+
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&hdr);
+
+        if let Some(ecn) = msg.ecn {
+            let (level, ty) = if is_ipv6 {
+                (libc::IPPROTO_IPV6, libc::IPV6_TCLASS)
+            } else {
+                (libc::IPPROTO_IP, libc::IP_TOS)
+            };
+            cmsg = encode_cmsg(cmsg, &hdr, level, ty, libc::c_int::from(ecn.bits()), &mut len);
+        }
+
+        match msg.src_ip {
+            Some(IpAddr::V4(ip)) => {
+                let mut pktinfo: libc::in_pktinfo = mem::zeroed();
+                pktinfo.ipi_spec_dst.s_addr = u32::from(ip).to_be();
+                cmsg = encode_cmsg(
+                    cmsg,
+                    &hdr,
+                    libc::IPPROTO_IP,
+                    libc::IP_PKTINFO,
+                    pktinfo,
+                    &mut len,
+                );
+            }
+            Some(IpAddr::V6(ip)) => {
+                let mut pktinfo: libc::in6_pktinfo = mem::zeroed();
+                pktinfo.ipi6_addr.s6_addr = ip.octets();
+                cmsg = encode_cmsg(
+                    cmsg,
+                    &hdr,
+                    libc::IPPROTO_IPV6,
+                    libc::IPV6_PKTINFO,
+                    pktinfo,
+                    &mut len,
+                );
+            }
+            None => {}
+        }
+
+        let _ = cmsg;
+    }
+
+    if len == 0 {
+        hdr.msg_control = std::ptr::null_mut();
+    }
+    hdr.msg_controllen = len as _;
+
+    let n = unsafe { libc::sendmsg(fd, &hdr, 0) };
+
+    if n < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(n as usize)
+}
+
+/// Sets a boolean socket option via `setsockopt(2)`.
+pub(super) fn set_opt(
+    fd: RawFd,
+    level: libc::c_int,
+    name: libc::c_int,
+    enabled: bool,
+) -> io::Result<()> {
+    let value: libc::c_int = if enabled { 1 } else { 0 };
+
+    let res = unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            name,
+            &value as *const libc::c_int as *const libc::c_void,
+            mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+
+    if res < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Writes one control message into the buffer reserved in `hdr`, returning
+/// the position for the next one.
+unsafe fn encode_cmsg<T>(
+    cmsg: *mut libc::cmsghdr,
+    hdr: &libc::msghdr,
+    level: libc::c_int,
+    ty: libc::c_int,
+    value: T,
+    len: &mut usize,
+) -> *mut libc::cmsghdr {
+    assert!(!cmsg.is_null(), "control message buffer too small");
+
+    (*cmsg).cmsg_level = level;
+    (*cmsg).cmsg_type = ty;
+    (*cmsg).cmsg_len = libc::CMSG_LEN(mem::size_of::<T>() as u32) as _;
+    std::ptr::write_unaligned(libc::CMSG_DATA(cmsg) as *mut T, value);
+
+    *len += libc::CMSG_SPACE(mem::size_of::<T>() as u32) as usize;
+
+    libc::CMSG_NXTHDR(hdr, cmsg)
+}
+
+fn decode_addr(addr: &libc::sockaddr_storage) -> io::Result<SocketAddr> {
+    match addr.ss_family as libc::c_int {
+        libc::AF_INET => {
+            let addr = unsafe { &*(addr as *const _ as *const libc::sockaddr_in) };
+            let ip = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
+            Ok(SocketAddr::V4(SocketAddrV4::new(
+                ip,
+                u16::from_be(addr.sin_port),
+            )))
+        }
+        libc::AF_INET6 => {
+            let addr = unsafe { &*(addr as *const _ as *const libc::sockaddr_in6) };
+            let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                u16::from_be(addr.sin6_port),
+                addr.sin6_flowinfo,
+                addr.sin6_scope_id,
+            )))
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unexpected address family",
+        )),
+    }
+}
+
+fn encode_addr(
+    addr: SocketAddr,
+    v4: &mut MaybeUninit<libc::sockaddr_in>,
+    v6: &mut MaybeUninit<libc::sockaddr_in6>,
+) -> (*mut libc::c_void, libc::socklen_t) {
+    match addr {
+        SocketAddr::V4(addr) => {
+            let mut sockaddr: libc::sockaddr_in = unsafe { mem::zeroed() };
+            sockaddr.sin_family = libc::AF_INET as libc::sa_family_t;
+            sockaddr.sin_port = addr.port().to_be();
+            sockaddr.sin_addr.s_addr = u32::from(*addr.ip()).to_be();
+            v4.write(sockaddr);
+            (
+                v4.as_mut_ptr() as *mut libc::c_void,
+                mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+            )
+        }
+        SocketAddr::V6(addr) => {
+            let mut sockaddr: libc::sockaddr_in6 = unsafe { mem::zeroed() };
+            sockaddr.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            sockaddr.sin6_port = addr.port().to_be();
+            sockaddr.sin6_addr.s6_addr = addr.ip().octets();
+            sockaddr.sin6_flowinfo = addr.flowinfo();
+            sockaddr.sin6_scope_id = addr.scope_id();
+            v6.write(sockaddr);
+            (
+                v6.as_mut_ptr() as *mut libc::c_void,
+                mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t,
+            )
+        }
+    }
+}
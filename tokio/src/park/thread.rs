@@ -109,7 +109,20 @@ impl Inner {
         }
 
         loop {
-            m = self.condvar.wait(m).unwrap();
+            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+            {
+                m = self.condvar.wait(m).unwrap();
+            }
+
+            // There are no condition variables on `wasm32-unknown-unknown`;
+            // block through the host instead. The host is allowed to return
+            // early, which is indistinguishable from a spurious wakeup.
+            #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+            {
+                drop(m);
+                crate::util::wasm_host::park(None);
+                m = self.mutex.lock();
+            }
 
             if self
                 .state
@@ -157,8 +170,16 @@ impl Inner {
         // from a notification, we just want to unconditionally set the state back to
         // empty, either consuming a notification or un-flagging ourselves as
         // parked.
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
         let (_m, _result) = self.condvar.wait_timeout(m, dur).unwrap();
 
+        // As in `park`, block through the host on `wasm32-unknown-unknown`.
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        {
+            drop(m);
+            crate::util::wasm_host::park(Some(dur));
+        }
+
         match self.state.swap(EMPTY, SeqCst) {
             NOTIFIED => {} // got a notification, hurray!
             PARKED => {}   // no notification, alas
@@ -0,0 +1,120 @@
+//! Per-task attribution for allocator shims.
+//!
+//! The runtime brackets every task poll with a thread-local marker holding
+//! the identifier of the task being polled. A custom [`GlobalAlloc`]
+//! implementation can read the marker from its `alloc` / `dealloc` paths and
+//! attribute each allocation to the task that made it, which is what makes it
+//! possible to find the one task out of thousands that is leaking memory.
+//!
+//! Task identifiers are opaque `u64` values. They are unique among live tasks
+//! and match [`JoinHandle::task_id`], but may be reused after a task is
+//! dropped.
+//!
+//! In addition to the marker, a pair of process-wide hooks can be installed
+//! with [`set_poll_hooks`]. The hooks are plain function pointers so that
+//! invoking them never allocates, and they run on the worker thread
+//! immediately before and after each poll.
+//!
+//! # Examples
+//!
+//! An allocator shim counting the bytes allocated while each task is being
+//! polled:
+//!
+//! ```no_run
+//! use std::alloc::{GlobalAlloc, Layout, System};
+//!
+//! struct TrackingAlloc;
+//!
+//! unsafe impl GlobalAlloc for TrackingAlloc {
+//!     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+//!         if let Some(task_id) = tokio::runtime::alloc_track::current_task() {
+//!             // Record `layout.size()` against `task_id`. The recording
+//!             // data structure must not itself allocate.
+//!             let _ = task_id;
+//!         }
+//!         System.alloc(layout)
+//!     }
+//!
+//!     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+//!         System.dealloc(ptr, layout)
+//!     }
+//! }
+//! ```
+//!
+//! [`GlobalAlloc`]: std::alloc::GlobalAlloc
+//! [`JoinHandle::task_id`]: crate::task::JoinHandle::task_id
+
+use std::cell::Cell;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering::Relaxed};
+
+thread_local! {
+    /// Identifier of the task currently being polled on this thread.
+    static CURRENT_TASK: Cell<Option<u64>> = Cell::new(None);
+}
+
+// Poll hooks, stored as type-erased `fn(u64)` pointers. Null means no hook
+// is installed.
+static POLL_START: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+static POLL_END: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Returns the identifier of the task currently being polled on this thread,
+/// or `None` when called outside a task poll.
+///
+/// This function never allocates and is safe to call from a global allocator.
+pub fn current_task() -> Option<u64> {
+    // `try_with` avoids panicking when called during thread-local
+    // destruction, which an allocator shim may well do.
+    CURRENT_TASK.try_with(Cell::get).unwrap_or(None)
+}
+
+/// Installs process-wide hooks invoked immediately before and after every
+/// task poll.
+///
+/// Both hooks receive the identifier of the polled task. They are plain
+/// function pointers so installing and invoking them never allocates.
+/// Installing new hooks replaces the previous ones; polls already in flight
+/// may still complete with the hooks that were installed when they started.
+pub fn set_poll_hooks(on_poll_start: fn(u64), on_poll_end: fn(u64)) {
+    POLL_START.store(on_poll_start as *mut (), Relaxed);
+    POLL_END.store(on_poll_end as *mut (), Relaxed);
+}
+
+fn hook(cell: &AtomicPtr<()>) -> Option<fn(u64)> {
+    let f = cell.load(Relaxed);
+
+    if f.is_null() {
+        None
+    } else {
+        // Safety: the only non-null values stored are `fn(u64)` pointers.
+        Some(unsafe { std::mem::transmute::<*mut (), fn(u64)>(f) })
+    }
+}
+
+/// Marks `id` as the task being polled on this thread until the returned
+/// guard is dropped.
+pub(crate) fn enter(id: u64) -> PollGuard {
+    let prev = CURRENT_TASK.with(|cell| cell.replace(Some(id)));
+
+    if let Some(f) = hook(&POLL_START) {
+        f(id);
+    }
+
+    PollGuard { id, prev }
+}
+
+/// Restores the previous marker and fires the poll-end hook on drop.
+pub(crate) struct PollGuard {
+    id: u64,
+    prev: Option<u64>,
+}
+
+impl Drop for PollGuard {
+    fn drop(&mut self) {
+        if let Some(f) = hook(&POLL_END) {
+            f(self.id);
+        }
+
+        let _ = CURRENT_TASK.try_with(|cell| cell.set(self.prev));
+    }
+}
@@ -3,6 +3,7 @@ use crate::loom::sync::atomic::AtomicBool;
 use crate::loom::sync::Mutex;
 use crate::park::{Park, Unpark};
 use crate::runtime::task::{self, JoinHandle, Schedule, Task};
+use crate::runtime::{Callback, UnhandledPanic};
 use crate::sync::notify::Notify;
 use crate::util::linked_list::{Link, LinkedList};
 use crate::util::{waker_ref, Wake, WakerRef};
@@ -49,6 +50,12 @@ struct Inner<P: Park> {
 
     /// Thread park handle
     park: P,
+
+    /// Callback fired before the thread parks with no work available.
+    on_idle: Option<Callback>,
+
+    /// Callback fired when the thread is unparked after being idle.
+    on_busy: Option<Callback>,
 }
 
 #[derive(Clone)]
@@ -92,6 +99,17 @@ struct Shared {
 
     // indicates whether the blocked on thread was woken
     woken: AtomicBool,
+
+    /// How to respond to a task panicking.
+    unhandled_panic_behavior: UnhandledPanic,
+
+    /// Set when a task panicked and the runtime is configured to shut down on
+    /// unhandled panic.
+    unhandled_panic: AtomicBool,
+
+    /// Custom task ordering policy, if any.
+    #[cfg(tokio_unstable)]
+    schedule_policy: Option<Arc<dyn crate::runtime::scheduler::SchedulePolicy>>,
 }
 
 /// Thread-local context.
@@ -119,7 +137,15 @@ const REMOTE_FIRST_INTERVAL: u8 = 31;
 scoped_thread_local!(static CURRENT: Context);
 
 impl<P: Park> BasicScheduler<P> {
-    pub(crate) fn new(park: P) -> BasicScheduler<P> {
+    pub(crate) fn new(
+        park: P,
+        unhandled_panic: UnhandledPanic,
+        on_idle: Option<Callback>,
+        on_busy: Option<Callback>,
+        #[cfg(tokio_unstable)] schedule_policy: Option<
+            Arc<dyn crate::runtime::scheduler::SchedulePolicy>,
+        >,
+    ) -> BasicScheduler<P> {
         let unpark = Box::new(park.unpark());
 
         let spawner = Spawner {
@@ -127,6 +153,10 @@ impl<P: Park> BasicScheduler<P> {
                 queue: Mutex::new(VecDeque::with_capacity(INITIAL_CAPACITY)),
                 unpark: unpark as Box<dyn Unpark>,
                 woken: AtomicBool::new(false),
+                unhandled_panic_behavior: unhandled_panic,
+                unhandled_panic: AtomicBool::new(false),
+                #[cfg(tokio_unstable)]
+                schedule_policy,
             }),
         };
 
@@ -138,6 +168,8 @@ impl<P: Park> BasicScheduler<P> {
             spawner: spawner.clone(),
             tick: 0,
             park,
+            on_idle,
+            on_busy,
         }));
 
         BasicScheduler {
@@ -242,16 +274,33 @@ impl<P: Park> Inner<P> {
                     let entry = match entry {
                         Some(entry) => entry,
                         None => {
+                            // The runtime transitions to idle: there is no
+                            // work available and the only thread is about to
+                            // park.
+                            if let Some(f) = &scheduler.on_idle {
+                                f();
+                            }
+
                             // Park until the thread is signaled
                             scheduler.park.park().expect("failed to park");
 
+                            if let Some(f) = &scheduler.on_busy {
+                                f();
+                            }
+
                             // Try polling the `block_on` future next
                             continue 'outer;
                         }
                     };
 
                     match entry {
-                        Entry::Schedule(task) => crate::coop::budget(|| task.run()),
+                        Entry::Schedule(task) => {
+                            crate::coop::budget(|| task.run());
+
+                            if scheduler.spawner.shared.unhandled_panic.load(Acquire) {
+                                panic!("a spawned task panicked and the runtime is configured to shut down on unhandled panic");
+                            }
+                        }
                         Entry::Release(ptr) => {
                             // Safety: the task header is only legally provided
                             // internally in the header, so we know that it is a
@@ -412,6 +461,30 @@ impl fmt::Debug for Spawner {
 
 // ===== impl Shared =====
 
+#[cfg(tokio_unstable)]
+impl Shared {
+    /// Consult the installed policy, if any, for where to place a task that
+    /// has become runnable.
+    fn placement(
+        &self,
+        task: &task::Notified<Arc<Shared>>,
+        queue_depth: usize,
+    ) -> crate::runtime::scheduler::Placement {
+        use crate::runtime::scheduler::{Placement, TaskMeta};
+
+        match &self.schedule_policy {
+            Some(policy) => {
+                let header: *const task::Header = task.header();
+                policy.placement(&TaskMeta {
+                    task_id: header as u64,
+                    queue_depth,
+                })
+            }
+            None => Placement::Back,
+        }
+    }
+}
+
 impl Schedule for Arc<Shared> {
     fn bind(task: Task<Self>) -> Arc<Shared> {
         CURRENT.with(|maybe_cx| {
@@ -442,14 +515,44 @@ impl Schedule for Arc<Shared> {
     fn schedule(&self, task: task::Notified<Self>) {
         CURRENT.with(|maybe_cx| match maybe_cx {
             Some(cx) if Arc::ptr_eq(self, &cx.shared) => {
-                cx.tasks.borrow_mut().queue.push_back(task);
+                let mut tasks = cx.tasks.borrow_mut();
+
+                #[cfg(tokio_unstable)]
+                if let crate::runtime::scheduler::Placement::Front =
+                    self.placement(&task, tasks.queue.len())
+                {
+                    tasks.queue.push_front(task);
+                    return;
+                }
+
+                tasks.queue.push_back(task);
             }
             _ => {
-                self.queue.lock().push_back(Entry::Schedule(task));
+                let mut queue = self.queue.lock();
+
+                #[cfg(tokio_unstable)]
+                if let crate::runtime::scheduler::Placement::Front =
+                    self.placement(&task, queue.len())
+                {
+                    queue.push_front(Entry::Schedule(task));
+                    drop(queue);
+                    self.unpark.unpark();
+                    return;
+                }
+
+                queue.push_back(Entry::Schedule(task));
+                drop(queue);
                 self.unpark.unpark();
             }
         });
     }
+
+    fn unhandled_panic(&self) {
+        if self.unhandled_panic_behavior == UnhandledPanic::ShutdownRuntime {
+            self.unhandled_panic.store(true, Release);
+            self.unpark.unpark();
+        }
+    }
 }
 
 impl Wake for Shared {
@@ -4,7 +4,10 @@
 //! compilation.
 
 mod pool;
-pub(crate) use pool::{spawn_blocking, BlockingPool, Spawner};
+pub(crate) use pool::{spawn_blocking, spawn_blocking_on, BlockingPool, Spawner};
+
+mod named;
+pub(crate) use named::NamedPools;
 
 mod schedule;
 mod shutdown;
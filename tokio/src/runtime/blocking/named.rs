@@ -0,0 +1,78 @@
+//! Registry of named blocking pools.
+//!
+//! Operators create pools up front through [`Handle::create_blocking_pool`]
+//! and libraries target them by name with `spawn_blocking_on`, keeping heavy
+//! blocking work (e.g. a storage engine) off the shared pool without the two
+//! parties having to coordinate through globals.
+//!
+//! [`Handle::create_blocking_pool`]: crate::runtime::Handle::create_blocking_pool
+
+use crate::loom::sync::{Arc, Mutex};
+use crate::runtime::blocking::{BlockingPool, Spawner};
+use crate::runtime::Builder;
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// The set of additional blocking pools owned by a runtime, keyed by name.
+///
+/// Cloning is shallow; all handles to a runtime share one registry. The
+/// runtime shuts the pools down together with the default blocking pool.
+#[derive(Clone)]
+pub(crate) struct NamedPools {
+    shared: Arc<Mutex<HashMap<String, BlockingPool>>>,
+}
+
+impl NamedPools {
+    pub(crate) fn new() -> NamedPools {
+        NamedPools {
+            shared: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Creates a pool under `name` with the given thread cap.
+    ///
+    /// Returns `false` without modifying anything if a pool with this name
+    /// already exists.
+    pub(crate) fn create(&self, name: &str, max_threads: usize) -> bool {
+        let mut shared = self.shared.lock();
+
+        if shared.contains_key(name) {
+            return false;
+        }
+
+        // Only the thread configuration is read from the builder; the pool
+        // does not get a scheduler of its own.
+        let mut builder = Builder::new_current_thread();
+        builder.thread_name(format!("tokio-{}-blocking", name));
+
+        shared.insert(name.to_string(), BlockingPool::new(&builder, max_threads));
+
+        true
+    }
+
+    pub(crate) fn contains(&self, name: &str) -> bool {
+        self.shared.lock().contains_key(name)
+    }
+
+    /// Returns the spawner for the pool registered under `name`, if any.
+    pub(crate) fn spawner(&self, name: &str) -> Option<Spawner> {
+        self.shared.lock().get(name).map(|pool| pool.spawner().clone())
+    }
+
+    /// Shuts down every registered pool, giving each up to `timeout` to
+    /// drain, mirroring what the runtime does for the default pool.
+    pub(crate) fn shutdown(&self, timeout: Option<Duration>) {
+        let mut shared = self.shared.lock();
+
+        for pool in shared.values_mut() {
+            pool.shutdown(timeout);
+        }
+    }
+}
+
+impl std::fmt::Debug for NamedPools {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("NamedPools").finish()
+    }
+}
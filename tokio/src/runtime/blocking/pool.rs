@@ -47,6 +47,9 @@ struct Inner {
     // Maximum number of threads
     thread_cap: usize,
 
+    // Number of threads kept alive even when idle
+    thread_floor: usize,
+
     // Customizable wait timeout
     keep_alive: Duration,
 }
@@ -86,6 +89,17 @@ where
     rt.spawn_blocking(func)
 }
 
+/// Runs the provided function on the named blocking pool of the current
+/// runtime, falling back to the default pool if no such pool exists.
+pub(crate) fn spawn_blocking_on<F, R>(name: &str, func: F) -> JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let rt = context::current().expect(CONTEXT_MISSING_ERROR);
+    rt.spawn_blocking_on(name, func)
+}
+
 #[allow(dead_code)]
 pub(crate) fn try_spawn_blocking<F, R>(func: F) -> Result<(), ()>
 where
@@ -125,6 +139,7 @@ impl BlockingPool {
                     after_start: builder.after_start.clone(),
                     before_stop: builder.before_stop.clone(),
                     thread_cap,
+                    thread_floor: builder.min_blocking_threads.min(thread_cap),
                     keep_alive,
                 }),
             },
@@ -186,6 +201,9 @@ impl fmt::Debug for BlockingPool {
 
 impl Spawner {
     pub(crate) fn spawn(&self, task: Task, rt: &Handle) -> Result<(), ()> {
+        #[cfg(tokio_unstable)]
+        crate::runtime::console::blocking_task_spawned();
+
         let shutdown_tx = {
             let mut shared = self.inner.shared.lock();
 
@@ -237,6 +255,37 @@ impl Spawner {
         Ok(())
     }
 
+    /// Spawns idle worker threads until the pool holds the configured floor.
+    ///
+    /// Called once when the runtime is built so that the first burst of
+    /// `spawn_blocking` calls does not pay thread-creation latency. The
+    /// spawned threads do not exit on the keep-alive timeout; see
+    /// `Inner::run`.
+    pub(crate) fn warmup(&self, rt: &Handle) {
+        let mut shared = self.inner.shared.lock();
+
+        while shared.num_th < self.inner.thread_floor && !shared.shutdown {
+            shared.num_th += 1;
+
+            assert!(shared.shutdown_tx.is_some());
+            let shutdown_tx = shared.shutdown_tx.clone().unwrap();
+
+            let id = shared.worker_thread_index;
+            shared.worker_thread_index += 1;
+
+            let handle = self.spawn_thread(shutdown_tx, rt, id);
+
+            shared.worker_threads.insert(id, handle);
+        }
+    }
+
+    /// Returns `(threads, idle threads, queue depth)` for the pool.
+    #[cfg(tokio_unstable)]
+    pub(crate) fn metrics(&self) -> (usize, usize, usize) {
+        let shared = self.inner.shared.lock();
+        (shared.num_th, shared.num_idle as usize, shared.queue.len())
+    }
+
     fn spawn_thread(
         &self,
         shutdown_tx: shutdown::Sender,
@@ -251,11 +300,15 @@ impl Spawner {
 
         let rt = rt.clone();
 
+        // Run this spawner's pool, which for named pools is not the one held
+        // by the runtime handle.
+        let spawner = self.clone();
+
         builder
             .spawn(move || {
                 // Only the reference should be moved into the closure
                 let _enter = crate::runtime::context::enter(rt.clone());
-                rt.blocking_spawner.inner.run(id);
+                spawner.inner.run(id);
                 drop(shutdown_tx);
             })
             .unwrap()
@@ -299,7 +352,13 @@ impl Inner {
 
                 // Even if the condvar "timed out", if the pool is entering the
                 // shutdown phase, we want to perform the cleanup logic.
-                if !shared.shutdown && timeout_result.timed_out() {
+                //
+                // Threads within the warm floor ignore the keep-alive timeout
+                // and go back to waiting.
+                if !shared.shutdown
+                    && timeout_result.timed_out()
+                    && shared.num_th > self.thread_floor
+                {
                     // We'll join the prior timed-out thread's JoinHandle after dropping the lock.
                     // This isn't done when shutting down, because the thread calling shutdown will
                     // handle joining everything.
@@ -58,6 +58,9 @@ pub struct Builder {
     /// Cap on thread usage.
     max_blocking_threads: usize,
 
+    /// Floor of blocking threads kept alive even when idle.
+    pub(super) min_blocking_threads: usize,
+
     /// Name fn used for threads spawned by the runtime.
     pub(super) thread_name: ThreadNameFn,
 
@@ -70,8 +73,51 @@ pub struct Builder {
     /// To run before each worker thread stops
     pub(super) before_stop: Option<Callback>,
 
+    /// To run when the last active worker parks
+    pub(super) on_idle: Option<Callback>,
+
+    /// To run when a worker is unparked while the runtime is idle
+    pub(super) on_busy: Option<Callback>,
+
     /// Customizable keep alive timeout for BlockingPool
     pub(super) keep_alive: Option<Duration>,
+
+    /// How to respond to a task panicking.
+    pub(super) unhandled_panic: UnhandledPanic,
+
+    /// Custom parking layer replacing the built-in driver stack.
+    ///
+    /// Consumed by `build`.
+    park_driver: Option<Box<dyn crate::runtime::ParkDriver>>,
+
+    /// Custom task ordering policy for the current-thread scheduler.
+    #[cfg(tokio_unstable)]
+    pub(super) schedule_policy: Option<std::sync::Arc<dyn crate::runtime::scheduler::SchedulePolicy>>,
+}
+
+/// How the runtime should respond to a task panicking.
+///
+/// Set with [`Builder::unhandled_panic`]. By default, a panic in a spawned
+/// task is only surfaced through its [`JoinHandle`].
+///
+/// [`Builder::unhandled_panic`]: Builder::unhandled_panic
+/// [`JoinHandle`]: crate::task::JoinHandle
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum UnhandledPanic {
+    /// The runtime ignores the panic. It is only surfaced to the application
+    /// through the task's [`JoinHandle`].
+    ///
+    /// This is the default behavior.
+    ///
+    /// [`JoinHandle`]: crate::task::JoinHandle
+    Ignore,
+
+    /// The runtime shuts down immediately. The panic is resurfaced out of the
+    /// call to [`Runtime::block_on`].
+    ///
+    /// [`Runtime::block_on`]: crate::runtime::Runtime::block_on
+    ShutdownRuntime,
 }
 
 pub(crate) type ThreadNameFn = std::sync::Arc<dyn Fn() -> String + Send + Sync + 'static>;
@@ -126,6 +172,9 @@ impl Builder {
 
             max_blocking_threads: 512,
 
+            // No blocking threads are kept warm by default
+            min_blocking_threads: 0,
+
             // Default thread name
             thread_name: std::sync::Arc::new(|| "tokio-runtime-worker".into()),
 
@@ -136,7 +185,21 @@ impl Builder {
             after_start: None,
             before_stop: None,
 
+            // No idle / busy transition callbacks
+            on_idle: None,
+            on_busy: None,
+
             keep_alive: None,
+
+            // Unhandled panics are only surfaced through the `JoinHandle`
+            unhandled_panic: UnhandledPanic::Ignore,
+
+            // Park on the built-in driver stack
+            park_driver: None,
+
+            // Default FIFO task ordering
+            #[cfg(tokio_unstable)]
+            schedule_policy: None,
         }
     }
 
@@ -248,6 +311,108 @@ impl Builder {
         self
     }
 
+    /// Specifies the number of blocking threads spawned eagerly and kept warm.
+    ///
+    /// By default, blocking threads are created lazily the first time they are
+    /// needed and exit once left idle for longer than [`thread_keep_alive`].
+    /// Setting a floor pre-spawns `val` threads when the runtime is built and
+    /// exempts that many threads from the keep-alive timeout, so an initial
+    /// burst of [`spawn_blocking`] calls (DNS lookups, TLS handshakes, file
+    /// I/O at startup) does not pay thread-creation latency.
+    ///
+    /// Values larger than [`max_blocking_threads`] are clamped to it.
+    ///
+    /// The default value is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime;
+    ///
+    /// let rt = runtime::Builder::new_multi_thread()
+    ///     .min_blocking_threads(4)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// [`spawn_blocking`]: fn@crate::task::spawn_blocking
+    /// [`max_blocking_threads`]: Self::max_blocking_threads
+    /// [`thread_keep_alive`]: Self::thread_keep_alive
+    pub fn min_blocking_threads(&mut self, val: usize) -> &mut Self {
+        self.min_blocking_threads = val;
+        self
+    }
+
+    /// Configure how the runtime responds to a task panicking.
+    ///
+    /// By default, a panic in a spawned task is caught and only surfaced to
+    /// the application through the task's [`JoinHandle`]
+    /// ([`UnhandledPanic::Ignore`]). With [`UnhandledPanic::ShutdownRuntime`],
+    /// the runtime shuts down as soon as a task panics and the panic is
+    /// resurfaced out of [`Runtime::block_on`].
+    ///
+    /// This option is only supported by the current-thread runtime.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if called on a builder for the multi thread runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```should_panic
+    /// use tokio::runtime::{self, UnhandledPanic};
+    ///
+    /// # pub fn main() {
+    /// let rt = runtime::Builder::new_current_thread()
+    ///     .unhandled_panic(UnhandledPanic::ShutdownRuntime)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// rt.spawn(async { panic!("boom"); });
+    ///
+    /// // The panic is resurfaced here, even though the future itself never
+    /// // completes.
+    /// rt.block_on(async {
+    ///     std::future::pending::<()>().await;
+    /// });
+    /// # }
+    /// ```
+    ///
+    /// [`JoinHandle`]: crate::task::JoinHandle
+    /// [`Runtime::block_on`]: crate::runtime::Runtime::block_on
+    pub fn unhandled_panic(&mut self, behavior: UnhandledPanic) -> &mut Self {
+        assert!(
+            matches!(self.kind, Kind::CurrentThread),
+            "the `unhandled_panic` option is only supported by the current-thread runtime"
+        );
+        self.unhandled_panic = behavior;
+        self
+    }
+
+    /// Installs a custom task ordering policy on the current-thread scheduler.
+    ///
+    /// The policy is consulted every time a task becomes runnable and decides
+    /// where the task is placed in the run queue. See
+    /// [`runtime::scheduler`](crate::runtime::scheduler) for details.
+    ///
+    /// This option is only supported by the current-thread runtime.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if called on a builder for the multi thread runtime.
+    #[cfg(tokio_unstable)]
+    pub fn schedule_policy<P>(&mut self, policy: P) -> &mut Self
+    where
+        P: crate::runtime::scheduler::SchedulePolicy,
+    {
+        assert!(
+            matches!(self.kind, Kind::CurrentThread),
+            "the `schedule_policy` option is only supported by the current-thread runtime"
+        );
+        self.schedule_policy = Some(std::sync::Arc::new(policy));
+        self
+    }
+
     /// Sets name of threads spawned by the `Runtime`'s thread pool.
     ///
     /// The default name is "tokio-runtime-worker".
@@ -374,6 +539,93 @@ impl Builder {
         self
     }
 
+    /// Executes function `f` when the runtime transitions to idle, i.e. when
+    /// the last active worker thread parks with no work available.
+    ///
+    /// Together with [`on_runtime_busy`], this enables power-management
+    /// integrations and scale-to-zero triggers in serverless hosts: the pair
+    /// of callbacks brackets the periods during which the runtime is doing no
+    /// work.
+    ///
+    /// The callback runs on a worker thread about to park and must not block.
+    /// A wakeup racing with the transition may cause the runtime to become
+    /// busy again at any point, including while the callback runs.
+    ///
+    /// [`on_runtime_busy`]: method@Self::on_runtime_busy
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio::runtime;
+    /// # pub fn main() {
+    /// let runtime = runtime::Builder::new_multi_thread()
+    ///     .on_runtime_idle(|| {
+    ///         println!("runtime is idle");
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    #[cfg(not(loom))]
+    pub fn on_runtime_idle<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_idle = Some(std::sync::Arc::new(f));
+        self
+    }
+
+    /// Executes function `f` when the idle runtime transitions back to busy,
+    /// i.e. when a worker thread is unparked while all workers are parked.
+    ///
+    /// See [`on_runtime_idle`] for the idle side of the transition.
+    ///
+    /// The callback runs on the thread triggering the wakeup, which may be a
+    /// runtime worker or any thread interacting with the runtime, and must
+    /// not block.
+    ///
+    /// [`on_runtime_idle`]: method@Self::on_runtime_idle
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use tokio::runtime;
+    /// # pub fn main() {
+    /// let runtime = runtime::Builder::new_multi_thread()
+    ///     .on_runtime_busy(|| {
+    ///         println!("runtime is busy");
+    ///     })
+    ///     .build();
+    /// # }
+    /// ```
+    #[cfg(not(loom))]
+    pub fn on_runtime_busy<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_busy = Some(std::sync::Arc::new(f));
+        self
+    }
+
+    /// Replaces the built-in driver stack with a custom parking layer.
+    ///
+    /// Idle worker threads park on `driver` instead of Tokio's own I/O and
+    /// time drivers, allowing an external event loop (a GUI main loop, libusb
+    /// or a custom epoll set) to be the thing workers block on. See
+    /// [`ParkDriver`] for the required contract and an example.
+    ///
+    /// The driver is consumed by [`build`]. Because it replaces the built-in
+    /// stack, combining it with [`enable_io`] or [`enable_time`] causes
+    /// `build` to panic.
+    ///
+    /// [`ParkDriver`]: crate::runtime::ParkDriver
+    /// [`build`]: method@Self::build
+    /// [`enable_io`]: method@Self::enable_io
+    /// [`enable_time`]: method@Self::enable_time
+    pub fn park_driver(&mut self, driver: impl crate::runtime::ParkDriver) -> &mut Self {
+        self.park_driver = Some(Box::new(driver));
+        self
+    }
+
     /// Creates the configured `Runtime`.
     ///
     /// The returned `Runtime` instance is ready to spawn tasks.
@@ -390,6 +642,10 @@ impl Builder {
     /// });
     /// ```
     pub fn build(&mut self) -> io::Result<Runtime> {
+        if self.park_driver.is_some() && (self.enable_io || self.enable_time) {
+            panic!("a custom park driver cannot be combined with `enable_io` or `enable_time`");
+        }
+
         match &self.kind {
             Kind::CurrentThread => self.build_basic_runtime(),
             #[cfg(feature = "rt-multi-thread")]
@@ -397,7 +653,7 @@ impl Builder {
         }
     }
 
-    fn get_cfg(&self) -> driver::Cfg {
+    fn get_cfg(&mut self) -> driver::Cfg {
         driver::Cfg {
             enable_pause_time: match self.kind {
                 Kind::CurrentThread => true,
@@ -407,6 +663,7 @@ impl Builder {
             enable_io: self.enable_io,
             enable_time: self.enable_time,
             start_paused: self.start_paused,
+            park_driver: self.park_driver.take(),
         }
     }
 
@@ -441,23 +698,37 @@ impl Builder {
         // there are no futures ready to do something, it'll let the timer or
         // the reactor to generate some new stimuli for the futures to continue
         // in their life.
-        let scheduler = BasicScheduler::new(driver);
+        let scheduler = BasicScheduler::new(
+            driver,
+            self.unhandled_panic.clone(),
+            self.on_idle.clone(),
+            self.on_busy.clone(),
+            #[cfg(tokio_unstable)]
+            self.schedule_policy.clone(),
+        );
         let spawner = Spawner::Basic(scheduler.spawner().clone());
 
         // Blocking pool
         let blocking_pool = blocking::create_blocking_pool(self, self.max_blocking_threads);
         let blocking_spawner = blocking_pool.spawner().clone();
 
+        let handle = Handle {
+            spawner,
+            id: crate::runtime::handle::next_id(),
+            io_handle: resources.io_handle,
+            time_handle: resources.time_handle,
+            signal_handle: resources.signal_handle,
+            clock: resources.clock,
+            blocking_spawner,
+            named_pools: blocking::NamedPools::new(),
+        };
+
+        // Pre-spawn the configured floor of blocking threads
+        blocking_pool.spawner().warmup(&handle);
+
         Ok(Runtime {
             kind: Kind::CurrentThread(scheduler),
-            handle: Handle {
-                spawner,
-                io_handle: resources.io_handle,
-                time_handle: resources.time_handle,
-                signal_handle: resources.signal_handle,
-                clock: resources.clock,
-                blocking_spawner,
-            },
+            handle,
             blocking_pool,
         })
     }
@@ -546,7 +817,12 @@ cfg_rt_multi_thread! {
 
             let (driver, resources) = driver::Driver::new(self.get_cfg())?;
 
-            let (scheduler, launch) = ThreadPool::new(core_threads, Parker::new(driver));
+            let (scheduler, launch) = ThreadPool::new(
+                core_threads,
+                Parker::new(driver),
+                self.on_idle.clone(),
+                self.on_busy.clone(),
+            );
             let spawner = Spawner::ThreadPool(scheduler.spawner().clone());
 
             // Create the blocking pool
@@ -556,13 +832,18 @@ cfg_rt_multi_thread! {
             // Create the runtime handle
             let handle = Handle {
                 spawner,
+                id: crate::runtime::handle::next_id(),
                 io_handle: resources.io_handle,
                 time_handle: resources.time_handle,
                 signal_handle: resources.signal_handle,
                 clock: resources.clock,
                 blocking_spawner,
+                named_pools: blocking::NamedPools::new(),
             };
 
+            // Pre-spawn the configured floor of blocking threads
+            blocking_pool.spawner().warmup(&handle);
+
             // Spawn the thread pool workers
             let _enter = crate::runtime::context::enter(handle.clone());
             launch.launch();
@@ -581,6 +862,7 @@ impl fmt::Debug for Builder {
         fmt.debug_struct("Builder")
             .field("worker_threads", &self.worker_threads)
             .field("max_blocking_threads", &self.max_blocking_threads)
+            .field("min_blocking_threads", &self.min_blocking_threads)
             .field(
                 "thread_name",
                 &"<dyn Fn() -> String + Send + Sync + 'static>",
@@ -588,6 +870,9 @@ impl fmt::Debug for Builder {
             .field("thread_stack_size", &self.thread_stack_size)
             .field("after_start", &self.after_start.as_ref().map(|_| "..."))
             .field("before_stop", &self.after_start.as_ref().map(|_| "..."))
+            .field("on_idle", &self.on_idle.as_ref().map(|_| "..."))
+            .field("on_busy", &self.on_busy.as_ref().map(|_| "..."))
+            .field("park_driver", &self.park_driver.as_ref().map(|_| "..."))
             .finish()
     }
 }
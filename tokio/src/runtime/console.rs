@@ -0,0 +1,205 @@
+//! A lightweight publisher for task and resource instrumentation.
+//!
+//! This module aggregates process-wide counters (task spawns, polls, wakes,
+//! and resource waits) and serves them over a local TCP socket so that a
+//! console or TUI can attach to any process without the application wiring up
+//! a subscriber stack.
+//!
+//! # Wire format
+//!
+//! The wire format is line-oriented UTF-8 and is considered stable. On
+//! connect, the server sends a single header line:
+//!
+//! ```text
+//! tokio-console v1
+//! ```
+//!
+//! It then sends one snapshot line per publish interval. Each line is a
+//! space-separated list of `key=value` pairs:
+//!
+//! ```text
+//! tasks_spawned=10 tasks_completed=7 task_polls=124 task_wakes=87 resource_waits=3
+//! ```
+//!
+//! New keys may be added over time; consumers must ignore keys they do not
+//! recognize.
+
+use std::io::{self, Write};
+use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering::Relaxed};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Process-wide instrumentation counters.
+pub(crate) struct Counters {
+    pub(crate) tasks_spawned: AtomicU64,
+    pub(crate) tasks_completed: AtomicU64,
+    pub(crate) task_polls: AtomicU64,
+    pub(crate) task_wakes: AtomicU64,
+    pub(crate) resource_waits: AtomicU64,
+    pub(crate) sync_waiters: AtomicU64,
+    pub(crate) blocking_tasks_spawned: AtomicU64,
+    pub(crate) io_ready_events: AtomicU64,
+    pub(crate) timer_fires: AtomicU64,
+}
+
+pub(crate) static COUNTERS: Counters = Counters {
+    tasks_spawned: AtomicU64::new(0),
+    tasks_completed: AtomicU64::new(0),
+    task_polls: AtomicU64::new(0),
+    task_wakes: AtomicU64::new(0),
+    resource_waits: AtomicU64::new(0),
+    sync_waiters: AtomicU64::new(0),
+    blocking_tasks_spawned: AtomicU64::new(0),
+    io_ready_events: AtomicU64::new(0),
+    timer_fires: AtomicU64::new(0),
+};
+
+pub(crate) fn task_spawned() {
+    COUNTERS.tasks_spawned.fetch_add(1, Relaxed);
+}
+
+pub(crate) fn task_completed() {
+    COUNTERS.tasks_completed.fetch_add(1, Relaxed);
+}
+
+pub(crate) fn task_polled() {
+    COUNTERS.task_polls.fetch_add(1, Relaxed);
+}
+
+pub(crate) fn task_woken() {
+    COUNTERS.task_wakes.fetch_add(1, Relaxed);
+}
+
+pub(crate) fn resource_wait_started() {
+    COUNTERS.resource_waits.fetch_add(1, Relaxed);
+}
+
+// The number of tracked sync waiters is a gauge, not a counter: it goes back
+// down when a waiter acquires the resource or gives up.
+pub(crate) fn sync_wait_started() {
+    COUNTERS.sync_waiters.fetch_add(1, Relaxed);
+}
+
+pub(crate) fn sync_wait_finished() {
+    COUNTERS.sync_waiters.fetch_sub(1, Relaxed);
+}
+
+pub(crate) fn blocking_task_spawned() {
+    COUNTERS.blocking_tasks_spawned.fetch_add(1, Relaxed);
+}
+
+pub(crate) fn io_ready_event() {
+    COUNTERS.io_ready_events.fetch_add(1, Relaxed);
+}
+
+pub(crate) fn timer_fired() {
+    COUNTERS.timer_fires.fetch_add(1, Relaxed);
+}
+
+impl Counters {
+    fn render(&self) -> String {
+        format!(
+            "tasks_spawned={} tasks_completed={} task_polls={} task_wakes={} resource_waits={} \
+             blocking_tasks_spawned={} io_ready_events={} timer_fires={} sync_waiters={}\n",
+            self.tasks_spawned.load(Relaxed),
+            self.tasks_completed.load(Relaxed),
+            self.task_polls.load(Relaxed),
+            self.task_wakes.load(Relaxed),
+            self.resource_waits.load(Relaxed),
+            self.blocking_tasks_spawned.load(Relaxed),
+            self.io_ready_events.load(Relaxed),
+            self.timer_fires.load(Relaxed),
+            self.sync_waiters.load(Relaxed),
+        )
+    }
+}
+
+/// Handle to a running console publisher.
+///
+/// The publisher serves instrumentation snapshots to every connected client
+/// until this handle is dropped. Created by [`serve`].
+#[derive(Debug)]
+pub struct ConsoleServer {
+    local_addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+}
+
+/// Starts a console publisher listening on `addr`.
+///
+/// A dedicated thread accepts connections; each client is served a snapshot
+/// of the instrumentation counters once per second. The publisher runs until
+/// the returned [`ConsoleServer`] is dropped.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// let server = tokio::runtime::console::serve("127.0.0.1:0")?;
+/// println!("console listening on {}", server.local_addr());
+/// # Ok(())
+/// # }
+/// ```
+pub fn serve<A: ToSocketAddrs>(addr: A) -> io::Result<ConsoleServer> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    let shutdown = Arc::new(AtomicBool::new(false));
+
+    let accept_shutdown = shutdown.clone();
+    std::thread::Builder::new()
+        .name("tokio-console-publisher".into())
+        .spawn(move || accept_loop(listener, accept_shutdown))?;
+
+    Ok(ConsoleServer {
+        local_addr,
+        shutdown,
+    })
+}
+
+impl ConsoleServer {
+    /// Returns the local address the publisher is listening on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for ConsoleServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Relaxed);
+        // Connect to our own listener to wake the accept loop up so it can
+        // observe the shutdown flag.
+        let _ = std::net::TcpStream::connect(self.local_addr);
+    }
+}
+
+fn accept_loop(listener: TcpListener, shutdown: Arc<AtomicBool>) {
+    loop {
+        let stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(_) => return,
+        };
+
+        if shutdown.load(Relaxed) {
+            return;
+        }
+
+        let client_shutdown = shutdown.clone();
+        let _ = std::thread::Builder::new()
+            .name("tokio-console-client".into())
+            .spawn(move || client_loop(stream, client_shutdown));
+    }
+}
+
+fn client_loop(mut stream: std::net::TcpStream, shutdown: Arc<AtomicBool>) {
+    if stream.write_all(b"tokio-console v1\n").is_err() {
+        return;
+    }
+
+    while !shutdown.load(Relaxed) {
+        if stream.write_all(COUNTERS.render().as_bytes()).is_err() {
+            return;
+        }
+
+        std::thread::sleep(Duration::from_secs(1));
+    }
+}
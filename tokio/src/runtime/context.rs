@@ -1,5 +1,5 @@
 //! Thread local runtime context
-use crate::runtime::Handle;
+use crate::runtime::{Handle, TryCurrentError};
 
 use std::cell::RefCell;
 
@@ -11,6 +11,14 @@ pub(crate) fn current() -> Option<Handle> {
     CONTEXT.with(|ctx| ctx.borrow().clone())
 }
 
+pub(crate) fn try_current() -> Result<Handle, TryCurrentError> {
+    match CONTEXT.try_with(|ctx| ctx.borrow().clone()) {
+        Ok(Some(handle)) => Ok(handle),
+        Ok(None) => Err(TryCurrentError::new_no_context()),
+        Err(_) => Err(TryCurrentError::new_thread_local_destroyed()),
+    }
+}
+
 cfg_io_driver! {
     pub(crate) fn io_handle() -> crate::runtime::driver::IoHandle {
         CONTEXT.with(|ctx| {
@@ -18,6 +26,13 @@ cfg_io_driver! {
             ctx.as_ref().expect(crate::util::error::CONTEXT_MISSING_ERROR).io_handle.clone()
         })
     }
+
+    /// Like `io_handle`, but returns `None` when there is no runtime context
+    /// instead of panicking.
+    #[cfg(debug_assertions)]
+    pub(crate) fn try_io_handle() -> Option<crate::runtime::driver::IoHandle> {
+        CONTEXT.with(|ctx| (*ctx.borrow()).as_ref().map(|ctx| ctx.io_handle.clone()))
+    }
 }
 
 cfg_signal_internal! {
@@ -38,6 +53,13 @@ cfg_time! {
         })
     }
 
+    /// Like `time_handle`, but returns `None` when there is no runtime context
+    /// instead of panicking.
+    #[cfg(debug_assertions)]
+    pub(crate) fn try_time_handle() -> Option<crate::runtime::driver::TimeHandle> {
+        CONTEXT.with(|ctx| (*ctx.borrow()).as_ref().map(|ctx| ctx.time_handle.clone()))
+    }
+
     cfg_test_util! {
         pub(crate) fn clock() -> Option<crate::runtime::driver::Clock> {
             CONTEXT.with(|ctx| (*ctx.borrow()).as_ref().map(|ctx| ctx.clock.clone()))
@@ -46,8 +68,8 @@ cfg_time! {
 }
 
 cfg_rt! {
-    pub(crate) fn spawn_handle() -> Option<crate::runtime::Spawner> {
-        CONTEXT.with(|ctx| (*ctx.borrow()).as_ref().map(|ctx| ctx.spawner.clone()))
+    pub(crate) fn spawn_handle() -> Option<(crate::runtime::Spawner, crate::runtime::RuntimeId)> {
+        CONTEXT.with(|ctx| (*ctx.borrow()).as_ref().map(|ctx| (ctx.spawner.clone(), ctx.id)))
     }
 }
 
@@ -2,6 +2,7 @@
 use crate::park::thread::ParkThread;
 use crate::park::Park;
 
+use std::fmt;
 use std::io;
 use std::time::Duration;
 
@@ -148,7 +149,18 @@ cfg_not_time! {
 
 #[derive(Debug)]
 pub(crate) struct Driver {
-    inner: TimeDriver,
+    inner: Inner,
+}
+
+enum Inner {
+    /// The built-in driver stack: I/O, signal, process and time drivers, or a
+    /// condvar park when those are disabled.
+    Stack(TimeDriver),
+
+    /// A user-provided park driver. See [`Builder::park_driver`].
+    ///
+    /// [`Builder::park_driver`]: crate::runtime::Builder::park_driver
+    Custom(Box<dyn crate::runtime::ParkDriver>),
 }
 
 pub(crate) struct Resources {
@@ -163,19 +175,37 @@ pub(crate) struct Cfg {
     pub(crate) enable_time: bool,
     pub(crate) enable_pause_time: bool,
     pub(crate) start_paused: bool,
+    pub(crate) park_driver: Option<Box<dyn crate::runtime::ParkDriver>>,
 }
 
 impl Driver {
     pub(crate) fn new(cfg: Cfg) -> io::Result<(Self, Resources)> {
-        let (io_stack, io_handle, signal_handle) = create_io_stack(cfg.enable_io)?;
-
         let clock = create_clock(cfg.enable_pause_time, cfg.start_paused);
 
+        // A custom park driver replaces the entire built-in stack.
+        if let Some(park_driver) = cfg.park_driver {
+            return Ok((
+                Self {
+                    inner: Inner::Custom(park_driver),
+                },
+                Resources {
+                    io_handle: Default::default(),
+                    signal_handle: Default::default(),
+                    time_handle: Default::default(),
+                    clock,
+                },
+            ));
+        }
+
+        let (io_stack, io_handle, signal_handle) = create_io_stack(cfg.enable_io)?;
+
         let (time_driver, time_handle) =
             create_time_driver(cfg.enable_time, io_stack, clock.clone());
 
         Ok((
-            Self { inner: time_driver },
+            Self {
+                inner: Inner::Stack(time_driver),
+            },
             Resources {
                 io_handle,
                 signal_handle,
@@ -187,22 +217,72 @@ impl Driver {
 }
 
 impl Park for Driver {
-    type Unpark = <TimeDriver as Park>::Unpark;
+    type Unpark = Unpark;
     type Error = <TimeDriver as Park>::Error;
 
-    fn unpark(&self) -> Self::Unpark {
-        self.inner.unpark()
+    fn unpark(&self) -> Unpark {
+        match &self.inner {
+            Inner::Stack(driver) => Unpark::Stack(driver.unpark()),
+            Inner::Custom(driver) => Unpark::Custom(driver.handle()),
+        }
     }
 
     fn park(&mut self) -> Result<(), Self::Error> {
-        self.inner.park()
+        match &mut self.inner {
+            Inner::Stack(driver) => driver.park(),
+            Inner::Custom(driver) => {
+                driver.park();
+                Ok(())
+            }
+        }
     }
 
     fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
-        self.inner.park_timeout(duration)
+        match &mut self.inner {
+            Inner::Stack(driver) => driver.park_timeout(duration),
+            Inner::Custom(driver) => {
+                driver.park_timeout(duration);
+                Ok(())
+            }
+        }
     }
 
     fn shutdown(&mut self) {
-        self.inner.shutdown()
+        match &mut self.inner {
+            Inner::Stack(driver) => driver.shutdown(),
+            Inner::Custom(driver) => driver.shutdown(),
+        }
+    }
+}
+
+impl fmt::Debug for Inner {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inner::Stack(driver) => driver.fmt(fmt),
+            Inner::Custom(_) => fmt.write_str("Custom(..)"),
+        }
+    }
+}
+
+pub(crate) enum Unpark {
+    Stack(<TimeDriver as Park>::Unpark),
+    Custom(std::sync::Arc<dyn crate::runtime::ParkHandle>),
+}
+
+impl crate::park::Unpark for Unpark {
+    fn unpark(&self) {
+        match self {
+            Unpark::Stack(unpark) => unpark.unpark(),
+            Unpark::Custom(handle) => handle.unpark(),
+        }
+    }
+}
+
+impl fmt::Debug for Unpark {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unpark::Stack(unpark) => unpark.fmt(fmt),
+            Unpark::Custom(_) => fmt.write_str("Custom(..)"),
+        }
     }
 }
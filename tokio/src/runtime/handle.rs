@@ -1,9 +1,10 @@
 use crate::runtime::blocking::task::BlockingTask;
 use crate::runtime::task::{self, JoinHandle};
 use crate::runtime::{blocking, context, driver, Spawner};
-use crate::util::error::CONTEXT_MISSING_ERROR;
+use crate::util::error::{CONTEXT_MISSING_ERROR, THREAD_LOCAL_DESTROYED_ERROR};
 
 use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
 use std::{error, fmt};
 
 /// Handle to the runtime.
@@ -16,6 +17,9 @@ use std::{error, fmt};
 pub struct Handle {
     pub(super) spawner: Spawner,
 
+    /// Identifies the runtime this handle belongs to.
+    pub(super) id: RuntimeId,
+
     /// Handles to the I/O drivers
     pub(super) io_handle: driver::IoHandle,
 
@@ -30,6 +34,46 @@ pub struct Handle {
 
     /// Blocking pool spawner
     pub(super) blocking_spawner: blocking::Spawner,
+
+    /// Additional blocking pools addressable by name
+    pub(super) named_pools: blocking::NamedPools,
+}
+
+/// The flavor of a runtime, as selected with [`Builder::new_current_thread`]
+/// or [`Builder::new_multi_thread`].
+///
+/// Returned by [`Handle::runtime_flavor`].
+///
+/// [`Builder::new_current_thread`]: crate::runtime::Builder::new_current_thread
+/// [`Builder::new_multi_thread`]: crate::runtime::Builder::new_multi_thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RuntimeFlavor {
+    /// The flavor that executes all tasks on the current thread.
+    CurrentThread,
+    /// The flavor that executes tasks across multiple threads.
+    MultiThread,
+}
+
+/// An opaque identifier for a runtime.
+///
+/// Returned by [`Handle::id`]. Identifiers are unique among runtimes in a
+/// process and are never reused, so a stored identifier always refers to at
+/// most one runtime. The identifier implements `Eq` and `Hash` and can be used
+/// as a map key; the value it displays as carries no meaning beyond identity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RuntimeId(u64);
+
+impl fmt::Display for RuntimeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Returns a runtime identifier that has not been used yet.
+pub(super) fn next_id() -> RuntimeId {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+    RuntimeId(NEXT_ID.fetch_add(1, Relaxed) as u64)
 }
 
 /// Runtime context guard.
@@ -104,11 +148,104 @@ impl Handle {
 
     /// Returns a Handle view over the currently running Runtime
     ///
-    /// Returns an error if no Runtime has been started
-    ///
-    /// Contrary to `current`, this never panics
+    /// Contrary to `current`, this never panics. The returned error
+    /// distinguishes between a thread that is simply not running inside a
+    /// runtime and a thread whose runtime context is being torn down; see
+    /// [`TryCurrentError`] for how to tell the cases apart.
     pub fn try_current() -> Result<Self, TryCurrentError> {
-        context::current().ok_or(TryCurrentError(()))
+        context::try_current()
+    }
+
+    /// Returns the identifier of the runtime this handle belongs to.
+    ///
+    /// Identifiers are unique among runtimes in the process and never reused,
+    /// so they can be stored by libraries to recognize the runtime they were
+    /// initialized on. Two handles refer to the same runtime if and only if
+    /// their identifiers compare equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    ///
+    /// let rt = Runtime::new().unwrap();
+    /// let rt2 = Runtime::new().unwrap();
+    ///
+    /// assert_eq!(rt.handle().id(), rt.handle().id());
+    /// assert_ne!(rt.handle().id(), rt2.handle().id());
+    /// ```
+    pub fn id(&self) -> RuntimeId {
+        self.id
+    }
+
+    /// Returns `true` if the provided task was spawned on this runtime.
+    ///
+    /// This covers tasks spawned through [`tokio::spawn`], [`Handle::spawn`],
+    /// and the `spawn_blocking` variants. Tasks spawned onto a
+    /// [`LocalSet`] are not owned by any runtime handle.
+    ///
+    /// [`tokio::spawn`]: crate::spawn
+    /// [`LocalSet`]: crate::task::LocalSet
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    ///
+    /// let rt = Runtime::new().unwrap();
+    /// let rt2 = Runtime::new().unwrap();
+    ///
+    /// let task = rt.spawn(async {});
+    ///
+    /// assert!(rt.handle().owns(&task));
+    /// assert!(!rt2.handle().owns(&task));
+    /// # rt.block_on(task).unwrap();
+    /// ```
+    pub fn owns<T>(&self, task: &JoinHandle<T>) -> bool {
+        task.runtime_id() == Some(self.id)
+    }
+
+    /// Returns the flavor of the runtime this handle belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::{Handle, RuntimeFlavor};
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     assert_eq!(Handle::current().runtime_flavor(), RuntimeFlavor::CurrentThread);
+    /// }
+    /// ```
+    pub fn runtime_flavor(&self) -> RuntimeFlavor {
+        match self.spawner {
+            #[cfg(feature = "rt")]
+            Spawner::Basic(_) => RuntimeFlavor::CurrentThread,
+            #[cfg(feature = "rt-multi-thread")]
+            Spawner::ThreadPool(_) => RuntimeFlavor::MultiThread,
+        }
+    }
+
+    /// Returns `true` if the current thread is running inside the runtime this
+    /// handle belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::{Handle, Runtime};
+    ///
+    /// let rt = Runtime::new().unwrap();
+    /// let other = Runtime::new().unwrap();
+    ///
+    /// rt.block_on(async {
+    ///     assert!(Handle::current().is_current());
+    ///     assert!(!other.handle().is_current());
+    /// });
+    /// ```
+    pub fn is_current(&self) -> bool {
+        context::try_current()
+            .map(|current| current.id == self.id)
+            .unwrap_or(false)
     }
 
     /// Spawn a future onto the Tokio runtime.
@@ -146,7 +283,56 @@ impl Handle {
     {
         #[cfg(all(tokio_unstable, feature = "tracing"))]
         let future = crate::util::trace::task(future, "task");
-        self.spawner.spawn(future)
+        let mut handle = self.spawner.spawn(future);
+        handle.set_runtime_id(self.id);
+        handle
+    }
+
+    /// Spawns a batch of futures onto the Tokio runtime, returning their
+    /// join handles in order.
+    ///
+    /// This is equivalent to calling [`spawn`] for each future, but the tasks
+    /// are enqueued in one pass over the scheduler's queues, which is
+    /// noticeably cheaper when spawning a large number of tasks at once.
+    ///
+    /// [`spawn`]: Handle::spawn
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    ///
+    /// let rt = Runtime::new().unwrap();
+    /// let handle = rt.handle();
+    ///
+    /// let handles = handle.spawn_iter((0..10).map(|i| async move { i * 2 }));
+    ///
+    /// rt.block_on(async move {
+    ///     let mut total = 0;
+    ///     for handle in handles {
+    ///         total += handle.await.unwrap();
+    ///     }
+    ///     assert_eq!(total, 90);
+    /// });
+    /// ```
+    pub fn spawn_iter<I>(&self, iter: I) -> Vec<JoinHandle<<I::Item as Future>::Output>>
+    where
+        I: IntoIterator,
+        I::Item: Future + Send + 'static,
+        <I::Item as Future>::Output: Send + 'static,
+    {
+        #[cfg(all(tokio_unstable, feature = "tracing"))]
+        let iter = iter
+            .into_iter()
+            .map(|future| crate::util::trace::task(future, "task"));
+
+        let mut handles = self.spawner.spawn_iter(iter);
+
+        for handle in &mut handles {
+            handle.set_runtime_id(self.id);
+        }
+
+        handles
     }
 
     /// Run the provided function on an executor dedicated to blocking
@@ -198,11 +384,130 @@ impl Handle {
                 func()
             }
         };
-        let (task, handle) = task::joinable(BlockingTask::new(func));
+        let (task, mut handle) = task::joinable(BlockingTask::new(func));
+        handle.set_runtime_id(self.id);
         let _ = self.blocking_spawner.spawn(task, &self);
         handle
     }
 
+    /// Creates a named blocking pool with its own thread cap.
+    ///
+    /// Work is directed to the pool with [`spawn_blocking_on`] or
+    /// [`task::spawn_blocking_on`]. This lets an application isolate a
+    /// category of blocking work — a storage engine, DNS resolution — from
+    /// the default pool, so neither can starve the other of threads.
+    ///
+    /// Returns `true` if the pool was created, `false` if a pool with this
+    /// name already exists (the existing pool is left untouched).
+    ///
+    /// The pool's threads are named `tokio-<name>-blocking` and are shut down
+    /// together with the runtime.
+    ///
+    /// [`spawn_blocking_on`]: method@Self::spawn_blocking_on
+    /// [`task::spawn_blocking_on`]: crate::task::spawn_blocking_on
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    ///
+    /// # fn dox() {
+    /// let rt = Runtime::new().unwrap();
+    /// let handle = rt.handle();
+    ///
+    /// handle.create_blocking_pool("rocksdb", 4);
+    ///
+    /// handle.spawn_blocking_on("rocksdb", || {
+    ///     // runs on a "rocksdb" pool thread
+    /// });
+    /// # }
+    /// ```
+    pub fn create_blocking_pool(&self, name: &str, max_threads: usize) -> bool {
+        self.named_pools.create(name, max_threads)
+    }
+
+    /// Returns `true` if a blocking pool with the given name exists on this
+    /// runtime.
+    pub fn has_blocking_pool(&self, name: &str) -> bool {
+        self.named_pools.contains(name)
+    }
+
+    /// Runs the provided function on the named blocking pool.
+    ///
+    /// If no pool was registered under `name`, the function runs on the
+    /// default blocking pool instead; a library can target a pool
+    /// unconditionally and leave it to the operator to decide whether that
+    /// pool gets dedicated threads. Use [`create_blocking_pool`] to register
+    /// a pool and [`has_blocking_pool`] to check for one.
+    ///
+    /// Aside from pool selection, this behaves like [`spawn_blocking`].
+    ///
+    /// [`create_blocking_pool`]: method@Self::create_blocking_pool
+    /// [`has_blocking_pool`]: method@Self::has_blocking_pool
+    /// [`spawn_blocking`]: method@Self::spawn_blocking
+    pub fn spawn_blocking_on<F, R>(&self, name: &str, func: F) -> JoinHandle<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let (task, mut handle) = task::joinable(BlockingTask::new(func));
+        handle.set_runtime_id(self.id);
+
+        match self.named_pools.spawner(name) {
+            Some(spawner) => {
+                let _ = spawner.spawn(task, self);
+            }
+            None => {
+                let _ = self.blocking_spawner.spawn(task, self);
+            }
+        }
+
+        handle
+    }
+
+    /// Returns a point-in-time snapshot of the runtime's counters.
+    ///
+    /// The snapshot gathers the task, blocking-pool, timer, and I/O driver
+    /// counters in a single call. See [`RuntimeMetrics`] for the meaning of
+    /// each field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    ///
+    /// # fn dox() {
+    /// let rt = Runtime::new().unwrap();
+    /// let metrics = rt.handle().metrics_snapshot();
+    /// println!("tasks spawned: {}", metrics.tasks_spawned);
+    /// # }
+    /// ```
+    ///
+    /// [`RuntimeMetrics`]: crate::runtime::metrics::RuntimeMetrics
+    #[cfg(tokio_unstable)]
+    pub fn metrics_snapshot(&self) -> crate::runtime::metrics::RuntimeMetrics {
+        crate::runtime::metrics::snapshot(self)
+    }
+
+    /// Registers a callback that is invoked with a fresh metrics snapshot once
+    /// per `interval`.
+    ///
+    /// The callback runs on a dedicated thread and is invoked until the
+    /// returned [`MetricsExporter`] guard is dropped.
+    ///
+    /// [`MetricsExporter`]: crate::runtime::metrics::MetricsExporter
+    #[cfg(tokio_unstable)]
+    pub fn register_metrics_exporter<F>(
+        &self,
+        interval: std::time::Duration,
+        f: F,
+    ) -> crate::runtime::metrics::MetricsExporter
+    where
+        F: FnMut(crate::runtime::metrics::RuntimeMetrics) + Send + 'static,
+    {
+        crate::runtime::metrics::start_exporter(self.clone(), interval, f)
+    }
+
     /// Run a future to completion on this `Handle`'s associated `Runtime`.
     ///
     /// This runs the given future on the current thread, blocking until it is
@@ -286,23 +591,106 @@ impl Handle {
             .expect("failed to park thread")
     }
 
+    cfg_time! {
+        /// Returns a [`VirtualClock`] handle to this runtime's timer.
+        ///
+        /// The clock can be moved to other threads and used to register
+        /// deadlines on the runtime's timer wheel directly, without creating
+        /// [`Sleep`] futures or entering the runtime context. This is
+        /// intended for external executors and protocol drivers that manage
+        /// many timeouts themselves.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the runtime was built without a timer; see
+        /// [`Builder::enable_time`].
+        ///
+        /// [`VirtualClock`]: struct@crate::time::VirtualClock
+        /// [`Sleep`]: struct@crate::time::Sleep
+        /// [`Builder::enable_time`]: method@crate::runtime::Builder::enable_time
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use tokio::runtime::Handle;
+        /// use tokio::time::{Duration, Instant};
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let clock = Handle::current().timer();
+        ///     let deadline = clock.deadline(Instant::now() + Duration::from_millis(10));
+        ///     assert!(!deadline.is_elapsed());
+        /// }
+        /// ```
+        pub fn timer(&self) -> crate::time::VirtualClock {
+            let handle = self.time_handle.clone().expect(
+                "timers are disabled on this runtime. Call `enable_time` on the runtime builder to enable timers.",
+            );
+            crate::time::VirtualClock::new(handle)
+        }
+    }
+
     pub(crate) fn shutdown(mut self) {
         self.spawner.shutdown();
     }
 }
 
-/// Error returned by `try_current` when no Runtime has been started
-pub struct TryCurrentError(());
+/// Error returned by [`Handle::try_current`] when no runtime context is
+/// available.
+pub struct TryCurrentError {
+    kind: TryCurrentErrorKind,
+}
+
+impl TryCurrentError {
+    pub(crate) fn new_no_context() -> Self {
+        Self {
+            kind: TryCurrentErrorKind::NoContext,
+        }
+    }
+
+    pub(crate) fn new_thread_local_destroyed() -> Self {
+        Self {
+            kind: TryCurrentErrorKind::ThreadLocalDestroyed,
+        }
+    }
+
+    /// Returns `true` if the call failed because the current thread is not
+    /// running inside a Tokio runtime.
+    pub fn is_missing_context(&self) -> bool {
+        matches!(self.kind, TryCurrentErrorKind::NoContext)
+    }
+
+    /// Returns `true` if the call failed because the runtime context is being
+    /// destroyed. This happens when the thread is shutting down and the
+    /// context thread-local has already been dropped, typically from the
+    /// destructor of another thread-local while a runtime worker exits.
+    pub fn is_thread_local_destroyed(&self) -> bool {
+        matches!(self.kind, TryCurrentErrorKind::ThreadLocalDestroyed)
+    }
+}
+
+enum TryCurrentErrorKind {
+    NoContext,
+    ThreadLocalDestroyed,
+}
 
 impl fmt::Debug for TryCurrentError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("TryCurrentError").finish()
+        use TryCurrentErrorKind::*;
+        match self.kind {
+            NoContext => f.write_str("TryCurrentError::NoContext"),
+            ThreadLocalDestroyed => f.write_str("TryCurrentError::ThreadLocalDestroyed"),
+        }
     }
 }
 
 impl fmt::Display for TryCurrentError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(CONTEXT_MISSING_ERROR)
+        use TryCurrentErrorKind::*;
+        match self.kind {
+            NoContext => f.write_str(CONTEXT_MISSING_ERROR),
+            ThreadLocalDestroyed => f.write_str(THREAD_LOCAL_DESTROYED_ERROR),
+        }
     }
 }
 
@@ -0,0 +1,119 @@
+//! Low-level per-poll instrumentation.
+//!
+//! An opt-in hook invoked after every task poll with the task identifier, the
+//! time the poll took, and how long the task waited between being woken and
+//! being polled. External profilers can feed these records into flame-style
+//! views of where executor time goes.
+//!
+//! This sits below the aggregate counters in
+//! [`metrics`](crate::runtime::metrics): every poll produces one record. The
+//! hook is a plain function pointer so that installing one adds no allocation
+//! or synchronization to the scheduler hot path; when no hook is installed the
+//! cost is a single relaxed atomic load per poll.
+//!
+//! Task identifiers match those reported by [`JoinHandle::task_id`] and
+//! [`alloc_track`](crate::runtime::alloc_track).
+//!
+//! The hook runs on runtime worker threads and must not block or panic.
+//!
+//! [`JoinHandle::task_id`]: crate::task::JoinHandle::task_id
+//!
+//! # Examples
+//!
+//! ```
+//! use tokio::runtime::instrument::{self, PollRecord};
+//!
+//! fn record(poll: &PollRecord) {
+//!     if poll.poll_duration.as_millis() > 10 {
+//!         eprintln!("slow poll: task {}", poll.task_id);
+//!     }
+//! }
+//!
+//! instrument::set_poll_hook(record);
+//! ```
+
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering::Relaxed};
+use std::time::{Duration, Instant};
+
+/// Describes one completed task poll.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PollRecord {
+    /// Identifies the polled task. Identifiers are unique among live tasks
+    /// but may be reused after a task is dropped.
+    pub task_id: u64,
+
+    /// How long the poll took.
+    pub poll_duration: Duration,
+
+    /// Time between the task being woken and this poll starting. `None` for
+    /// polls that were not preceded by a wakeup, such as a task's first poll.
+    pub schedule_delay: Option<Duration>,
+}
+
+/// The installed hook, stored as a type-erased function pointer so it can
+/// live in a static. Null means no hook is installed.
+static POLL_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Installs a hook invoked after every task poll on every runtime in the
+/// process.
+///
+/// The hook must be a plain function pointer; it is called on runtime worker
+/// threads and must not block or panic. Installing a new hook replaces the
+/// previous one, though polls already in flight may still report to it.
+pub fn set_poll_hook(hook: fn(&PollRecord)) {
+    POLL_HOOK.store(hook as *mut (), Relaxed);
+}
+
+/// Returns `true` if a poll hook is installed.
+pub(crate) fn enabled() -> bool {
+    !POLL_HOOK.load(Relaxed).is_null()
+}
+
+fn hook() -> Option<fn(&PollRecord)> {
+    let hook = POLL_HOOK.load(Relaxed);
+    if hook.is_null() {
+        None
+    } else {
+        // Safety: the only non-null values stored are `fn(&PollRecord)`
+        // pointers, stored by `set_poll_hook`.
+        Some(unsafe { std::mem::transmute::<*mut (), fn(&PollRecord)>(hook) })
+    }
+}
+
+/// Starts timing a poll of the given task. The returned guard reports to the
+/// installed hook when dropped; returns `None` when no hook is installed.
+pub(crate) fn enter(task_id: u64, scheduled_at: Option<Instant>) -> Option<PollTimer> {
+    let hook = hook()?;
+
+    Some(PollTimer {
+        task_id,
+        scheduled_at,
+        start: Instant::now(),
+        hook,
+    })
+}
+
+/// Times a single poll. Reports to the hook on drop so that panics while
+/// polling are still recorded.
+pub(crate) struct PollTimer {
+    task_id: u64,
+    scheduled_at: Option<Instant>,
+    start: Instant,
+    hook: fn(&PollRecord),
+}
+
+impl Drop for PollTimer {
+    fn drop(&mut self) {
+        let record = PollRecord {
+            task_id: self.task_id,
+            poll_duration: self.start.elapsed(),
+            schedule_delay: self
+                .scheduled_at
+                .map(|scheduled_at| self.start.saturating_duration_since(scheduled_at)),
+        };
+
+        (self.hook)(&record);
+    }
+}
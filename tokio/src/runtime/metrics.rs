@@ -0,0 +1,121 @@
+//! Runtime metrics.
+//!
+//! This module exposes a point-in-time snapshot of the counters maintained by
+//! the runtime and its drivers. A snapshot is taken with
+//! [`Handle::metrics_snapshot`], and a periodic exporter can be registered
+//! with [`Handle::register_metrics_exporter`].
+//!
+//! [`Handle::metrics_snapshot`]: crate::runtime::Handle::metrics_snapshot
+//! [`Handle::register_metrics_exporter`]: crate::runtime::Handle::register_metrics_exporter
+
+use crate::runtime::Handle;
+
+use std::sync::atomic::{AtomicBool, Ordering::Relaxed};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A point-in-time snapshot of the runtime's counters.
+///
+/// All fields are plain data, so a snapshot can be handed to whatever
+/// serialization or export pipeline the application uses. The task and driver
+/// counters are cumulative for the lifetime of the process; the blocking-pool
+/// values are gauges describing the pool at the time of the snapshot.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct RuntimeMetrics {
+    /// Total number of tasks spawned.
+    pub tasks_spawned: u64,
+
+    /// Total number of tasks that ran to completion.
+    pub tasks_completed: u64,
+
+    /// Total number of times a task was polled.
+    pub task_polls: u64,
+
+    /// Total number of times a task was woken.
+    pub task_wakes: u64,
+
+    /// Total number of times a task started waiting on a sync resource.
+    pub resource_waits: u64,
+
+    /// Total number of blocking tasks handed to the blocking pool.
+    pub blocking_tasks_spawned: u64,
+
+    /// Number of threads currently alive in the blocking pool.
+    pub blocking_threads: usize,
+
+    /// Number of idle threads in the blocking pool.
+    pub blocking_idle_threads: usize,
+
+    /// Number of blocking tasks queued and not yet picked up by a thread.
+    pub blocking_queue_depth: usize,
+
+    /// Total number of readiness events dispatched by the I/O driver.
+    pub io_ready_events: u64,
+
+    /// Total number of timer entries fired by the time driver.
+    pub timer_fires: u64,
+}
+
+/// Handle to a registered metrics exporter.
+///
+/// The exporter callback is invoked periodically until this handle is
+/// dropped. Created by [`Handle::register_metrics_exporter`].
+///
+/// [`Handle::register_metrics_exporter`]: crate::runtime::Handle::register_metrics_exporter
+#[derive(Debug)]
+pub struct MetricsExporter {
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Drop for MetricsExporter {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Relaxed);
+    }
+}
+
+pub(crate) fn snapshot(handle: &Handle) -> RuntimeMetrics {
+    use crate::runtime::console::COUNTERS;
+
+    let (blocking_threads, blocking_idle_threads, blocking_queue_depth) =
+        handle.blocking_spawner.metrics();
+
+    RuntimeMetrics {
+        tasks_spawned: COUNTERS.tasks_spawned.load(Relaxed),
+        tasks_completed: COUNTERS.tasks_completed.load(Relaxed),
+        task_polls: COUNTERS.task_polls.load(Relaxed),
+        task_wakes: COUNTERS.task_wakes.load(Relaxed),
+        resource_waits: COUNTERS.resource_waits.load(Relaxed),
+        blocking_tasks_spawned: COUNTERS.blocking_tasks_spawned.load(Relaxed),
+        blocking_threads,
+        blocking_idle_threads,
+        blocking_queue_depth,
+        io_ready_events: COUNTERS.io_ready_events.load(Relaxed),
+        timer_fires: COUNTERS.timer_fires.load(Relaxed),
+    }
+}
+
+pub(crate) fn start_exporter<F>(handle: Handle, interval: Duration, mut f: F) -> MetricsExporter
+where
+    F: FnMut(RuntimeMetrics) + Send + 'static,
+{
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let thread_shutdown = shutdown.clone();
+
+    std::thread::Builder::new()
+        .name("tokio-metrics-exporter".into())
+        .spawn(move || {
+            while !thread_shutdown.load(Relaxed) {
+                std::thread::sleep(interval);
+
+                if thread_shutdown.load(Relaxed) {
+                    return;
+                }
+
+                f(snapshot(&handle));
+            }
+        })
+        .expect("failed to spawn metrics exporter thread");
+
+    MetricsExporter { shutdown }
+}
@@ -187,10 +187,28 @@ cfg_rt! {
 
     mod blocking;
     use blocking::BlockingPool;
-    pub(crate) use blocking::spawn_blocking;
+    pub(crate) use blocking::{spawn_blocking, spawn_blocking_on};
 
     mod builder;
-    pub use self::builder::Builder;
+    pub use self::builder::{Builder, UnhandledPanic};
+
+    mod park_driver;
+    pub use park_driver::{ParkDriver, ParkHandle};
+
+    #[cfg(tokio_unstable)]
+    pub mod alloc_track;
+
+    #[cfg(tokio_unstable)]
+    pub mod instrument;
+
+    #[cfg(tokio_unstable)]
+    pub mod console;
+
+    #[cfg(tokio_unstable)]
+    pub mod metrics;
+
+    #[cfg(tokio_unstable)]
+    pub mod scheduler;
 
     pub(crate) mod context;
     pub(crate) mod driver;
@@ -198,7 +216,7 @@ cfg_rt! {
     use self::enter::enter;
 
     mod handle;
-    pub use handle::{EnterGuard, Handle};
+    pub use handle::{EnterGuard, Handle, RuntimeFlavor, RuntimeId, TryCurrentError};
 
     mod spawner;
     use self::spawner::Spawner;
@@ -526,6 +544,7 @@ cfg_rt! {
         /// ```
         pub fn shutdown_timeout(mut self, duration: Duration) {
             // Wakeup and shutdown all the worker threads
+            self.handle.named_pools.shutdown(Some(duration));
             self.handle.shutdown();
             self.blocking_pool.shutdown(Some(duration));
         }
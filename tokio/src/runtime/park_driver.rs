@@ -0,0 +1,122 @@
+//! Pluggable parking for runtime worker threads.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Parks runtime worker threads on an external event source.
+///
+/// By default, an idle runtime worker parks on Tokio's own driver stack: the
+/// I/O driver's `mio::Poll`, the time driver, or a plain condition variable.
+/// Installing a `ParkDriver` with [`Builder::park_driver`] replaces that
+/// stack, making the external event source — a GUI main loop, libusb, a
+/// custom epoll set — the thing workers block on. Waking a worker then goes
+/// through the driver's [`ParkHandle`] rather than the built-in unpark path.
+///
+/// Because the custom driver replaces the built-in stack, it cannot be
+/// combined with [`enable_io`] or [`enable_time`]; dispatching foreign events
+/// to the runtime is the driver's job.
+///
+/// The contract mirrors `std::thread::park`:
+///
+/// * [`park`] blocks until [`ParkHandle::unpark`] is called. If `unpark` was
+///   called since the last `park` returned, `park` returns immediately.
+/// * Spurious wakeups are permitted; the runtime re-checks for work and parks
+///   again.
+///
+/// # Examples
+///
+/// Parking on a channel that an external loop feeds:
+///
+/// ```
+/// use tokio::runtime::{Builder, ParkDriver, ParkHandle};
+///
+/// use std::sync::mpsc::{channel, Receiver, Sender};
+/// use std::sync::{Arc, Mutex};
+/// use std::time::Duration;
+///
+/// struct EventLoopPark {
+///     events: Receiver<()>,
+///     handle: Arc<EventLoopUnpark>,
+/// }
+///
+/// struct EventLoopUnpark {
+///     notify: Mutex<Sender<()>>,
+/// }
+///
+/// impl ParkDriver for EventLoopPark {
+///     fn handle(&self) -> Arc<dyn ParkHandle> {
+///         self.handle.clone()
+///     }
+///
+///     fn park(&mut self) {
+///         let _ = self.events.recv();
+///     }
+///
+///     fn park_timeout(&mut self, duration: Duration) {
+///         let _ = self.events.recv_timeout(duration);
+///     }
+/// }
+///
+/// impl ParkHandle for EventLoopUnpark {
+///     fn unpark(&self) {
+///         let _ = self.notify.lock().unwrap().send(());
+///     }
+/// }
+///
+/// let (tx, rx) = channel();
+/// let driver = EventLoopPark {
+///     events: rx,
+///     handle: Arc::new(EventLoopUnpark {
+///         notify: Mutex::new(tx),
+///     }),
+/// };
+///
+/// let rt = Builder::new_current_thread()
+///     .park_driver(driver)
+///     .build()
+///     .unwrap();
+///
+/// rt.block_on(async {
+///     // ...
+/// });
+/// ```
+///
+/// [`Builder::park_driver`]: crate::runtime::Builder::park_driver
+/// [`enable_io`]: crate::runtime::Builder::enable_io
+/// [`enable_time`]: crate::runtime::Builder::enable_time
+/// [`park`]: ParkDriver::park
+pub trait ParkDriver: Send + 'static {
+    /// Returns a handle used to unpark this driver from other threads.
+    fn handle(&self) -> Arc<dyn ParkHandle>;
+
+    /// Blocks the current thread until the driver is unparked.
+    ///
+    /// Spurious returns are permitted.
+    fn park(&mut self);
+
+    /// Blocks the current thread until the driver is unparked or `duration`
+    /// elapses.
+    fn park_timeout(&mut self, duration: Duration);
+
+    /// Called once when the runtime shuts down.
+    ///
+    /// Implementations should wake any thread blocked in [`park`] and release
+    /// resources tied to the external event source. The default does nothing.
+    ///
+    /// [`park`]: ParkDriver::park
+    fn shutdown(&mut self) {}
+}
+
+/// Unparks a worker blocked on a [`ParkDriver`].
+///
+/// Handles are called from arbitrary threads, including from within the
+/// external event loop itself.
+pub trait ParkHandle: Send + Sync + 'static {
+    /// Unblocks the driver's [`park`] call.
+    ///
+    /// If the driver is not currently parked, the next call to [`park`] must
+    /// return immediately.
+    ///
+    /// [`park`]: ParkDriver::park
+    fn unpark(&self);
+}
@@ -534,6 +534,43 @@ impl<T: 'static> Inject<T> {
         self.len.store(len + 1, Release);
     }
 
+    /// Pushes several values into the queue under a single lock acquisition.
+    pub(super) fn push_iter(&self, tasks: impl Iterator<Item = task::Notified<T>>) {
+        // Acquire queue lock
+        let mut p = self.pointers.lock();
+
+        if p.is_closed {
+            // Drop the mutex to avoid a potential deadlock when
+            // re-entering.
+            drop(p);
+            for task in tasks {
+                drop(task);
+            }
+            return;
+        }
+
+        // safety: only mutated with the lock held
+        let mut len = unsafe { self.len.unsync_load() };
+
+        for task in tasks {
+            let task = task.into_raw();
+
+            // The next pointer should already be null
+            debug_assert!(get_next(task).is_none());
+
+            if let Some(tail) = p.tail {
+                set_next(tail, Some(task));
+            } else {
+                p.head = Some(task);
+            }
+
+            p.tail = Some(task);
+            len += 1;
+        }
+
+        self.len.store(len, Release);
+    }
+
     pub(super) fn push_batch(
         &self,
         batch_head: task::Notified<T>,
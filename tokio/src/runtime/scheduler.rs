@@ -0,0 +1,86 @@
+//! Pluggable scheduling policy for the current-thread runtime.
+//!
+//! By default the current-thread runtime runs tasks in FIFO order, with a
+//! periodic preference for the remote queue. A [`SchedulePolicy`] installed
+//! through [`Builder::schedule_policy`] is consulted every time a task becomes
+//! runnable and decides where the task is placed in the run queue, while
+//! continuing to reuse Tokio's drivers, blocking pool, and task machinery.
+//! This is a trait boundary for research and specialized real-time workloads
+//! (e.g. earliest-deadline-first or strictly LIFO ordering) that would
+//! otherwise have to fork the whole runtime.
+//!
+//! Task identifiers match those reported by [`JoinHandle::task_id`], so a
+//! policy can be fed per-task ordering keys (such as deadlines) out of band.
+//!
+//! The policy runs on the scheduling path, possibly from threads outside the
+//! runtime, and must not block or panic.
+//!
+//! [`Builder::schedule_policy`]: crate::runtime::Builder::schedule_policy
+//! [`JoinHandle::task_id`]: crate::task::JoinHandle::task_id
+//!
+//! # Examples
+//!
+//! A strictly LIFO scheduler:
+//!
+//! ```
+//! use tokio::runtime::scheduler::{Placement, SchedulePolicy, TaskMeta};
+//!
+//! struct Lifo;
+//!
+//! impl SchedulePolicy for Lifo {
+//!     fn placement(&self, _task: &TaskMeta) -> Placement {
+//!         Placement::Front
+//!     }
+//! }
+//!
+//! let rt = tokio::runtime::Builder::new_current_thread()
+//!     .schedule_policy(Lifo)
+//!     .build()
+//!     .unwrap();
+//! # drop(rt);
+//! ```
+
+/// Decides where runnable tasks are placed in the current-thread runtime's
+/// run queue.
+///
+/// Installed with [`Builder::schedule_policy`]. See the [module
+/// documentation](self) for details.
+///
+/// [`Builder::schedule_policy`]: crate::runtime::Builder::schedule_policy
+pub trait SchedulePolicy: Send + Sync + 'static {
+    /// Called when a task becomes runnable, returning where the task is
+    /// placed in the run queue.
+    ///
+    /// This is called from whichever thread wakes the task and must not block
+    /// or panic.
+    fn placement(&self, task: &TaskMeta) -> Placement;
+}
+
+/// Describes a task that has become runnable.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct TaskMeta {
+    /// Identifies the task. Identifiers are unique among live tasks but may
+    /// be reused after a task is dropped, and match those reported by
+    /// [`JoinHandle::task_id`].
+    ///
+    /// [`JoinHandle::task_id`]: crate::task::JoinHandle::task_id
+    pub task_id: u64,
+
+    /// Number of tasks already waiting in the queue the task is being placed
+    /// into.
+    pub queue_depth: usize,
+}
+
+/// Where a runnable task is placed in the run queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Placement {
+    /// Place the task at the back of the run queue, running it after the
+    /// tasks already scheduled. This is the default FIFO behavior.
+    Back,
+
+    /// Place the task at the front of the run queue, running it before the
+    /// tasks already scheduled.
+    Front,
+}
@@ -1,6 +1,6 @@
 cfg_rt! {
     use crate::runtime::basic_scheduler;
-    use crate::task::JoinHandle;
+    use crate::task::{JoinHandle, SpawnHint};
 
     use std::future::Future;
 }
@@ -42,5 +42,41 @@ cfg_rt! {
                 Spawner::ThreadPool(spawner) => spawner.spawn(future),
             }
         }
+
+        pub(crate) fn spawn_iter<I>(&self, iter: I) -> Vec<JoinHandle<<I::Item as Future>::Output>>
+        where
+            I: IntoIterator,
+            I::Item: Future + Send + 'static,
+            <I::Item as Future>::Output: Send + 'static,
+        {
+            match self {
+                #[cfg(feature = "rt")]
+                Spawner::Basic(spawner) => {
+                    // The basic scheduler has a single queue; spawning one by
+                    // one is already a single pass.
+                    iter.into_iter().map(|future| spawner.spawn(future)).collect()
+                }
+                #[cfg(feature = "rt-multi-thread")]
+                Spawner::ThreadPool(spawner) => spawner.spawn_iter(iter),
+            }
+        }
+
+        pub(crate) fn spawn_hinted<F>(&self, future: F, hint: SpawnHint) -> JoinHandle<F::Output>
+        where
+            F: Future + Send + 'static,
+            F::Output: Send + 'static,
+        {
+            match self {
+                #[cfg(feature = "rt")]
+                Spawner::Basic(spawner) => {
+                    // The basic scheduler runs everything on one thread, so
+                    // the hint carries no information.
+                    let _ = hint;
+                    spawner.spawn(future)
+                }
+                #[cfg(feature = "rt-multi-thread")]
+                Spawner::ThreadPool(spawner) => spawner.spawn_hinted(future, hint),
+            }
+        }
     }
 }
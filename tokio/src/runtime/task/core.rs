@@ -71,6 +71,12 @@ pub(crate) struct Header {
 
     /// Table of function pointers for executing actions on the task.
     pub(super) vtable: &'static Vtable,
+
+    /// When the task was last woken, used to compute the schedule delay
+    /// reported by the poll instrumentation hook. Only written while the
+    /// task is notified and read once it transitions to running.
+    #[cfg(tokio_unstable)]
+    pub(super) woken_at: UnsafeCell<Option<std::time::Instant>>,
 }
 
 unsafe impl Send for Header {}
@@ -100,6 +106,8 @@ impl<T: Future, S: Schedule> Cell<T, S> {
                 queue_next: UnsafeCell::new(None),
                 stack_next: UnsafeCell::new(None),
                 vtable: raw::vtable::<T, S>(),
+                #[cfg(tokio_unstable)]
+                woken_at: UnsafeCell::new(None),
             },
             core: Core {
                 scheduler: Scheduler {
@@ -180,6 +188,18 @@ impl<S: Schedule> Scheduler<S> {
         });
     }
 
+    /// Polling the future resulted in a panic. Notify the scheduler so it can
+    /// apply its unhandled-panic policy.
+    pub(super) fn unhandled_panic(&self) {
+        self.scheduler.with(|ptr| {
+            // Safety: Can only be called after initial `poll`, which is the
+            // only time the field is mutated.
+            if let Some(scheduler) = unsafe { &*ptr } {
+                scheduler.unhandled_panic();
+            }
+        });
+    }
+
     /// Release the task
     ///
     /// If the `Scheduler` implementation is able to, it returns the `Task`
@@ -56,8 +56,22 @@ where
     ///
     /// Panics raised while polling the future are handled.
     pub(super) fn poll(self) {
+        #[cfg(tokio_unstable)]
+        crate::runtime::console::task_polled();
+
         match self.poll_inner() {
             PollFuture::Notified => {
+                // The task was woken while it was running (e.g. by
+                // `yield_now`), so the wakeup was not stamped by
+                // `wake_by_ref`. Stamp it here; this thread still holds the
+                // notified task, giving it exclusive access to the timestamp.
+                #[cfg(tokio_unstable)]
+                if crate::runtime::instrument::enabled() {
+                    self.header()
+                        .woken_at
+                        .with_mut(|ptr| unsafe { *ptr = Some(std::time::Instant::now()) });
+                }
+
                 // Signal yield
                 self.core().scheduler.yield_now(Notified(self.to_task()));
                 // The ref-count was incremented as part of
@@ -68,6 +82,15 @@ where
                 self.drop_reference();
             }
             PollFuture::Complete(out, is_join_interested) => {
+                if let Err(err) = &out {
+                    if err.is_panic() {
+                        self.core().scheduler.unhandled_panic();
+                    }
+                }
+
+                #[cfg(tokio_unstable)]
+                crate::runtime::console::task_completed();
+
                 self.complete(out, is_join_interested);
             }
             PollFuture::None => (),
@@ -86,6 +109,28 @@ where
 
         let waker_ref = waker_ref::<T, S>(self.header());
         let cx = Context::from_waker(&*waker_ref);
+
+        // Mark this task as the one being polled on this thread so that
+        // allocations made while the future runs can be attributed to it.
+        #[cfg(tokio_unstable)]
+        let _poll_guard = {
+            let header: *const Header = self.header();
+            crate::runtime::alloc_track::enter(header as u64)
+        };
+
+        // Time the poll for the instrumentation hook. The wakeup timestamp is
+        // taken here: the transition to `Running` above gives this thread
+        // exclusive access to it.
+        #[cfg(tokio_unstable)]
+        let _poll_timer = {
+            let header: *const Header = self.header();
+            let woken_at = self
+                .header()
+                .woken_at
+                .with_mut(|ptr| unsafe { (*ptr).take() });
+            crate::runtime::instrument::enter(header as u64, woken_at)
+        };
+
         poll_future(self.header(), &self.core().stage, snapshot, cx)
     }
 
@@ -135,7 +180,19 @@ where
     }
 
     pub(super) fn wake_by_ref(&self) {
+        #[cfg(tokio_unstable)]
+        crate::runtime::console::task_woken();
+
         if self.header().state.transition_to_notified() {
+            // The transition gives this thread exclusive access to the
+            // notified task, including its wakeup timestamp.
+            #[cfg(tokio_unstable)]
+            if crate::runtime::instrument::enabled() {
+                self.header()
+                    .woken_at
+                    .with_mut(|ptr| unsafe { *ptr = Some(std::time::Instant::now()) });
+            }
+
             self.core().scheduler.schedule(Notified(self.to_task()));
         }
     }
@@ -143,6 +143,7 @@ cfg_rt! {
     /// [`JoinError`]: crate::task::JoinError
     pub struct JoinHandle<T> {
         raw: Option<RawTask>,
+        runtime_id: Option<crate::runtime::RuntimeId>,
         _p: PhantomData<T>,
     }
 }
@@ -154,10 +155,38 @@ impl<T> JoinHandle<T> {
     pub(super) fn new(raw: RawTask) -> JoinHandle<T> {
         JoinHandle {
             raw: Some(raw),
+            runtime_id: None,
             _p: PhantomData,
         }
     }
 
+    /// Records the runtime the task was spawned on. Set at the spawn sites
+    /// that know their runtime; checked by `Handle::owns`.
+    pub(crate) fn set_runtime_id(&mut self, id: crate::runtime::RuntimeId) {
+        self.runtime_id = Some(id);
+    }
+
+    /// Returns the identifier of the runtime the task was spawned on, if
+    /// known.
+    pub(crate) fn runtime_id(&self) -> Option<crate::runtime::RuntimeId> {
+        self.runtime_id
+    }
+
+    /// Returns the identifier used to attribute allocations made by this
+    /// task.
+    ///
+    /// This is the value reported by
+    /// [`runtime::alloc_track`](crate::runtime::alloc_track) while the task
+    /// is being polled. Identifiers are unique among live tasks but may be
+    /// reused after a task is dropped.
+    #[cfg(tokio_unstable)]
+    pub fn task_id(&self) -> u64 {
+        // The handle holds a reference to the task, so `raw` is set.
+        let raw = self.raw.as_ref().expect("missing RawTask");
+        let header: *const _ = raw.header();
+        header as u64
+    }
+
     /// Abort the task associated with the handle.
     ///
     /// Awaiting a cancelled task might complete as usual if the task was
@@ -258,3 +287,156 @@ where
         fmt.debug_struct("JoinHandle").finish()
     }
 }
+
+impl<T: 'static> JoinHandle<T> {
+    /// Transforms the output of the task without spawning another task.
+    ///
+    /// The returned future resolves to the task's output passed through `f`,
+    /// or to the [`JoinError`] if the task panicked or was cancelled. The
+    /// closure runs on whichever task awaits the returned future, so it
+    /// should be cheap; use [`and_then_spawn`] for continuations that do real
+    /// work.
+    ///
+    /// [`JoinError`]: crate::task::JoinError
+    /// [`and_then_spawn`]: JoinHandle::and_then_spawn
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let task = tokio::spawn(async { 21 }).map(|n| n * 2);
+    ///
+    ///     assert_eq!(task.await.unwrap(), 42);
+    /// }
+    /// ```
+    pub fn map<U, F>(self, f: F) -> MappedJoinHandle<T, F>
+    where
+        F: FnOnce(T) -> U,
+    {
+        MappedJoinHandle {
+            handle: self,
+            f: Some(f),
+        }
+    }
+
+    /// Spawns a continuation for the task once it completes successfully.
+    ///
+    /// When the task finishes, `f` is called with its output and the future
+    /// it returns is spawned on the runtime the returned adapter is being
+    /// polled on. The adapter resolves to the continuation's output, or to
+    /// the first [`JoinError`] encountered if either task panicked or was
+    /// cancelled.
+    ///
+    /// Unlike wrapping both steps in a new task, this does not increase the
+    /// task count until the first task has completed.
+    ///
+    /// # Panics
+    ///
+    /// The returned future panics if polled outside the context of a Tokio
+    /// runtime, as it needs the runtime to spawn the continuation.
+    ///
+    /// [`JoinError`]: crate::task::JoinError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let task = tokio::spawn(async { 6 })
+    ///         .and_then_spawn(|n| async move { n * 7 });
+    ///
+    ///     assert_eq!(task.await.unwrap(), 42);
+    /// }
+    /// ```
+    pub fn and_then_spawn<F, Fut>(self, f: F) -> AndThenSpawn<T, F, Fut>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        AndThenSpawn {
+            state: AndThenSpawnState::First(self, Some(f)),
+        }
+    }
+}
+
+/// Future returned by [`JoinHandle::map`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct MappedJoinHandle<T, F> {
+    handle: JoinHandle<T>,
+    f: Option<F>,
+}
+
+impl<T, F> Unpin for MappedJoinHandle<T, F> {}
+
+impl<T, U, F> Future for MappedJoinHandle<T, F>
+where
+    F: FnOnce(T) -> U,
+{
+    type Output = super::Result<U>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let output = ready!(Pin::new(&mut self.handle).poll(cx));
+        let f = self
+            .f
+            .take()
+            .expect("polled after `MappedJoinHandle` already completed");
+        Poll::Ready(output.map(f))
+    }
+}
+
+impl<T, F> fmt::Debug for MappedJoinHandle<T, F> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("MappedJoinHandle").finish()
+    }
+}
+
+/// Future returned by [`JoinHandle::and_then_spawn`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct AndThenSpawn<T, F, Fut: Future> {
+    state: AndThenSpawnState<T, F, Fut::Output>,
+}
+
+enum AndThenSpawnState<T, F, U> {
+    /// Waiting for the first task; the closure has not run yet.
+    First(JoinHandle<T>, Option<F>),
+
+    /// The continuation has been spawned.
+    Second(JoinHandle<U>),
+}
+
+impl<T, F, Fut: Future> Unpin for AndThenSpawn<T, F, Fut> {}
+
+impl<T, F, Fut> Future for AndThenSpawn<T, F, Fut>
+where
+    F: FnOnce(T) -> Fut,
+    Fut: Future + Send + 'static,
+    Fut::Output: Send + 'static,
+{
+    type Output = super::Result<Fut::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            match &mut self.state {
+                AndThenSpawnState::First(handle, f) => {
+                    let output = match ready!(Pin::new(handle).poll(cx)) {
+                        Ok(output) => output,
+                        Err(e) => return Poll::Ready(Err(e)),
+                    };
+                    let f = f
+                        .take()
+                        .expect("polled after `AndThenSpawn` already completed");
+                    self.state = AndThenSpawnState::Second(crate::task::spawn(f(output)));
+                }
+                AndThenSpawnState::Second(handle) => return Pin::new(handle).poll(cx),
+            }
+        }
+    }
+}
+
+impl<T, F, Fut: Future> fmt::Debug for AndThenSpawn<T, F, Fut> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("AndThenSpawn").finish()
+    }
+}
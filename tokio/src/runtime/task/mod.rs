@@ -11,7 +11,7 @@ use self::harness::Harness;
 
 mod join;
 #[allow(unreachable_pub)] // https://github.com/rust-lang/rust/issues/57411
-pub use self::join::JoinHandle;
+pub use self::join::{AndThenSpawn, JoinHandle, MappedJoinHandle};
 
 mod raw;
 use self::raw::RawTask;
@@ -77,6 +77,12 @@ pub(crate) trait Schedule: Sync + Sized + 'static {
     fn yield_now(&self, task: Notified<Self>) {
         self.schedule(task);
     }
+
+    /// Polling the task resulted in a panic. The scheduler may use this to
+    /// apply its unhandled-panic policy.
+    fn unhandled_panic(&self) {
+        // By default, the panic is only surfaced through the `JoinHandle`.
+    }
 }
 
 cfg_rt! {
@@ -95,6 +101,9 @@ cfg_rt! {
 
         let join = JoinHandle::new(raw);
 
+        #[cfg(tokio_unstable)]
+        crate::runtime::console::task_spawned();
+
         (Notified(task), join)
     }
 }
@@ -158,6 +167,13 @@ cfg_rt_multi_thread! {
     }
 }
 
+#[cfg(all(tokio_unstable, not(feature = "rt-multi-thread")))]
+impl<S: 'static> Notified<S> {
+    pub(crate) fn header(&self) -> &Header {
+        self.0.header()
+    }
+}
+
 impl<S: Schedule> Task<S> {
     /// Pre-emptively cancel the task as part of the shutdown process.
     pub(crate) fn shutdown(&self) {
@@ -0,0 +1,57 @@
+use crate::runtime::{alloc_track, Builder};
+
+use std::sync::atomic::{
+    AtomicU64, AtomicUsize,
+    Ordering::{Relaxed, SeqCst},
+};
+use std::sync::{Arc, Mutex};
+
+/// The task the hooks below count polls for. The hooks are process-wide and
+/// fire for every poll in the test binary, so they filter on this id.
+static TARGET: AtomicU64 = AtomicU64::new(0);
+static STARTS: AtomicUsize = AtomicUsize::new(0);
+static ENDS: AtomicUsize = AtomicUsize::new(0);
+
+fn on_poll_start(id: u64) {
+    if id == TARGET.load(Relaxed) {
+        STARTS.fetch_add(1, SeqCst);
+    }
+}
+
+fn on_poll_end(id: u64) {
+    if id == TARGET.load(Relaxed) {
+        ENDS.fetch_add(1, SeqCst);
+    }
+}
+
+#[test]
+fn current_task_and_poll_hooks() {
+    let rt = Builder::new_current_thread().build().unwrap();
+
+    alloc_track::set_poll_hooks(on_poll_start, on_poll_end);
+
+    let seen = Arc::new(Mutex::new(None));
+
+    rt.block_on(async {
+        let seen2 = seen.clone();
+        let handle = crate::spawn(async move {
+            *seen2.lock().unwrap() = alloc_track::current_task();
+        });
+
+        // The task is not polled until this future yields, so the id is
+        // published before the hooks can fire for it.
+        let id = handle.task_id();
+        TARGET.store(id, Relaxed);
+        handle.await.unwrap();
+
+        // `current_task()` was set while the task body ran.
+        assert_eq!(*seen.lock().unwrap(), Some(id));
+    });
+
+    // Both hooks fired for the task's poll.
+    assert!(STARTS.load(SeqCst) >= 1);
+    assert!(ENDS.load(SeqCst) >= 1);
+
+    // Outside a poll there is no current task.
+    assert_eq!(alloc_track::current_task(), None);
+}
@@ -0,0 +1,58 @@
+use crate::runtime::instrument::{self, PollRecord};
+use crate::runtime::Builder;
+use crate::task;
+
+use std::sync::atomic::{
+    AtomicU64, AtomicUsize,
+    Ordering::{Relaxed, SeqCst},
+};
+use std::time::Duration;
+
+/// The task the hook below records polls for. The hook is process-wide and
+/// fires for every poll in the test binary, so it filters on this id.
+static TARGET: AtomicU64 = AtomicU64::new(0);
+static RECORDS: AtomicUsize = AtomicUsize::new(0);
+static DELAYED: AtomicUsize = AtomicUsize::new(0);
+static MAX_POLL_NANOS: AtomicU64 = AtomicU64::new(0);
+
+fn record(poll: &PollRecord) {
+    if poll.task_id != TARGET.load(Relaxed) {
+        return;
+    }
+
+    RECORDS.fetch_add(1, SeqCst);
+    if poll.schedule_delay.is_some() {
+        DELAYED.fetch_add(1, SeqCst);
+    }
+    MAX_POLL_NANOS.fetch_max(poll.poll_duration.as_nanos() as u64, SeqCst);
+}
+
+#[test]
+fn poll_hook_reports_durations() {
+    let rt = Builder::new_current_thread().build().unwrap();
+
+    instrument::set_poll_hook(record);
+
+    rt.block_on(async {
+        let handle = crate::spawn(async {
+            // Make the first poll take a measurable amount of time, then
+            // yield so the second poll is preceded by a wakeup.
+            std::thread::sleep(Duration::from_millis(10));
+            task::yield_now().await;
+        });
+
+        // The task is not polled until this future yields, so the id is
+        // published before the hook can fire for it.
+        TARGET.store(handle.task_id(), Relaxed);
+        handle.await.unwrap();
+    });
+
+    // One record per poll: the initial poll and the one after the yield.
+    assert!(RECORDS.load(SeqCst) >= 2);
+
+    // The poll that slept is reflected in `poll_duration`.
+    assert!(MAX_POLL_NANOS.load(SeqCst) >= Duration::from_millis(10).as_nanos() as u64);
+
+    // The poll following the yield reported the wakeup-to-poll delay.
+    assert!(DELAYED.load(SeqCst) >= 1);
+}
@@ -7,8 +7,17 @@ cfg_loom! {
 }
 
 cfg_not_loom! {
+    #[cfg(tokio_unstable)]
+    mod alloc_track;
+
+    #[cfg(tokio_unstable)]
+    mod instrument;
+
     mod queue;
 
+    #[cfg(tokio_unstable)]
+    mod schedule_policy;
+
     #[cfg(miri)]
     mod task;
 }
@@ -0,0 +1,54 @@
+use crate::runtime::scheduler::{Placement, SchedulePolicy, TaskMeta};
+use crate::runtime::Builder;
+
+use std::sync::{Arc, Mutex};
+
+/// Records every task it is consulted for and runs the queue in LIFO order.
+struct Lifo {
+    scheduled: Arc<Mutex<Vec<u64>>>,
+}
+
+impl SchedulePolicy for Lifo {
+    fn placement(&self, task: &TaskMeta) -> Placement {
+        self.scheduled.lock().unwrap().push(task.task_id);
+        Placement::Front
+    }
+}
+
+#[test]
+fn policy_is_consulted_and_reorders() {
+    let scheduled = Arc::new(Mutex::new(Vec::new()));
+    let rt = Builder::new_current_thread()
+        .schedule_policy(Lifo {
+            scheduled: scheduled.clone(),
+        })
+        .build()
+        .unwrap();
+
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let ids = rt.block_on(async {
+        let mut handles = Vec::new();
+        for i in 0..3 {
+            let order = order.clone();
+            handles.push(crate::spawn(async move {
+                order.lock().unwrap().push(i);
+            }));
+        }
+
+        let ids: Vec<_> = handles.iter().map(|handle| handle.task_id()).collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        ids
+    });
+
+    // The policy was consulted for every spawned task...
+    let scheduled = scheduled.lock().unwrap();
+    for id in &ids {
+        assert!(scheduled.contains(id));
+    }
+
+    // ...and `Placement::Front` ran them in reverse spawn order.
+    assert_eq!(*order.lock().unwrap(), vec![2, 1, 0]);
+}
@@ -2,6 +2,7 @@
 
 use crate::loom::sync::atomic::AtomicUsize;
 use crate::loom::sync::Mutex;
+use crate::runtime::Callback;
 
 use std::fmt;
 use std::sync::atomic::Ordering::{self, SeqCst};
@@ -18,6 +19,12 @@ pub(super) struct Idle {
 
     /// Total number of workers.
     num_workers: usize,
+
+    /// Callback fired when the last unparked worker parks.
+    on_idle: Option<Callback>,
+
+    /// Callback fired when a worker is unparked while all workers are parked.
+    on_busy: Option<Callback>,
 }
 
 const UNPARK_SHIFT: usize = 16;
@@ -28,13 +35,19 @@ const SEARCH_MASK: usize = (1 << UNPARK_SHIFT) - 1;
 struct State(usize);
 
 impl Idle {
-    pub(super) fn new(num_workers: usize) -> Idle {
+    pub(super) fn new(
+        num_workers: usize,
+        on_idle: Option<Callback>,
+        on_busy: Option<Callback>,
+    ) -> Idle {
         let init = State::new(num_workers);
 
         Idle {
             state: AtomicUsize::new(init.into()),
             sleepers: Mutex::new(Vec::with_capacity(num_workers)),
             num_workers,
+            on_idle,
+            on_busy,
         }
     }
 
@@ -64,12 +77,16 @@ impl Idle {
 
         // A worker should be woken up, atomically increment the number of
         // searching workers as well as the number of unparked workers.
-        State::unpark_one(&self.state);
+        let prev = State::unpark_one(&self.state);
 
         // Get the worker to unpark
         let ret = sleepers.pop();
         debug_assert!(ret.is_some());
 
+        // Fire the callback after releasing the lock.
+        drop(sleepers);
+        self.notify_busy(prev);
+
         ret
     }
 
@@ -80,11 +97,22 @@ impl Idle {
         let mut sleepers = self.sleepers.lock();
 
         // Decrement the number of unparked threads
-        let ret = State::dec_num_unparked(&self.state, is_searching);
+        let prev = State::dec_num_unparked(&self.state, is_searching);
+        let ret = is_searching && prev.num_searching() == 1;
 
         // Track the sleeping worker
         sleepers.push(worker);
 
+        // The last unparked worker just parked; the runtime is now idle. Fire
+        // the callback after releasing the lock.
+        drop(sleepers);
+
+        if prev.num_unparked() == 1 {
+            if let Some(f) = &self.on_idle {
+                f();
+            }
+        }
+
         ret
     }
 
@@ -111,7 +139,8 @@ impl Idle {
 
     /// Unpark a specific worker. This happens if tasks are submitted from
     /// within the worker's park routine.
-    pub(super) fn unpark_worker_by_id(&self, worker_id: usize) {
+    /// Returns `true` if the worker was parked and is now unparked.
+    pub(super) fn unpark_worker_by_id(&self, worker_id: usize) -> bool {
         let mut sleepers = self.sleepers.lock();
 
         for index in 0..sleepers.len() {
@@ -119,11 +148,17 @@ impl Idle {
                 sleepers.swap_remove(index);
 
                 // Update the state accordingly while the lock is held.
-                State::unpark_one(&self.state);
+                let prev = State::unpark_one(&self.state);
 
-                return;
+                // Fire the callback after releasing the lock.
+                drop(sleepers);
+                self.notify_busy(prev);
+
+                return true;
             }
         }
+
+        false
     }
 
     /// Returns `true` if `worker_id` is contained in the sleep set
@@ -136,6 +171,15 @@ impl Idle {
         let state = State(self.state.fetch_add(0, SeqCst));
         state.num_searching() == 0 && state.num_unparked() < self.num_workers
     }
+
+    /// Fires the busy callback if `prev` shows all workers were parked.
+    fn notify_busy(&self, prev: State) {
+        if prev.num_unparked() == 0 {
+            if let Some(f) = &self.on_busy {
+                f();
+            }
+        }
+    }
 }
 
 impl State {
@@ -151,8 +195,9 @@ impl State {
         State(cell.load(ordering))
     }
 
-    fn unpark_one(cell: &AtomicUsize) {
-        cell.fetch_add(1 | (1 << UNPARK_SHIFT), SeqCst);
+    /// Returns the state prior to the transition.
+    fn unpark_one(cell: &AtomicUsize) -> State {
+        State(cell.fetch_add(1 | (1 << UNPARK_SHIFT), SeqCst))
     }
 
     fn inc_num_searching(cell: &AtomicUsize, ordering: Ordering) {
@@ -167,16 +212,15 @@ impl State {
 
     /// Track a sleeping worker
     ///
-    /// Returns `true` if this is the final searching worker.
-    fn dec_num_unparked(cell: &AtomicUsize, is_searching: bool) -> bool {
+    /// Returns the state prior to the transition.
+    fn dec_num_unparked(cell: &AtomicUsize, is_searching: bool) -> State {
         let mut dec = 1 << UNPARK_SHIFT;
 
         if is_searching {
             dec += 1;
         }
 
-        let prev = State(cell.fetch_sub(dec, SeqCst));
-        is_searching && prev.num_searching() == 1
+        State(cell.fetch_sub(dec, SeqCst))
     }
 
     /// Number of workers currently searching
@@ -13,7 +13,8 @@ pub(crate) use worker::block_in_place;
 
 use crate::loom::sync::Arc;
 use crate::runtime::task::{self, JoinHandle};
-use crate::runtime::Parker;
+use crate::runtime::{Callback, Parker};
+use crate::task::{HintKind, SpawnHint};
 
 use std::fmt;
 use std::future::Future;
@@ -43,8 +44,13 @@ pub(crate) struct Spawner {
 // ===== impl ThreadPool =====
 
 impl ThreadPool {
-    pub(crate) fn new(size: usize, parker: Parker) -> (ThreadPool, Launch) {
-        let (shared, launch) = worker::create(size, parker);
+    pub(crate) fn new(
+        size: usize,
+        parker: Parker,
+        on_idle: Option<Callback>,
+        on_busy: Option<Callback>,
+    ) -> (ThreadPool, Launch) {
+        let (shared, launch) = worker::create(size, parker, on_idle, on_busy);
         let spawner = Spawner { shared };
         let thread_pool = ThreadPool { spawner };
 
@@ -98,6 +104,51 @@ impl Spawner {
         handle
     }
 
+    /// Spawns a batch of futures onto the thread pool.
+    ///
+    /// All tasks are pushed onto the injection queue in one pass, avoiding
+    /// per-spawn queue contention.
+    pub(crate) fn spawn_iter<I>(&self, iter: I) -> Vec<JoinHandle<<I::Item as Future>::Output>>
+    where
+        I: IntoIterator,
+        I::Item: Future + Send + 'static,
+        <I::Item as Future>::Output: Send + 'static,
+    {
+        let iter = iter.into_iter();
+        let (lower, upper) = iter.size_hint();
+        let cap = upper.unwrap_or(lower);
+
+        let mut tasks = Vec::with_capacity(cap);
+        let mut handles = Vec::with_capacity(cap);
+
+        for future in iter {
+            let (task, handle) = task::joinable(future);
+            tasks.push(task);
+            handles.push(handle);
+        }
+
+        self.shared.schedule_batch(tasks);
+        handles
+    }
+
+    /// Spawns a future onto the thread pool with a worker placement hint
+    pub(crate) fn spawn_hinted<F>(&self, future: F, hint: SpawnHint) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let (task, handle) = task::joinable(future);
+
+        match hint.kind {
+            // `schedule` already prefers the current worker when called from
+            // a worker thread.
+            HintKind::CurrentWorker => self.shared.schedule(task, false),
+            HintKind::Worker(index) => self.shared.schedule_for_worker(task, index),
+        }
+
+        handle
+    }
+
     pub(crate) fn shutdown(&mut self) {
         self.shared.close();
     }
@@ -12,7 +12,7 @@ use crate::runtime;
 use crate::runtime::enter::EnterContext;
 use crate::runtime::park::{Parker, Unparker};
 use crate::runtime::thread_pool::{AtomicCell, Idle};
-use crate::runtime::{queue, task};
+use crate::runtime::{queue, task, Callback};
 use crate::util::linked_list::{Link, LinkedList};
 use crate::util::FastRand;
 
@@ -95,6 +95,11 @@ struct Remote {
     /// worker pops.
     pending_drop: task::TransferStack<Arc<Worker>>,
 
+    /// Tasks hinted to run on this worker. The owning worker pops from here
+    /// before falling back on the global queue, but any searching worker may
+    /// steal from it if the owner is busy.
+    mailbox: queue::Inject<Arc<Worker>>,
+
     /// Unparks the associated worker thread
     unpark: Unparker,
 }
@@ -125,7 +130,12 @@ type Notified = task::Notified<Arc<Worker>>;
 // Tracks thread-local state
 scoped_thread_local!(static CURRENT: Context);
 
-pub(super) fn create(size: usize, park: Parker) -> (Arc<Shared>, Launch) {
+pub(super) fn create(
+    size: usize,
+    park: Parker,
+    on_idle: Option<Callback>,
+    on_busy: Option<Callback>,
+) -> (Arc<Shared>, Launch) {
     let mut cores = vec![];
     let mut remotes = vec![];
 
@@ -150,6 +160,7 @@ pub(super) fn create(size: usize, park: Parker) -> (Arc<Shared>, Launch) {
         remotes.push(Remote {
             steal,
             pending_drop: task::TransferStack::new(),
+            mailbox: queue::Inject::new(),
             unpark,
         });
     }
@@ -157,7 +168,7 @@ pub(super) fn create(size: usize, park: Parker) -> (Arc<Shared>, Launch) {
     let shared = Arc::new(Shared {
         remotes: remotes.into_boxed_slice(),
         inject: queue::Inject::new(),
-        idle: Idle::new(size),
+        idle: Idle::new(size, on_idle, on_busy),
         shutdown_cores: Mutex::new(vec![]),
     });
 
@@ -446,9 +457,15 @@ impl Core {
     /// Return the next notified task available to this worker.
     fn next_task(&mut self, worker: &Worker) -> Option<Notified> {
         if self.tick % GLOBAL_POLL_INTERVAL == 0 {
-            worker.inject().pop().or_else(|| self.next_local_task())
+            worker
+                .inject()
+                .pop()
+                .or_else(|| worker.remote().mailbox.pop())
+                .or_else(|| self.next_local_task())
         } else {
-            self.next_local_task().or_else(|| worker.inject().pop())
+            self.next_local_task()
+                .or_else(|| worker.remote().mailbox.pop())
+                .or_else(|| worker.inject().pop())
         }
     }
 
@@ -479,6 +496,21 @@ impl Core {
             }
         }
 
+        // Check the mailboxes of the other workers. A mailbox entry is only a
+        // placement hint; claiming it here keeps the task from stranding
+        // behind a busy worker.
+        for i in 0..num {
+            let i = (start + i) % num;
+
+            if i == worker.index {
+                continue;
+            }
+
+            if let Some(task) = worker.shared.remotes[i].mailbox.pop() {
+                return Some(task);
+            }
+        }
+
         // Fallback on checking the global queue
         worker.shared.inject.pop()
     }
@@ -737,6 +769,46 @@ impl Shared {
         });
     }
 
+    /// Schedules a batch of tasks at once.
+    ///
+    /// The tasks are appended to the injection queue under a single lock
+    /// acquisition, and enough sleeping workers to start on the batch are
+    /// notified afterwards. This is cheaper than scheduling the tasks one by
+    /// one when spawning many tasks at job start.
+    pub(super) fn schedule_batch(&self, tasks: Vec<Notified>) {
+        let num = tasks.len();
+
+        if num == 0 {
+            return;
+        }
+
+        self.inject.push_iter(tasks.into_iter());
+
+        for _ in 0..num.min(self.remotes.len()) {
+            self.notify_parked();
+        }
+    }
+
+    /// Schedule a task with a preference for the given worker.
+    ///
+    /// The task is pushed to the worker's mailbox and the worker is unparked
+    /// if it is sleeping. The preference is only a hint: if the worker is
+    /// busy, any searching worker may claim the task instead.
+    pub(super) fn schedule_for_worker(&self, task: Notified, index: usize) {
+        let index = index % self.remotes.len();
+
+        self.remotes[index].mailbox.push(task);
+
+        // Unpark the target worker if it is sleeping. If it is not, notify an
+        // idle worker so the task is picked up promptly even when the target
+        // is busy with a long-running task.
+        if self.idle.unpark_worker_by_id(index) {
+            self.remotes[index].unpark.unpark();
+        } else {
+            self.notify_parked();
+        }
+    }
+
     fn schedule_local(&self, core: &mut Core, task: Notified, is_yield: bool) {
         // Spawning from the worker thread. If scheduling a "yield" then the
         // task must always be pushed to the back of the queue, enabling other
@@ -769,6 +841,10 @@ impl Shared {
 
     pub(super) fn close(&self) {
         if self.inject.close() {
+            for remote in &self.remotes[..] {
+                remote.mailbox.close();
+            }
+
             self.notify_all();
         }
     }
@@ -787,7 +863,7 @@ impl Shared {
 
     fn notify_if_work_pending(&self) {
         for remote in &self.remotes[..] {
-            if !remote.steal.is_empty() {
+            if !remote.steal.is_empty() || !remote.mailbox.is_empty() {
                 self.notify_parked();
                 return;
             }
@@ -822,8 +898,12 @@ impl Shared {
             core.shutdown();
         }
 
-        // Drain the injection queue
+        // Drain the injection queue and mailboxes
         while self.inject.pop().is_some() {}
+
+        for remote in &self.remotes[..] {
+            while remote.mailbox.pop().is_some() {}
+        }
     }
 
     fn ptr_eq(&self, other: &Shared) -> bool {
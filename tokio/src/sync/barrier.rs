@@ -41,13 +41,13 @@ use std::sync::Mutex;
 pub struct Barrier {
     state: Mutex<BarrierState>,
     wait: watch::Receiver<usize>,
-    n: usize,
 }
 
 #[derive(Debug)]
 struct BarrierState {
     waker: watch::Sender<usize>,
     arrived: usize,
+    required: usize,
     generation: usize,
 }
 
@@ -70,13 +70,49 @@ impl Barrier {
             state: Mutex::new(BarrierState {
                 waker,
                 arrived: 0,
+                required: n,
                 generation: 1,
             }),
-            n,
             wait,
         }
     }
 
+    /// Increases the number of threads that must rendezvous to release the barrier by one.
+    ///
+    /// The change applies to the current generation: threads already waiting now need one
+    /// more arrival before they are released.
+    pub fn add_party(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.required += 1;
+    }
+
+    /// Decreases the number of threads that must rendezvous to release the barrier by one.
+    ///
+    /// The change applies to the current generation. If the threads currently waiting are
+    /// no longer short a party, the generation is released immediately; since none of them
+    /// is the final arriver, none of them sees itself as the leader, and the next
+    /// generation starts fresh.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this would leave the barrier with no parties at all.
+    pub fn remove_party(&self) {
+        let mut state = self.state.lock().unwrap();
+        assert!(
+            state.required > 1,
+            "cannot remove the last party from a barrier"
+        );
+        state.required -= 1;
+        if state.arrived == state.required {
+            state
+                .waker
+                .send(state.generation)
+                .expect("there is at least one receiver");
+            state.arrived = 0;
+            state.generation += 1;
+        }
+    }
+
     /// Does not resolve until all tasks have rendezvoused here.
     ///
     /// Barriers are re-usable after all threads have rendezvoused once, and can
@@ -97,7 +133,7 @@ impl Barrier {
             let mut state = self.state.lock().unwrap();
             let generation = state.generation;
             state.arrived += 1;
-            if state.arrived == self.n {
+            if state.arrived == state.required {
                 // we are the leader for this generation
                 // wake everyone, increment the generation, and return
                 state
@@ -19,12 +19,13 @@ use crate::loom::cell::UnsafeCell;
 use crate::loom::sync::atomic::AtomicUsize;
 use crate::loom::sync::{Mutex, MutexGuard};
 use crate::util::linked_list::{self, LinkedList};
+use crate::util::wake_list::WakeList;
 
 use std::future::Future;
 use std::marker::PhantomPinned;
 use std::pin::Pin;
 use std::ptr::NonNull;
-use std::sync::atomic::Ordering::*;
+use std::sync::atomic::Ordering::{self, *};
 use std::task::Poll::*;
 use std::task::{Context, Poll, Waker};
 use std::{cmp, fmt};
@@ -34,15 +35,91 @@ pub(crate) struct Semaphore {
     waiters: Mutex<Waitlist>,
     /// The current number of available permits in the semaphore.
     permits: AtomicUsize,
+    /// The number of waiters currently linked into the wait list.
+    ///
+    /// This is used by `release` to skip locking the wait list entirely when
+    /// no tasks are waiting. It is only modified while holding the `waiters`
+    /// lock, but is read without it; see `release` for the protocol making
+    /// that sound.
+    queued_waiters: AtomicUsize,
     /// The current underflow preventing the acquisition of new permits.
     underflow: AtomicUsize,
+    /// The number of priority classes waiters may be tagged with. This is `1`
+    /// unless the semaphore was created with `new_weighted`.
+    num_classes: u32,
+    /// The maximum number of permits the counter may hold. This is
+    /// `MAX_PERMITS` unless the semaphore was created with `new_with_max`, in
+    /// which case releases saturate at this value instead of panicking.
+    max_permits: usize,
+    /// Identifies this semaphore in the events it emits.
+    #[cfg(all(tokio_unstable, feature = "tracing"))]
+    resource_id: u64,
 }
 
+type WaitQueue = LinkedList<Waiter, <Waiter as linked_list::Link>::Target>;
+
 struct Waitlist {
-    queue: LinkedList<Waiter, <Waiter as linked_list::Link>::Target>,
+    queue: WaitQueue,
+    /// Per-class wait queues, used instead of `queue` when the semaphore was
+    /// created with a weighted fairness policy. Empty otherwise.
+    classes: Vec<ClassQueue>,
+    /// When `true`, permits are handed to the most recently queued waiter
+    /// rather than the oldest one.
+    lifo: bool,
+    /// Wakers of tasks waiting for the semaphore to be closed.
+    closed_wakers: Vec<Waker>,
+    /// Wakers registered by `poll_try_acquire`. Unlike queued waiters these
+    /// hold no reservation: they are woken whenever permits land in the
+    /// counter and race to claim them. Each registration is counted in
+    /// `queued_waiters` so `release` takes the locked path and drains them.
+    ready_wakers: Vec<Waker>,
     closed: bool,
 }
 
+/// The wait queue for a single priority class of a weighted semaphore.
+struct ClassQueue {
+    queue: WaitQueue,
+    /// The relative share of permits this class is entitled to.
+    weight: u32,
+    /// The number of permits assigned to this class so far. Permits are handed
+    /// to the waiting class with the smallest `served / weight` ratio, which
+    /// keeps long-run distribution proportional to the weights.
+    served: u64,
+}
+
+impl Waitlist {
+    /// Returns the queue holding waiters of the given class.
+    fn queue_mut(&mut self, class: u32) -> &mut WaitQueue {
+        if self.classes.is_empty() {
+            &mut self.queue
+        } else {
+            &mut self.classes[class as usize].queue
+        }
+    }
+
+    /// Enqueues a waiter in the queue for its class.
+    fn push(&mut self, waiter: NonNull<Waiter>, class: u32) {
+        if !self.classes.is_empty() && self.classes[class as usize].queue.is_empty() {
+            // The class is waking up from an idle period. Advance its `served`
+            // count to the least-served class that is currently waiting, so
+            // that an idle class does not accumulate unbounded credit and then
+            // monopolize the semaphore to "catch up".
+            if let Some(min) = self
+                .classes
+                .iter()
+                .filter(|c| !c.queue.is_empty())
+                .map(|c| c.served)
+                .min()
+            {
+                let entry = &mut self.classes[class as usize];
+                entry.served = cmp::max(entry.served, min);
+            }
+        }
+
+        self.queue_mut(class).push_front(waiter);
+    }
+}
+
 /// Error returned from the [`Semaphore::try_acquire`] function.
 ///
 /// [`Semaphore::try_acquire`]: crate::sync::Semaphore::try_acquire
@@ -72,7 +149,6 @@ pub(crate) struct Acquire<'a> {
     num_permits: u32,
     queued: bool,
 }
-
 /// An entry in the wait queue.
 struct Waiter {
     /// The current state of the waiter.
@@ -88,6 +164,11 @@ struct Waiter {
     /// This may only be accessed while the wait queue is locked.
     waker: UnsafeCell<Option<Waker>>,
 
+    /// The priority class this waiter belongs to. Determines which wait queue
+    /// the waiter is linked into on a weighted semaphore; always `0` on an
+    /// unweighted one.
+    class: u32,
+
     /// Intrusive linked-list pointers.
     ///
     /// # Safety
@@ -104,6 +185,11 @@ struct Waiter {
 
     /// Should not be `Unpin`.
     _p: PhantomPinned,
+
+    /// When the `Acquire` future owning this waiter was created; used to
+    /// report wait durations.
+    #[cfg(all(tokio_unstable, feature = "tracing"))]
+    created_at: std::time::Instant,
 }
 
 impl Semaphore {
@@ -134,12 +220,81 @@ impl Semaphore {
             permits: AtomicUsize::new(permits << Self::PERMIT_SHIFT),
             waiters: Mutex::new(Waitlist {
                 queue: LinkedList::new(),
+                classes: Vec::new(),
+                lifo: false,
+                closed_wakers: Vec::new(),
+                ready_wakers: Vec::new(),
                 closed: false,
             }),
+            queued_waiters: AtomicUsize::new(0),
             underflow: AtomicUsize::new(0),
+            num_classes: 1,
+            max_permits: Self::MAX_PERMITS,
+            #[cfg(all(tokio_unstable, feature = "tracing"))]
+            resource_id: crate::util::trace::resource_id(),
         }
     }
 
+    /// Creates a new semaphore with a weighted fairness policy.
+    ///
+    /// Waiters are tagged with a class (an index into `weights`) when
+    /// acquiring. Instead of a single FIFO queue, each class has its own
+    /// queue, and permits are distributed between the waiting classes
+    /// proportionally to their weights.
+    pub(crate) fn new_weighted(permits: usize, weights: &[u32]) -> Self {
+        assert!(
+            !weights.is_empty(),
+            "a weighted semaphore requires at least one class"
+        );
+        assert!(
+            weights.iter().all(|&weight| weight > 0),
+            "class weights must be non-zero"
+        );
+
+        let mut sem = Self::new(permits);
+        sem.num_classes = weights.len() as u32;
+        sem.waiters.lock().classes = weights
+            .iter()
+            .map(|&weight| ClassQueue {
+                queue: LinkedList::new(),
+                weight,
+                served: 0,
+            })
+            .collect();
+        sem
+    }
+
+    /// Creates a new semaphore that wakes waiters in LIFO order.
+    ///
+    /// Permits released while waiters are queued are handed to the most
+    /// recently queued waiter instead of the oldest one.
+    pub(crate) fn new_unfair(permits: usize) -> Self {
+        let sem = Self::new(permits);
+        sem.waiters.lock().lifo = true;
+        sem
+    }
+
+    /// Creates a new semaphore with a maximum capacity.
+    ///
+    /// Released permits saturate at `max_permits` instead of panicking when
+    /// the counter would exceed it.
+    pub(crate) fn new_with_max(permits: usize, max_permits: usize) -> Self {
+        assert!(
+            max_permits <= Self::MAX_PERMITS,
+            "a semaphore may not have more than MAX_PERMITS permits ({})",
+            Self::MAX_PERMITS
+        );
+        assert!(
+            permits <= max_permits,
+            "the initial number of permits ({}) may not exceed the maximum ({})",
+            permits,
+            max_permits
+        );
+        let mut sem = Self::new(permits);
+        sem.max_permits = max_permits;
+        sem
+    }
+
     /// Creates a new semaphore with the initial number of permits
     ///
     /// Maximum number of permits on 32-bit platforms is `1<<29`.
@@ -156,9 +311,20 @@ impl Semaphore {
             permits: AtomicUsize::new(permits << Self::PERMIT_SHIFT),
             waiters: Mutex::const_new(Waitlist {
                 queue: LinkedList::new(),
+                classes: Vec::new(),
+                lifo: false,
+                closed_wakers: Vec::new(),
+                ready_wakers: Vec::new(),
                 closed: false,
             }),
+            queued_waiters: AtomicUsize::new(0),
             underflow: AtomicUsize::new(0),
+            num_classes: 1,
+            max_permits: Self::MAX_PERMITS,
+            // A fresh ID cannot be allocated in a `const fn`; `0` marks the
+            // resource as unidentified.
+            #[cfg(all(tokio_unstable, feature = "tracing"))]
+            resource_id: 0,
         }
     }
 
@@ -167,6 +333,43 @@ impl Semaphore {
         self.permits.load(Acquire) >> Self::PERMIT_SHIFT
     }
 
+    /// Returns the number of tasks currently waiting in the queue.
+    pub(crate) fn waiters(&self) -> usize {
+        self.queued_waiters.load(SeqCst)
+    }
+
+    /// Returns the maximum number of permits the semaphore may hold.
+    pub(crate) fn max_permits(&self) -> usize {
+        self.max_permits
+    }
+
+    /// Returns the total number of permits the queued waiters are still
+    /// waiting for.
+    #[cfg(feature = "sync")]
+    pub(crate) fn queued_permits(&self) -> usize {
+        let waiters = self.waiters.lock();
+        let Waitlist { queue, classes, .. } = &*waiters;
+        std::iter::once(queue)
+            .chain(classes.iter().map(|class| &class.queue))
+            .flat_map(|queue| queue.iter())
+            .map(|waiter| waiter.state.load(Acquire))
+            .sum()
+    }
+
+    /// Returns the ID identifying this semaphore in the events it emits.
+    #[cfg(all(tokio_unstable, feature = "tracing"))]
+    pub(crate) fn resource_id(&self) -> u64 {
+        self.resource_id
+    }
+
+    /// Identifier under which this semaphore is tracked by
+    /// `sync::diagnostics`. The address is stable for the semaphore's
+    /// lifetime and unique among live semaphores.
+    #[cfg(all(tokio_unstable, feature = "rt"))]
+    pub(crate) fn diag_id(&self) -> u64 {
+        self as *const Semaphore as u64
+    }
+
     /// Adds `added` new permits to the semaphore.
     ///
     /// The maximum number of permits is `usize::MAX >> 3`, and this function will panic if the limit is exceeded.
@@ -175,6 +378,18 @@ impl Semaphore {
             return;
         }
 
+        #[cfg(all(tokio_unstable, feature = "rt"))]
+        crate::sync::diagnostics::released(self.diag_id());
+
+        #[cfg(all(tokio_unstable, feature = "tracing"))]
+        tracing::trace!(
+            target: "tokio::sync",
+            resource_id = self.resource_id,
+            released = added,
+            permits = self.available_permits(),
+            "semaphore.release",
+        );
+
         // Try to empty the underflow with the number of permits requested and
         // return the number of permits remaining after the operation.
         let remaining = match self
@@ -185,10 +400,92 @@ impl Semaphore {
             Err(_) => unreachable!(),
         };
 
+        // Fast path: when no tasks are waiting, the permits can be added
+        // directly to the counter without locking the wait list. This is the
+        // hot path when the semaphore is contended on permits but not on
+        // waiters (e.g. released permits are consumed by `try_acquire`).
+        //
+        // The protocol is a classic store/load fence dance with
+        // `poll_acquire`: a waiter increments `queued_waiters` (while holding
+        // the lock) *before* it re-checks the permit counter, and the release
+        // re-checks `queued_waiters` *after* adding to the permit counter.
+        // With sequentially consistent ordering on these four accesses, at
+        // least one side observes the other, so a waiter can never be left
+        // sleeping while permits sit in the counter.
+        let qw = self.queued_waiters.load(SeqCst);
+        if qw == 0 {
+            self.add_to_counter(remaining, SeqCst);
+
+            if self.queued_waiters.load(SeqCst) != 0 {
+                // A waiter was enqueued concurrently and may have missed the
+                // permits added above. Pull whatever is left in the counter
+                // back out and assign it to the wait queue.
+                let waiters = self.waiters.lock();
+                let drained = self.permits.fetch_and(Self::CLOSED, AcqRel) >> Self::PERMIT_SHIFT;
+                self.add_permits_locked(drained, waiters);
+            }
+
+            return;
+        }
+
         // Assign permits to the wait queue
         self.add_permits_locked(remaining, self.waiters.lock());
     }
 
+    /// Adds `added` new permits to the semaphore, saturating at
+    /// [`max_permits`] instead of panicking when the counter would overflow.
+    ///
+    /// [`max_permits`]: Semaphore::max_permits
+    pub(crate) fn release_saturating(&self, added: usize) {
+        // A semaphore created with `new_with_max` already saturates in
+        // `release`; for the default maximum, clamp the amount up front so
+        // the counter cannot overflow `MAX_PERMITS`.
+        let added = cmp::min(
+            added,
+            self.max_permits.saturating_sub(self.available_permits()),
+        );
+        self.release(added);
+    }
+
+    /// Adds `rem` permits to the permit counter.
+    ///
+    /// For a semaphore with the default maximum this panics if the counter
+    /// would exceed `MAX_PERMITS`; for one created with `new_with_max` the
+    /// counter saturates at the configured maximum instead.
+    fn add_to_counter(&self, rem: usize, ordering: Ordering) {
+        if self.max_permits == Self::MAX_PERMITS {
+            assert!(
+                rem <= Self::MAX_PERMITS,
+                "cannot add more than MAX_PERMITS permits ({})",
+                Self::MAX_PERMITS
+            );
+            let prev = self.permits.fetch_add(rem << Self::PERMIT_SHIFT, ordering);
+            assert!(
+                (prev >> Self::PERMIT_SHIFT) + rem <= Self::MAX_PERMITS,
+                "number of added permits ({}) would overflow MAX_PERMITS ({})",
+                rem,
+                Self::MAX_PERMITS
+            );
+        } else {
+            // The `CLOSED` bit occupies the low bit and is preserved by the
+            // shifted addition below.
+            let mut curr = self.permits.load(Relaxed);
+            loop {
+                let available = curr >> Self::PERMIT_SHIFT;
+                let add = cmp::min(rem, self.max_permits.saturating_sub(available));
+                match self.permits.compare_exchange_weak(
+                    curr,
+                    curr + (add << Self::PERMIT_SHIFT),
+                    ordering,
+                    Relaxed,
+                ) {
+                    Ok(_) => return,
+                    Err(actual) => curr = actual,
+                }
+            }
+        }
+    }
+
     /// Closes the semaphore. This prevents the semaphore from issuing new
     /// permits and notifies all pending waiters.
     pub(crate) fn close(&self) {
@@ -202,10 +499,22 @@ impl Semaphore {
         // permit counter is closed, but the wait list is not.
         self.permits.fetch_or(Self::CLOSED, Release);
         waiters.closed = true;
-        while let Some(mut waiter) = waiters.queue.pop_back() {
-            let waker = unsafe { waiter.as_mut().waker.with_mut(|waker| (*waker).take()) };
-            if let Some(waker) = waker {
-                waker.wake();
+        for waker in waiters.closed_wakers.drain(..) {
+            waker.wake();
+        }
+        for waker in waiters.ready_wakers.drain(..) {
+            self.queued_waiters.fetch_sub(1, SeqCst);
+            waker.wake();
+        }
+        let Waitlist { queue, classes, .. } = &mut *waiters;
+        for queue in std::iter::once(queue).chain(classes.iter_mut().map(|class| &mut class.queue))
+        {
+            while let Some(mut waiter) = queue.pop_back() {
+                self.queued_waiters.fetch_sub(1, SeqCst);
+                let waker = unsafe { waiter.as_mut().waker.with_mut(|waker| (*waker).take()) };
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
             }
         }
     }
@@ -215,6 +524,32 @@ impl Semaphore {
         self.permits.load(Acquire) & Self::CLOSED == Self::CLOSED
     }
 
+    /// Polls for the semaphore to be closed, registering the current task for
+    /// wakeup if it is not.
+    pub(crate) fn poll_closed(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.is_closed() {
+            return Ready(());
+        }
+
+        let mut waiters = self.waiters.lock();
+        if waiters.closed {
+            return Ready(());
+        }
+
+        // Register the waker unless an earlier poll by the same task already
+        // did. Stale wakers left behind by dropped futures are drained on
+        // close and cause nothing worse than a spurious wakeup.
+        if !waiters
+            .closed_wakers
+            .iter()
+            .any(|waker| waker.will_wake(cx.waker()))
+        {
+            waiters.closed_wakers.push(cx.waker().clone());
+        }
+
+        Pending
+    }
+
     pub(crate) fn try_acquire(&self, num_permits: u32) -> Result<(), TryAcquireError> {
         assert!(
             num_permits as usize <= Self::MAX_PERMITS,
@@ -242,14 +577,145 @@ impl Semaphore {
             let next = curr - num_permits;
 
             match self.permits.compare_exchange(curr, next, AcqRel, Acquire) {
-                Ok(_) => return Ok(()),
+                Ok(_) => {
+                    #[cfg(all(tokio_unstable, feature = "rt"))]
+                    crate::sync::diagnostics::acquired(self.diag_id());
+
+                    return Ok(());
+                }
+                Err(actual) => curr = actual,
+            }
+        }
+    }
+
+    /// Polls to acquire `num_permits` without queueing a waiter.
+    ///
+    /// Unlike [`acquire`], the task holds no position in the wait queue and
+    /// no state between polls: on `Pending` its waker is parked and woken the
+    /// next time permits land in the permit counter, at which point it races
+    /// other callers to claim them. Queued waiters are still served first, so
+    /// pollers cannot starve them. This is the building block for poll-based
+    /// callers that cannot store an [`Acquire`] future.
+    ///
+    /// [`acquire`]: Semaphore::acquire
+    pub(crate) fn poll_try_acquire(
+        &self,
+        num_permits: u32,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), TryAcquireError>> {
+        match self.try_acquire(num_permits) {
+            Ok(()) => return Ready(Ok(())),
+            Err(TryAcquireError::Closed) => return Ready(Err(TryAcquireError::Closed)),
+            Err(TryAcquireError::NoPermits) => {}
+        }
+
+        {
+            let mut waiters = self.waiters.lock();
+
+            if waiters.closed {
+                return Ready(Err(TryAcquireError::Closed));
+            }
+
+            // Register the waker unless an earlier poll by the same task
+            // already did. Each registration is counted in `queued_waiters`
+            // while it is parked, forcing `release` onto the locked path
+            // where the registration is drained.
+            if !waiters
+                .ready_wakers
+                .iter()
+                .any(|waker| waker.will_wake(cx.waker()))
+            {
+                waiters.ready_wakers.push(cx.waker().clone());
+                self.queued_waiters.fetch_add(1, SeqCst);
+            }
+        }
+
+        // Permits released between the failed attempt above and the
+        // registration becoming visible would not have woken this task;
+        // re-check now.
+        match self.try_acquire(num_permits) {
+            Ok(()) => {
+                self.forget_ready_waker(cx);
+                Ready(Ok(()))
+            }
+            Err(TryAcquireError::Closed) => {
+                self.forget_ready_waker(cx);
+                Ready(Err(TryAcquireError::Closed))
+            }
+            Err(TryAcquireError::NoPermits) => Pending,
+        }
+    }
+
+    /// Removes the registration left by `poll_try_acquire` once the poll
+    /// completes, so a completed poller does not keep `release` on its slow
+    /// path indefinitely.
+    fn forget_ready_waker(&self, cx: &mut Context<'_>) {
+        let mut waiters = self.waiters.lock();
+
+        let before = waiters.ready_wakers.len();
+        waiters
+            .ready_wakers
+            .retain(|waker| !waker.will_wake(cx.waker()));
+
+        if waiters.ready_wakers.len() < before {
+            self.queued_waiters.fetch_sub(1, SeqCst);
+        }
+    }
+
+    /// Tries to acquire up to `max_permits` permits, returning however many
+    /// (at least one) were immediately available.
+    pub(crate) fn try_acquire_upto(&self, max_permits: u32) -> Result<u32, TryAcquireError> {
+        assert!(
+            max_permits as usize <= Self::MAX_PERMITS,
+            "a semaphore may not have more than MAX_PERMITS permits ({})",
+            Self::MAX_PERMITS
+        );
+
+        if self.underflow.load(Acquire) > 0 {
+            return Err(TryAcquireError::NoPermits);
+        }
+
+        let mut curr = self.permits.load(Acquire);
+        loop {
+            // Has the semaphore closed?
+            if curr & Self::CLOSED == Self::CLOSED {
+                return Err(TryAcquireError::Closed);
+            }
+
+            let available = curr >> Self::PERMIT_SHIFT;
+            let acquired = cmp::min(available, max_permits as usize);
+            if acquired == 0 {
+                return Err(TryAcquireError::NoPermits);
+            }
+
+            let next = curr - (acquired << Self::PERMIT_SHIFT);
+
+            match self.permits.compare_exchange(curr, next, AcqRel, Acquire) {
+                Ok(_) => return Ok(acquired as u32),
                 Err(actual) => curr = actual,
             }
         }
     }
 
     pub(crate) fn acquire(&self, num_permits: u32) -> Acquire<'_> {
-        Acquire::new(self, num_permits)
+        Acquire::new(self, num_permits, 0)
+    }
+
+    /// Acquires permits as a member of the given priority class.
+    ///
+    /// On a semaphore created with [`new_weighted`], a waiter that cannot be
+    /// satisfied immediately joins the queue of its class rather than a global
+    /// FIFO queue. On an unweighted semaphore only class `0` exists.
+    ///
+    /// [`new_weighted`]: Semaphore::new_weighted
+    pub(crate) fn acquire_for_class(&self, num_permits: u32, class: u32) -> Acquire<'_> {
+        assert!(
+            class < self.num_classes,
+            "class index {} out of bounds for a semaphore with {} classes",
+            class,
+            self.num_classes
+        );
+        Acquire::new(self, num_permits, class)
     }
 
     /// Release `rem` permits to the semaphore's wait list, starting from the
@@ -257,58 +723,84 @@ impl Semaphore {
     ///
     /// If `rem` exceeds the number of permits needed by the wait list, the
     /// remainder are assigned back to the semaphore.
-    fn add_permits_locked(&self, mut rem: usize, waiters: MutexGuard<'_, Waitlist>) {
-        let mut wakers: [Option<Waker>; 8] = Default::default();
-        let mut lock = Some(waiters);
-        let mut is_empty = false;
+    fn add_permits_locked(&self, mut rem: usize, mut waiters: MutexGuard<'_, Waitlist>) {
+        // All eligible wakers are collected in a single critical section. The
+        // list spills to the heap beyond its inline capacity, so the lock is
+        // never re-acquired no matter how many waiters are woken; it used to
+        // be dropped and re-taken every 8 wakes.
+        let mut wakers = WakeList::new();
+
+        // In LIFO mode waiters are served from the head of the queue (the most
+        // recently queued waiter) instead of the tail.
+        let lifo = waiters.lifo;
+
         while rem > 0 {
-            let mut waiters = lock.take().unwrap_or_else(|| self.waiters.lock());
-            'inner: for slot in &mut wakers[..] {
-                // Was the waiter assigned enough permits to wake it?
-                match waiters.queue.last() {
-                    Some(waiter) => {
-                        if !waiter.assign_permits(&mut rem) {
-                            break 'inner;
-                        }
-                    }
-                    None => {
-                        is_empty = true;
-                        // If we assigned permits to all the waiters in the queue, and there are
-                        // still permits left over, assign them back to the semaphore.
-                        break 'inner;
+            // Pick the queue to serve next. For an unweighted semaphore this
+            // is always the single FIFO queue; for a weighted one it is the
+            // queue of the waiting class with the smallest served-to-weight
+            // ratio, which distributes permits proportionally to the weights.
+            let queue = if waiters.classes.is_empty() {
+                &mut waiters.queue
+            } else {
+                match next_class_locked(&waiters.classes) {
+                    Some(idx) => {
+                        let class = &mut waiters.classes[idx];
+                        // Permits handed to the waiter below all count against
+                        // this class, even if the waiter is not yet satisfied.
+                        class.served += cmp::min(
+                            rem,
+                            class.queue.last().map_or(0, |w| w.state.load(Acquire)),
+                        ) as u64;
+                        &mut class.queue
                     }
-                };
-                let mut waiter = waiters.queue.pop_back().unwrap();
-                *slot = unsafe { waiter.as_mut().waker.with_mut(|waker| (*waker).take()) };
-            }
+                    None => &mut waiters.queue,
+                }
+            };
 
-            if rem > 0 && is_empty {
-                let permits = rem;
-                assert!(
-                    permits <= Self::MAX_PERMITS,
-                    "cannot add more than MAX_PERMITS permits ({})",
-                    Self::MAX_PERMITS
-                );
-                let prev = self.permits.fetch_add(rem << Self::PERMIT_SHIFT, Release);
-                let prev = prev >> Self::PERMIT_SHIFT;
-                assert!(
-                    prev + permits <= Self::MAX_PERMITS,
-                    "number of added permits ({}) would overflow MAX_PERMITS ({})",
-                    rem,
-                    Self::MAX_PERMITS
-                );
-                rem = 0;
-            }
+            // Was the waiter assigned enough permits to wake it?
+            let next = if lifo { queue.first() } else { queue.last() };
+            match next {
+                Some(waiter) => {
+                    let sat = waiter.assign_permits(&mut rem);
+                    if !sat {
+                        break;
+                    }
+                }
+                None => {
+                    // If we assigned permits to all the waiters in the queue,
+                    // and there are still permits left over, assign them back
+                    // to the semaphore.
+                    self.add_to_counter(rem, Release);
+                    rem = 0;
+
+                    // Permits landed in the counter: tasks polling with
+                    // `poll_try_acquire` can now race for them.
+                    for waker in waiters.ready_wakers.drain(..) {
+                        self.queued_waiters.fetch_sub(1, SeqCst);
+                        wakers.push(waker);
+                    }
 
-            drop(waiters); // release the lock
+                    break;
+                }
+            };
 
-            wakers
-                .iter_mut()
-                .filter_map(Option::take)
-                .for_each(Waker::wake);
+            let mut waiter = if lifo {
+                queue.pop_front().unwrap()
+            } else {
+                queue.pop_back().unwrap()
+            };
+            self.queued_waiters.fetch_sub(1, SeqCst);
+            if let Some(waker) = unsafe { waiter.as_mut().waker.with_mut(|waker| (*waker).take()) }
+            {
+                wakers.push(waker);
+            }
         }
 
         assert_eq!(rem, 0);
+
+        drop(waiters); // release the lock
+
+        wakers.wake_all();
     }
 
     fn poll_acquire(
@@ -318,75 +810,99 @@ impl Semaphore {
         node: Pin<&mut Waiter>,
         queued: bool,
     ) -> Poll<Result<(), AcquireError>> {
-        let mut acquired = 0;
+        if queued {
+            // A queued waiter is assigned permits exclusively by
+            // `add_permits_locked`; it must not help itself to the permit
+            // counter. Permits sitting in the counter while the queue is
+            // non-empty are transiently owned by a release's fast path and
+            // earmarked for the queue in FIFO order, so taking them here
+            // could satisfy this waiter with permits an earlier waiter is
+            // owed, leaving that waiter asleep forever.
+            let waiters = self.waiters.lock();
+
+            if waiters.closed {
+                return Ready(Err(AcquireError::closed()));
+            }
 
-        let needed = if queued {
-            node.state.load(Acquire) << Self::PERMIT_SHIFT
-        } else {
-            (num_permits as usize) << Self::PERMIT_SHIFT
-        };
+            // The state is written under the lock by whoever assigns the
+            // final permits, in the same critical section that unlinks the
+            // node, so on zero the node is guaranteed to no longer be queued.
+            if node.state.load(Acquire) == 0 {
+                return Ready(Ok(()));
+            }
+
+            // Woken without being satisfied (e.g. spuriously); re-register
+            // the waker if it changed.
+            Self::register_waker(&node, cx);
+            return Pending;
+        }
+
+        let needed = (num_permits as usize) << Self::PERMIT_SHIFT;
 
-        let mut lock = None;
         // First, try to take the requested number of permits from the
-        // semaphore.
+        // permit counter. Only a complete acquisition is made here: taking a
+        // partial batch out of the counter could steal permits a release has
+        // placed there for the waiters already in the queue.
         let mut curr = self.permits.load(Acquire);
-        let mut waiters = loop {
+        loop {
             // Has the semaphore closed?
             if curr & Self::CLOSED > 0 {
                 return Ready(Err(AcquireError::closed()));
             }
 
-            let mut remaining = 0;
-            let total = curr
-                .checked_add(acquired)
-                .expect("number of permits must not overflow");
-            let (next, acq) = if total >= needed {
-                let next = curr - (needed - acquired);
-                (next, needed >> Self::PERMIT_SHIFT)
-            } else {
-                remaining = (needed - acquired) - curr;
-                (0, curr >> Self::PERMIT_SHIFT)
-            };
-
-            if remaining > 0 && lock.is_none() {
-                // No permits were immediately available, so this permit will
-                // (probably) need to wait. We'll need to acquire a lock on the
-                // wait queue before continuing. We need to do this _before_ the
-                // CAS that sets the new value of the semaphore's `permits`
-                // counter. Otherwise, if we subtract the permits and then
-                // acquire the lock, we might miss additional permits being
-                // added while waiting for the lock.
-                lock = Some(self.waiters.lock());
+            if curr < needed {
+                break;
             }
 
-            match self.permits.compare_exchange(curr, next, AcqRel, Acquire) {
-                Ok(_) => {
-                    acquired += acq;
-                    if remaining == 0 {
-                        if !queued {
-                            return Ready(Ok(()));
-                        } else if lock.is_none() {
-                            break self.waiters.lock();
-                        }
-                    }
-                    break lock.expect("lock must be acquired before waiting");
-                }
+            match self
+                .permits
+                .compare_exchange(curr, curr - needed, AcqRel, Acquire)
+            {
+                Ok(_) => return Ready(Ok(())),
                 Err(actual) => curr = actual,
             }
-        };
+        }
+
+        // Not enough permits are immediately available; register the waker
+        // and enqueue the node.
+        let mut waiters = self.waiters.lock();
 
         if waiters.closed {
             return Ready(Err(AcquireError::closed()));
         }
 
-        if node.assign_permits(&mut acquired) {
-            self.add_permits_locked(acquired, waiters);
-            return Ready(Ok(()));
+        Self::register_waker(&node, cx);
+
+        let class = node.class;
+        let node = unsafe {
+            let node = Pin::into_inner_unchecked(node) as *mut _;
+            NonNull::new_unchecked(node)
+        };
+
+        waiters.push(node, class);
+        self.queued_waiters.fetch_add(1, SeqCst);
+
+        // A `release` may have observed `queued_waiters == 0` after this
+        // waiter's failed attempt on the permit counter but before the
+        // increment above, and placed its permits in the counter without
+        // touching the wait list. Re-check the counter while holding the
+        // lock and assign anything found to the queue (which now at least
+        // contains this waiter), so that release cannot be lost. If the
+        // queue was empty, this also delivers the permits this waiter found
+        // in the counter but could not take above.
+        let drained = self.permits.fetch_and(Self::CLOSED, SeqCst) >> Self::PERMIT_SHIFT;
+        if drained > 0 {
+            self.add_permits_locked(drained, waiters);
         }
 
-        assert_eq!(acquired, 0);
+        Pending
+    }
 
-        // Otherwise, register the waker & enqueue the node.
+    /// Stores the task's waker in `node` unless an equivalent waker is
+    /// already registered.
+    ///
+    /// The wait list lock must be held while calling this.
+    fn register_waker(node: &Pin<&mut Waiter>, cx: &mut Context<'_>) {
         node.waker.with_mut(|waker| {
             // Safety: the wait list is locked, so we may modify the waker.
             let waker = unsafe { &mut *waker };
@@ -399,18 +915,6 @@ impl Semaphore {
                 *waker = Some(cx.waker().clone());
             }
         });
-
-        // If the waiter is not already in the wait queue, enqueue it.
-        if !queued {
-            let node = unsafe {
-                let node = Pin::into_inner_unchecked(node) as *mut _;
-                NonNull::new_unchecked(node)
-            };
-
-            waiters.queue.push_front(node);
-        }
-
-        Pending
     }
 
     /// Shrinks the number of available permits by the indicated reduction.
@@ -427,6 +931,14 @@ impl Semaphore {
 
         self.underflow.store(underflow, Release);
     }
+
+    /// Returns the current permit deficit caused by `reduce_permits`.
+    ///
+    /// While non-zero, released permits pay down the deficit instead of
+    /// becoming available to new acquisitions.
+    pub(crate) fn underflow(&self) -> usize {
+        self.underflow.load(Acquire)
+    }
 }
 
 impl fmt::Debug for Semaphore {
@@ -437,13 +949,42 @@ impl fmt::Debug for Semaphore {
     }
 }
 
+/// Returns the index of the class that should receive permits next: the one
+/// with the smallest served-to-weight ratio among classes with waiters.
+fn next_class_locked(classes: &[ClassQueue]) -> Option<usize> {
+    let mut best: Option<usize> = None;
+    for (idx, class) in classes.iter().enumerate() {
+        if class.queue.is_empty() {
+            continue;
+        }
+        best = match best {
+            None => Some(idx),
+            Some(prev) => {
+                // `class.served / class.weight < classes[prev].served /
+                // classes[prev].weight`, avoiding division.
+                let lhs = class.served as u128 * classes[prev].weight as u128;
+                let rhs = classes[prev].served as u128 * class.weight as u128;
+                if lhs < rhs {
+                    Some(idx)
+                } else {
+                    Some(prev)
+                }
+            }
+        };
+    }
+    best
+}
+
 impl Waiter {
-    fn new(num_permits: u32) -> Self {
+    fn new(num_permits: u32, class: u32) -> Self {
         Waiter {
             waker: UnsafeCell::new(None),
             state: AtomicUsize::new(num_permits as usize),
+            class,
             pointers: linked_list::Pointers::new(),
             _p: PhantomPinned,
+            #[cfg(all(tokio_unstable, feature = "tracing"))]
+            created_at: std::time::Instant::now(),
         }
     }
 
@@ -474,15 +1015,50 @@ impl Future for Acquire<'_> {
         let coop = ready!(crate::coop::poll_proceed(cx));
 
         let (node, semaphore, needed, queued) = self.project();
+        let needed = *needed;
+
+        #[cfg(all(tokio_unstable, feature = "tracing"))]
+        let created_at = node.created_at;
 
         match semaphore.poll_acquire(cx, needed, node, *queued) {
             Pending => {
+                #[cfg(all(tokio_unstable, feature = "rt"))]
+                if !*queued {
+                    crate::runtime::console::resource_wait_started();
+                    crate::sync::diagnostics::wait_started(semaphore.diag_id());
+                }
+
+                #[cfg(all(tokio_unstable, feature = "tracing"))]
+                if !*queued {
+                    tracing::trace!(
+                        target: "tokio::sync",
+                        resource_id = semaphore.resource_id,
+                        permits = needed,
+                        "semaphore.waiting",
+                    );
+                }
+
                 *queued = true;
                 Pending
             }
             Ready(r) => {
                 coop.made_progress();
                 r?;
+
+                #[cfg(all(tokio_unstable, feature = "rt"))]
+                crate::sync::diagnostics::acquired(semaphore.diag_id());
+
+                #[cfg(all(tokio_unstable, feature = "tracing"))]
+                if *queued {
+                    tracing::trace!(
+                        target: "tokio::sync",
+                        resource_id = semaphore.resource_id,
+                        permits = needed,
+                        wait_us = created_at.elapsed().as_micros() as u64,
+                        "semaphore.acquired",
+                    );
+                }
+
                 *queued = false;
                 Ready(Ok(()))
             }
@@ -491,16 +1067,16 @@ impl Future for Acquire<'_> {
 }
 
 impl<'a> Acquire<'a> {
-    fn new(semaphore: &'a Semaphore, num_permits: u32) -> Self {
+    fn new(semaphore: &'a Semaphore, num_permits: u32, class: u32) -> Self {
         Self {
-            node: Waiter::new(num_permits),
+            node: Waiter::new(num_permits, class),
             semaphore,
             num_permits,
             queued: false,
         }
     }
 
-    fn project(self: Pin<&mut Self>) -> (Pin<&mut Waiter>, &Semaphore, u32, &mut bool) {
+    fn project(self: Pin<&mut Self>) -> (Pin<&mut Waiter>, &Semaphore, &mut u32, &mut bool) {
         fn is_unpin<T: Unpin>() {}
         unsafe {
             // Safety: all fields other than `node` are `Unpin`
@@ -513,11 +1089,120 @@ impl<'a> Acquire<'a> {
             (
                 Pin::new_unchecked(&mut this.node),
                 &this.semaphore,
-                this.num_permits,
+                &mut this.num_permits,
                 &mut this.queued,
             )
         }
     }
+
+    /// Cancels the acquisition, removing the waiter from the wait queue and
+    /// releasing any permits that were already assigned to it.
+    ///
+    /// Unlike simply dropping the future, this makes the released permits
+    /// available to other waiters before control returns to the caller, and
+    /// leaves the future in a state where dropping it is a no-op.
+    pub(crate) fn cancel(self: Pin<&mut Self>) {
+        let (node, semaphore, needed, queued) = self.project();
+
+        if !*queued {
+            return;
+        }
+
+        #[cfg(all(tokio_unstable, feature = "rt"))]
+        crate::sync::diagnostics::wait_cancelled(semaphore.diag_id());
+
+        let mut waiters = semaphore.waiters.lock();
+
+        let remaining = node.state.load(Acquire);
+        // Reset the node so a subsequent drop does not release anything.
+        node.state.store(*needed as usize, Release);
+
+        let class = node.class;
+        let ptr = unsafe {
+            let node = Pin::into_inner_unchecked(node) as *mut _;
+            NonNull::new_unchecked(node)
+        };
+        if unsafe { waiters.queue_mut(class).remove(ptr) }.is_some() {
+            semaphore.queued_waiters.fetch_sub(1, SeqCst);
+        }
+        *queued = false;
+
+        let acquired = *needed as usize - remaining;
+        if acquired > 0 {
+            semaphore.add_permits_locked(acquired, waiters);
+        }
+    }
+
+    /// Changes the number of permits this future is waiting for, without
+    /// losing its place in the wait queue.
+    ///
+    /// Permits already assigned to the waiter are kept and counted against
+    /// the new request; if the new request is already satisfied by them, the
+    /// excess is released back to the semaphore and the next poll completes.
+    /// The future should be polled after calling this method.
+    pub(crate) fn set_permits(self: Pin<&mut Self>, num_permits: u32) {
+        let (node, semaphore, needed, queued) = self.project();
+
+        if !*queued {
+            // Not yet in the wait queue; the next poll will request the new
+            // amount from scratch.
+            node.state.store(num_permits as usize, Release);
+            *needed = num_permits;
+            return;
+        }
+
+        let mut waiters = semaphore.waiters.lock();
+
+        // The number of permits already assigned to this waiter.
+        let remaining = node.state.load(Acquire);
+        let acquired = *needed as usize - remaining;
+        *needed = num_permits;
+
+        if num_permits as usize <= acquired {
+            // The request shrank to no more than what has already been
+            // assigned: the waiter is satisfied. Unlink it and release the
+            // excess; the next poll observes `state == 0` and completes.
+            node.state.store(0, Release);
+            let class = node.class;
+            let ptr = unsafe {
+                let node = Pin::into_inner_unchecked(node) as *mut _;
+                NonNull::new_unchecked(node)
+            };
+            // The node may have been unlinked already by a concurrent
+            // release that satisfied the previous request.
+            if unsafe { waiters.queue_mut(class).remove(ptr) }.is_some() {
+                semaphore.queued_waiters.fetch_sub(1, SeqCst);
+            }
+            let excess = acquired - num_permits as usize;
+            if excess > 0 {
+                semaphore.add_permits_locked(excess, waiters);
+            }
+            return;
+        }
+
+        node.state.store(num_permits as usize - acquired, Release);
+
+        if remaining == 0 {
+            // A concurrent release satisfied the previous request and
+            // unlinked the node. The enlarged request re-joins the queue as a
+            // new entry.
+            let class = node.class;
+            let ptr = unsafe {
+                let node = Pin::into_inner_unchecked(node) as *mut _;
+                NonNull::new_unchecked(node)
+            };
+            waiters.push(ptr, class);
+            semaphore.queued_waiters.fetch_add(1, SeqCst);
+        }
+
+        // Permits may be sitting in the counter (e.g. returned as leftovers
+        // when the previous, smaller request was at the head of the queue).
+        // Pull them into the queue so the enlarged request sees them.
+        let drained = semaphore.permits.fetch_and(Semaphore::CLOSED, SeqCst) >> Semaphore::PERMIT_SHIFT;
+        if drained > 0 {
+            semaphore.add_permits_locked(drained, waiters);
+        }
+    }
 }
 
 impl Drop for Acquire<'_> {
@@ -528,15 +1213,21 @@ impl Drop for Acquire<'_> {
             return;
         }
 
+        #[cfg(all(tokio_unstable, feature = "rt"))]
+        crate::sync::diagnostics::wait_cancelled(self.semaphore.diag_id());
+
         // This is where we ensure safety. The future is being dropped,
         // which means we must ensure that the waiter entry is no longer stored
         // in the linked list.
         let mut waiters = self.semaphore.waiters.lock();
 
         // remove the entry from the list
+        let class = self.node.class;
         let node = NonNull::from(&mut self.node);
         // Safety: we have locked the wait list.
-        unsafe { waiters.queue.remove(node) };
+        if unsafe { waiters.queue_mut(class).remove(node) }.is_some() {
+            self.semaphore.queued_waiters.fetch_sub(1, SeqCst);
+        }
 
         let acquired_permits = self.num_permits as usize - self.node.state.load(Acquire);
         if acquired_permits > 0 {
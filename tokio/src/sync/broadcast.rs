@@ -44,6 +44,10 @@
 //! either by aborting its task or by tolerating lost messages and resuming
 //! consumption of the channel.
 //!
+//! Alternatively, a channel created with [`channel_with_receiver_buffer`]
+//! gives each receiver its own bounded side buffer fed from the shared ring,
+//! so that a slow receiver lags independently of the others.
+//!
 //! ## Closing
 //!
 //! When **all** [`Sender`] handles have been dropped, no new values may be
@@ -112,8 +116,10 @@
 use crate::loom::cell::UnsafeCell;
 use crate::loom::sync::atomic::AtomicUsize;
 use crate::loom::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use crate::sync::watch;
 use crate::util::linked_list::{self, LinkedList};
 
+use std::collections::{HashMap, VecDeque};
 use std::fmt;
 use std::future::Future;
 use std::marker::PhantomPinned;
@@ -200,6 +206,42 @@ pub struct Receiver<T> {
 
     /// Next position to read from
     next: u64,
+
+    /// Identifies this receiver's side buffer when the channel was created
+    /// with [`channel_with_receiver_buffer`]. `None` for regular channels.
+    id: Option<u64>,
+
+    /// How this receiver responds to lagging too far behind.
+    lag_policy: LagPolicy,
+}
+
+/// How a broadcast [`Receiver`] responds to lagging too far behind.
+///
+/// When a receiver falls more than the channel capacity behind the sender,
+/// the oldest messages it has not yet seen are overwritten. The policy,
+/// selected per receiver with [`Receiver::set_lag_policy`], decides what the
+/// receiver observes at that point. Note that no policy slows the sender
+/// down: a broadcast [`send`] always completes immediately, so a consumer
+/// that must never miss a message needs a channel with enough capacity.
+///
+/// [`send`]: Sender::send
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LagPolicy {
+    /// Return an [`error::RecvError::Lagged`] reporting the number of skipped
+    /// messages, then continue from the oldest message still retained.
+    ///
+    /// This is the default.
+    Report,
+
+    /// Silently continue from the oldest message still retained, skipping
+    /// ahead without reporting an error.
+    Resume,
+
+    /// Silently skip to the most recent message, dropping everything still
+    /// buffered. Use this for consumers that only care about the latest
+    /// state, such as market-data snapshots.
+    Latest,
 }
 
 pub mod error {
@@ -278,6 +320,24 @@ pub mod error {
         Lagged(u64),
     }
 
+    /// An error yielded by a broadcast receiver [stream] when the receiver
+    /// lagged too far behind.
+    ///
+    /// The next item yielded by the stream is the oldest message still
+    /// retained by the channel. Includes the number of skipped messages.
+    ///
+    /// [stream]: crate::sync::broadcast::Receiver::into_stream
+    #[derive(Debug, PartialEq)]
+    pub struct Lagged(pub u64);
+
+    impl fmt::Display for Lagged {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "channel lagged by {}", self.0)
+        }
+    }
+
+    impl std::error::Error for Lagged {}
+
     impl fmt::Display for TryRecvError {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
             match self {
@@ -302,14 +362,22 @@ struct Shared<T> {
     mask: usize,
 
     /// Tail of the queue. Includes the rx wait list.
-    tail: Mutex<Tail>,
+    tail: Mutex<Tail<T>>,
 
     /// Number of outstanding Sender handles
     num_tx: AtomicUsize,
+
+    /// Publishes the receiver count, see [`Sender::receiver_count_watch`].
+    /// Updated while holding the tail lock so counts are published in order.
+    rx_cnt_watch: watch::Sender<usize>,
+
+    /// Keeps the watch channel writable even while no external watcher is
+    /// subscribed.
+    rx_cnt_watch_rx: watch::Receiver<usize>,
 }
 
 /// Next position to write a value
-struct Tail {
+struct Tail<T> {
     /// Next position to write to
     pos: u64,
 
@@ -321,6 +389,44 @@ struct Tail {
 
     /// Receivers waiting for a value
     waiters: LinkedList<Waiter, <Waiter as linked_list::Link>::Target>,
+
+    /// Per-receiver side buffers, when the channel was created with
+    /// [`channel_with_receiver_buffer`]. `None` for regular channels.
+    isolated: Option<Isolated<T>>,
+}
+
+/// Per-receiver buffering state for a channel created with
+/// [`channel_with_receiver_buffer`].
+struct Isolated<T> {
+    /// Capacity of each receiver's side buffer.
+    capacity: usize,
+
+    /// Side buffers, keyed by receiver id.
+    receivers: HashMap<u64, SideRx<T>>,
+
+    /// Id assigned to the next receiver.
+    next_id: u64,
+}
+
+/// Side buffer for a single receiver.
+///
+/// When the ring is about to overwrite a value that this receiver has not yet
+/// seen, the sender clones the value into the side buffer instead of forcing
+/// the receiver to lag. The receiver only lags once its own side buffer
+/// overflows.
+struct SideRx<T> {
+    /// Values evicted from the ring before this receiver saw them, paired
+    /// with their positions. Positions are consecutive when `missed` is zero.
+    queue: VecDeque<(u64, T)>,
+
+    /// Mirror of the receiver's `next` position, maintained under the tail
+    /// lock so that senders know which evicted values the receiver still
+    /// needs.
+    next: u64,
+
+    /// Number of values dropped because the side buffer was full. Reported as
+    /// `Lagged` on the receiver's next `recv`.
+    missed: u64,
 }
 
 /// Slot in the buffer
@@ -425,7 +531,65 @@ const MAX_RECEIVERS: usize = usize::MAX >> 2;
 ///     tx.send(20).unwrap();
 /// }
 /// ```
-pub fn channel<T: Clone>(mut capacity: usize) -> (Sender<T>, Receiver<T>) {
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    channel2(capacity, None)
+}
+
+/// Create a broadcast channel where each receiver additionally gets its own
+/// bounded side buffer holding up to `receiver_capacity` values.
+///
+/// On a regular broadcast channel, a value overwritten in the shared ring
+/// forces every receiver that has not yet seen it to return
+/// [`RecvError::Lagged`]: the lag cutoff is tied to a single global ring
+/// position. With per-receiver buffering, a value about to be overwritten is
+/// instead cloned into the side buffer of each receiver still needing it. A
+/// receiver only observes [`RecvError::Lagged`] once its **own** side buffer
+/// overflows, so one slow consumer lags independently without being affected
+/// by, or affecting, the others.
+///
+/// This trades memory and synchronization for isolation: up to
+/// `receiver_capacity` extra clones may be buffered **per receiver**, and
+/// every receive operation acquires the channel's tail lock.
+///
+/// # Panics
+///
+/// Panics if `capacity` or `receiver_capacity` is zero, or if `capacity` is
+/// larger than `usize::MAX / 2`.
+///
+/// [`RecvError::Lagged`]: crate::sync::broadcast::error::RecvError::Lagged
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::broadcast;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (tx, mut fast) = broadcast::channel_with_receiver_buffer(2, 4);
+///     let mut slow = tx.subscribe();
+///
+///     for i in 0..5 {
+///         tx.send(i).unwrap();
+///         // The fast receiver keeps up with the ring.
+///         assert_eq!(i, fast.recv().await.unwrap());
+///     }
+///
+///     // The slow receiver fell behind the ring, but the overwritten
+///     // values were kept in its side buffer.
+///     for i in 0..5 {
+///         assert_eq!(i, slow.recv().await.unwrap());
+///     }
+/// }
+/// ```
+pub fn channel_with_receiver_buffer<T: Clone>(
+    capacity: usize,
+    receiver_capacity: usize,
+) -> (Sender<T>, Receiver<T>) {
+    assert!(receiver_capacity > 0, "receiver capacity is empty");
+    channel2(capacity, Some(receiver_capacity))
+}
+
+fn channel2<T>(mut capacity: usize, receiver_capacity: Option<usize>) -> (Sender<T>, Receiver<T>) {
     assert!(capacity > 0, "capacity is empty");
     assert!(capacity <= usize::MAX >> 1, "requested capacity too large");
 
@@ -443,6 +607,28 @@ pub fn channel<T: Clone>(mut capacity: usize) -> (Sender<T>, Receiver<T>) {
         }));
     }
 
+    let mut isolated = receiver_capacity.map(|capacity| Isolated {
+        capacity,
+        receivers: HashMap::new(),
+        next_id: 0,
+    });
+
+    // Register the initial receiver's side buffer.
+    let id = isolated.as_mut().map(|isolated| {
+        isolated.receivers.insert(
+            0,
+            SideRx {
+                queue: VecDeque::new(),
+                next: 0,
+                missed: 0,
+            },
+        );
+        isolated.next_id = 1;
+        0
+    });
+
+    let (rx_cnt_watch, rx_cnt_watch_rx) = watch::channel(1);
+
     let shared = Arc::new(Shared {
         buffer: buffer.into_boxed_slice(),
         mask: capacity - 1,
@@ -451,13 +637,18 @@ pub fn channel<T: Clone>(mut capacity: usize) -> (Sender<T>, Receiver<T>) {
             rx_cnt: 1,
             closed: false,
             waiters: LinkedList::new(),
+            isolated,
         }),
         num_tx: AtomicUsize::new(1),
+        rx_cnt_watch,
+        rx_cnt_watch_rx,
     });
 
     let rx = Receiver {
         shared: shared.clone(),
         next: 0,
+        id,
+        lag_policy: LagPolicy::Report,
     };
 
     let tx = Sender { shared };
@@ -522,9 +713,60 @@ impl<T> Sender<T> {
     ///     tx.send(20).unwrap();
     /// }
     /// ```
-    pub fn send(&self, value: T) -> Result<usize, SendError<T>> {
-        self.send2(Some(value))
-            .map_err(|SendError(maybe_v)| SendError(maybe_v.unwrap()))
+    pub fn send(&self, value: T) -> Result<usize, SendError<T>>
+    where
+        T: Clone,
+    {
+        let mut tail = self.shared.tail.lock();
+
+        if tail.rx_cnt == 0 {
+            return Err(SendError(value));
+        }
+
+        // Position to write into
+        let pos = tail.pos;
+        let rem = tail.rx_cnt;
+        let idx = (pos & self.shared.mask as u64) as usize;
+
+        // Update the tail position
+        tail.pos = tail.pos.wrapping_add(1);
+
+        // Get the slot
+        let mut slot = self.shared.buffer[idx].write().unwrap();
+
+        // If receivers have side buffers and the value about to be
+        // overwritten has not been seen by all of them, move it into the
+        // side buffers of the receivers that still need it.
+        if let Some(isolated) = tail.isolated.as_mut() {
+            let old_pos = pos.wrapping_sub(self.shared.buffer.len() as u64);
+
+            if slot.pos == old_pos {
+                if let Some(old) = slot.val.with_mut(|ptr| unsafe { (*ptr).take() }) {
+                    isolated.feed(old_pos, old);
+                }
+            }
+        }
+
+        // Track the position
+        slot.pos = pos;
+
+        // Set remaining receivers
+        slot.rem.with_mut(|v| *v = rem);
+
+        // Write the value
+        slot.val.with_mut(|ptr| unsafe { *ptr = Some(value) });
+
+        // Release the slot lock before notifying the receivers.
+        drop(slot);
+
+        tail.notify_rx();
+
+        // Release the mutex. This must happen after the slot lock is released,
+        // otherwise the writer lock bit could be cleared while another thread
+        // is in the critical section.
+        drop(tail);
+
+        Ok(rem)
     }
 
     /// Creates a new [`Receiver`] handle that will receive values sent **after**
@@ -596,11 +838,97 @@ impl<T> Sender<T> {
         tail.rx_cnt
     }
 
-    fn send2(&self, value: Option<T>) -> Result<usize, SendError<Option<T>>> {
+    /// Returns a [`watch::Receiver`] tracking the number of active receivers.
+    ///
+    /// The watched value is updated whenever a receiver subscribes or is
+    /// dropped, so event producers can react to subscriber changes without
+    /// polling [`receiver_count`]. The returned handle does not count as a
+    /// broadcast receiver.
+    ///
+    /// [`watch::Receiver`]: crate::sync::watch::Receiver
+    /// [`receiver_count`]: Sender::receiver_count
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::broadcast;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, rx1) = broadcast::channel::<i32>(16);
+    ///     let mut count = tx.receiver_count_watch();
+    ///
+    ///     assert_eq!(*count.borrow(), 1);
+    ///
+    ///     let rx2 = tx.subscribe();
+    ///     count.changed().await.unwrap();
+    ///     assert_eq!(*count.borrow(), 2);
+    ///
+    ///     drop(rx1);
+    ///     drop(rx2);
+    ///     count.changed().await.unwrap();
+    ///     assert_eq!(*count.borrow(), 0);
+    /// }
+    /// ```
+    pub fn receiver_count_watch(&self) -> watch::Receiver<usize> {
+        self.shared.rx_cnt_watch_rx.clone()
+    }
+
+    /// Waits until at least one receiver is subscribed to the channel.
+    ///
+    /// Completes immediately if a receiver already exists. This lets a
+    /// producer pause expensive event generation while nobody is listening
+    /// and resume as soon as a subscriber shows up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::broadcast;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, rx) = broadcast::channel(16);
+    ///     drop(rx);
+    ///
+    ///     let tx2 = tx.clone();
+    ///     let producer = tokio::spawn(async move {
+    ///         tx2.subscribed().await;
+    ///         tx2.send("hello").unwrap();
+    ///     });
+    ///
+    ///     let mut rx = tx.subscribe();
+    ///     assert_eq!(rx.recv().await.unwrap(), "hello");
+    ///
+    ///     producer.await.unwrap();
+    /// }
+    /// ```
+    pub async fn subscribed(&self) {
+        let mut rx = self.shared.rx_cnt_watch_rx.clone();
+
+        loop {
+            if *rx.borrow() > 0 {
+                return;
+            }
+
+            // The sender half is stored in `Shared`, which we keep alive, so
+            // `changed` cannot fail here.
+            if rx.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Marks the channel closed by writing a closed marker at the tail
+    /// position.
+    ///
+    /// Unlike `send`, this does not require `T: Clone`, so a value
+    /// overwritten by the marker cannot be recovered: receivers with side
+    /// buffers count it as missed instead.
+    fn close_channel(&self) {
         let mut tail = self.shared.tail.lock();
 
         if tail.rx_cnt == 0 {
-            return Err(SendError(value));
+            return;
         }
 
         // Position to write into
@@ -610,23 +938,28 @@ impl<T> Sender<T> {
 
         // Update the tail position
         tail.pos = tail.pos.wrapping_add(1);
+        tail.closed = true;
 
         // Get the slot
         let mut slot = self.shared.buffer[idx].write().unwrap();
 
+        if let Some(isolated) = tail.isolated.as_mut() {
+            let old_pos = pos.wrapping_sub(self.shared.buffer.len() as u64);
+            let has_val = slot.val.with(|ptr| unsafe { (*ptr).is_some() });
+
+            if slot.pos == old_pos && has_val {
+                isolated.abandon(old_pos);
+            }
+        }
+
         // Track the position
         slot.pos = pos;
 
         // Set remaining receivers
         slot.rem.with_mut(|v| *v = rem);
 
-        // Set the closed bit if the value is `None`; otherwise write the value
-        if value.is_none() {
-            tail.closed = true;
-            slot.closed = true;
-        } else {
-            slot.val.with_mut(|ptr| unsafe { *ptr = value });
-        }
+        // Set the closed bit
+        slot.closed = true;
 
         // Release the slot lock before notifying the receivers.
         drop(slot);
@@ -637,8 +970,6 @@ impl<T> Sender<T> {
         // otherwise the writer lock bit could be cleared while another thread
         // is in the critical section.
         drop(tail);
-
-        Ok(rem)
     }
 }
 
@@ -651,14 +982,40 @@ fn new_receiver<T>(shared: Arc<Shared<T>>) -> Receiver<T> {
 
     tail.rx_cnt = tail.rx_cnt.checked_add(1).expect("overflow");
 
+    // Publish the new count while holding the tail lock so observers see
+    // counts in the order they changed. The stored receiver keeps the watch
+    // channel open, so this cannot fail.
+    let _ = shared.rx_cnt_watch.send(tail.rx_cnt);
+
     let next = tail.pos;
 
+    let id = tail.isolated.as_mut().map(|isolated| {
+        let id = isolated.next_id;
+        isolated.next_id += 1;
+
+        isolated.receivers.insert(
+            id,
+            SideRx {
+                queue: VecDeque::new(),
+                next,
+                missed: 0,
+            },
+        );
+
+        id
+    });
+
     drop(tail);
 
-    Receiver { shared, next }
+    Receiver {
+        shared,
+        next,
+        id,
+        lag_policy: LagPolicy::Report,
+    }
 }
 
-impl Tail {
+impl<T> Tail<T> {
     fn notify_rx(&mut self) {
         while let Some(mut waiter) = self.waiters.pop_back() {
             // Safety: `waiters` lock is still held.
@@ -671,6 +1028,51 @@ impl Tail {
             waker.wake();
         }
     }
+
+    /// Updates the mirrored cursor of the receiver identified by `id`.
+    fn update_side_next(&mut self, id: u64, next: u64) {
+        let isolated = self.isolated.as_mut().unwrap();
+        isolated.receivers.get_mut(&id).unwrap().next = next;
+    }
+}
+
+impl<T> Isolated<T> {
+    /// Records the value evicted from the ring at `pos` as missed by every
+    /// receiver that has not yet seen it.
+    fn abandon(&mut self, pos: u64) {
+        for state in self.receivers.values_mut() {
+            if state.next <= pos {
+                state.missed += 1;
+            }
+        }
+    }
+}
+
+impl<T: Clone> Isolated<T> {
+    /// Distributes the value evicted from the ring at `pos` to the side
+    /// buffer of every receiver that has not yet seen it.
+    fn feed(&mut self, pos: u64, value: T) {
+        let capacity = self.capacity;
+
+        for state in self.receivers.values_mut() {
+            if state.next <= pos {
+                state.push(pos, value.clone(), capacity);
+            }
+        }
+    }
+}
+
+impl<T> SideRx<T> {
+    /// Appends an evicted value, dropping the oldest buffered value if the
+    /// side buffer is full.
+    fn push(&mut self, pos: u64, value: T, capacity: usize) {
+        if self.queue.len() == capacity {
+            self.queue.pop_front();
+            self.missed += 1;
+        }
+
+        self.queue.push_back((pos, value));
+    }
 }
 
 impl<T> Clone for Sender<T> {
@@ -685,7 +1087,7 @@ impl<T> Clone for Sender<T> {
 impl<T> Drop for Sender<T> {
     fn drop(&mut self) {
         if 1 == self.shared.num_tx.fetch_sub(1, SeqCst) {
-            let _ = self.send2(None);
+            self.close_channel();
         }
     }
 }
@@ -809,6 +1211,128 @@ impl<T> Receiver<T> {
 }
 
 impl<T: Clone> Receiver<T> {
+    /// Receives the next value on a channel created with
+    /// [`channel_with_receiver_buffer`].
+    ///
+    /// Values evicted from the ring into this receiver's side buffer are
+    /// returned before values still in the ring. The cursor is mirrored into
+    /// the shared state on every call so that senders know which evicted
+    /// values this receiver still needs; this is why the tail lock is taken
+    /// even on the hot path.
+    fn recv_isolated(
+        &mut self,
+        waiter: Option<(&UnsafeCell<Waiter>, &Waker)>,
+    ) -> Result<T, TryRecvError> {
+        let id = self.id.unwrap();
+        let mut next = self.next;
+
+        let res;
+
+        {
+            let mut tail = self.shared.tail.lock();
+
+            let capacity = self.shared.buffer.len() as u64;
+
+            // The oldest value still stored in the ring. When the channel is
+            // closed, the tail position accounts for a closed marker that is
+            // not stored in the ring itself, see `recv_ref` for details.
+            let adjust = if tail.closed { 1 } else { 0 };
+            let oldest = tail.pos.wrapping_sub(capacity + adjust);
+
+            let isolated = tail.isolated.as_mut().unwrap();
+            let state = isolated.receivers.get_mut(&id).unwrap();
+
+            if state.missed > 0 {
+                // Values were dropped from a full side buffer. Report the
+                // lag and resume at the oldest value still available.
+                let missed = state.missed;
+                state.missed = 0;
+
+                next = match state.queue.front() {
+                    Some(&(pos, _)) => pos,
+                    None => oldest,
+                };
+                state.next = next;
+
+                res = Err(TryRecvError::Lagged(missed));
+            } else if let Some((pos, value)) = state.queue.pop_front() {
+                // Values in the side buffer precede anything in the ring.
+                debug_assert_eq!(pos, next);
+
+                next = next.wrapping_add(1);
+                state.next = next;
+
+                res = Ok(value);
+            } else {
+                // Read from the ring. Acquiring the slot lock while holding
+                // the tail lock matches the order used by `send`, so this
+                // cannot deadlock.
+                let idx = (next & self.shared.mask as u64) as usize;
+                let slot = self.shared.buffer[idx].read().unwrap();
+
+                if slot.pos == next {
+                    next = next.wrapping_add(1);
+                    tail.update_side_next(id, next);
+
+                    if slot.closed {
+                        res = Err(TryRecvError::Closed);
+                    } else {
+                        let guard = RecvGuard { slot };
+                        res = guard.clone_value().ok_or(TryRecvError::Closed);
+                    }
+                } else {
+                    let next_pos = slot.pos.wrapping_add(capacity);
+
+                    if next_pos == next {
+                        // Caught up with the ring; store the waker.
+                        if let Some((waiter, waker)) = waiter {
+                            // Safety: called while locked.
+                            unsafe {
+                                // Only queue if not already queued
+                                waiter.with_mut(|ptr| {
+                                    // If there is no waker **or** if the currently
+                                    // stored waker references a **different** task,
+                                    // track the tasks' waker to be notified on
+                                    // receipt of a new value.
+                                    match (*ptr).waker {
+                                        Some(ref w) if w.will_wake(waker) => {}
+                                        _ => {
+                                            (*ptr).waker = Some(waker.clone());
+                                        }
+                                    }
+
+                                    if !(*ptr).queued {
+                                        (*ptr).queued = true;
+                                        tail.waiters
+                                            .push_front(NonNull::new_unchecked(&mut *ptr));
+                                    }
+                                });
+                            }
+                        }
+
+                        res = Err(TryRecvError::Empty);
+                    } else {
+                        // Every value evicted from the ring is routed through
+                        // the side buffer under the tail lock, so a receiver
+                        // with an empty side buffer and no missed values is
+                        // never behind the ring. Fall back to the regular lag
+                        // behavior regardless.
+                        debug_assert!(false, "isolated receiver behind the ring");
+
+                        let missed = oldest.wrapping_sub(next);
+                        next = oldest;
+                        tail.update_side_next(id, next);
+
+                        res = Err(TryRecvError::Lagged(missed));
+                    }
+                }
+            }
+        }
+
+        self.next = next;
+        res
+    }
+
     /// Receives the next value for this receiver.
     ///
     /// Each [`Receiver`] handle will receive a clone of all values sent
@@ -920,8 +1444,100 @@ impl<T: Clone> Receiver<T> {
     /// }
     /// ```
     pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
-        let guard = self.recv_ref(None)?;
-        guard.clone_value().ok_or(TryRecvError::Closed)
+        loop {
+            let res = if self.id.is_some() {
+                self.recv_isolated(None)
+            } else {
+                match self.recv_ref(None) {
+                    Ok(guard) => guard.clone_value().ok_or(TryRecvError::Closed),
+                    Err(err) => Err(err),
+                }
+            };
+
+            match res {
+                Err(TryRecvError::Lagged(_)) if self.apply_lag_policy(self.lag_policy) => continue,
+                res => return res,
+            }
+        }
+    }
+
+    /// Sets how this receiver responds to lagging too far behind.
+    ///
+    /// The policy applies to this receiver only; other subscribers to the
+    /// same channel keep their own. The default is [`LagPolicy::Report`],
+    /// which preserves the behavior of surfacing lag as an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::broadcast;
+    /// use tokio::sync::broadcast::LagPolicy;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = broadcast::channel(2);
+    ///     rx.set_lag_policy(LagPolicy::Latest);
+    ///
+    ///     for i in 0..5 {
+    ///         tx.send(i).unwrap();
+    ///     }
+    ///
+    ///     // Everything before the most recent message was dropped
+    ///     // silently.
+    ///     assert_eq!(rx.recv().await.unwrap(), 4);
+    /// }
+    /// ```
+    pub fn set_lag_policy(&mut self, policy: LagPolicy) {
+        self.lag_policy = policy;
+    }
+
+    /// Returns this receiver's current [`LagPolicy`].
+    pub fn lag_policy(&self) -> LagPolicy {
+        self.lag_policy
+    }
+
+    /// Applies `policy` after a receive operation reported lagging.
+    ///
+    /// Returns `true` when the receive should be retried instead of
+    /// surfacing the lag to the caller.
+    fn apply_lag_policy(&mut self, policy: LagPolicy) -> bool {
+        match policy {
+            LagPolicy::Report => false,
+            // The cursor was already moved to the oldest retained message.
+            LagPolicy::Resume => true,
+            LagPolicy::Latest => {
+                let mut tail = self.shared.tail.lock();
+
+                // When the channel is closed, the tail position accounts for
+                // a closed marker that must not be skipped over, see
+                // `recv_ref` for details.
+                let pos = if tail.closed {
+                    tail.pos.wrapping_sub(1)
+                } else {
+                    tail.pos
+                };
+
+                // Leave the most recent value to be received; lagging
+                // guarantees one exists.
+                let latest = pos.wrapping_sub(1);
+
+                if let Some(id) = self.id {
+                    let state = tail
+                        .isolated
+                        .as_mut()
+                        .unwrap()
+                        .receivers
+                        .get_mut(&id)
+                        .unwrap();
+                    state.queue.clear();
+                    state.missed = 0;
+                    state.next = latest;
+                }
+
+                self.next = latest;
+                true
+            }
+        }
     }
 }
 
@@ -930,8 +1546,15 @@ impl<T> Drop for Receiver<T> {
         let mut tail = self.shared.tail.lock();
 
         tail.rx_cnt -= 1;
+        let _ = self.shared.rx_cnt_watch.send(tail.rx_cnt);
         let until = tail.pos;
 
+        // Remove this receiver's side buffer so that senders stop feeding
+        // it. Values remaining in the ring are drained below.
+        if let Some(id) = self.id {
+            tail.isolated.as_mut().unwrap().receivers.remove(&id);
+        }
+
         drop(tail);
 
         while self.next < until {
@@ -983,14 +1606,28 @@ where
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, RecvError>> {
         let (receiver, waiter) = self.project();
 
-        let guard = match receiver.recv_ref(Some((waiter, cx.waker()))) {
-            Ok(value) => value,
-            Err(TryRecvError::Empty) => return Poll::Pending,
-            Err(TryRecvError::Lagged(n)) => return Poll::Ready(Err(RecvError::Lagged(n))),
-            Err(TryRecvError::Closed) => return Poll::Ready(Err(RecvError::Closed)),
-        };
-
-        Poll::Ready(guard.clone_value().ok_or(RecvError::Closed))
+        loop {
+            let res = if receiver.id.is_some() {
+                receiver.recv_isolated(Some((waiter, cx.waker())))
+            } else {
+                match receiver.recv_ref(Some((waiter, cx.waker()))) {
+                    Ok(guard) => guard.clone_value().ok_or(TryRecvError::Closed),
+                    Err(err) => Err(err),
+                }
+            };
+
+            return match res {
+                Ok(value) => Poll::Ready(Ok(value)),
+                Err(TryRecvError::Empty) => Poll::Pending,
+                Err(TryRecvError::Lagged(n)) => {
+                    if receiver.apply_lag_policy(receiver.lag_policy) {
+                        continue;
+                    }
+                    Poll::Ready(Err(RecvError::Lagged(n)))
+                }
+                Err(TryRecvError::Closed) => Poll::Ready(Err(RecvError::Closed)),
+            };
+        }
     }
 }
 
@@ -1069,3 +1706,160 @@ impl<'a, T> Drop for RecvGuard<'a, T> {
 }
 
 fn is_unpin<T: Unpin>() {}
+
+cfg_stream! {
+    /// A [`Stream`] wrapping a broadcast [`Receiver`].
+    ///
+    /// Created by [`Receiver::into_stream`]. The stream ends once the channel
+    /// is closed and all retained messages have been yielded; lagging is
+    /// handled according to the configured [`LagPolicy`].
+    ///
+    /// [`Stream`]: futures_core::Stream
+    pub struct IntoStream<T> {
+        /// Receiver being streamed from.
+        receiver: Receiver<T>,
+
+        /// Entry in the waiter `LinkedList`, owned here instead of by a
+        /// short-lived `Recv` future so waker registration survives between
+        /// polls.
+        waiter: UnsafeCell<Waiter>,
+
+        /// How to respond to lagging.
+        policy: LagPolicy,
+    }
+
+    unsafe impl<T: Send> Send for IntoStream<T> {}
+    unsafe impl<T: Send> Sync for IntoStream<T> {}
+
+    impl<T> Receiver<T> {
+        /// Converts the receiver into a [`Stream`] of values.
+        ///
+        /// The stream yields `Ok` items for received values and, with the
+        /// default [`LagPolicy::Report`], an `Err(`[`error::Lagged`]`)` item
+        /// when the receiver lagged too far behind; configure this with
+        /// [`IntoStream::lag_policy`]. The stream ends when the channel is
+        /// closed and all retained messages have been yielded.
+        ///
+        /// [`Stream`]: futures_core::Stream
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use futures::StreamExt;
+        /// use tokio::sync::broadcast;
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let (tx, rx) = broadcast::channel(16);
+        ///
+        ///     tx.send(10).unwrap();
+        ///     tx.send(20).unwrap();
+        ///     drop(tx);
+        ///
+        ///     let stream = rx.into_stream();
+        ///     tokio::pin!(stream);
+        ///
+        ///     assert_eq!(stream.next().await, Some(Ok(10)));
+        ///     assert_eq!(stream.next().await, Some(Ok(20)));
+        ///     assert_eq!(stream.next().await, None);
+        /// }
+        /// ```
+        pub fn into_stream(self) -> IntoStream<T> {
+            IntoStream {
+                receiver: self,
+                waiter: UnsafeCell::new(Waiter {
+                    queued: false,
+                    waker: None,
+                    pointers: linked_list::Pointers::new(),
+                    _p: PhantomPinned,
+                }),
+                policy: LagPolicy::Report,
+            }
+        }
+    }
+
+    impl<T> IntoStream<T> {
+        /// Sets how the stream responds to the receiver lagging too far
+        /// behind. The default is [`LagPolicy::Report`].
+        pub fn lag_policy(mut self, policy: LagPolicy) -> IntoStream<T> {
+            self.policy = policy;
+            self
+        }
+
+        /// A custom `project` implementation is used in place of
+        /// `pin-project-lite` as a custom drop implementation is needed.
+        fn project(self: Pin<&mut Self>) -> (&mut Receiver<T>, &UnsafeCell<Waiter>, LagPolicy) {
+            unsafe {
+                // Safety: Receiver is Unpin
+                is_unpin::<&mut Receiver<T>>();
+
+                let me = self.get_unchecked_mut();
+                (&mut me.receiver, &me.waiter, me.policy)
+            }
+        }
+    }
+
+    impl<T> futures_core::Stream for IntoStream<T>
+    where
+        T: Clone,
+    {
+        type Item = Result<T, error::Lagged>;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let (receiver, waiter, policy) = self.project();
+
+            loop {
+                let res = if receiver.id.is_some() {
+                    receiver.recv_isolated(Some((waiter, cx.waker())))
+                } else {
+                    match receiver.recv_ref(Some((waiter, cx.waker()))) {
+                        Ok(guard) => guard.clone_value().ok_or(TryRecvError::Closed),
+                        Err(err) => Err(err),
+                    }
+                };
+
+                return match res {
+                    Ok(value) => Poll::Ready(Some(Ok(value))),
+                    Err(TryRecvError::Empty) => Poll::Pending,
+                    Err(TryRecvError::Closed) => Poll::Ready(None),
+                    Err(TryRecvError::Lagged(n)) => match policy {
+                        LagPolicy::Report => Poll::Ready(Some(Err(error::Lagged(n)))),
+                        LagPolicy::Resume | LagPolicy::Latest => {
+                            receiver.apply_lag_policy(policy);
+                            continue;
+                        }
+                    },
+                };
+            }
+        }
+    }
+
+    impl<T> Drop for IntoStream<T> {
+        fn drop(&mut self) {
+            // Acquire the tail lock. This is required for safety before
+            // accessing the waiter node.
+            let mut tail = self.receiver.shared.tail.lock();
+
+            // safety: tail lock is held
+            let queued = self.waiter.with(|ptr| unsafe { (*ptr).queued });
+
+            if queued {
+                // Remove the node
+                //
+                // safety: tail lock is held and the wait node is verified to
+                // be in the list.
+                unsafe {
+                    self.waiter.with_mut(|ptr| {
+                        tail.waiters.remove((&mut *ptr).into());
+                    });
+                }
+            }
+        }
+    }
+
+    impl<T> fmt::Debug for IntoStream<T> {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt.debug_struct("broadcast::IntoStream").finish()
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::loom::sync::atomic::AtomicBool;
+use crate::loom::sync::Mutex;
+use crate::sync::Notify;
+
+use std::future::Future;
+use std::sync::atomic::Ordering::SeqCst;
+// The loom facade has no `Weak`, so the token state is reference-counted
+// with the `std` types; loom still models the mutex and the notifications.
+use std::sync::{Arc, Weak};
+
+/// A token that can be used to signal cancellation to any number of tasks.
+///
+/// Cloning a token produces another handle to the same cancellation state:
+/// cancelling any clone cancels them all. [`child_token`] instead creates a
+/// token that is cancelled when its parent is cancelled, but can also be
+/// cancelled on its own without affecting the parent — use it to build a
+/// shutdown hierarchy where cancelling a service cancels its workers, while
+/// a worker can still be stopped individually.
+///
+/// Cancellation is level-triggered and permanent: once cancelled, a token
+/// stays cancelled, and [`cancelled`] resolves immediately no matter when it
+/// is called.
+///
+/// [`child_token`]: CancellationToken::child_token
+/// [`cancelled`]: CancellationToken::cancelled
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::CancellationToken;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let token = CancellationToken::new();
+///     let worker_token = token.child_token();
+///
+///     let join_handle = tokio::spawn(async move {
+///         worker_token.cancelled().await;
+///         "cancelled"
+///     });
+///
+///     token.cancel();
+///
+///     assert_eq!(join_handle.await.unwrap(), "cancelled");
+/// }
+/// ```
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// Set once, before the waiters are notified.
+    cancelled: AtomicBool,
+
+    /// Wakes the `cancelled()` futures when the token is cancelled.
+    notify: Notify,
+
+    /// Tokens created by `child_token` that must be cancelled along with
+    /// this one. Weak so that dropped children do not linger; the list is
+    /// pruned whenever a new child is registered.
+    children: Mutex<Vec<Weak<Inner>>>,
+}
+
+impl Inner {
+    fn new() -> Arc<Inner> {
+        Arc::new(Inner {
+            cancelled: AtomicBool::new(false),
+            notify: Notify::new(),
+            children: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn cancel(this: &Arc<Inner>) {
+        if this.cancelled.swap(true, SeqCst) {
+            return;
+        }
+
+        this.notify.notify_waiters();
+
+        // A cancelled token no longer needs to track its children; taking
+        // the list also keeps the lock release ahead of the recursion.
+        let children = std::mem::take(&mut *this.children.lock());
+        for child in children {
+            if let Some(child) = child.upgrade() {
+                Inner::cancel(&child);
+            }
+        }
+    }
+}
+
+impl CancellationToken {
+    /// Creates a new token that is not cancelled and has no parent.
+    pub fn new() -> CancellationToken {
+        CancellationToken { inner: Inner::new() }
+    }
+
+    /// Creates a token that is cancelled when this one is cancelled, but can
+    /// also be cancelled independently without affecting this one.
+    ///
+    /// If this token is already cancelled, the child starts out cancelled.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = Inner::new();
+
+        {
+            let mut children = self.inner.children.lock();
+            // Prune children that no longer exist while we are here.
+            children.retain(|c| c.strong_count() > 0);
+            children.push(Arc::downgrade(&child));
+        }
+
+        // The parent may have been cancelled between the flag being set and
+        // the child list being drained; registration above could then have
+        // been missed, so check the flag after registering.
+        if self.is_cancelled() {
+            Inner::cancel(&child);
+        }
+
+        CancellationToken { inner: child }
+    }
+
+    /// Cancels this token and all tokens created from it with
+    /// [`child_token`], waking every pending [`cancelled`] future.
+    ///
+    /// [`child_token`]: CancellationToken::child_token
+    /// [`cancelled`]: CancellationToken::cancelled
+    pub fn cancel(&self) {
+        Inner::cancel(&self.inner);
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(SeqCst)
+    }
+
+    /// Waits until the token is cancelled.
+    ///
+    /// Returns immediately if the token is already cancelled.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe: dropping the returned future does not
+    /// consume any signal, and a later call still completes when the token
+    /// is cancelled.
+    pub async fn cancelled(&self) {
+        loop {
+            // Registering interest before re-checking the flag ensures a
+            // cancellation between the check and the await is not missed.
+            let notified = self.inner.notify.notified();
+
+            if self.is_cancelled() {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+
+    /// Runs a future to completion, or until this token is cancelled.
+    ///
+    /// Returns `Some` with the future's output if it completes first, and
+    /// `None` if the token is cancelled first; the future is dropped in that
+    /// case. When both are ready at the same time, completing the future
+    /// wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::CancellationToken;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let token = CancellationToken::new();
+    ///     token.cancel();
+    ///
+    ///     let result = token
+    ///         .run_until_cancelled(std::future::pending::<()>())
+    ///         .await;
+    ///     assert_eq!(result, None);
+    /// }
+    /// ```
+    pub async fn run_until_cancelled<F>(&self, fut: F) -> Option<F::Output>
+    where
+        F: Future,
+    {
+        use std::task::Poll;
+
+        let cancelled = self.cancelled();
+        crate::pin!(fut, cancelled);
+
+        crate::future::poll_fn(|cx| {
+            if let Poll::Ready(out) = fut.as_mut().poll(cx) {
+                return Poll::Ready(Some(out));
+            }
+
+            if cancelled.as_mut().poll(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+impl Clone for CancellationToken {
+    /// Returns another handle to the same cancellation state. Cancelling any
+    /// clone cancels them all.
+    fn clone(&self) -> CancellationToken {
+        CancellationToken {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> CancellationToken {
+        CancellationToken::new()
+    }
+}
+
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
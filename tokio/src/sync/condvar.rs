@@ -0,0 +1,206 @@
+use crate::loom::sync::atomic::AtomicUsize;
+use crate::sync::batch_semaphore as semaphore;
+use crate::sync::mutex::MutexGuard;
+
+use std::fmt;
+use std::mem;
+use std::sync::atomic::Ordering::SeqCst;
+
+/// An asynchronous condition variable.
+///
+/// This type acts similarly to [`std::sync::Condvar`]: it blocks tasks
+/// waiting for some condition on shared state to become true, releasing a
+/// [`Mutex`] guard while waiting and reacquiring the lock before returning.
+/// Waiting is an async operation, so it does not block the thread.
+///
+/// As with the standard library condition variable, waits are subject to
+/// spurious wakeups: a call to [`wait`] may return even though nothing was
+/// notified. Always re-check the condition after waking, or use
+/// [`wait_while`], which does so internally.
+///
+/// A `Condvar` is not tied to a single `Mutex`; each call names the guard it
+/// releases. Using one condition variable with several mutexes at once is
+/// possible but usually a design smell, just as it is with the standard
+/// library.
+///
+/// [`std::sync::Condvar`]: std::sync::Condvar
+/// [`Mutex`]: crate::sync::Mutex
+/// [`wait`]: Condvar::wait
+/// [`wait_while`]: Condvar::wait_while
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::{Condvar, Mutex};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let pair = Arc::new((Mutex::new(false), Condvar::new()));
+///     let pair2 = pair.clone();
+///
+///     tokio::spawn(async move {
+///         let (lock, cvar) = &*pair2;
+///         let mut started = lock.lock().await;
+///         *started = true;
+///         // The guard may be held while notifying.
+///         cvar.notify_one();
+///     });
+///
+///     let (lock, cvar) = &*pair;
+///     let guard = lock.lock().await;
+///     let guard = cvar.wait_while(guard, |started| !*started).await;
+///     assert!(*guard);
+/// }
+/// ```
+pub struct Condvar {
+    /// The number of waiters a notification may still be owed to. Incremented
+    /// before the mutex guard is released, so a notification sent while the
+    /// waiter is still on its way into the semaphore queue is not lost: the
+    /// released permit is counted and picked up by the waiter's first poll.
+    waiters: AtomicUsize,
+
+    /// Waiters queue up on this semaphore, which starts (and in the absence
+    /// of pending notifications stays) at zero permits.
+    s: semaphore::Semaphore,
+}
+
+/// Restores the waiter count when a `wait` is cancelled before it was
+/// notified; forgotten on the normal path, where the notifier has already
+/// taken the waiter's count over.
+struct WaitGuard<'a>(&'a AtomicUsize);
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        let mut curr = self.0.load(SeqCst);
+        loop {
+            if curr == 0 {
+                // A concurrent notification already consumed this waiter's
+                // count; the permit it released becomes a spurious wakeup.
+                return;
+            }
+            match self.0.compare_exchange(curr, curr - 1, SeqCst, SeqCst) {
+                Ok(_) => return,
+                Err(actual) => curr = actual,
+            }
+        }
+    }
+}
+
+impl Condvar {
+    /// Creates a new condition variable ready for use.
+    pub fn new() -> Condvar {
+        Condvar {
+            waiters: AtomicUsize::new(0),
+            s: semaphore::Semaphore::new(0),
+        }
+    }
+
+    /// Releases the guard's mutex and waits for a notification, reacquiring
+    /// the lock before returning.
+    ///
+    /// The guard is released atomically with respect to notifications: a
+    /// [`notify_one`] call made after `wait` has been entered (typically by a
+    /// task that took the lock the waiter released) is guaranteed to be seen.
+    ///
+    /// Like its [`std` counterpart], this is subject to spurious wakeups;
+    /// re-check the condition after waking or use [`wait_while`].
+    ///
+    /// # Cancel safety
+    ///
+    /// If the returned future is dropped before completing, the mutex is not
+    /// held and the task counts as no longer waiting. A notification sent
+    /// concurrently with the cancellation may be turned into a spurious
+    /// wakeup of another waiter.
+    ///
+    /// [`notify_one`]: Condvar::notify_one
+    /// [`wait_while`]: Condvar::wait_while
+    /// [`std` counterpart]: std::sync::Condvar::wait
+    pub async fn wait<'a, T: ?Sized>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+        let mutex = guard.lock;
+
+        self.waiters.fetch_add(1, SeqCst);
+        let undo = WaitGuard(&self.waiters);
+        drop(guard);
+
+        self.s.acquire(1).await.unwrap_or_else(|_| {
+            // The semaphore was closed. but, we never explicitly close it, and
+            // we own it exclusively, which means that this can never happen.
+            unreachable!()
+        });
+
+        // The notifier decremented the waiter count on this task's behalf.
+        mem::forget(undo);
+
+        mutex.lock().await
+    }
+
+    /// Releases the guard's mutex and waits until `condition` returns
+    /// `false`, reacquiring the lock before every check and before
+    /// returning.
+    ///
+    /// This is the spurious-wakeup-proof way to wait: the condition is
+    /// checked with the lock held, and waiting resumes whenever it still
+    /// holds.
+    ///
+    /// # Cancel safety
+    ///
+    /// The same considerations apply as for [`wait`].
+    ///
+    /// [`wait`]: Condvar::wait
+    pub async fn wait_while<'a, T, F>(
+        &self,
+        mut guard: MutexGuard<'a, T>,
+        mut condition: F,
+    ) -> MutexGuard<'a, T>
+    where
+        T: ?Sized,
+        F: FnMut(&mut T) -> bool,
+    {
+        while condition(&mut *guard) {
+            guard = self.wait(guard).await;
+        }
+        guard
+    }
+
+    /// Wakes up one task waiting on this condition variable.
+    ///
+    /// If no task is waiting, the call is a no-op; notifications are not
+    /// stored up for tasks that start waiting later.
+    pub fn notify_one(&self) {
+        let mut curr = self.waiters.load(SeqCst);
+        loop {
+            if curr == 0 {
+                return;
+            }
+            match self.waiters.compare_exchange(curr, curr - 1, SeqCst, SeqCst) {
+                Ok(_) => break,
+                Err(actual) => curr = actual,
+            }
+        }
+
+        self.s.release(1);
+    }
+
+    /// Wakes up every task currently waiting on this condition variable.
+    ///
+    /// If no task is waiting, the call is a no-op.
+    pub fn notify_all(&self) {
+        let waiters = self.waiters.swap(0, SeqCst);
+        if waiters > 0 {
+            self.s.release(waiters);
+        }
+    }
+}
+
+impl Default for Condvar {
+    fn default() -> Condvar {
+        Condvar::new()
+    }
+}
+
+impl fmt::Debug for Condvar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Condvar").finish()
+    }
+}
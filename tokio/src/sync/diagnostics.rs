@@ -0,0 +1,300 @@
+//! Lock-dependency tracking for the synchronization primitives.
+//!
+//! When tracking is enabled, every acquisition that goes through the internal
+//! semaphore — [`Mutex`], [`RwLock`], [`Semaphore`], and the channel capacity
+//! limits built on top of it — records the identifier of the task holding or
+//! waiting for the resource. [`detect_cycles`] inspects the resulting
+//! wait-for graph and reports the groups of tasks that are blocked on each
+//! other, turning "the service is stuck" into a list of task identifiers to
+//! look at.
+//!
+//! Task identifiers are the same opaque `u64` values reported by
+//! [`JoinHandle::task_id`] and [`alloc_track::current_task`]. Resource
+//! identifiers are opaque values that are unique among live resources; they
+//! exist only to distinguish resources within a reported cycle.
+//!
+//! Tracking is disabled by default because it takes a global lock on every
+//! acquisition and release. It is enabled with [`enable_tracking`], typically
+//! from a debug endpoint once a process is already suspected of being stuck,
+//! and the wait-for graph is populated as tasks continue to block.
+//!
+//! # Accuracy
+//!
+//! Tracking is best effort. Acquisitions and releases performed outside a
+//! task poll (for example from a thread that is not running a task) cannot
+//! be attributed to a task and are not recorded. A guard moved to and
+//! dropped by a different task has its release attributed to the releasing
+//! task when that task is a recorded holder, and to the oldest recorded
+//! holder otherwise, so records can be misattributed but do not accumulate.
+//! [`detect_cycles`] reports at least one cycle for every group of
+//! mutually-blocked tasks, but not necessarily every cycle through the
+//! group.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use tokio::sync::diagnostics;
+//!
+//! diagnostics::enable_tracking();
+//!
+//! // ... after giving blocked tasks a moment to be recorded ...
+//!
+//! for cycle in diagnostics::detect_cycles() {
+//!     eprintln!("deadlocked tasks: {:?}", cycle.tasks());
+//! }
+//! ```
+//!
+//! [`Mutex`]: crate::sync::Mutex
+//! [`RwLock`]: crate::sync::RwLock
+//! [`Semaphore`]: crate::sync::Semaphore
+//! [`JoinHandle::task_id`]: crate::task::JoinHandle::task_id
+//! [`alloc_track::current_task`]: crate::runtime::alloc_track::current_task
+
+use std::collections::{HashMap, HashSet};
+use std::ptr;
+use std::sync::atomic::{
+    AtomicBool, AtomicPtr,
+    Ordering::{AcqRel, Acquire, Relaxed},
+};
+use std::sync::{Mutex, MutexGuard};
+
+/// Whether acquisitions and releases are currently being recorded.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// A cycle in the wait-for graph of the tracked synchronization primitives.
+///
+/// Returned by [`detect_cycles`]. Every task in the cycle is waiting for a
+/// resource held by the next task in the cycle, so none of them can make
+/// progress.
+#[derive(Debug, Clone)]
+pub struct DeadlockCycle {
+    tasks: Vec<u64>,
+    resources: Vec<u64>,
+}
+
+impl DeadlockCycle {
+    /// The identifiers of the tasks participating in the cycle, in cycle
+    /// order: `tasks()[i]` is waiting for [`resources()`]`[i]`, which is held
+    /// by the next task in the cycle.
+    ///
+    /// [`resources()`]: DeadlockCycle::resources
+    pub fn tasks(&self) -> &[u64] {
+        &self.tasks
+    }
+
+    /// The identifiers of the resources the tasks are waiting for, in the
+    /// same order as [`tasks()`].
+    ///
+    /// [`tasks()`]: DeadlockCycle::tasks
+    pub fn resources(&self) -> &[u64] {
+        &self.resources
+    }
+}
+
+/// The recorded holders and waiters of a single resource.
+#[derive(Default)]
+struct Resource {
+    holders: Vec<u64>,
+    waiters: Vec<u64>,
+}
+
+#[derive(Default)]
+struct Registry {
+    resources: HashMap<u64, Resource>,
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: AtomicPtr<Mutex<Registry>> = AtomicPtr::new(ptr::null_mut());
+
+    let mut curr = REGISTRY.load(Acquire);
+    if curr.is_null() {
+        let init = Box::into_raw(Box::new(Mutex::new(Registry::default())));
+        curr = match REGISTRY.compare_exchange(ptr::null_mut(), init, AcqRel, Acquire) {
+            Ok(_) => init,
+            Err(existing) => {
+                // Lost the race to initialize; free ours and use the winner's.
+                drop(unsafe { Box::from_raw(init) });
+                existing
+            }
+        };
+    }
+
+    // Safety: the pointer, once published, is never replaced or freed.
+    unsafe { &*curr }
+}
+
+fn lock() -> MutexGuard<'static, Registry> {
+    // A panic while the registry is locked only leaves diagnostics data in a
+    // possibly inconsistent state; keep recording rather than poisoning every
+    // subsequent guard drop.
+    match registry().lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Starts recording lock holders and waiters.
+///
+/// Only acquisitions performed while tracking is enabled are recorded, so a
+/// deadlock that formed before this call is only visible once its waiters
+/// have been polled again (they are, at latest, when the process receives
+/// new work and the scheduler wakes other tasks on the same resources).
+pub fn enable_tracking() {
+    ENABLED.store(true, Relaxed);
+}
+
+/// Stops recording and discards all recorded holders and waiters.
+pub fn disable_tracking() {
+    ENABLED.store(false, Relaxed);
+    lock().resources.clear();
+}
+
+/// Records the current task as a waiter on `resource`.
+pub(crate) fn wait_started(resource: u64) {
+    if !ENABLED.load(Relaxed) {
+        return;
+    }
+    let task = match crate::runtime::alloc_track::current_task() {
+        Some(task) => task,
+        None => return,
+    };
+
+    crate::runtime::console::sync_wait_started();
+    lock()
+        .resources
+        .entry(resource)
+        .or_default()
+        .waiters
+        .push(task);
+}
+
+/// Removes the current task from the waiters of `resource` after a cancelled
+/// acquisition.
+pub(crate) fn wait_cancelled(resource: u64) {
+    if !ENABLED.load(Relaxed) {
+        return;
+    }
+    let task = match crate::runtime::alloc_track::current_task() {
+        Some(task) => task,
+        None => return,
+    };
+
+    let mut registry = lock();
+    if let Some(res) = registry.resources.get_mut(&resource) {
+        if let Some(i) = res.waiters.iter().position(|&t| t == task) {
+            res.waiters.remove(i);
+            crate::runtime::console::sync_wait_finished();
+        }
+        if res.holders.is_empty() && res.waiters.is_empty() {
+            registry.resources.remove(&resource);
+        }
+    }
+}
+
+/// Records the current task as a holder of `resource`, removing it from the
+/// waiters if the acquisition went through the wait queue.
+pub(crate) fn acquired(resource: u64) {
+    if !ENABLED.load(Relaxed) {
+        return;
+    }
+    let task = match crate::runtime::alloc_track::current_task() {
+        Some(task) => task,
+        None => return,
+    };
+
+    let mut registry = lock();
+    let res = registry.resources.entry(resource).or_default();
+    if let Some(i) = res.waiters.iter().position(|&t| t == task) {
+        res.waiters.remove(i);
+        crate::runtime::console::sync_wait_finished();
+    }
+    res.holders.push(task);
+}
+
+/// Removes a holder record of `resource`: the current task's if it is a
+/// recorded holder, the oldest one otherwise.
+pub(crate) fn released(resource: u64) {
+    if !ENABLED.load(Relaxed) {
+        return;
+    }
+
+    let mut registry = lock();
+    if let Some(res) = registry.resources.get_mut(&resource) {
+        let i = crate::runtime::alloc_track::current_task()
+            .and_then(|task| res.holders.iter().position(|&t| t == task))
+            .unwrap_or(0);
+        if i < res.holders.len() {
+            res.holders.remove(i);
+        }
+        if res.holders.is_empty() && res.waiters.is_empty() {
+            registry.resources.remove(&resource);
+        }
+    }
+}
+
+/// Searches the recorded wait-for graph for cycles.
+///
+/// A cycle is a group of tasks each waiting for a resource held by the next:
+/// none of them can make progress without outside intervention. At least one
+/// cycle is reported for every group of mutually-blocked tasks.
+///
+/// This inspects a snapshot of the recorded state; tracking must have been
+/// enabled with [`enable_tracking`] for anything to be recorded. A task that
+/// is merely slow shows up as a waiter but not as part of a cycle, so a
+/// non-empty result is a strong signal of an actual deadlock.
+pub fn detect_cycles() -> Vec<DeadlockCycle> {
+    // Edges of the wait-for graph: waiting task -> (holding task, resource).
+    let mut edges: HashMap<u64, Vec<(u64, u64)>> = HashMap::new();
+    {
+        let registry = lock();
+        for (&resource, res) in &registry.resources {
+            for &waiter in &res.waiters {
+                for &holder in &res.holders {
+                    edges.entry(waiter).or_default().push((holder, resource));
+                }
+            }
+        }
+    }
+
+    let mut cycles = Vec::new();
+    let mut visited = HashSet::new();
+    let mut path = Vec::new();
+
+    for &start in edges.keys() {
+        if visited.insert(start) {
+            visit(start, &edges, &mut path, &mut visited, &mut cycles);
+        }
+    }
+
+    cycles
+}
+
+/// Depth-first search over the wait-for graph, recording every cycle the
+/// current path closes.
+fn visit(
+    node: u64,
+    edges: &HashMap<u64, Vec<(u64, u64)>>,
+    path: &mut Vec<(u64, u64)>,
+    visited: &mut HashSet<u64>,
+    cycles: &mut Vec<DeadlockCycle>,
+) {
+    let outgoing = match edges.get(&node) {
+        Some(outgoing) => outgoing,
+        None => return,
+    };
+
+    for &(to, resource) in outgoing {
+        path.push((node, resource));
+        if let Some(pos) = path.iter().position(|&(task, _)| task == to) {
+            // The path closes back on `to`: everything from its position on
+            // is a cycle.
+            cycles.push(DeadlockCycle {
+                tasks: path[pos..].iter().map(|&(task, _)| task).collect(),
+                resources: path[pos..].iter().map(|&(_, resource)| resource).collect(),
+            });
+        } else if visited.insert(to) {
+            visit(to, edges, path, visited, cycles);
+        }
+        path.pop();
+    }
+}
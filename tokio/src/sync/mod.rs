@@ -433,24 +433,57 @@ cfg_sync! {
 
     pub mod broadcast;
 
+    mod cancellation_token;
+    pub use cancellation_token::CancellationToken;
+
+    mod condvar;
+    pub use condvar::Condvar;
+
+    #[cfg(all(tokio_unstable, feature = "rt"))]
+    pub mod diagnostics;
+
+    pub mod mpmc;
+
     pub mod mpsc;
 
     mod mutex;
-    pub use mutex::{Mutex, MutexGuard, TryLockError, OwnedMutexGuard, MappedMutexGuard};
+    pub use mutex::{
+        MappedMutexGuard, Mutex, MutexGuard, OwnedMappedMutexGuard, OwnedMutexGuard, TryLockError,
+    };
 
     pub(crate) mod notify;
-    pub use notify::Notify;
+    pub use notify::{Notify, OwnedNotified};
 
     pub mod oneshot;
 
+    pub mod rpc;
+
+    pub mod spsc;
+
+    mod priority_mutex;
+    pub use priority_mutex::{PriorityMutex, PriorityMutexGuard};
+
     pub(crate) mod batch_semaphore;
     pub use batch_semaphore::{AcquireError, TryAcquireError};
 
     mod semaphore;
-    pub use semaphore::{Semaphore, SemaphorePermit, OwnedSemaphorePermit};
+    pub use semaphore::{AcquireMany, Semaphore, SemaphorePermit, OwnedSemaphorePermit};
+
+    cfg_time! {
+        pub use mutex::LockTimeoutError;
+        pub use semaphore::AcquireTimeoutError;
+
+        mod rate_limiter;
+        pub use rate_limiter::RateLimiter;
+    }
+
+    mod signal_slot;
+    pub use signal_slot::SignalSlot;
+
+    pub mod slot;
 
     mod rwlock;
-    pub use rwlock::RwLock;
+    pub use rwlock::{RwLock, RwLockPolicy};
     pub use rwlock::owned_read_guard::OwnedRwLockReadGuard;
     pub use rwlock::owned_write_guard::OwnedRwLockWriteGuard;
     pub use rwlock::owned_write_guard_mapped::OwnedRwLockMappedWriteGuard;
@@ -464,6 +497,10 @@ cfg_sync! {
     mod once_cell;
     pub use self::once_cell::{OnceCell, SetError};
 
+    cfg_time! {
+        pub use self::once_cell::InitTimeoutError;
+    }
+
     pub mod watch;
 }
 
@@ -474,7 +511,12 @@ cfg_not_sync! {
         pub(crate) use mutex::Mutex;
     }
 
-    #[cfg(any(feature = "rt", feature = "signal", all(unix, feature = "process")))]
+    #[cfg(any(
+        feature = "net",
+        feature = "rt",
+        feature = "signal",
+        all(unix, feature = "process")
+    ))]
     pub(crate) mod notify;
 
     #[cfg(any(feature = "rt", all(windows, feature = "process")))]
@@ -0,0 +1,329 @@
+//! A bounded multi-producer, multi-consumer queue for sending values between
+//! asynchronous tasks.
+//!
+//! This differs from [`mpsc`] in that the [`Receiver`] is [`Clone`] too:
+//! any number of tasks can pull from the channel, and each value is
+//! delivered to exactly one of them. Backpressure works the same way as in
+//! mpsc — senders wait on a semaphore permit per buffered value, so waiting
+//! senders are queued fairly and participate in coop budgeting.
+//!
+//! The channel is closed for sending once every `Receiver` is dropped, and
+//! closed for receiving once every `Sender` is dropped and the buffer has
+//! been drained.
+//!
+//! [`mpsc`]: crate::sync::mpsc
+//!
+//! # Examples
+//!
+//! ```
+//! use tokio::sync::mpmc;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (tx, rx) = mpmc::channel(16);
+//!
+//!     let mut workers = Vec::new();
+//!     for _ in 0..4 {
+//!         let rx = rx.clone();
+//!         workers.push(tokio::spawn(async move {
+//!             let mut received = 0;
+//!             while rx.recv().await.is_some() {
+//!                 received += 1;
+//!             }
+//!             received
+//!         }));
+//!     }
+//!     drop(rx);
+//!
+//!     for i in 0..20 {
+//!         tx.send(i).await.unwrap();
+//!     }
+//!     drop(tx);
+//!
+//!     let mut total = 0;
+//!     for worker in workers {
+//!         total += worker.await.unwrap();
+//!     }
+//!
+//!     // Every value went to exactly one worker.
+//!     assert_eq!(total, 20);
+//! }
+//! ```
+
+use crate::loom::sync::atomic::AtomicUsize;
+use crate::loom::sync::{Arc, Mutex};
+use crate::sync::batch_semaphore::{Semaphore, TryAcquireError};
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::Ordering::SeqCst;
+
+pub mod error {
+    //! MPMC channel error types
+
+    use std::error::Error;
+    use std::fmt;
+
+    /// Error returned by [`Sender::send`](super::Sender::send): every
+    /// receiver was dropped, and the value is handed back.
+    #[derive(Debug)]
+    pub struct SendError<T>(pub T);
+
+    impl<T> fmt::Display for SendError<T> {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(fmt, "channel closed")
+        }
+    }
+
+    impl<T: fmt::Debug> Error for SendError<T> {}
+
+    /// Error returned by [`Sender::try_send`](super::Sender::try_send).
+    #[derive(Debug)]
+    pub enum TrySendError<T> {
+        /// The channel's buffer is full.
+        Full(T),
+
+        /// Every receiver was dropped.
+        Closed(T),
+    }
+
+    impl<T> fmt::Display for TrySendError<T> {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                fmt,
+                "{}",
+                match self {
+                    TrySendError::Full(..) => "no available capacity",
+                    TrySendError::Closed(..) => "channel closed",
+                }
+            )
+        }
+    }
+
+    impl<T: fmt::Debug> Error for TrySendError<T> {}
+
+    /// Error returned by [`Receiver::try_recv`](super::Receiver::try_recv).
+    #[derive(Debug, PartialEq)]
+    pub enum TryRecvError {
+        /// The buffer is currently empty, but senders are still alive.
+        Empty,
+
+        /// Every sender was dropped and all buffered values have been
+        /// received.
+        Closed,
+    }
+
+    impl fmt::Display for TryRecvError {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                fmt,
+                "{}",
+                match self {
+                    TryRecvError::Empty => "channel empty",
+                    TryRecvError::Closed => "channel closed",
+                }
+            )
+        }
+    }
+
+    impl Error for TryRecvError {}
+}
+
+use error::{SendError, TryRecvError, TrySendError};
+
+/// Creates a bounded multi-producer, multi-consumer channel with room for
+/// `capacity` buffered values.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "mpmc bounded channel requires capacity > 0");
+
+    let chan = Arc::new(Channel {
+        queue: Mutex::new(VecDeque::with_capacity(capacity)),
+        capacity: Semaphore::new(capacity),
+        items: Semaphore::new(0),
+        tx_count: AtomicUsize::new(1),
+        rx_count: AtomicUsize::new(1),
+    });
+
+    (
+        Sender { chan: chan.clone() },
+        Receiver { chan },
+    )
+}
+
+/// Sends values to the associated [`Receiver`] handles.
+///
+/// Created by the [`channel`] function; may be cloned freely.
+pub struct Sender<T> {
+    chan: Arc<Channel<T>>,
+}
+
+/// Receives values from the associated [`Sender`] handles.
+///
+/// Created by the [`channel`] function; may be cloned freely. Each value
+/// sent on the channel is received by exactly one `Receiver`.
+pub struct Receiver<T> {
+    chan: Arc<Channel<T>>,
+}
+
+struct Channel<T> {
+    /// Buffered values. Every entry is matched by one acquired `capacity`
+    /// permit and one available `items` permit.
+    queue: Mutex<VecDeque<T>>,
+
+    /// Free buffer slots; senders acquire one permit per send. Closed when
+    /// the last `Receiver` is dropped.
+    capacity: Semaphore,
+
+    /// Buffered values; receivers acquire one permit per receive. Closed
+    /// when the last `Sender` is dropped, after which the queue is drained
+    /// directly.
+    items: Semaphore,
+
+    tx_count: AtomicUsize,
+    rx_count: AtomicUsize,
+}
+
+impl<T> Sender<T> {
+    /// Sends a value, waiting until there is capacity.
+    ///
+    /// An error is returned if every receiver was dropped; the value is
+    /// handed back in the error.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If the future is dropped before
+    /// completing, no slot was consumed and the value is dropped along with
+    /// the future.
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if self.chan.capacity.acquire(1).await.is_err() {
+            return Err(SendError(value));
+        }
+
+        self.chan.queue.lock().push_back(value);
+        self.chan.items.release(1);
+        Ok(())
+    }
+
+    /// Attempts to send a value without waiting.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        match self.chan.capacity.try_acquire(1) {
+            Ok(()) => {
+                self.chan.queue.lock().push_back(value);
+                self.chan.items.release(1);
+                Ok(())
+            }
+            Err(TryAcquireError::NoPermits) => Err(TrySendError::Full(value)),
+            Err(TryAcquireError::Closed) => Err(TrySendError::Closed(value)),
+        }
+    }
+
+    /// Returns `true` if every receiver has been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.chan.rx_count.load(SeqCst) == 0
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.chan.tx_count.fetch_add(1, SeqCst);
+        Sender {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if self.chan.tx_count.fetch_sub(1, SeqCst) == 1 {
+            // The last sender is gone: fail pending and future receives so
+            // they fall back to draining the queue.
+            self.chan.items.close();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Sender").finish()
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Receives the next value, waiting for one if the buffer is empty.
+    ///
+    /// Returns `None` once every sender has been dropped and all buffered
+    /// values have been received. Values are handed to waiting receivers in
+    /// the order the receivers arrived.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If the future is dropped before
+    /// completing, no value was taken out of the channel.
+    pub async fn recv(&self) -> Option<T> {
+        match self.chan.items.acquire(1).await {
+            Ok(()) => {
+                let value = self
+                    .chan
+                    .queue
+                    .lock()
+                    .pop_front()
+                    .expect("item permit without a buffered value");
+                self.chan.capacity.release(1);
+                Some(value)
+            }
+            Err(_) => {
+                // Every sender is gone; no permits are tracked any more, so
+                // drain what is left under the queue lock.
+                self.chan.queue.lock().pop_front()
+            }
+        }
+    }
+
+    /// Attempts to receive the next value without waiting.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        match self.chan.items.try_acquire(1) {
+            Ok(()) => {
+                let value = self
+                    .chan
+                    .queue
+                    .lock()
+                    .pop_front()
+                    .expect("item permit without a buffered value");
+                self.chan.capacity.release(1);
+                Ok(value)
+            }
+            Err(TryAcquireError::NoPermits) => Err(TryRecvError::Empty),
+            Err(TryAcquireError::Closed) => {
+                self.chan.queue.lock().pop_front().ok_or(TryRecvError::Closed)
+            }
+        }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        self.chan.rx_count.fetch_add(1, SeqCst);
+        Receiver {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        if self.chan.rx_count.fetch_sub(1, SeqCst) == 1 {
+            // The last receiver is gone: fail pending and future sends.
+            self.chan.capacity.close();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Receiver").finish()
+    }
+}
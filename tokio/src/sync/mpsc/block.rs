@@ -1,21 +1,34 @@
 use crate::loom::cell::UnsafeCell;
 use crate::loom::sync::atomic::{AtomicPtr, AtomicUsize};
 use crate::loom::thread;
+use crate::sync::mpsc::pool::BlockPool;
 
+use std::alloc::Layout;
 use std::mem::MaybeUninit;
-use std::ops;
 use std::ptr::{self, NonNull};
 use std::sync::atomic::Ordering::{self, AcqRel, Acquire, Release};
 
 /// A block in a linked list.
 ///
-/// Each block in the list can hold up to `BLOCK_CAP` messages.
+/// Each block in the list can hold up to `cap` messages. All blocks in a
+/// channel share the same capacity, which is fixed when the channel is
+/// created.
+///
+/// The message slots are stored inline, directly after the block header in
+/// the same allocation, so blocks must be created with [`Block::new`] and
+/// freed with [`Block::dealloc`].
 pub(crate) struct Block<T> {
     /// The start index of this block.
     ///
-    /// Slots in this block have indices in `start_index .. start_index + BLOCK_CAP`.
+    /// Slots in this block have indices in `start_index .. start_index + cap`.
     start_index: usize,
 
+    /// The number of values this block can hold.
+    ///
+    /// Always a power of two, and must be small enough to leave room for the
+    /// `RELEASED` and `TX_CLOSED` flags above the slot-ready bits.
+    cap: usize,
+
     /// The next block in the linked list.
     next: AtomicPtr<Block<T>>,
 
@@ -26,10 +39,17 @@ pub(crate) struct Block<T> {
     /// `block_tail`.
     observed_tail_position: UnsafeCell<usize>,
 
-    /// Array containing values pushed into the block. Values are stored in a
-    /// continuous array in order to improve cache line behavior when reading.
-    /// The values must be manually dropped.
-    values: Values<T>,
+    /// The pool this block was allocated from, if any.
+    ///
+    /// When set, the block's memory is returned to the pool on dealloc
+    /// instead of the global allocator, and blocks appended by `grow` use the
+    /// same pool.
+    pool: Option<BlockPool>,
+
+    /// Values are stored in a continuous array directly after the header in
+    /// order to improve cache line behavior when reading. The values must be
+    /// manually dropped.
+    _values: [UnsafeCell<MaybeUninit<T>>; 0],
 }
 
 pub(crate) enum Read<T> {
@@ -37,62 +57,152 @@ pub(crate) enum Read<T> {
     Closed,
 }
 
-struct Values<T>([UnsafeCell<MaybeUninit<T>>; BLOCK_CAP]);
+/// Returns the index of the first slot in the block referenced by
+/// `slot_index`, for blocks of capacity `cap`.
+#[inline(always)]
+pub(crate) fn start_index(slot_index: usize, cap: usize) -> usize {
+    !(cap - 1) & slot_index
+}
 
-use super::BLOCK_CAP;
+/// Returns the offset into the block referenced by `slot_index`, for blocks
+/// of capacity `cap`.
+#[inline(always)]
+pub(crate) fn offset(slot_index: usize, cap: usize) -> usize {
+    (cap - 1) & slot_index
+}
 
-/// Masks an index to get the block identifier
-const BLOCK_MASK: usize = !(BLOCK_CAP - 1);
+impl<T> Block<T> {
+    /// Allocates a new block with capacity `cap`.
+    ///
+    /// `cap` must be a power of two no greater than `usize::BITS - 2`, so
+    /// that the `RELEASED` and `TX_CLOSED` flags fit above the slot-ready
+    /// bits.
+    pub(crate) fn new(start_index: usize, cap: usize, pool: Option<BlockPool>) -> NonNull<Block<T>> {
+        debug_assert!(cap.is_power_of_two());
+        debug_assert!(cap <= usize::MAX.count_ones() as usize - 2);
+
+        let (layout, slots_offset) = Self::layout(cap);
+
+        unsafe {
+            let ptr = pool
+                .as_ref()
+                .and_then(|pool| pool.allocate(layout))
+                .map(|ptr| ptr.as_ptr())
+                .unwrap_or_else(|| std::alloc::alloc(layout));
+
+            let block = match NonNull::new(ptr as *mut Block<T>) {
+                Some(block) => block,
+                None => std::alloc::handle_alloc_error(layout),
+            };
 
-/// Masks an index to get the value offset in a block.
-const SLOT_MASK: usize = BLOCK_CAP - 1;
+            ptr::write(
+                block.as_ptr(),
+                Block {
+                    // The absolute index in the channel of the first slot in
+                    // the block.
+                    start_index,
 
-/// Flag tracking that a block has gone through the sender's release routine.
-///
-/// When this is set, the receiver may consider freeing the block.
-const RELEASED: usize = 1 << BLOCK_CAP;
+                    cap,
 
-/// Flag tracking all senders dropped.
-///
-/// When this flag is set, the send half of the channel has closed.
-const TX_CLOSED: usize = RELEASED << 1;
+                    // Pointer to the next block in the linked list.
+                    next: AtomicPtr::new(ptr::null_mut()),
 
-/// Mask covering all bits used to track slot readiness.
-const READY_MASK: usize = RELEASED - 1;
+                    ready_slots: AtomicUsize::new(0),
 
-/// Returns the index of the first slot in the block referenced by `slot_index`.
-#[inline(always)]
-pub(crate) fn start_index(slot_index: usize) -> usize {
-    BLOCK_MASK & slot_index
-}
+                    observed_tail_position: UnsafeCell::new(0),
 
-/// Returns the offset into the block referenced by `slot_index`.
-#[inline(always)]
-pub(crate) fn offset(slot_index: usize) -> usize {
-    SLOT_MASK & slot_index
-}
+                    pool,
 
-impl<T> Block<T> {
-    pub(crate) fn new(start_index: usize) -> Block<T> {
-        Block {
-            // The absolute index in the channel of the first slot in the block.
-            start_index,
+                    _values: [],
+                },
+            );
 
-            // Pointer to the next block in the linked list.
-            next: AtomicPtr::new(ptr::null_mut()),
+            // When fuzzing, `UnsafeCell` needs to be initialized.
+            if_loom! {
+                let p = ptr.add(slots_offset) as *mut UnsafeCell<MaybeUninit<T>>;
+                for i in 0..cap {
+                    p.add(i)
+                        .write(UnsafeCell::new(MaybeUninit::uninit()));
+                }
+            }
 
-            ready_slots: AtomicUsize::new(0),
+            // Outside of loom, the slots are left uninitialized; the ready
+            // bits track which slots hold values.
+            let _ = slots_offset;
 
-            observed_tail_position: UnsafeCell::new(0),
+            block
+        }
+    }
+
+    /// Frees the block.
+    ///
+    /// # Safety
+    ///
+    /// To maintain safety, the caller must ensure:
+    ///
+    /// * All slots are empty.
+    /// * The caller holds a unique pointer to the block.
+    pub(crate) unsafe fn dealloc(this: NonNull<Block<T>>) {
+        let (layout, _) = Self::layout(this.as_ref().cap);
+
+        // Move the pool handle out before dropping the header so the block's
+        // memory can be released after the header is gone.
+        let pool = (*this.as_ptr()).pool.take();
 
-            // Value storage
-            values: unsafe { Values::uninitialized() },
+        ptr::drop_in_place(this.as_ptr());
+
+        match pool {
+            Some(pool) => pool.release(this.cast(), layout),
+            None => std::alloc::dealloc(this.as_ptr() as *mut u8, layout),
+        }
+    }
+
+    /// Returns the allocation layout for a block of capacity `cap`, and the
+    /// offset of the value array within the allocation.
+    fn layout(cap: usize) -> (Layout, usize) {
+        let header = Layout::new::<Block<T>>();
+        let values = Layout::array::<UnsafeCell<MaybeUninit<T>>>(cap).expect("block too large");
+
+        let (layout, offset) = header.extend(values).expect("block too large");
+        (layout.pad_to_align(), offset)
+    }
+
+    /// Returns a reference to the value slot at `offset`.
+    fn slot(&self, offset: usize) -> &UnsafeCell<MaybeUninit<T>> {
+        debug_assert!(offset < self.cap);
+
+        let (_, slots_offset) = Self::layout(self.cap);
+
+        unsafe {
+            let base =
+                (self as *const Block<T> as *const u8).add(slots_offset) as *const UnsafeCell<MaybeUninit<T>>;
+            &*base.add(offset)
         }
     }
 
+    /// Flag tracking that a block has gone through the sender's release
+    /// routine.
+    ///
+    /// When this is set, the receiver may consider freeing the block.
+    fn released_bit(&self) -> usize {
+        1 << self.cap
+    }
+
+    /// Flag tracking all senders dropped.
+    ///
+    /// When this flag is set, the send half of the channel has closed.
+    fn tx_closed_bit(&self) -> usize {
+        self.released_bit() << 1
+    }
+
+    /// Mask covering all bits used to track slot readiness.
+    fn ready_mask(&self) -> usize {
+        self.released_bit() - 1
+    }
+
     /// Returns `true` if the block matches the given index
     pub(crate) fn is_at_index(&self, index: usize) -> bool {
-        debug_assert!(offset(index) == 0);
+        debug_assert!(offset(index, self.cap) == 0);
         self.start_index == index
     }
 
@@ -101,8 +211,8 @@ impl<T> Block<T> {
     ///
     /// `start_index` must represent a block *after* `self`.
     pub(crate) fn distance(&self, other_index: usize) -> usize {
-        debug_assert!(offset(other_index) == 0);
-        other_index.wrapping_sub(self.start_index) / BLOCK_CAP
+        debug_assert!(offset(other_index, self.cap) == 0);
+        other_index.wrapping_sub(self.start_index) / self.cap
     }
 
     /// Reads the value at the given offset.
@@ -115,12 +225,12 @@ impl<T> Block<T> {
     ///
     /// * No concurrent access to the slot.
     pub(crate) unsafe fn read(&self, slot_index: usize) -> Option<Read<T>> {
-        let offset = offset(slot_index);
+        let offset = offset(slot_index, self.cap);
 
         let ready_bits = self.ready_slots.load(Acquire);
 
         if !is_ready(ready_bits, offset) {
-            if is_tx_closed(ready_bits) {
+            if self.is_tx_closed(ready_bits) {
                 return Some(Read::Closed);
             }
 
@@ -128,11 +238,37 @@ impl<T> Block<T> {
         }
 
         // Get the value
-        let value = self.values[offset].with(|ptr| ptr::read(ptr));
+        let value = self.slot(offset).with(|ptr| ptr::read(ptr));
 
         Some(Read::Value(value.assume_init()))
     }
 
+    /// Returns a reference to the value in the slot without consuming it.
+    ///
+    /// The slot remains ready; a subsequent `read` of the same index returns
+    /// the value itself.
+    ///
+    /// # Safety
+    ///
+    /// To maintain safety, the caller must be the only one reading slots.
+    pub(crate) unsafe fn peek(&self, slot_index: usize) -> Option<Read<&T>> {
+        let offset = offset(slot_index, self.cap);
+
+        let ready_bits = self.ready_slots.load(Acquire);
+
+        if !is_ready(ready_bits, offset) {
+            if self.is_tx_closed(ready_bits) {
+                return Some(Read::Closed);
+            }
+
+            return None;
+        }
+
+        let value = self.slot(offset).with(|ptr| &*(*ptr).as_ptr());
+
+        Some(Read::Value(value))
+    }
+
     /// Writes a value to the block at the given offset.
     ///
     /// # Safety
@@ -143,9 +279,9 @@ impl<T> Block<T> {
     /// * No concurrent access to the slot.
     pub(crate) unsafe fn write(&self, slot_index: usize, value: T) {
         // Get the offset into the block
-        let slot_offset = offset(slot_index);
+        let slot_offset = offset(slot_index, self.cap);
 
-        self.values[slot_offset].with_mut(|ptr| {
+        self.slot(slot_offset).with_mut(|ptr| {
             ptr::write(ptr, MaybeUninit::new(value));
         });
 
@@ -157,7 +293,13 @@ impl<T> Block<T> {
 
     /// Signal to the receiver that the sender half of the list is closed.
     pub(crate) unsafe fn tx_close(&self) {
-        self.ready_slots.fetch_or(TX_CLOSED, Release);
+        self.ready_slots.fetch_or(self.tx_closed_bit(), Release);
+    }
+
+    /// Returns `true` if the closed flag has been set.
+    fn is_tx_closed(&self, bits: usize) -> bool {
+        let tx_closed = self.tx_closed_bit();
+        tx_closed == bits & tx_closed
     }
 
     /// Resets the block to a blank state. This enables reusing blocks in the
@@ -194,7 +336,7 @@ impl<T> Block<T> {
         // Set the released bit, signalling to the receiver that it is safe to
         // free the block's memory as soon as all slots **prior** to
         // `observed_tail_position` have been filled.
-        self.ready_slots.fetch_or(RELEASED, Release);
+        self.ready_slots.fetch_or(self.released_bit(), Release);
     }
 
     /// Mark a slot as ready
@@ -215,12 +357,12 @@ impl<T> Block<T> {
     /// single atomic cell. However, this could have negative impact on cache
     /// behavior as there would be many more mutations to a single slot.
     pub(crate) fn is_final(&self) -> bool {
-        self.ready_slots.load(Acquire) & READY_MASK == READY_MASK
+        self.ready_slots.load(Acquire) & self.ready_mask() == self.ready_mask()
     }
 
     /// Returns the `observed_tail_position` value, if set
     pub(crate) fn observed_tail_position(&self) -> Option<usize> {
-        if 0 == RELEASED & self.ready_slots.load(Acquire) {
+        if 0 == self.released_bit() & self.ready_slots.load(Acquire) {
             None
         } else {
             Some(self.observed_tail_position.with(|ptr| unsafe { *ptr }))
@@ -232,7 +374,7 @@ impl<T> Block<T> {
         let ret = NonNull::new(self.next.load(ordering));
 
         debug_assert!(unsafe {
-            ret.map(|block| block.as_ref().start_index == self.start_index.wrapping_add(BLOCK_CAP))
+            ret.map(|block| block.as_ref().start_index == self.start_index.wrapping_add(self.cap))
                 .unwrap_or(true)
         });
 
@@ -261,7 +403,7 @@ impl<T> Block<T> {
         success: Ordering,
         failure: Ordering,
     ) -> Result<(), NonNull<Block<T>>> {
-        block.as_mut().start_index = self.start_index.wrapping_add(BLOCK_CAP);
+        block.as_mut().start_index = self.start_index.wrapping_add(self.cap);
 
         let next_ptr = self
             .next
@@ -292,9 +434,7 @@ impl<T> Block<T> {
         // Create the new block. It is assumed that the block will become the
         // next one after `&self`. If this turns out to not be the case,
         // `start_index` is updated accordingly.
-        let new_block = Box::new(Block::new(self.start_index + BLOCK_CAP));
-
-        let mut new_block = unsafe { NonNull::new_unchecked(Box::into_raw(new_block)) };
+        let mut new_block = Block::new(self.start_index + self.cap, self.cap, self.pool.clone());
 
         // Attempt to store the block. The first compare-and-swap attempt is
         // "unrolled" due to minor differences in logic
@@ -355,33 +495,3 @@ fn is_ready(bits: usize, slot: usize) -> bool {
     let mask = 1 << slot;
     mask == mask & bits
 }
-
-/// Returns `true` if the closed flag has been set.
-fn is_tx_closed(bits: usize) -> bool {
-    TX_CLOSED == bits & TX_CLOSED
-}
-
-impl<T> Values<T> {
-    unsafe fn uninitialized() -> Values<T> {
-        let mut vals = MaybeUninit::uninit();
-
-        // When fuzzing, `UnsafeCell` needs to be initialized.
-        if_loom! {
-            let p = vals.as_mut_ptr() as *mut UnsafeCell<MaybeUninit<T>>;
-            for i in 0..BLOCK_CAP {
-                p.add(i)
-                    .write(UnsafeCell::new(MaybeUninit::uninit()));
-            }
-        }
-
-        Values(vals.assume_init())
-    }
-}
-
-impl<T> ops::Index<usize> for Values<T> {
-    type Output = UnsafeCell<MaybeUninit<T>>;
-
-    fn index(&self, index: usize) -> &Self::Output {
-        self.0.index(index)
-    }
-}
@@ -1,4 +1,5 @@
 use crate::sync::mpsc::chan;
+use crate::sync::mpsc::pool::BlockPool;
 use crate::sync::mpsc::error::{SendError, TrySendError};
 use crate::{
     loom::sync::atomic::AtomicUsize,
@@ -23,6 +24,27 @@ use std::task::{Context, Poll};
 /// [`PollSender`]: https://docs.rs/tokio-util/0.6/tokio_util/sync/struct.PollSender.html
 pub struct Sender<T> {
     chan: chan::Tx<T, Semaphore>,
+
+    /// `true` while this handle holds a permit acquired by [`poll_reserve`]
+    /// that has not yet been consumed by [`send_reserved`].
+    ///
+    /// [`poll_reserve`]: Sender::poll_reserve
+    /// [`send_reserved`]: Sender::send_reserved
+    reserved: bool,
+}
+
+/// A sender that does not prevent the channel from being closed.
+///
+/// If all [`Sender`] instances of a channel were dropped and only `WeakSender`
+/// instances remain, the channel is closed. Unlike `Sender`, a `WeakSender`
+/// cannot send directly: call [`upgrade`], which returns `Some(Sender)` while
+/// at least one strong sender is still alive and `None` afterwards.
+///
+/// Instances are created by [`Sender::downgrade`].
+///
+/// [`upgrade`]: WeakSender::upgrade
+pub struct WeakSender<T> {
+    chan: chan::WeakTx<T, Semaphore>,
 }
 
 /// Permit to send one value into the channel.
@@ -52,6 +74,64 @@ pub struct OwnedPermit<T> {
     chan: Option<chan::Tx<T, Semaphore>>,
 }
 
+/// An iterator of [`OwnedPermit`]s reserved as a batch.
+///
+/// `OwnedPermitIterator` values are returned by [`Sender::reserve_many`] and
+/// [`Sender::try_reserve_many`]. Dropping the iterator releases any permits
+/// that have not been taken from it back to the channel.
+///
+/// [`Sender::reserve_many`]: Sender::reserve_many
+/// [`Sender::try_reserve_many`]: Sender::try_reserve_many
+pub struct OwnedPermitIterator<T> {
+    chan: chan::Tx<T, Semaphore>,
+
+    /// Permits held by the iterator that have not yet been handed out.
+    remaining: u32,
+}
+
+/// Policy applied to sends while a channel is over capacity.
+///
+/// A channel is over capacity when [`Receiver::resize`] shrinks it below the
+/// number of messages currently in flight. The policy decides what happens to
+/// values sent before the excess has drained; it is selected with
+/// [`Receiver::resize_with_policy`] and stays in effect until the next
+/// resize.
+pub enum OverflowPolicy<T> {
+    /// Sends wait until enough messages have been received to bring the
+    /// channel back under capacity.
+    ///
+    /// This is the default behavior.
+    Drain,
+
+    /// Values sent while the channel is over capacity are dropped and the
+    /// send completes immediately.
+    DropNewest,
+
+    /// Values sent while the channel is over capacity are handed to the
+    /// callback and the send completes immediately.
+    Callback(std::sync::Arc<dyn Fn(T) + Send + Sync>),
+}
+
+impl<T> Clone for OverflowPolicy<T> {
+    fn clone(&self) -> OverflowPolicy<T> {
+        match self {
+            OverflowPolicy::Drain => OverflowPolicy::Drain,
+            OverflowPolicy::DropNewest => OverflowPolicy::DropNewest,
+            OverflowPolicy::Callback(callback) => OverflowPolicy::Callback(callback.clone()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for OverflowPolicy<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverflowPolicy::Drain => fmt.write_str("Drain"),
+            OverflowPolicy::DropNewest => fmt.write_str("DropNewest"),
+            OverflowPolicy::Callback(_) => fmt.write_str("Callback(..)"),
+        }
+    }
+}
+
 /// Receive values from the associated `Sender`.
 ///
 /// Instances are created by the [`channel`](channel) function.
@@ -62,6 +142,22 @@ pub struct OwnedPermit<T> {
 pub struct Receiver<T> {
     /// The channel receiver
     chan: chan::Rx<T, Semaphore>,
+
+    /// Observer hooks installed by [`tap`]. Each is handed every received
+    /// value and reports whether the tap is still live.
+    ///
+    /// [`tap`]: Receiver::tap
+    taps: Vec<Box<dyn Fn(&T) -> bool + Send + Sync>>,
+}
+
+/// Observes values flowing through a bounded channel.
+///
+/// Instances are created by [`Receiver::tap`] and receive a clone of every
+/// value the main receiver consumes. A tap never holds channel capacity: the
+/// channel's permits are released by the main receiver exactly as if the tap
+/// did not exist, and a slow tap only grows its own queue.
+pub struct TapReceiver<T> {
+    rx: super::UnboundedReceiver<T>,
 }
 
 /// Creates a bounded mpsc channel for communicating between asynchronous tasks
@@ -81,9 +177,26 @@ pub struct Receiver<T> {
 /// will return a `SendError`. Similarly, if `Sender` is disconnected while
 /// trying to `recv`, the `recv` method will return a `RecvError`.
 ///
-/// # Panics
+/// Channels with a small buffer store their messages in a fixed inline ring
+/// instead of the general linked list of blocks, avoiding block allocations.
+/// This is transparent except that [`Receiver::resize`] cannot grow such a
+/// channel past the ring size.
+///
+/// # Rendezvous channels
+///
+/// A buffer of `0` creates a rendezvous channel: the channel holds no
+/// standing capacity, and a `send` only completes while a receiver is
+/// actively waiting in [`recv`]. This gives a CSP-style handoff where the
+/// sender cannot run ahead of the receiver by even one message. [`try_send`]
+/// on a rendezvous channel succeeds only when a receiver is already waiting.
+/// A rendezvous channel cannot be resized.
 ///
-/// Panics if the buffer capacity is 0.
+/// Note that cancelling a [`recv`] future after it has been polled may let
+/// one in-flight `send` complete; the value is not lost and is returned by
+/// the next [`recv`].
+///
+/// [`recv`]: Receiver::recv
+/// [`try_send`]: Sender::try_send
 ///
 /// # Examples
 ///
@@ -109,9 +222,181 @@ pub struct Receiver<T> {
 /// }
 /// ```
 pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
+    let semaphore = (semaphore::Semaphore::new(buffer), AtomicUsize::new(buffer));
+
+    let (tx, rx) = if buffer <= super::ring::MAX_INLINE_CAP {
+        chan::channel_inline(semaphore)
+    } else {
+        chan::channel(semaphore)
+    };
+
+    let tx = Sender::new(tx);
+    let rx = Receiver::new(rx);
+
+    (tx, rx)
+}
+
+/// Creates a bounded mpsc channel with the given block size for the internal
+/// linked list.
+///
+/// Messages are stored in blocks of `block_size` slots each. The default used
+/// by [`channel`] favors throughput; a smaller block size reduces the memory
+/// footprint of channels that only ever hold a few messages, since a channel
+/// preallocates one block. With `block_size >= buffer`, a channel typically
+/// runs on a single recycled block for its entire lifetime.
+///
+/// # Panics
+///
+/// Panics if `buffer` is zero, or if `block_size` is zero, not a power of
+/// two, or greater than the default block size.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // A tiny channel that allocates a single one-slot block.
+///     let (tx, mut rx) = mpsc::channel_with_block_size(1, 1);
+///
+///     tx.send("hello").await.unwrap();
+///     assert_eq!(rx.recv().await, Some("hello"));
+/// }
+/// ```
+pub fn channel_with_block_size<T>(buffer: usize, block_size: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(buffer > 0, "mpsc bounded channel requires buffer > 0");
+    assert!(
+        block_size.is_power_of_two(),
+        "mpsc block size must be a power of two"
+    );
+    assert!(
+        block_size <= super::BLOCK_CAP,
+        "mpsc block size must not exceed {}",
+        super::BLOCK_CAP
+    );
+
+    let semaphore = (semaphore::Semaphore::new(buffer), AtomicUsize::new(buffer));
+    let (tx, rx) = chan::channel_with_block_size(semaphore, block_size, None);
+
+    let tx = Sender::new(tx);
+    let rx = Receiver::new(rx);
+
+    (tx, rx)
+}
+
+/// Creates a bounded mpsc channel whose blocks are recycled through the given
+/// pool.
+///
+/// Freed blocks are returned to `pool` instead of the global allocator, and
+/// new blocks are taken from the pool when a compatible one is cached. This
+/// reduces allocation churn for workloads that create and destroy many
+/// short-lived channels. See [`BlockPool`] for details.
+///
+/// The block size follows the same rules as [`channel_with_block_size`].
+///
+/// # Panics
+///
+/// Panics if `buffer` is zero, or if `block_size` is zero, not a power of
+/// two, or greater than the default block size.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let pool = mpsc::BlockPool::new();
+///
+///     for _ in 0..1000 {
+///         // After the first iteration, the block is reused from the pool.
+///         let (tx, mut rx) = mpsc::channel_with_pool(1, 1, &pool);
+///
+///         tx.send("hello").await.unwrap();
+///         assert_eq!(rx.recv().await, Some("hello"));
+///     }
+/// }
+/// ```
+///
+/// [`BlockPool`]: super::BlockPool
+pub fn channel_with_pool<T>(
+    buffer: usize,
+    block_size: usize,
+    pool: &BlockPool,
+) -> (Sender<T>, Receiver<T>) {
     assert!(buffer > 0, "mpsc bounded channel requires buffer > 0");
+    assert!(
+        block_size.is_power_of_two(),
+        "mpsc block size must be a power of two"
+    );
+    assert!(
+        block_size <= super::BLOCK_CAP,
+        "mpsc block size must not exceed {}",
+        super::BLOCK_CAP
+    );
+
     let semaphore = (semaphore::Semaphore::new(buffer), AtomicUsize::new(buffer));
-    let (tx, rx) = chan::channel(semaphore);
+    let (tx, rx) = chan::channel_with_block_size(semaphore, block_size, Some(pool.clone()));
+
+    let tx = Sender::new(tx);
+    let rx = Receiver::new(rx);
+
+    (tx, rx)
+}
+
+/// Creates a bounded mpsc channel whose capacity is a budget rather than a
+/// message count.
+///
+/// Each value sent occupies `cost(&value)` units of the `capacity` budget
+/// instead of one, and returns them when it is received. This bounds channels
+/// whose messages vary widely in size — with `capacity` in bytes and a cost
+/// function returning the message length, the channel holds at most
+/// `capacity` bytes regardless of how the bytes are split into messages.
+///
+/// The cost of a value is clamped to `1..=capacity`: a zero-cost value still
+/// occupies one unit so it cannot bypass backpressure, and a value costing
+/// more than the whole budget waits for the channel to be empty rather than
+/// forever. The cost function must be pure — it is called once when a value
+/// is sent and once when it is received, and the two results must agree.
+///
+/// The permit-reservation APIs ([`Sender::reserve`] and related methods)
+/// reserve capacity for exactly one unit and therefore cannot be used on a
+/// cost-based channel; they panic if called. [`Receiver::resize`] is likewise
+/// unsupported. All other send and receive methods behave as on a channel
+/// created with [`channel`].
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // A budget of 1 KiB, with each message costing its length.
+///     let (tx, mut rx) = mpsc::channel_with_cost(1024, |msg: &Vec<u8>| msg.len());
+///
+///     tx.send(vec![0; 1000]).await.unwrap();
+///
+///     // Only 24 bytes of budget remain.
+///     assert!(tx.try_send(vec![0; 100]).is_err());
+///
+///     rx.recv().await.unwrap();
+///     assert_eq!(tx.capacity(), 1024);
+/// }
+/// ```
+pub fn channel_with_cost<T, F>(capacity: usize, cost: F) -> (Sender<T>, Receiver<T>)
+where
+    F: Fn(&T) -> usize + Send + Sync + 'static,
+{
+    assert!(capacity > 0, "mpsc bounded channel requires buffer > 0");
+
+    let semaphore = (semaphore::Semaphore::new(capacity), AtomicUsize::new(capacity));
+    let (tx, rx) = chan::channel_with_cost(semaphore, std::sync::Arc::new(cost));
 
     let tx = Sender::new(tx);
     let rx = Receiver::new(rx);
@@ -123,9 +408,45 @@ pub fn channel<T>(buffer: usize) -> (Sender<T>, Receiver<T>) {
 /// representing the channel bound.
 type Semaphore = (semaphore::Semaphore, AtomicUsize);
 
+/// Point-in-time statistics for a bounded channel.
+///
+/// Returned by [`Sender::stats`] and [`Receiver::stats`]. The fields are
+/// sampled individually, so under concurrent sends and receives they may not
+/// be mutually consistent; they are intended for monitoring and debugging,
+/// not for flow-control decisions.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ChannelStats {
+    /// The configured capacity of the channel.
+    pub capacity: usize,
+
+    /// The number of values that can be sent before the channel is full.
+    pub available: usize,
+
+    /// The permit deficit left by a [`Receiver::resize`] that shrank the
+    /// channel below its occupancy.
+    ///
+    /// This many messages must be received before capacity frees up for
+    /// senders again. Zero when the channel is not over capacity.
+    pub permit_deficit: usize,
+}
+
+fn channel_stats(semaphore: &Semaphore) -> ChannelStats {
+    use chan::Semaphore as _;
+
+    ChannelStats {
+        capacity: semaphore.cap(),
+        available: semaphore.0.available_permits(),
+        permit_deficit: semaphore.deficit(),
+    }
+}
+
 impl<T> Receiver<T> {
     pub(crate) fn new(chan: chan::Rx<T, Semaphore>) -> Receiver<T> {
-        Receiver { chan }
+        Receiver {
+            chan,
+            taps: Vec::new(),
+        }
     }
 
     /// Receives the next value for this receiver.
@@ -182,7 +503,105 @@ impl<T> Receiver<T> {
     /// ```
     pub async fn recv(&mut self) -> Option<T> {
         use crate::future::poll_fn;
-        poll_fn(|cx| self.chan.recv(cx)).await
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Receives the next values for this receiver and extends `buffer`.
+    ///
+    /// This method extends `buffer` by no more than a fixed number of values
+    /// as specified by `limit`. If `limit` is zero, the method returns `0`
+    /// immediately. Otherwise the method waits until at least one value is
+    /// ready, appends everything ready up to `limit`, and returns the number
+    /// of values added.
+    ///
+    /// Compared to calling [`recv`] in a loop, this wakes the receiving task
+    /// once per batch and returns the batch's permits to waiting senders in a
+    /// single operation.
+    ///
+    /// A return of `0` with a non-zero `limit` means the channel has been
+    /// closed and there are no remaining messages in the channel's queue.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If `recv_many` is used as the event in a
+    /// [`tokio::select!`] statement and some other branch completes first, it
+    /// is guaranteed that no messages were received on this channel.
+    ///
+    /// [`recv`]: Receiver::recv
+    /// [`tokio::select!`]: crate::select
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::channel(100);
+    ///     let mut buffer = Vec::with_capacity(3);
+    ///
+    ///     for i in 0..5 {
+    ///         tx.send(i).await.unwrap();
+    ///     }
+    ///
+    ///     assert_eq!(rx.recv_many(&mut buffer, 3).await, 3);
+    ///     assert_eq!(buffer, vec![0, 1, 2]);
+    ///
+    ///     assert_eq!(rx.recv_many(&mut buffer, 3).await, 2);
+    ///     assert_eq!(buffer, vec![0, 1, 2, 3, 4]);
+    /// }
+    /// ```
+    pub async fn recv_many(&mut self, buffer: &mut Vec<T>, limit: usize) -> usize {
+        use crate::future::poll_fn;
+        let start = buffer.len();
+        let count = poll_fn(|cx| self.chan.recv_many(cx, buffer, limit)).await;
+        self.forward_to_taps(&buffer[start..]);
+        count
+    }
+
+    /// Waits for the next value and returns a reference to it without
+    /// receiving it.
+    ///
+    /// The value stays at the head of the channel: it is not dequeued, no
+    /// permit is released to senders, and the next call to [`recv`] returns
+    /// the same value. This lets a consumer inspect the head-of-line message
+    /// to decide whether to consume it now, without buffering it outside the
+    /// channel and breaking [`Sender::capacity`] accounting.
+    ///
+    /// Returns `None` if the channel has been closed and there are no
+    /// remaining messages in the channel's buffer.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe: it never removes a value from the
+    /// channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::channel(4);
+    ///
+    ///     tx.send(1).await.unwrap();
+    ///
+    ///     assert_eq!(rx.peek().await, Some(&1));
+    ///     // Peeking does not consume the value.
+    ///     assert_eq!(rx.recv().await, Some(1));
+    /// }
+    /// ```
+    ///
+    /// [`recv`]: Receiver::recv
+    pub async fn peek(&mut self) -> Option<&T> {
+        use crate::future::poll_fn;
+
+        if poll_fn(|cx| self.chan.poll_peek_ready(cx)).await {
+            self.chan.peek_ref()
+        } else {
+            None
+        }
     }
 
     /// Blocking receive to call outside of asynchronous contexts.
@@ -240,6 +659,52 @@ impl<T> Receiver<T> {
         crate::future::block_on(self.recv())
     }
 
+    /// Blocking batched receive to call outside of asynchronous contexts.
+    ///
+    /// This is the blocking counterpart of [`recv_many`]: it extends
+    /// `buffer` with up to `limit` values, blocking until at least one value
+    /// is ready, and returns the number of values added. A return of `0`
+    /// with a non-zero `limit` means the channel has been closed and
+    /// drained. Compared to calling [`blocking_recv`] in a loop, the
+    /// blocking machinery is entered once per batch rather than once per
+    /// value.
+    ///
+    /// [`recv_many`]: Receiver::recv_many
+    /// [`blocking_recv`]: Receiver::blocking_recv
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called within an asynchronous execution
+    /// context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use tokio::runtime::Runtime;
+    /// use tokio::sync::mpsc;
+    ///
+    /// fn main() {
+    ///     let (tx, mut rx) = mpsc::channel::<u8>(10);
+    ///
+    ///     Runtime::new().unwrap().block_on(async {
+    ///         let _ = tx.send(10).await;
+    ///         let _ = tx.send(20).await;
+    ///     });
+    ///
+    ///     let sync_code = thread::spawn(move || {
+    ///         let mut buffer = Vec::new();
+    ///         assert_eq!(rx.blocking_recv_many(&mut buffer, 4), 2);
+    ///         assert_eq!(buffer, vec![10, 20]);
+    ///     });
+    ///     sync_code.join().unwrap()
+    /// }
+    /// ```
+    #[cfg(feature = "sync")]
+    pub fn blocking_recv_many(&mut self, buffer: &mut Vec<T>, limit: usize) -> usize {
+        crate::future::block_on(self.recv_many(buffer, limit))
+    }
+
     /// Closes the receiving half of a channel without dropping it.
     ///
     /// This prevents any further messages from being sent on the channel while
@@ -284,6 +749,78 @@ impl<T> Receiver<T> {
         self.chan.close();
     }
 
+    /// Closes the receiving half of a channel, recording a reason.
+    ///
+    /// This behaves exactly like [`close`], except that senders can retrieve
+    /// `reason` through [`Sender::close_reason`] after a send fails. This
+    /// lets producers distinguish a receiver that finished normally from one
+    /// that aborted due to an error, without carrying the reason on a side
+    /// channel.
+    ///
+    /// Calling this more than once keeps the first recorded reason.
+    ///
+    /// [`close`]: Receiver::close
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::channel::<i32>(8);
+    ///
+    ///     rx.close_with("disk full".to_string());
+    ///
+    ///     assert!(tx.send(1).await.is_err());
+    ///     assert_eq!(tx.close_reason::<String>().as_deref(), Some("disk full"));
+    /// }
+    /// ```
+    pub fn close_with<E>(&mut self, reason: E)
+    where
+        E: Send + Sync + 'static,
+    {
+        self.chan.close_with_reason(std::sync::Arc::new(reason));
+    }
+
+    /// Closes the receiving half of a channel and returns all buffered
+    /// messages.
+    ///
+    /// This is equivalent to calling [`close`] and then `try_recv` in a loop,
+    /// except that the buffered messages are collected in a single operation
+    /// and their permits are returned to the semaphore as one batch. This is
+    /// the method to reach for in shutdown paths that want to dispose of
+    /// whatever is still in flight.
+    ///
+    /// Messages sent through [`Permit`] or [`OwnedPermit`] values that are
+    /// outstanding when `drain` is called are **not** included in the returned
+    /// `Vec`. As with [`close`], call `recv()` until `None` is returned to
+    /// collect those stragglers.
+    ///
+    /// [`close`]: Receiver::close
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::channel(8);
+    ///
+    ///     tx.send(1).await.unwrap();
+    ///     tx.send(2).await.unwrap();
+    ///
+    ///     assert_eq!(rx.drain(), vec![1, 2]);
+    ///     assert!(tx.send(3).await.is_err());
+    /// }
+    /// ```
+    pub fn drain(&mut self) -> Vec<T> {
+        let drained = self.chan.drain();
+        self.forward_to_taps(&drained);
+        drained
+    }
+
     /// Polls to receive the next message on this channel.
     ///
     /// This method returns:
@@ -300,7 +837,13 @@ impl<T> Receiver<T> {
     /// `poll_recv`, only the `Waker` from the `Context` passed to the most
     /// recent call is scheduled to receive a wakeup.
     pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
-        self.chan.recv(cx)
+        match self.chan.recv(cx) {
+            Poll::Ready(Some(value)) => {
+                self.forward_to_taps(std::slice::from_ref(&value));
+                Poll::Ready(Some(value))
+            }
+            other => other,
+        }
     }
 
     /// Resizes the channel buffer to the provided size
@@ -308,7 +851,18 @@ impl<T> Receiver<T> {
     /// If the buffer is reduced to a smaller number of elements than it already
     /// contains, it is no longer possible to send elements. It will be possible
     /// to send new elements when the excess messages are consumed and there is
-    /// capacity available with respect to the new size.
+    /// capacity available with respect to the new size. Use
+    /// [`resize_with_policy`] to control what happens to values sent while the
+    /// channel is over capacity; this method resets the policy to
+    /// [`OverflowPolicy::Drain`].
+    ///
+    /// [`resize_with_policy`]: Receiver::resize_with_policy
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_capacity` is zero, if the channel is a rendezvous
+    /// channel, or if the channel was created with a small buffer and
+    /// `new_capacity` exceeds the inline ring size.
     ///
     /// # Examples
     ///
@@ -329,8 +883,163 @@ impl<T> Receiver<T> {
     /// }
     /// ```
     pub fn resize(&self, new_capacity: usize) {
+        self.resize_with_policy(new_capacity, OverflowPolicy::Drain)
+    }
+
+    /// Resizes the channel buffer, selecting the policy applied to sends
+    /// while the channel is over capacity.
+    ///
+    /// When `new_capacity` is smaller than the number of messages currently
+    /// in flight, the channel is over capacity until the excess has been
+    /// received. The `policy` decides what happens to values sent in the
+    /// meantime: with [`OverflowPolicy::Drain`] senders wait as usual, while
+    /// [`OverflowPolicy::DropNewest`] and [`OverflowPolicy::Callback`] shed
+    /// arriving values so senders are never blocked on the shrink.
+    ///
+    /// The policy stays in effect until the next resize.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_capacity` is zero, if the channel is a rendezvous
+    /// channel, or if the channel was created with a small buffer and
+    /// `new_capacity` exceeds the inline ring size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    /// use tokio::sync::mpsc::OverflowPolicy;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::channel(2);
+    ///
+    ///     tx.send(1).await.unwrap();
+    ///     tx.send(2).await.unwrap();
+    ///
+    ///     // Shrink below the two in-flight messages; values sent while the
+    ///     // channel drains are dropped rather than blocking the sender.
+    ///     rx.resize_with_policy(1, OverflowPolicy::DropNewest);
+    ///     tx.send(3).await.unwrap();
+    ///
+    ///     assert_eq!(rx.recv().await, Some(1));
+    ///     assert_eq!(rx.recv().await, Some(2));
+    /// }
+    /// ```
+    pub fn resize_with_policy(&self, new_capacity: usize, policy: OverflowPolicy<T>) {
+        use chan::Semaphore as _;
+
         assert!(new_capacity > 0, "mpsc bounded channel requires buffer > 0");
-        self.chan.resize(new_capacity)
+        assert!(
+            !self.chan.semaphore().is_rendezvous(),
+            "cannot resize a rendezvous channel"
+        );
+        // The cost of buffered values was clamped against the old budget;
+        // changing it would break the send/receive permit symmetry.
+        assert!(
+            !self.chan.is_cost_based(),
+            "cannot resize a cost-based channel"
+        );
+        self.chan.resize(new_capacity, policy)
+    }
+
+    /// Sets the channel's backpressure limit to `new_capacity`.
+    ///
+    /// This is equivalent to [`resize`] and exists so the intent reads
+    /// naturally next to [`Sender::max_capacity`], which reflects the new
+    /// limit. Senders blocked in [`Sender::send`] or [`Sender::reserve`]
+    /// when the channel grows are woken as the new capacity becomes
+    /// available; when it shrinks, they stay blocked until the in-flight
+    /// excess has been received.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `new_capacity` is zero, if the channel is a rendezvous
+    /// channel, or if the channel was created with a small buffer and
+    /// `new_capacity` exceeds the inline ring size.
+    ///
+    /// [`resize`]: Receiver::resize
+    /// [`Sender::send`]: Sender::send
+    /// [`Sender::reserve`]: Sender::reserve
+    pub fn set_capacity(&self, new_capacity: usize) {
+        self.resize(new_capacity);
+    }
+
+    /// Returns point-in-time statistics for the channel.
+    ///
+    /// See [`Sender::stats`] for details.
+    pub fn stats(&self) -> ChannelStats {
+        channel_stats(self.chan.semaphore())
+    }
+
+    /// Attaches an observer to this receiver.
+    ///
+    /// The returned [`TapReceiver`] is handed a clone of every value
+    /// subsequently consumed through [`recv`], [`recv_many`] or
+    /// [`poll_recv`]. Values already buffered in the channel are not
+    /// replayed, and [`peek`] does not feed taps since it leaves the value in
+    /// the channel.
+    ///
+    /// Taps are intended for attaching metrics or debugging probes to an
+    /// existing channel without restructuring it as a [`broadcast`] channel:
+    /// the channel's backpressure is unaffected because taps buffer their
+    /// copies on the side rather than holding channel capacity. A dropped
+    /// tap is detached on the next receive; dropping this `Receiver` closes
+    /// all of its taps once they drain.
+    ///
+    /// Multiple taps may be attached; each gets its own clone.
+    ///
+    /// [`recv`]: Receiver::recv
+    /// [`recv_many`]: Receiver::recv_many
+    /// [`poll_recv`]: Receiver::poll_recv
+    /// [`peek`]: Receiver::peek
+    /// [`broadcast`]: crate::sync::broadcast
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::channel(8);
+    ///     let mut tap = rx.tap();
+    ///
+    ///     tx.send(1).await.unwrap();
+    ///     tx.send(2).await.unwrap();
+    ///     drop(tx);
+    ///
+    ///     assert_eq!(rx.recv().await, Some(1));
+    ///     assert_eq!(rx.recv().await, Some(2));
+    ///     assert_eq!(rx.recv().await, None);
+    ///
+    ///     assert_eq!(tap.recv().await, Some(1));
+    ///     assert_eq!(tap.recv().await, Some(2));
+    ///     drop(rx);
+    ///     assert_eq!(tap.recv().await, None);
+    /// }
+    /// ```
+    pub fn tap(&mut self) -> TapReceiver<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        let (tap_tx, tap_rx) = super::unbounded_channel();
+
+        self.taps
+            .push(Box::new(move |value: &T| tap_tx.send(value.clone()).is_ok()));
+
+        TapReceiver { rx: tap_rx }
+    }
+
+    /// Hands `values` to every attached tap, detaching taps whose receiver
+    /// has been dropped.
+    fn forward_to_taps(&mut self, values: &[T]) {
+        if self.taps.is_empty() {
+            return;
+        }
+
+        self.taps
+            .retain(|tap| values.iter().all(|value| tap(value)));
     }
 }
 
@@ -344,9 +1053,41 @@ impl<T> fmt::Debug for Receiver<T> {
 
 impl<T> Unpin for Receiver<T> {}
 
+impl<T> TapReceiver<T> {
+    /// Receives the next observed value.
+    ///
+    /// Returns `None` once the tapped [`Receiver`] has been dropped and all
+    /// values observed before that have been received.
+    pub async fn recv(&mut self) -> Option<T> {
+        self.rx.recv().await
+    }
+
+    /// Polls to receive the next observed value.
+    ///
+    /// This method returns:
+    ///
+    ///  * `Poll::Pending` if no observed values are available but the tapped
+    ///    [`Receiver`] is still alive.
+    ///  * `Poll::Ready(Some(value))` if an observed value is available.
+    ///  * `Poll::Ready(None)` if the tapped [`Receiver`] has been dropped and
+    ///    all observed values have been received.
+    pub fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl<T> fmt::Debug for TapReceiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TapReceiver").field("rx", &self.rx).finish()
+    }
+}
+
 impl<T> Sender<T> {
     pub(crate) fn new(chan: chan::Tx<T, Semaphore>) -> Sender<T> {
-        Sender { chan }
+        Sender {
+            chan,
+            reserved: false,
+        }
     }
 
     /// Sends a value, waiting until there is capacity.
@@ -395,9 +1136,19 @@ impl<T> Sender<T> {
     /// }
     /// ```
     pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
-        match self.reserve().await {
-            Ok(permit) => {
-                permit.send(value);
+        let value = match self.chan.shed_overflow(value) {
+            Some(value) => value,
+            None => return Ok(()),
+        };
+
+        // On a cost-based channel the value may occupy more than one permit;
+        // acquiring them in one operation keeps competing senders from
+        // deadlocking on partially acquired budgets.
+        let permits = self.chan.charge(&value).min(u32::MAX as usize) as u32;
+
+        match self.chan.semaphore().0.acquire(permits).await {
+            Ok(()) => {
+                self.chan.send(value);
                 Ok(())
             }
             Err(_) => Err(SendError(value)),
@@ -498,7 +1249,14 @@ impl<T> Sender<T> {
     /// }
     /// ```
     pub fn try_send(&self, message: T) -> Result<(), TrySendError<T>> {
-        match self.chan.semaphore().0.try_acquire(1) {
+        let message = match self.chan.shed_overflow(message) {
+            Some(message) => message,
+            None => return Ok(()),
+        };
+
+        let permits = self.chan.charge(&message).min(u32::MAX as usize) as u32;
+
+        match self.chan.semaphore().0.try_acquire(permits) {
             Ok(_) => {}
             Err(TryAcquireError::Closed) => return Err(TrySendError::Closed(message)),
             Err(TryAcquireError::NoPermits) => return Err(TrySendError::Full(message)),
@@ -561,18 +1319,46 @@ impl<T> Sender<T> {
         value: T,
         timeout: Duration,
     ) -> Result<(), SendTimeoutError<T>> {
-        let permit = match crate::time::timeout(timeout, self.reserve()).await {
-            Err(_) => {
-                return Err(SendTimeoutError::Timeout(value));
+        use std::future::Future;
+        use std::task::Poll;
+
+        let value = match self.chan.shed_overflow(value) {
+            Some(value) => value,
+            None => return Ok(()),
+        };
+
+        // The deadline is woven into the permit wait itself rather than
+        // wrapping it in `time::timeout`: on expiry the waiter is cancelled
+        // and unlinked from the semaphore's queue in the same poll, and the
+        // value is handed back in the error instead of being lost to a
+        // dropped future.
+        let permits = self.chan.charge(&value).min(u32::MAX as usize) as u32;
+        let acquire = self.chan.semaphore().0.acquire(permits);
+        let delay = crate::time::sleep(timeout);
+        crate::pin!(acquire, delay);
+
+        let acquired = crate::future::poll_fn(|cx| {
+            if let Poll::Ready(result) = acquire.as_mut().poll(cx) {
+                return Poll::Ready(Some(result));
             }
-            Ok(Err(_)) => {
-                return Err(SendTimeoutError::Closed(value));
+
+            if delay.as_mut().poll(cx).is_ready() {
+                acquire.as_mut().cancel();
+                return Poll::Ready(None);
             }
-            Ok(Ok(permit)) => permit,
-        };
 
-        permit.send(value);
-        Ok(())
+            Poll::Pending
+        })
+        .await;
+
+        match acquired {
+            None => Err(SendTimeoutError::Timeout(value)),
+            Some(Err(_)) => Err(SendTimeoutError::Closed(value)),
+            Some(Ok(())) => {
+                self.chan.send(value);
+                Ok(())
+            }
+        }
     }
 
     /// Blocking send to call outside of asynchronous contexts.
@@ -613,6 +1399,59 @@ impl<T> Sender<T> {
         crate::future::block_on(self.send(value))
     }
 
+    /// Blocking batched send to call outside of asynchronous contexts.
+    ///
+    /// Sends every value yielded by `values` in order, waiting for capacity
+    /// as needed. Compared to calling [`blocking_send`] in a loop, the
+    /// blocking machinery is entered once for the whole batch rather than
+    /// once per value.
+    ///
+    /// If the channel closes partway through, the error carries the value
+    /// whose send failed and the rest of the iterator is dropped.
+    ///
+    /// [`blocking_send`]: Sender::blocking_send
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called within an asynchronous execution
+    /// context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use tokio::runtime::Runtime;
+    /// use tokio::sync::mpsc;
+    ///
+    /// fn main() {
+    ///     let (tx, mut rx) = mpsc::channel::<u8>(2);
+    ///
+    ///     let sync_code = thread::spawn(move || {
+    ///         tx.blocking_send_all(vec![1, 2, 3, 4]).unwrap();
+    ///     });
+    ///
+    ///     Runtime::new().unwrap().block_on(async move {
+    ///         for i in 1..=4 {
+    ///             assert_eq!(rx.recv().await, Some(i));
+    ///         }
+    ///     });
+    ///     sync_code.join().unwrap()
+    /// }
+    /// ```
+    #[cfg(feature = "sync")]
+    pub fn blocking_send_all<I>(&self, values: I) -> Result<(), SendError<T>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        crate::future::block_on(async {
+            for value in values {
+                self.send(value).await?;
+            }
+
+            Ok(())
+        })
+    }
+
     /// Checks if the channel has been closed. This happens when the
     /// [`Receiver`] is dropped, or when the [`Receiver::close`] method is
     /// called.
@@ -635,6 +1474,25 @@ impl<T> Sender<T> {
         self.chan.is_closed()
     }
 
+    /// Returns the reason the receiver closed the channel, if one was
+    /// recorded with [`Receiver::close_with`].
+    ///
+    /// Returns `None` if the channel is still open, was closed without a
+    /// reason, or if the recorded reason is not of type `E`. Typically
+    /// called after a [`send`] fails to decide whether the failure is
+    /// expected shutdown or an error worth propagating.
+    ///
+    /// [`Receiver::close_with`]: Receiver::close_with
+    /// [`send`]: Sender::send
+    pub fn close_reason<E>(&self) -> Option<E>
+    where
+        E: Clone + Send + Sync + 'static,
+    {
+        self.chan
+            .close_reason()
+            .and_then(|reason| reason.downcast_ref::<E>().cloned())
+    }
+
     /// Wait for channel capacity. Once capacity to send one message is
     /// available, it is reserved for the caller.
     ///
@@ -755,17 +1613,42 @@ impl<T> Sender<T> {
     pub async fn reserve_owned(self) -> Result<OwnedPermit<T>, SendError<()>> {
         self.reserve_inner().await?;
         Ok(OwnedPermit {
-            chan: Some(self.chan),
+            chan: Some(self.into_tx()),
         })
     }
 
+    /// Consumes the `Sender`, returning its channel handle.
+    ///
+    /// Releases a `poll_reserve` reservation if one is held; `Drop` would do
+    /// the same, this just does it before the handle is extracted.
+    fn into_tx(mut self) -> chan::Tx<T, Semaphore> {
+        self.abort_reserve();
+
+        // `Sender` implements `Drop`, so the field cannot be moved out
+        // directly. Cloning bumps the sender count, and dropping `self`
+        // right after brings it back down.
+        self.chan.clone()
+    }
+
     async fn reserve_inner(&self) -> Result<(), SendError<()>> {
+        self.assert_not_cost_based();
+
         match self.chan.semaphore().0.acquire(1).await {
             Ok(_) => Ok(()),
             Err(_) => Err(SendError(())),
         }
     }
 
+    /// A permit stands for exactly one permit's worth of capacity, but on a
+    /// cost-based channel the value eventually sent through it occupies
+    /// `cost(&value)` permits, which the semaphore accounting cannot support.
+    fn assert_not_cost_based(&self) {
+        assert!(
+            !self.chan.is_cost_based(),
+            "cannot reserve capacity on a cost-based channel"
+        );
+    }
+
     /// Try to acquire a slot in the channel without waiting for the slot to become
     /// available.
     ///
@@ -810,6 +1693,8 @@ impl<T> Sender<T> {
     /// }
     /// ```
     pub fn try_reserve(&self) -> Result<Permit<'_, T>, TrySendError<()>> {
+        self.assert_not_cost_based();
+
         match self.chan.semaphore().0.try_acquire(1) {
             Ok(_) => {}
             Err(_) => return Err(TrySendError::Full(())),
@@ -874,13 +1759,215 @@ impl<T> Sender<T> {
     /// }
     /// ```
     pub fn try_reserve_owned(self) -> Result<OwnedPermit<T>, TrySendError<Self>> {
+        self.assert_not_cost_based();
+
         match self.chan.semaphore().0.try_acquire(1) {
             Ok(_) => {}
             Err(_) => return Err(TrySendError::Full(self)),
         }
 
         Ok(OwnedPermit {
-            chan: Some(self.chan),
+            chan: Some(self.into_tx()),
+        })
+    }
+
+    /// Polls to reserve capacity to send one message.
+    ///
+    /// This method returns:
+    ///
+    ///  * `Poll::Ready(Ok(()))` once capacity for one message is reserved by
+    ///    this handle.
+    ///  * `Poll::Ready(Err(_))` if the channel is closed.
+    ///  * `Poll::Pending` if the channel is full. The task is woken when
+    ///    capacity frees up.
+    ///
+    /// The reservation is held by this `Sender` handle (it is not carried
+    /// over by [`clone`]) until it is consumed by [`send_reserved`] or given
+    /// up with [`abort_reserve`]; further calls to `poll_reserve` return
+    /// `Ready(Ok(()))` immediately while it is held. This makes the method
+    /// usable from manual `Future` or `Sink` implementations, which cannot
+    /// store a [`reserve`] future between polls: the readiness protocol is
+    /// the usual `poll_reserve` / `send_reserved` pair, with no allocation
+    /// per poll.
+    ///
+    /// Unlike a task waiting in [`reserve`], a task parked in `poll_reserve`
+    /// holds no place in the channel's capacity queue: it is woken when
+    /// capacity becomes available and races other senders for it, though
+    /// waiting `reserve` callers are always served first.
+    ///
+    /// [`clone`]: Sender::clone
+    /// [`reserve`]: Sender::reserve
+    /// [`send_reserved`]: Sender::send_reserved
+    /// [`abort_reserve`]: Sender::abort_reserve
+    pub fn poll_reserve(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), SendError<()>>> {
+        self.assert_not_cost_based();
+
+        if self.reserved {
+            return Poll::Ready(Ok(()));
+        }
+
+        match self.chan.semaphore().0.poll_try_acquire(1, cx) {
+            Poll::Ready(Ok(())) => {
+                self.reserved = true;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(_)) => Poll::Ready(Err(SendError(()))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    /// Sends a value using capacity reserved by [`poll_reserve`].
+    ///
+    /// Like [`Permit::send`], the send completes immediately and succeeds
+    /// even if the receiver half has since been closed, so a clean shutdown
+    /// can drain in-flight messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns the value back if this handle holds no reservation, i.e.
+    /// [`poll_reserve`] has not returned `Ready(Ok(()))` since the last
+    /// `send_reserved` or [`abort_reserve`].
+    ///
+    /// [`poll_reserve`]: Sender::poll_reserve
+    /// [`abort_reserve`]: Sender::abort_reserve
+    /// [`Permit::send`]: Permit::send
+    pub fn send_reserved(&mut self, value: T) -> Result<(), SendError<T>> {
+        if !self.reserved {
+            return Err(SendError(value));
+        }
+
+        self.reserved = false;
+        self.chan.send(value);
+        Ok(())
+    }
+
+    /// Releases capacity reserved by [`poll_reserve`] without sending.
+    ///
+    /// Returns `true` if a reservation was held. Dropping the `Sender`
+    /// releases the reservation the same way.
+    ///
+    /// [`poll_reserve`]: Sender::poll_reserve
+    pub fn abort_reserve(&mut self) -> bool {
+        use chan::Semaphore;
+
+        if !self.reserved {
+            return false;
+        }
+
+        self.reserved = false;
+
+        let semaphore = self.chan.semaphore();
+        semaphore.add_permits(1);
+
+        // Mirrors dropping a `Permit`: if the channel is closed and this was
+        // the last outstanding reservation, let the receiver observe the
+        // closure.
+        if semaphore.is_closed() && semaphore.is_idle() {
+            self.chan.wake_rx();
+        }
+
+        true
+    }
+
+    /// Waits for capacity to send `n` messages and reserves it atomically.
+    ///
+    /// The `n` slots are acquired in a single semaphore operation: the batch
+    /// either succeeds as a whole or continues waiting, so two tasks batch
+    /// reserving against the same channel cannot deadlock by each holding
+    /// part of the other's batch. The returned iterator yields `n`
+    /// [`OwnedPermit`]s; permits never taken from the iterator are released
+    /// back to the channel when it is dropped.
+    ///
+    /// Requesting more permits than the channel's capacity waits until a
+    /// [`Receiver::resize`] makes the batch satisfiable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the receive half of the channel is closed, either
+    /// by [`Receiver::close`] or the [`Receiver`] handle being dropped.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe: if dropped before completion, no capacity
+    /// is held.
+    ///
+    /// [`Receiver::close`]: Receiver::close
+    /// [`Receiver::resize`]: Receiver::resize
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::channel(4);
+    ///
+    ///     let permits = tx.reserve_many(3).await.unwrap();
+    ///     for (i, permit) in permits.enumerate() {
+    ///         permit.send(i);
+    ///     }
+    ///
+    ///     assert_eq!(rx.recv().await, Some(0));
+    ///     assert_eq!(rx.recv().await, Some(1));
+    ///     assert_eq!(rx.recv().await, Some(2));
+    /// }
+    /// ```
+    pub async fn reserve_many(&self, n: u32) -> Result<OwnedPermitIterator<T>, SendError<()>> {
+        self.assert_not_cost_based();
+
+        match self.chan.semaphore().0.acquire(n).await {
+            Ok(_) => {}
+            Err(_) => return Err(SendError(())),
+        }
+
+        Ok(OwnedPermitIterator {
+            chan: self.chan.clone(),
+            remaining: n,
+        })
+    }
+
+    /// Tries to reserve capacity to send `n` messages without waiting.
+    ///
+    /// Like [`reserve_many`] the slots are acquired atomically: either all
+    /// `n` are reserved or the channel is left untouched and
+    /// [`TrySendError::Full`] is returned.
+    ///
+    /// [`reserve_many`]: Sender::reserve_many
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::channel(4);
+    ///
+    ///     // Half the batch being available is not enough.
+    ///     let partial = tx.reserve_many(2).await.unwrap();
+    ///     assert!(tx.try_reserve_many(3).is_err());
+    ///     drop(partial);
+    ///
+    ///     let permits = tx.try_reserve_many(3).unwrap();
+    ///     for (i, permit) in permits.enumerate() {
+    ///         permit.send(i);
+    ///     }
+    ///
+    ///     assert_eq!(rx.recv().await, Some(0));
+    /// }
+    /// ```
+    pub fn try_reserve_many(&self, n: u32) -> Result<OwnedPermitIterator<T>, TrySendError<()>> {
+        self.assert_not_cost_based();
+
+        match self.chan.semaphore().0.try_acquire(n) {
+            Ok(_) => {}
+            Err(_) => return Err(TrySendError::Full(())),
+        }
+
+        Ok(OwnedPermitIterator {
+            chan: self.chan.clone(),
+            remaining: n,
         })
     }
 
@@ -932,16 +2019,109 @@ impl<T> Sender<T> {
     pub fn capacity(&self) -> usize {
         self.chan.semaphore().0.available_permits()
     }
+
+    /// Returns the channel's total capacity.
+    ///
+    /// Unlike [`capacity`], this does not change as messages are sent and
+    /// received; it only changes when the receiver adjusts the limit with
+    /// [`Receiver::set_capacity`] or [`Receiver::resize`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, rx) = mpsc::channel::<()>(16);
+    ///
+    ///     let _permit = tx.reserve().await.unwrap();
+    ///     assert_eq!(tx.capacity(), 15);
+    ///     assert_eq!(tx.max_capacity(), 16);
+    ///
+    ///     rx.set_capacity(32);
+    ///     assert_eq!(tx.max_capacity(), 32);
+    /// }
+    /// ```
+    ///
+    /// [`capacity`]: Sender::capacity
+    pub fn max_capacity(&self) -> usize {
+        use chan::Semaphore;
+
+        self.chan.semaphore().cap()
+    }
+
+    /// Returns point-in-time statistics for the channel.
+    ///
+    /// This is primarily useful for diagnosing the effect of a
+    /// [`Receiver::resize`]: a non-zero [`permit_deficit`] explains senders
+    /// that stay blocked even though the receiver is consuming messages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, rx) = mpsc::channel(2);
+    ///
+    ///     tx.send(1).await.unwrap();
+    ///     tx.send(2).await.unwrap();
+    ///     rx.resize(1);
+    ///
+    ///     let stats = tx.stats();
+    ///     assert_eq!(stats.capacity, 1);
+    ///     assert_eq!(stats.available, 0);
+    ///     assert_eq!(stats.permit_deficit, 1);
+    /// }
+    /// ```
+    ///
+    /// [`permit_deficit`]: ChannelStats::permit_deficit
+    pub fn stats(&self) -> ChannelStats {
+        channel_stats(self.chan.semaphore())
+    }
+
+    /// Converts this `Sender` into a [`WeakSender`] that does not count
+    /// towards RAII semantics, i.e. if all `Sender` instances of the channel
+    /// were dropped and only `WeakSender` instances remain, the channel is
+    /// closed.
+    pub fn downgrade(&self) -> WeakSender<T> {
+        WeakSender {
+            chan: self.chan.downgrade(),
+        }
+    }
+
+    /// Returns the number of [`Sender`] handles.
+    pub fn strong_count(&self) -> usize {
+        self.chan.strong_count()
+    }
+
+    /// Returns the number of [`WeakSender`] handles.
+    pub fn weak_count(&self) -> usize {
+        self.chan.weak_count()
+    }
 }
 
 impl<T> Clone for Sender<T> {
     fn clone(&self) -> Self {
         Sender {
             chan: self.chan.clone(),
+            // A permit reserved with `poll_reserve` stays with the handle
+            // that reserved it.
+            reserved: false,
         }
     }
 }
 
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        // Release a permit reserved with `poll_reserve` that was never
+        // consumed, exactly as dropping a `Permit` would.
+        self.abort_reserve();
+    }
+}
+
 impl<T> fmt::Debug for Sender<T> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("Sender")
@@ -950,6 +2130,85 @@ impl<T> fmt::Debug for Sender<T> {
     }
 }
 
+// ===== impl WeakSender =====
+
+impl<T> WeakSender<T> {
+    /// Tries to convert the `WeakSender` into a [`Sender`].
+    ///
+    /// This returns `Some` while at least one strong [`Sender`] handle is
+    /// still alive, and `None` once they have all been dropped; a closed send
+    /// half can never be reopened.
+    pub fn upgrade(&self) -> Option<Sender<T>> {
+        self.chan.upgrade().map(Sender::new)
+    }
+
+    /// Returns the number of [`Sender`] handles.
+    pub fn strong_count(&self) -> usize {
+        self.chan.strong_count()
+    }
+
+    /// Returns the number of [`WeakSender`] handles.
+    pub fn weak_count(&self) -> usize {
+        self.chan.weak_count()
+    }
+
+    /// Completes when the last [`Sender`] handle is dropped.
+    ///
+    /// This lets a background task holding only a `WeakSender` exit when the
+    /// send half of the channel closes, instead of polling [`upgrade`]
+    /// periodically. If no strong sender exists when this is called, it
+    /// completes immediately.
+    ///
+    /// Note that this tracks the *send* half: it does not complete when the
+    /// [`Receiver`] is dropped while strong senders remain.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. Once the last strong sender is dropped the
+    /// send half stays closed, so calling it again completes immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::channel::<i32>(8);
+    ///     let weak_tx = tx.downgrade();
+    ///
+    ///     let watcher = tokio::spawn(async move {
+    ///         weak_tx.closed().await;
+    ///     });
+    ///
+    ///     tx.send(1).await.unwrap();
+    ///     assert_eq!(rx.recv().await, Some(1));
+    ///
+    ///     drop(tx);
+    ///     watcher.await.unwrap();
+    /// }
+    /// ```
+    ///
+    /// [`upgrade`]: WeakSender::upgrade
+    pub async fn closed(&self) {
+        self.chan.closed().await
+    }
+}
+
+impl<T> Clone for WeakSender<T> {
+    fn clone(&self) -> Self {
+        WeakSender {
+            chan: self.chan.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for WeakSender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("WeakSender").finish()
+    }
+}
+
 // ===== impl Permit =====
 
 impl<T> Permit<'_, T> {
@@ -1063,7 +2322,7 @@ impl<T> OwnedPermit<T> {
         });
         chan.send(value);
 
-        Sender { chan }
+        Sender::new(chan)
     }
 
     /// Release the reserved capacity *without* sending a message, returning the
@@ -1105,7 +2364,7 @@ impl<T> OwnedPermit<T> {
 
         // Add the permit back to the semaphore
         chan.semaphore().add_permits(1);
-        Sender { chan }
+        Sender::new(chan)
     }
 }
 
@@ -1139,3 +2398,62 @@ impl<T> fmt::Debug for OwnedPermit<T> {
             .finish()
     }
 }
+
+// ===== impl OwnedPermitIterator =====
+
+impl<T> Iterator for OwnedPermitIterator<T> {
+    type Item = OwnedPermit<T>;
+
+    fn next(&mut self) -> Option<OwnedPermit<T>> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        // Transfer one of the iterator's permits to the new `OwnedPermit`,
+        // along with its own sender handle so the permit is `'static`.
+        self.remaining -= 1;
+
+        Some(OwnedPermit {
+            chan: Some(self.chan.clone()),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for OwnedPermitIterator<T> {}
+
+impl<T> Drop for OwnedPermitIterator<T> {
+    fn drop(&mut self) {
+        use chan::Semaphore;
+
+        if self.remaining > 0 {
+            // Release the permits that were never handed out.
+            self.chan.semaphore().add_permits(self.remaining as usize);
+        }
+    }
+}
+
+impl<T> fmt::Debug for OwnedPermitIterator<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("OwnedPermitIterator")
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+cfg_stream! {
+    impl<T> futures_core::Stream for Receiver<T> {
+        type Item = T;
+
+        fn poll_next(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<T>> {
+            self.poll_recv(cx)
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use crate::loom::cell::UnsafeCell;
 use crate::loom::future::AtomicWaker;
 use crate::loom::sync::atomic::AtomicUsize;
-use crate::loom::sync::Arc;
-use crate::sync::mpsc::list;
+use crate::loom::sync::{Arc, Mutex};
+use crate::sync::mpsc::bounded::OverflowPolicy;
+use crate::sync::mpsc::ring::Ring;
+use crate::sync::mpsc::{block, list};
 use crate::sync::notify::Notify;
 
+use std::any::Any;
 use std::fmt;
 use std::process;
-use std::sync::atomic::Ordering::{AcqRel, Relaxed};
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed};
 use std::task::Poll::{Pending, Ready};
 use std::task::{Context, Poll};
 
@@ -22,6 +25,20 @@ impl<T, S: fmt::Debug> fmt::Debug for Tx<T, S> {
     }
 }
 
+/// A sender handle that does not keep the send half open.
+///
+/// Holding a `WeakTx` does not count towards `tx_count`; the channel closes
+/// when the last [`Tx`] is dropped even if weak handles remain.
+pub(crate) struct WeakTx<T, S> {
+    inner: Arc<Chan<T, S>>,
+}
+
+impl<T, S: fmt::Debug> fmt::Debug for WeakTx<T, S> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("WeakTx").field("inner", &self.inner).finish()
+    }
+}
+
 /// Channel receiver
 pub(crate) struct Rx<T, S: Semaphore> {
     inner: Arc<Chan<T, S>>,
@@ -36,6 +53,10 @@ impl<T, S: Semaphore + fmt::Debug> fmt::Debug for Rx<T, S> {
 pub(crate) trait Semaphore {
     fn is_idle(&self) -> bool;
 
+    /// The number of messages currently buffered in the channel.
+    #[cfg(all(tokio_unstable, feature = "tracing"))]
+    fn len(&self) -> usize;
+
     fn add_permits(&self, addition: usize);
 
     fn reduce_permits(&self, reduction: usize);
@@ -47,14 +68,29 @@ pub(crate) trait Semaphore {
     fn cap(&self) -> usize;
 
     fn set_cap(&self, new_capacity: usize);
+
+    /// `true` for a zero-capacity (rendezvous) channel.
+    ///
+    /// Such a channel has no standing capacity: permits are granted one at a
+    /// time by a parked receiver and are not returned when a message is
+    /// received.
+    fn is_rendezvous(&self) -> bool;
+
+    /// The number of granted but unclaimed permits in the semaphore. Only
+    /// meaningful for rendezvous channels.
+    fn unclaimed_permits(&self) -> usize;
+
+    /// The number of permits the channel is short after a shrink; zero when
+    /// the channel is not over capacity.
+    fn deficit(&self) -> usize;
 }
 
 struct Chan<T, S> {
     /// Notifies all tasks listening for the receiver being dropped
     notify_rx_closed: Notify,
 
-    /// Handle to the push half of the lock-free list.
-    tx: list::Tx<T>,
+    /// Push half of the message queue.
+    tx: SendQueue<T>,
 
     /// Coordinates access to channel's capacity.
     semaphore: S,
@@ -67,8 +103,34 @@ struct Chan<T, S> {
     /// When this drops to zero, the send half of the channel is closed.
     tx_count: AtomicUsize,
 
+    /// Tracks the number of outstanding weak sender handles. Purely
+    /// informational; weak handles do not keep the send half open.
+    tx_weak_count: AtomicUsize,
+
+    /// Notifies weak sender handles when the last strong sender is dropped.
+    notify_tx_closed: Notify,
+
+    /// Policy applied to sends while the channel is over capacity after a
+    /// shrink. Only consulted when the semaphore reports a deficit.
+    overflow_policy: Mutex<OverflowPolicy<T>>,
+
+    /// Cost function for channels created with `channel_with_cost`.
+    ///
+    /// When set, each value occupies `cost(&value)` permits instead of one,
+    /// so the channel bound is a budget rather than a message count.
+    cost: Option<std::sync::Arc<dyn Fn(&T) -> usize + Send + Sync>>,
+
+    /// Reason recorded by `Rx::close_with_reason`, if any. Type-erased so the
+    /// channel does not carry the reason type as a parameter; senders
+    /// downcast when reading it back.
+    close_reason: Mutex<Option<std::sync::Arc<dyn Any + Send + Sync>>>,
+
     /// Only accessed by `Rx` handle.
     rx_fields: UnsafeCell<RxFields<T>>,
+
+    /// Identifies this channel in the events it emits.
+    #[cfg(all(tokio_unstable, feature = "tracing"))]
+    resource_id: u64,
 }
 
 impl<T, S> fmt::Debug for Chan<T, S>
@@ -86,19 +148,86 @@ where
     }
 }
 
+/// Push half of the message queue.
+///
+/// Small bounded channels store messages in a fixed inline ring; all other
+/// channels use the general linked list of blocks. Selected at construction.
+enum SendQueue<T> {
+    List(list::Tx<T>),
+    Ring(Ring<T>),
+}
+
+impl<T> SendQueue<T> {
+    /// Pushes a value. The caller must hold channel capacity.
+    fn push(&self, value: T) {
+        match self {
+            SendQueue::List(tx) => tx.push(value),
+            SendQueue::Ring(ring) => ring.push(value),
+        }
+    }
+
+    /// Marks the queue as closed. No values may be pushed afterwards.
+    fn close(&self) {
+        match self {
+            SendQueue::List(tx) => tx.close(),
+            SendQueue::Ring(ring) => ring.close(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for SendQueue<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendQueue::List(tx) => tx.fmt(fmt),
+            SendQueue::Ring(_) => fmt.debug_struct("Ring").finish(),
+        }
+    }
+}
+
+/// Pop half of the message queue. Matches the `SendQueue` variant.
+enum RecvQueue<T> {
+    List(list::Rx<T>),
+
+    /// The receiver's cursor into the ring stored in the send half.
+    Ring { head: usize },
+}
+
+impl<T> RecvQueue<T> {
+    fn pop(&mut self, tx: &SendQueue<T>) -> Option<block::Read<T>> {
+        match (self, tx) {
+            (RecvQueue::List(rx), SendQueue::List(tx)) => rx.pop(tx),
+            (RecvQueue::Ring { head }, SendQueue::Ring(ring)) => ring.pop(head),
+            // The two halves are constructed in lockstep.
+            _ => unreachable!(),
+        }
+    }
+
+    fn peek<'a>(&'a mut self, tx: &'a SendQueue<T>) -> Option<block::Read<&'a T>> {
+        match (self, tx) {
+            (RecvQueue::List(rx), SendQueue::List(tx)) => rx.peek(tx),
+            (RecvQueue::Ring { head }, SendQueue::Ring(ring)) => ring.peek(*head),
+            // The two halves are constructed in lockstep.
+            _ => unreachable!(),
+        }
+    }
+}
+
 /// Fields only accessed by `Rx` handle.
 struct RxFields<T> {
     /// Channel receiver. This field is only accessed by the `Receiver` type.
-    list: list::Rx<T>,
+    list: RecvQueue<T>,
 
     /// `true` if `Rx::close` is called.
     rx_closed: bool,
+
+    /// `true` while a rendezvous channel has a permit granted on behalf of a
+    /// parked receiver whose matching value has not yet been received.
+    rendezvous_grant: bool,
 }
 
 impl<T> fmt::Debug for RxFields<T> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("RxFields")
-            .field("list", &self.list)
             .field("rx_closed", &self.rx_closed)
             .finish()
     }
@@ -108,18 +237,66 @@ unsafe impl<T: Send, S: Send> Send for Chan<T, S> {}
 unsafe impl<T: Send, S: Sync> Sync for Chan<T, S> {}
 
 pub(crate) fn channel<T, S: Semaphore>(semaphore: S) -> (Tx<T, S>, Rx<T, S>) {
-    let (tx, rx) = list::channel();
+    channel_with_block_size(semaphore, super::BLOCK_CAP, None)
+}
+
+pub(crate) fn channel_with_block_size<T, S: Semaphore>(
+    semaphore: S,
+    block_size: usize,
+    pool: Option<super::BlockPool>,
+) -> (Tx<T, S>, Rx<T, S>) {
+    let (tx, rx) = list::channel_with_block_size(block_size, pool);
+
+    make_channel(semaphore, SendQueue::List(tx), RecvQueue::List(rx), None)
+}
+
+/// Creates a channel backed by the fixed inline ring instead of the block
+/// list. Only valid for bounded channels with a capacity of at most
+/// [`super::ring::MAX_INLINE_CAP`].
+pub(crate) fn channel_inline<T, S: Semaphore>(semaphore: S) -> (Tx<T, S>, Rx<T, S>) {
+    make_channel(
+        semaphore,
+        SendQueue::Ring(Ring::new()),
+        RecvQueue::Ring { head: 0 },
+        None,
+    )
+}
+
+/// Creates a channel where each value occupies `cost(&value)` permits instead
+/// of one, turning the semaphore capacity into a budget.
+pub(crate) fn channel_with_cost<T, S: Semaphore>(
+    semaphore: S,
+    cost: std::sync::Arc<dyn Fn(&T) -> usize + Send + Sync>,
+) -> (Tx<T, S>, Rx<T, S>) {
+    let (tx, rx) = list::channel_with_block_size(super::BLOCK_CAP, None);
+
+    make_channel(semaphore, SendQueue::List(tx), RecvQueue::List(rx), Some(cost))
+}
 
+fn make_channel<T, S: Semaphore>(
+    semaphore: S,
+    tx: SendQueue<T>,
+    rx: RecvQueue<T>,
+    cost: Option<std::sync::Arc<dyn Fn(&T) -> usize + Send + Sync>>,
+) -> (Tx<T, S>, Rx<T, S>) {
     let chan = Arc::new(Chan {
         notify_rx_closed: Notify::new(),
         tx,
         semaphore,
         rx_waker: AtomicWaker::new(),
         tx_count: AtomicUsize::new(1),
+        tx_weak_count: AtomicUsize::new(0),
+        notify_tx_closed: Notify::new(),
+        overflow_policy: Mutex::new(OverflowPolicy::Drain),
+        cost,
+        close_reason: Mutex::new(None),
         rx_fields: UnsafeCell::new(RxFields {
             list: rx,
             rx_closed: false,
+            rendezvous_grant: false,
         }),
+        #[cfg(all(tokio_unstable, feature = "tracing"))]
+        resource_id: crate::util::trace::resource_id(),
     });
 
     (Tx::new(chan.clone()), Rx::new(chan))
@@ -136,11 +313,6 @@ impl<T, S> Tx<T, S> {
         &self.inner.semaphore
     }
 
-    /// Send a message and notify the receiver.
-    pub(crate) fn send(&self, value: T) {
-        self.inner.send(value);
-    }
-
     /// Wake the receive half
     pub(crate) fn wake_rx(&self) {
         self.inner.rx_waker.wake();
@@ -153,10 +325,70 @@ impl<T, S> Tx<T, S> {
 }
 
 impl<T, S: Semaphore> Tx<T, S> {
+    /// Send a message and notify the receiver.
+    pub(crate) fn send(&self, value: T) {
+        self.inner.send(value);
+    }
+
+    /// The number of permits `value` occupies in the channel; 1 unless the
+    /// channel was created with a cost function.
+    pub(crate) fn charge(&self, value: &T) -> usize {
+        self.inner.charge(value)
+    }
+
+    /// Returns `true` if the channel was created with a cost function.
+    pub(crate) fn is_cost_based(&self) -> bool {
+        self.inner.cost.is_some()
+    }
+
+    /// Applies the channel's overflow policy to a value sent while the
+    /// channel is over capacity after a shrink.
+    ///
+    /// Returns the value back when the send should proceed normally. When
+    /// `None` is returned, the policy consumed the value and the send
+    /// completes immediately.
+    pub(crate) fn shed_overflow(&self, value: T) -> Option<T> {
+        if self.inner.semaphore.deficit() == 0 || self.inner.semaphore.is_closed() {
+            return Some(value);
+        }
+
+        match &*self.inner.overflow_policy.lock() {
+            OverflowPolicy::Drain => Some(value),
+            OverflowPolicy::DropNewest => None,
+            OverflowPolicy::Callback(callback) => {
+                callback(value);
+                None
+            }
+        }
+    }
+
     pub(crate) fn is_closed(&self) -> bool {
         self.inner.semaphore.is_closed()
     }
 
+    /// Returns the reason recorded when the receive half closed the channel,
+    /// if one was provided.
+    pub(crate) fn close_reason(&self) -> Option<std::sync::Arc<dyn Any + Send + Sync>> {
+        self.inner.close_reason.lock().clone()
+    }
+
+    /// Creates a weak sender handle to the same channel.
+    pub(crate) fn downgrade(&self) -> WeakTx<T, S> {
+        self.inner.tx_weak_count.fetch_add(1, Relaxed);
+
+        WeakTx {
+            inner: self.inner.clone(),
+        }
+    }
+
+    pub(crate) fn strong_count(&self) -> usize {
+        self.inner.tx_count.load(Acquire)
+    }
+
+    pub(crate) fn weak_count(&self) -> usize {
+        self.inner.tx_weak_count.load(Relaxed)
+    }
+
     pub(crate) async fn closed(&self) {
         // In order to avoid a race condition, we first request a notification,
         // **then** check whether the semaphore is closed. If the semaphore is
@@ -193,12 +425,94 @@ impl<T, S> Drop for Tx<T, S> {
 
         // Notify the receiver
         self.wake_rx();
+
+        // Notify weak handles waiting for the send half to close
+        self.inner.notify_tx_closed.notify_waiters();
+    }
+}
+
+// ===== impl WeakTx =====
+
+impl<T, S> WeakTx<T, S> {
+    /// Attempts to create a strong sender handle.
+    ///
+    /// Returns `None` if the last strong sender was already dropped; a closed
+    /// send half can never be reopened.
+    pub(crate) fn upgrade(&self) -> Option<Tx<T, S>> {
+        let mut tx_count = self.inner.tx_count.load(Acquire);
+
+        loop {
+            if tx_count == 0 {
+                // Every strong sender is gone; the send half is closed.
+                return None;
+            }
+
+            match self.inner.tx_count.compare_exchange_weak(
+                tx_count,
+                tx_count + 1,
+                AcqRel,
+                Acquire,
+            ) {
+                Ok(_) => {
+                    return Some(Tx {
+                        inner: self.inner.clone(),
+                    })
+                }
+                Err(prev_count) => tx_count = prev_count,
+            }
+        }
+    }
+
+    pub(crate) fn strong_count(&self) -> usize {
+        self.inner.tx_count.load(Acquire)
+    }
+
+    pub(crate) fn weak_count(&self) -> usize {
+        self.inner.tx_weak_count.load(Relaxed)
+    }
+
+    /// Completes when the last strong sender handle is dropped.
+    pub(crate) async fn closed(&self) {
+        // As in `Tx::closed`, request the notification before checking the
+        // state so a concurrent close is not missed.
+        let notified = self.inner.notify_tx_closed.notified();
+
+        if self.inner.tx_count.load(Acquire) == 0 {
+            return;
+        }
+
+        notified.await;
+    }
+}
+
+impl<T, S> Clone for WeakTx<T, S> {
+    fn clone(&self) -> WeakTx<T, S> {
+        self.inner.tx_weak_count.fetch_add(1, Relaxed);
+
+        WeakTx {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T, S> Drop for WeakTx<T, S> {
+    fn drop(&mut self) {
+        self.inner.tx_weak_count.fetch_sub(1, Relaxed);
     }
 }
 
 // ===== impl Rx =====
 
 impl<T, S: Semaphore> Rx<T, S> {
+    pub(super) fn semaphore(&self) -> &S {
+        &self.inner.semaphore
+    }
+
+    /// Returns `true` if the channel was created with a cost function.
+    pub(crate) fn is_cost_based(&self) -> bool {
+        self.inner.cost.is_some()
+    }
+
     fn new(chan: Arc<Chan<T, S>>) -> Rx<T, S> {
         Rx { inner: chan }
     }
@@ -218,6 +532,89 @@ impl<T, S: Semaphore> Rx<T, S> {
         self.inner.notify_rx_closed.notify_waiters();
     }
 
+    /// Closes the channel and removes everything currently buffered.
+    ///
+    /// The permits for the removed values are returned to the semaphore in a
+    /// single operation, as in `recv_many`, so outstanding reserved permits
+    /// and the closed-and-idle bookkeeping behave exactly as if the values
+    /// had been received one by one.
+    pub(crate) fn drain(&mut self) -> Vec<T> {
+        use super::block::Read::*;
+
+        self.close();
+
+        let rx_fields_ptr = self.inner.rx_fields.with_mut(|ptr| ptr);
+
+        // Safety: only the `Rx` handle accesses `rx_fields`, and the
+        // reference does not outlive the `&mut self` borrow.
+        let rx_fields = unsafe { &mut *rx_fields_ptr };
+
+        let mut drained = Vec::new();
+        while let Some(Value(value)) = rx_fields.list.pop(&self.inner.tx) {
+            drained.push(value);
+        }
+
+        if !drained.is_empty() {
+            if self.inner.semaphore.is_rendezvous() {
+                rx_fields.rendezvous_grant = false;
+            } else {
+                let permits = drained.iter().map(|value| self.inner.charge(value)).sum();
+                self.inner.semaphore.add_permits(permits);
+            }
+        }
+
+        drained
+    }
+
+    /// Records `reason` and closes the channel.
+    ///
+    /// The reason is stored before the semaphore is closed so a sender that
+    /// observes the closed channel also observes the reason. If a reason was
+    /// already recorded, the first one is kept.
+    pub(crate) fn close_with_reason(&mut self, reason: std::sync::Arc<dyn Any + Send + Sync>) {
+        {
+            let mut slot = self.inner.close_reason.lock();
+
+            if slot.is_none() {
+                *slot = Some(reason);
+            }
+        }
+
+        self.close();
+    }
+
+    /// Whether the channel has no outstanding sends, taking a rendezvous
+    /// grant into account.
+    ///
+    /// An unclaimed grant is not an outstanding send: once the semaphore is
+    /// closed no sender can claim it anymore. A claimed grant means a sender
+    /// is mid-push and its value is still in flight.
+    fn is_idle(&self, rx_fields: &RxFields<T>) -> bool {
+        if rx_fields.rendezvous_grant {
+            self.inner.semaphore.unclaimed_permits() == 1
+        } else {
+            self.inner.semaphore.is_idle()
+        }
+    }
+
+    /// Hands one permit to senders on behalf of a parked receiver.
+    ///
+    /// A rendezvous channel has no standing capacity: a send can only
+    /// complete while a receiver is actively waiting. The permit is granted
+    /// here, after the receiver has registered its waker, and consumed when
+    /// the matching value is received.
+    fn grant_rendezvous(&self, rx_fields: &mut RxFields<T>) {
+        if !self.inner.semaphore.is_rendezvous()
+            || rx_fields.rendezvous_grant
+            || rx_fields.rx_closed
+        {
+            return;
+        }
+
+        rx_fields.rendezvous_grant = true;
+        self.inner.semaphore.add_permits(1);
+    }
+
     /// Receive the next value
     pub(crate) fn recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
         use super::block::Read::*;
@@ -232,8 +629,24 @@ impl<T, S: Semaphore> Rx<T, S> {
                 () => {
                     match rx_fields.list.pop(&self.inner.tx) {
                         Some(Value(value)) => {
-                            self.inner.semaphore.add_permits(1);
+                            if self.inner.semaphore.is_rendezvous() {
+                                // The permit backing this value was granted
+                                // by the receiver and consumed by the
+                                // sender; there is no capacity to return.
+                                rx_fields.rendezvous_grant = false;
+                            } else {
+                                self.inner.semaphore.add_permits(self.inner.charge(&value));
+                            }
                             coop.made_progress();
+
+                            #[cfg(all(tokio_unstable, feature = "tracing"))]
+                            tracing::trace!(
+                                target: "tokio::sync",
+                                resource_id = self.inner.resource_id,
+                                len = self.inner.semaphore.len(),
+                                "mpsc.recv",
+                            );
+
                             return Ready(Some(value));
                         }
                         Some(Closed) => {
@@ -243,7 +656,7 @@ impl<T, S: Semaphore> Rx<T, S> {
                             // dropped. Dropping a tx handle releases memory,
                             // which ensures that if dropping the tx handle is
                             // visible, then all messages sent are also visible.
-                            assert!(self.inner.semaphore.is_idle());
+                            assert!(self.is_idle(rx_fields));
                             coop.made_progress();
                             return Ready(None);
                         }
@@ -261,16 +674,169 @@ impl<T, S: Semaphore> Rx<T, S> {
             // second time here.
             try_recv!();
 
-            if rx_fields.rx_closed && self.inner.semaphore.is_idle() {
+            if rx_fields.rx_closed && self.is_idle(rx_fields) {
                 coop.made_progress();
                 Ready(None)
             } else {
+                self.grant_rendezvous(rx_fields);
+                Pending
+            }
+        })
+    }
+
+    /// Polls until a value is ready to be peeked at, without dequeuing it.
+    ///
+    /// Returns `true` when a value is available and `false` when the channel
+    /// is closed and drained. No permit is released either way.
+    pub(crate) fn poll_peek_ready(&mut self, cx: &mut Context<'_>) -> Poll<bool> {
+        use super::block::Read::*;
+
+        // Keep track of task budget
+        let coop = ready!(crate::coop::poll_proceed(cx));
+
+        self.inner.rx_fields.with_mut(|rx_fields_ptr| {
+            let rx_fields = unsafe { &mut *rx_fields_ptr };
+
+            macro_rules! try_peek {
+                () => {
+                    match rx_fields.list.peek(&self.inner.tx) {
+                        Some(Value(_)) => {
+                            coop.made_progress();
+                            return Ready(true);
+                        }
+                        Some(Closed) => {
+                            coop.made_progress();
+                            return Ready(false);
+                        }
+                        None => {} // fall through
+                    }
+                };
+            }
+
+            try_peek!();
+
+            self.inner.rx_waker.register_by_ref(cx.waker());
+
+            // It is possible that a value was pushed between attempting to read
+            // and registering the task, so we have to check the channel a
+            // second time here.
+            try_peek!();
+
+            if rx_fields.rx_closed && self.is_idle(rx_fields) {
+                coop.made_progress();
+                Ready(false)
+            } else {
+                self.grant_rendezvous(rx_fields);
                 Pending
             }
         })
     }
 
-    pub(crate) fn resize(&self, new_capacity: usize) {
+    /// Returns a reference to the next value, if one is ready.
+    pub(crate) fn peek_ref(&mut self) -> Option<&T> {
+        let rx_fields_ptr = self.inner.rx_fields.with_mut(|ptr| ptr);
+
+        // Safety: only the `Rx` handle accesses `rx_fields`, and the
+        // reference does not outlive the `&mut self` borrow.
+        let rx_fields = unsafe { &mut *rx_fields_ptr };
+
+        match rx_fields.list.peek(&self.inner.tx) {
+            Some(super::block::Read::Value(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Receives up to `limit` values, appending them to `buffer`.
+    ///
+    /// The permits for the whole batch are returned to the semaphore in a
+    /// single `add_permits` call, rather than one per message.
+    pub(crate) fn recv_many(
+        &mut self,
+        cx: &mut Context<'_>,
+        buffer: &mut Vec<T>,
+        limit: usize,
+    ) -> Poll<usize> {
+        use super::block::Read::*;
+
+        // Keep track of task budget
+        let coop = ready!(crate::coop::poll_proceed(cx));
+
+        if limit == 0 {
+            coop.made_progress();
+            return Ready(0);
+        }
+
+        self.inner.rx_fields.with_mut(|rx_fields_ptr| {
+            let rx_fields = unsafe { &mut *rx_fields_ptr };
+
+            macro_rules! try_recv {
+                () => {
+                    let mut popped = 0;
+                    let mut permits = 0;
+                    while popped < limit {
+                        match rx_fields.list.pop(&self.inner.tx) {
+                            Some(Value(value)) => {
+                                permits += self.inner.charge(&value);
+                                buffer.push(value);
+                                popped += 1;
+                            }
+                            Some(Closed) => {
+                                rx_fields.rx_closed = true;
+                                break;
+                            }
+                            None => break,
+                        }
+                    }
+
+                    if popped > 0 {
+                        if self.inner.semaphore.is_rendezvous() {
+                            // See `recv`: the permits backing these values
+                            // were granted by the receiver and consumed by
+                            // the senders.
+                            rx_fields.rendezvous_grant = false;
+                        } else {
+                            self.inner.semaphore.add_permits(permits);
+                        }
+                        coop.made_progress();
+                        return Ready(popped);
+                    }
+                };
+            }
+
+            try_recv!();
+
+            if rx_fields.rx_closed && self.is_idle(rx_fields) {
+                coop.made_progress();
+                return Ready(0);
+            }
+
+            self.inner.rx_waker.register_by_ref(cx.waker());
+
+            // It is possible that a value was pushed between attempting to read
+            // and registering the task, so we have to check the channel a
+            // second time here.
+            try_recv!();
+
+            if rx_fields.rx_closed && self.is_idle(rx_fields) {
+                coop.made_progress();
+                Ready(0)
+            } else {
+                self.grant_rendezvous(rx_fields);
+                Pending
+            }
+        })
+    }
+
+    pub(crate) fn resize(&self, new_capacity: usize, policy: OverflowPolicy<T>) {
+        *self.inner.overflow_policy.lock() = policy;
+
+        if let SendQueue::Ring(ring) = &self.inner.tx {
+            assert!(
+                new_capacity <= ring.capacity(),
+                "cannot resize past the inline capacity of a small bounded channel"
+            );
+        }
+
         let curr = self.inner.semaphore.cap();
 
         if new_capacity == curr {
@@ -296,8 +862,8 @@ impl<T, S: Semaphore> Drop for Rx<T, S> {
         self.inner.rx_fields.with_mut(|rx_fields_ptr| {
             let rx_fields = unsafe { &mut *rx_fields_ptr };
 
-            while let Some(Value(_)) = rx_fields.list.pop(&self.inner.tx) {
-                self.inner.semaphore.add_permits(1);
+            while let Some(Value(value)) = rx_fields.list.pop(&self.inner.tx) {
+                self.inner.semaphore.add_permits(self.inner.charge(&value));
             }
         })
     }
@@ -305,14 +871,35 @@ impl<T, S: Semaphore> Drop for Rx<T, S> {
 
 // ===== impl Chan =====
 
-impl<T, S> Chan<T, S> {
+impl<T, S: Semaphore> Chan<T, S> {
     fn send(&self, value: T) {
         // Push the value
         self.tx.push(value);
 
+        #[cfg(all(tokio_unstable, feature = "tracing"))]
+        tracing::trace!(
+            target: "tokio::sync",
+            resource_id = self.resource_id,
+            len = self.semaphore.len(),
+            "mpsc.send",
+        );
+
         // Notify the rx task
         self.rx_waker.wake();
     }
+
+    /// The number of permits a value occupies in the channel.
+    ///
+    /// This is 1 unless the channel was created with `channel_with_cost`, in
+    /// which case the user's cost function decides. The cost is clamped to
+    /// `1..=cap` so a value can neither bypass backpressure entirely nor
+    /// exceed the total budget and block forever.
+    fn charge(&self, value: &T) -> usize {
+        match &self.cost {
+            Some(cost) => cost(value).clamp(1, self.semaphore.cap().max(1)),
+            None => 1,
+        }
+    }
 }
 
 impl<T, S> Drop for Chan<T, S> {
@@ -325,7 +912,10 @@ impl<T, S> Drop for Chan<T, S> {
             let rx_fields = unsafe { &mut *rx_fields_ptr };
 
             while let Some(Value(_)) = rx_fields.list.pop(&self.tx) {}
-            unsafe { rx_fields.list.free_blocks() };
+
+            if let RecvQueue::List(list) = &mut rx_fields.list {
+                unsafe { list.free_blocks() };
+            }
         });
     }
 }
@@ -333,6 +923,11 @@ impl<T, S> Drop for Chan<T, S> {
 // ===== impl Semaphore for (::Semaphore, capacity) =====
 
 impl Semaphore for (crate::sync::batch_semaphore::Semaphore, AtomicUsize) {
+    #[cfg(all(tokio_unstable, feature = "tracing"))]
+    fn len(&self) -> usize {
+        self.cap().saturating_sub(self.0.available_permits())
+    }
+
     fn add_permits(&self, addition: usize) {
         self.0.release(addition)
     }
@@ -360,45 +955,198 @@ impl Semaphore for (crate::sync::batch_semaphore::Semaphore, AtomicUsize) {
     fn set_cap(&self, new_capacity: usize) {
         self.1.store(new_capacity, Release);
     }
-}
 
-// ===== impl Semaphore for AtomicUsize =====
+    fn is_rendezvous(&self) -> bool {
+        self.cap() == 0
+    }
 
-use std::sync::atomic::Ordering::{Acquire, Release};
+    fn unclaimed_permits(&self) -> usize {
+        self.0.available_permits()
+    }
+
+    fn deficit(&self) -> usize {
+        self.0.underflow()
+    }
+}
+
+use std::sync::atomic::Ordering::Release;
 use std::usize;
 
-impl Semaphore for AtomicUsize {
+// ===== impl Semaphore for UnboundedLimit =====
+
+/// Semaphore for unbounded channels with a soft high-water mark.
+///
+/// The message count is tracked exactly as in the plain `AtomicUsize`
+/// semaphore: shifted left by one, with the low bit marking the channel
+/// closed. In addition, tasks can park until the queue length drops below a
+/// configurable mark, giving producers a weak form of backpressure without
+/// converting the channel to a bounded one.
+pub(crate) struct UnboundedLimit {
+    /// Message count (shifted) plus the closed bit.
+    count: AtomicUsize,
+
+    /// The soft high-water mark. Zero when unset.
+    watermark: AtomicUsize,
+
+    /// Wakers of tasks waiting for the queue to drain below the mark.
+    below_wakers: Mutex<Vec<std::task::Waker>>,
+}
+
+impl UnboundedLimit {
+    pub(crate) fn new() -> UnboundedLimit {
+        UnboundedLimit {
+            count: AtomicUsize::new(0),
+            watermark: AtomicUsize::new(0),
+            below_wakers: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The number of messages currently buffered in the channel.
+    pub(crate) fn num_messages(&self) -> usize {
+        self.count.load(Acquire) >> 1
+    }
+
+    /// Sets the high-water mark. A mark of zero disables notifications.
+    pub(crate) fn set_watermark(&self, mark: usize) {
+        self.watermark.store(mark, Release);
+
+        // Raising (or clearing) the mark may make parked waiters eligible
+        // immediately.
+        if mark == 0 || self.num_messages() < mark {
+            self.wake_below_watermark();
+        }
+    }
+
+    /// Increments the message count unless the channel is closed.
+    ///
+    /// Returns `false` when the receiver has been dropped.
+    pub(crate) fn inc_num_messages(&self) -> bool {
+        let mut curr = self.count.load(Acquire);
+
+        loop {
+            if curr & 1 == 1 {
+                return false;
+            }
+
+            if curr == usize::MAX ^ 1 {
+                // Overflowed the ref count. There is no safe way to recover,
+                // so abort the process. In practice, this should never happen.
+                process::abort()
+            }
+
+            match self.count.compare_exchange(curr, curr + 2, AcqRel, Acquire) {
+                Ok(_) => return true,
+                Err(actual) => {
+                    curr = actual;
+                }
+            }
+        }
+    }
+
+    /// Polls until the queue length is below the high-water mark.
+    ///
+    /// Resolves immediately when no mark is set or the channel is closed.
+    pub(crate) fn poll_below_watermark(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.is_below_watermark() {
+            return Ready(());
+        }
+
+        {
+            let mut below_wakers = self.below_wakers.lock();
+            if !below_wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                below_wakers.push(cx.waker().clone());
+            }
+        }
+
+        // A message may have been received between the check and parking the
+        // waker; re-check so the wakeup cannot be missed.
+        if self.is_below_watermark() {
+            return Ready(());
+        }
+
+        Pending
+    }
+
+    fn is_below_watermark(&self) -> bool {
+        let mark = self.watermark.load(Acquire);
+        mark == 0 || self.num_messages() < mark || self.is_closed()
+    }
+
+    fn wake_below_watermark(&self) {
+        for waker in self.below_wakers.lock().drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl fmt::Debug for UnboundedLimit {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("UnboundedLimit")
+            .field("count", &self.count.load(Relaxed))
+            .field("watermark", &self.watermark.load(Relaxed))
+            .finish()
+    }
+}
+
+impl Semaphore for UnboundedLimit {
+    #[cfg(all(tokio_unstable, feature = "tracing"))]
+    fn len(&self) -> usize {
+        self.num_messages()
+    }
+
     fn add_permits(&self, addition: usize) {
-        let prev = self.fetch_sub(addition << 1, Release);
+        let prev = self.count.fetch_sub(addition << 1, Release);
 
         if prev >> 1 == 0 {
             // Something went wrong
             process::abort();
         }
+
+        let mark = self.watermark.load(Acquire);
+        if mark != 0 && (prev >> 1) - addition < mark {
+            self.wake_below_watermark();
+        }
     }
 
     fn reduce_permits(&self, reduction: usize) {
-        self.fetch_update(Acquire, Release, |v| Some(v.saturating_sub(reduction)))
+        self.count
+            .fetch_update(AcqRel, Acquire, |v| Some(v.saturating_sub(reduction)))
             .expect("update failed");
     }
 
     fn is_idle(&self) -> bool {
-        self.load(Acquire) >> 1 == 0
+        self.count.load(Acquire) >> 1 == 0
     }
 
     fn close(&self) {
-        self.fetch_or(1, Release);
+        self.count.fetch_or(1, Release);
+
+        // Waiters must not be left parked on a channel that can no longer
+        // drain.
+        self.wake_below_watermark();
     }
 
     fn is_closed(&self) -> bool {
-        self.load(Acquire) & 1 == 1
+        self.count.load(Acquire) & 1 == 1
     }
 
     fn cap(&self) -> usize {
-        self.load(Acquire)
+        self.count.load(Acquire)
     }
 
     fn set_cap(&self, _: usize) {
         unreachable!()
     }
+
+    fn is_rendezvous(&self) -> bool {
+        false
+    }
+
+    fn unclaimed_permits(&self) -> usize {
+        0
+    }
+
+    fn deficit(&self) -> usize {
+        0
+    }
 }
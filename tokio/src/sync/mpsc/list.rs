@@ -3,6 +3,8 @@
 use crate::loom::sync::atomic::{AtomicPtr, AtomicUsize};
 use crate::loom::thread;
 use crate::sync::mpsc::block::{self, Block};
+use crate::sync::mpsc::pool::BlockPool;
+use crate::sync::mpsc::BLOCK_CAP;
 
 use std::fmt;
 use std::ptr::NonNull;
@@ -16,6 +18,9 @@ pub(crate) struct Tx<T> {
     /// Position to push the next message. This reference a block and offset
     /// into the block.
     tail_position: AtomicUsize,
+
+    /// Number of values each block in the list can hold.
+    block_cap: usize,
 }
 
 /// List queue receive handle
@@ -28,24 +33,33 @@ pub(crate) struct Rx<T> {
 
     /// Pointer to the next block pending release
     free_head: NonNull<Block<T>>,
+
+    /// Number of values each block in the list can hold.
+    block_cap: usize,
 }
 
-pub(crate) fn channel<T>() -> (Tx<T>, Rx<T>) {
-    // Create the initial block shared between the tx and rx halves.
-    let initial_block = Box::new(Block::new(0));
-    let initial_block_ptr = Box::into_raw(initial_block);
+pub(crate) fn channel_with_block_size<T>(
+    block_cap: usize,
+    pool: Option<BlockPool>,
+) -> (Tx<T>, Rx<T>) {
+    debug_assert!(block_cap.is_power_of_two());
+    debug_assert!(block_cap <= BLOCK_CAP);
+
+    // Create the initial block shared between the tx and rx halves. Blocks
+    // appended later inherit the pool from the block they are linked after.
+    let head = Block::new(0, block_cap, pool);
 
     let tx = Tx {
-        block_tail: AtomicPtr::new(initial_block_ptr),
+        block_tail: AtomicPtr::new(head.as_ptr()),
         tail_position: AtomicUsize::new(0),
+        block_cap,
     };
 
-    let head = NonNull::new(initial_block_ptr).unwrap();
-
     let rx = Rx {
         head,
         index: 0,
         free_head: head,
+        block_cap,
     };
 
     (tx, rx)
@@ -83,10 +97,10 @@ impl<T> Tx<T> {
 
     fn find_block(&self, slot_index: usize) -> NonNull<Block<T>> {
         // The start index of the block that contains `index`.
-        let start_index = block::start_index(slot_index);
+        let start_index = block::start_index(slot_index, self.block_cap);
 
         // The index offset into the block
-        let offset = block::offset(slot_index);
+        let offset = block::offset(slot_index, self.block_cap);
 
         // Load the current head of the block
         let mut block_ptr = self.block_tail.load(Acquire);
@@ -203,7 +217,7 @@ impl<T> Tx<T> {
         }
 
         if !reused {
-            let _ = Box::from_raw(block.as_ptr());
+            Block::dealloc(block);
         }
     }
 }
@@ -240,11 +254,26 @@ impl<T> Rx<T> {
         }
     }
 
+    /// Returns a reference to the next value without dequeuing it.
+    pub(crate) fn peek(&mut self, tx: &Tx<T>) -> Option<block::Read<&T>> {
+        // Advance `head`, if needed
+        if !self.try_advancing_head() {
+            return None;
+        }
+
+        self.reclaim_blocks(tx);
+
+        unsafe {
+            let block = self.head.as_ref();
+            block.peek(self.index)
+        }
+    }
+
     /// Tries advancing the block pointer to the block referenced by `self.index`.
     ///
     /// Returns `true` if successful, `false` if there is no next block to load.
     fn try_advancing_head(&mut self) -> bool {
-        let block_index = block::start_index(self.index);
+        let block_index = block::start_index(self.index, self.block_cap);
 
         loop {
             let next_block = {
@@ -323,7 +352,7 @@ impl<T> Rx<T> {
 
         while let Some(block) = cur {
             cur = block.as_ref().load_next(Relaxed);
-            drop(Box::from_raw(block.as_ptr()));
+            Block::dealloc(block);
         }
     }
 }
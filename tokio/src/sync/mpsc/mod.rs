@@ -73,12 +73,24 @@
 pub(super) mod block;
 
 mod bounded;
-pub use self::bounded::{channel, OwnedPermit, Permit, Receiver, Sender};
+pub use self::bounded::{
+    channel, channel_with_block_size, channel_with_cost, channel_with_pool, ChannelStats,
+    OverflowPolicy, OwnedPermit,
+    OwnedPermitIterator, Permit, Receiver, Sender, TapReceiver, WeakSender,
+};
 
 mod chan;
 
 pub(super) mod list;
 
+mod pool;
+pub use self::pool::BlockPool;
+
+mod priority;
+pub use self::priority::{priority_channel, PriorityReceiver, PrioritySender};
+
+mod ring;
+
 mod unbounded;
 pub use self::unbounded::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
@@ -89,10 +101,10 @@ pub mod error;
 /// This value must be a power of 2. It also must be smaller than the number of
 /// bits in `usize`.
 #[cfg(all(target_pointer_width = "64", not(loom)))]
-const BLOCK_CAP: usize = 32;
+pub(crate) const BLOCK_CAP: usize = 32;
 
 #[cfg(all(not(target_pointer_width = "64"), not(loom)))]
-const BLOCK_CAP: usize = 16;
+pub(crate) const BLOCK_CAP: usize = 16;
 
 #[cfg(loom)]
-const BLOCK_CAP: usize = 2;
+pub(crate) const BLOCK_CAP: usize = 2;
@@ -0,0 +1,123 @@
+//! A shared pool of mpsc list blocks.
+
+use crate::loom::sync::Mutex;
+
+use std::alloc::Layout;
+use std::collections::HashMap;
+use std::fmt;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+/// A shared allocator that recycles mpsc list blocks across channels.
+///
+/// By default, blocks freed by a channel are returned to the global
+/// allocator. Workloads that create and destroy many short-lived channels can
+/// instead create channels with [`channel_with_pool`], which returns freed
+/// blocks to the pool so that later channels reuse them.
+///
+/// The pool may be cloned cheaply and shared between any number of channels,
+/// including channels with different message types and block sizes. Cached
+/// blocks are freed when the last clone of the pool is dropped.
+///
+/// [`channel_with_pool`]: super::channel_with_pool
+#[derive(Clone)]
+pub struct BlockPool {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    /// Cached block allocations, keyed by `(size, align)`.
+    ///
+    /// Raw addresses are stored as `usize` so the map is `Send` and `Sync`;
+    /// every entry is an unaliased allocation of the keyed layout.
+    classes: Mutex<HashMap<(usize, usize), Vec<usize>>>,
+
+    /// Maximum number of blocks retained per `(size, align)` class.
+    max_per_class: usize,
+}
+
+/// Number of blocks retained per allocation class before freed blocks are
+/// returned to the global allocator.
+const DEFAULT_MAX_PER_CLASS: usize = 64;
+
+impl BlockPool {
+    /// Creates a new, empty block pool.
+    pub fn new() -> BlockPool {
+        BlockPool {
+            inner: Arc::new(Inner {
+                classes: Mutex::new(HashMap::new()),
+                max_per_class: DEFAULT_MAX_PER_CLASS,
+            }),
+        }
+    }
+
+    /// Returns the number of blocks currently cached by the pool.
+    pub fn cached_blocks(&self) -> usize {
+        self.inner
+            .classes
+            .lock()
+            .values()
+            .map(|entries| entries.len())
+            .sum()
+    }
+
+    /// Takes a cached allocation of the given layout, if one is available.
+    pub(super) fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let mut classes = self.inner.classes.lock();
+
+        classes
+            .get_mut(&(layout.size(), layout.align()))?
+            .pop()
+            .map(|addr| {
+                // The address came from a successful allocation, so it is
+                // known to be non-null.
+                unsafe { NonNull::new_unchecked(addr as *mut u8) }
+            })
+    }
+
+    /// Returns an allocation of the given layout to the pool.
+    ///
+    /// If the pool already retains the maximum number of blocks for the
+    /// layout, the allocation is freed instead.
+    pub(super) fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+        let mut classes = self.inner.classes.lock();
+
+        let entries = classes
+            .entry((layout.size(), layout.align()))
+            .or_insert_with(Vec::new);
+
+        if entries.len() < self.inner.max_per_class {
+            entries.push(ptr.as_ptr() as usize);
+        } else {
+            drop(classes);
+
+            unsafe { std::alloc::dealloc(ptr.as_ptr(), layout) };
+        }
+    }
+}
+
+impl Default for BlockPool {
+    fn default() -> BlockPool {
+        BlockPool::new()
+    }
+}
+
+impl fmt::Debug for BlockPool {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("BlockPool")
+            .field("cached_blocks", &self.cached_blocks())
+            .finish()
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        for ((size, align), entries) in self.classes.lock().drain() {
+            let layout = Layout::from_size_align(size, align).unwrap();
+
+            for addr in entries {
+                unsafe { std::alloc::dealloc(addr as *mut u8, layout) };
+            }
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use crate::loom::future::AtomicWaker;
+use crate::loom::sync::atomic::AtomicUsize;
+use crate::loom::sync::{Arc, Mutex};
+use crate::sync::batch_semaphore::{Semaphore, TryAcquireError};
+use crate::sync::mpsc::error::{SendError, TrySendError};
+
+use std::collections::BinaryHeap;
+use std::fmt;
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed};
+use std::task::{Context, Poll};
+
+/// Send values to the associated [`PriorityReceiver`].
+///
+/// Instances are created by the [`priority_channel`] function.
+pub struct PrioritySender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Receive values from the associated [`PrioritySender`]s, highest first.
+///
+/// Instances are created by the [`priority_channel`] function.
+pub struct PriorityReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+struct Shared<T> {
+    /// Buffered messages, ordered by their `Ord` implementation.
+    heap: Mutex<BinaryHeap<T>>,
+    /// Bounds the number of buffered messages across all priorities.
+    semaphore: Semaphore,
+    /// Waker of the task blocked in `recv`.
+    rx_waker: AtomicWaker,
+    /// Number of live senders.
+    tx_count: AtomicUsize,
+}
+
+/// Creates a bounded mpsc channel that delivers messages in priority order.
+///
+/// [`recv`] always returns the greatest buffered message according to the
+/// message type's [`Ord`] implementation, rather than the oldest one. The
+/// order in which messages of equal priority are delivered is unspecified.
+///
+/// The channel buffers up to `buffer` messages in total, regardless of their
+/// priority. Once the buffer is full, sends wait until a message is received,
+/// exactly like the [`channel`] they would otherwise be split across: running
+/// one channel per priority with a biased `select!` loses this shared
+/// backpressure accounting.
+///
+/// # Panics
+///
+/// Panics if `buffer` is 0.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::mpsc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (tx, mut rx) = mpsc::priority_channel(8);
+///
+///     tx.send(1).await.unwrap();
+///     tx.send(3).await.unwrap();
+///     tx.send(2).await.unwrap();
+///     drop(tx);
+///
+///     assert_eq!(rx.recv().await, Some(3));
+///     assert_eq!(rx.recv().await, Some(2));
+///     assert_eq!(rx.recv().await, Some(1));
+///     assert_eq!(rx.recv().await, None);
+/// }
+/// ```
+///
+/// [`recv`]: PriorityReceiver::recv
+/// [`channel`]: super::channel
+pub fn priority_channel<T: Ord>(buffer: usize) -> (PrioritySender<T>, PriorityReceiver<T>) {
+    assert!(buffer > 0, "mpsc priority channel requires buffer > 0");
+
+    let shared = Arc::new(Shared {
+        heap: Mutex::new(BinaryHeap::new()),
+        semaphore: Semaphore::new(buffer),
+        rx_waker: AtomicWaker::new(),
+        tx_count: AtomicUsize::new(1),
+    });
+
+    let tx = PrioritySender {
+        shared: shared.clone(),
+    };
+    let rx = PriorityReceiver { shared };
+
+    (tx, rx)
+}
+
+// ===== impl PrioritySender =====
+
+impl<T: Ord> PrioritySender<T> {
+    /// Sends a value, waiting until there is capacity.
+    ///
+    /// A successful send occurs when it is determined that the other end of
+    /// the channel has not hung up already. An unsuccessful send would be one
+    /// where the corresponding receiver has already been closed or dropped;
+    /// the returned error contains the value.
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        match self.shared.semaphore.acquire(1).await {
+            Ok(()) => {
+                self.push(value);
+                Ok(())
+            }
+            Err(_) => Err(SendError(value)),
+        }
+    }
+
+    /// Attempts to immediately send a value.
+    ///
+    /// This method differs from [`send`] by returning immediately if the
+    /// channel's buffer is full or no receiver is waiting to acquire the
+    /// message.
+    ///
+    /// [`send`]: PrioritySender::send
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        match self.shared.semaphore.try_acquire(1) {
+            Ok(()) => {
+                self.push(value);
+                Ok(())
+            }
+            Err(TryAcquireError::Closed) => Err(TrySendError::Closed(value)),
+            Err(TryAcquireError::NoPermits) => Err(TrySendError::Full(value)),
+        }
+    }
+
+    fn push(&self, value: T) {
+        self.shared.heap.lock().push(value);
+        self.shared.rx_waker.wake();
+    }
+}
+
+impl<T> PrioritySender<T> {
+    /// Returns `true` if the channel has been closed by the receiver.
+    pub fn is_closed(&self) -> bool {
+        self.shared.semaphore.is_closed()
+    }
+
+    /// Returns the number of additional messages the channel can buffer.
+    pub fn capacity(&self) -> usize {
+        self.shared.semaphore.available_permits()
+    }
+}
+
+impl<T> Clone for PrioritySender<T> {
+    fn clone(&self) -> Self {
+        self.shared.tx_count.fetch_add(1, Relaxed);
+
+        PrioritySender {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for PrioritySender<T> {
+    fn drop(&mut self) {
+        if self.shared.tx_count.fetch_sub(1, AcqRel) != 1 {
+            return;
+        }
+
+        // This was the last sender; wake the receiver so a pending `recv`
+        // can observe the disconnection.
+        self.shared.rx_waker.wake();
+    }
+}
+
+impl<T> fmt::Debug for PrioritySender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("PrioritySender").finish()
+    }
+}
+
+// ===== impl PriorityReceiver =====
+
+impl<T: Ord> PriorityReceiver<T> {
+    /// Receives the highest-priority buffered value.
+    ///
+    /// Returns `None` when all senders have been dropped and every buffered
+    /// message has been received.
+    pub async fn recv(&mut self) -> Option<T> {
+        use crate::future::poll_fn;
+
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        // Keep track of task budget
+        let coop = ready!(crate::coop::poll_proceed(cx));
+
+        macro_rules! try_recv {
+            () => {
+                if let Some(value) = self.pop() {
+                    coop.made_progress();
+                    return Poll::Ready(Some(value));
+                }
+
+                // Disconnected, or closed by this receiver: once the buffer
+                // is drained there is nothing left to wait for.
+                if self.shared.tx_count.load(Acquire) == 0 || self.shared.semaphore.is_closed() {
+                    coop.made_progress();
+                    return Poll::Ready(None);
+                }
+            };
+        }
+
+        try_recv!();
+
+        self.shared.rx_waker.register_by_ref(cx.waker());
+
+        // It is possible that a value was pushed between attempting to read
+        // and registering the task, so we have to check the channel a second
+        // time here.
+        try_recv!();
+
+        Poll::Pending
+    }
+
+    fn pop(&self) -> Option<T> {
+        let value = self.shared.heap.lock().pop()?;
+
+        // Return the message's slot to waiting senders.
+        self.shared.semaphore.release(1);
+        Some(value)
+    }
+}
+
+impl<T> PriorityReceiver<T> {
+    /// Closes the receiving half of the channel without dropping it.
+    ///
+    /// This prevents any further messages from being sent on the channel
+    /// while still enabling the receiver to drain messages that are already
+    /// buffered.
+    pub fn close(&mut self) {
+        self.shared.semaphore.close();
+    }
+}
+
+impl<T> Drop for PriorityReceiver<T> {
+    fn drop(&mut self) {
+        self.shared.semaphore.close();
+    }
+}
+
+impl<T> fmt::Debug for PriorityReceiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("PriorityReceiver").finish()
+    }
+}
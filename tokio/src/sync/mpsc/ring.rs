@@ -0,0 +1,153 @@
+//! A fixed-capacity inline ring used by small bounded channels.
+//!
+//! Bounded channels with a capacity of [`MAX_INLINE_CAP`] or less store their
+//! messages in this ring instead of the general linked list of blocks. The
+//! ring is allocated once at construction; small channels dominate channel
+//! allocations in practice and the block list is overkill for them.
+
+use crate::loom::cell::UnsafeCell;
+use crate::loom::sync::atomic::{AtomicBool, AtomicUsize};
+use crate::sync::mpsc::block::Read;
+
+use std::mem::MaybeUninit;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+
+/// Largest channel capacity served by the inline ring.
+pub(crate) const MAX_INLINE_CAP: usize = 8;
+
+/// A bounded MPSC ring.
+///
+/// Slot reuse is safe because the channel's semaphore bounds the number of
+/// claimed-but-unreceived messages by the ring length: a producer only claims
+/// a slot while holding a permit, and the permit is returned after the
+/// receiver empties the slot.
+pub(crate) struct Ring<T> {
+    slots: Box<[Slot<T>]>,
+
+    /// Next slot to claim for a push.
+    tail: AtomicUsize,
+
+    /// Set once every sender handle has been dropped.
+    closed: AtomicBool,
+}
+
+struct Slot<T> {
+    /// `true` if `value` holds a message.
+    ready: AtomicBool,
+
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send> Send for Ring<T> {}
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+impl<T> Ring<T> {
+    pub(crate) fn new() -> Ring<T> {
+        let slots = (0..MAX_INLINE_CAP)
+            .map(|_| Slot {
+                ready: AtomicBool::new(false),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+            .collect();
+
+        Ring {
+            slots,
+            tail: AtomicUsize::new(0),
+            closed: AtomicBool::new(false),
+        }
+    }
+
+    /// Number of slots in the ring, bounding `resize`.
+    pub(crate) fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Pushes a value. The caller must hold a semaphore permit.
+    pub(crate) fn push(&self, value: T) {
+        let claim = self.tail.fetch_add(1, Relaxed);
+        let slot = &self.slots[claim % self.slots.len()];
+
+        // Safety: the semaphore permit guarantees exclusive access to the
+        // claimed slot until the receiver empties it.
+        slot.value.with_mut(|ptr| unsafe {
+            ptr.write(MaybeUninit::new(value));
+        });
+
+        slot.ready.store(true, Release);
+    }
+
+    /// Marks the ring closed. Called when the last sender is dropped.
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Release);
+    }
+
+    /// Pops the value at `head`, the receiver's cursor.
+    pub(crate) fn pop(&self, head: &mut usize) -> Option<Read<T>> {
+        if let Some(value) = self.take(*head) {
+            *head += 1;
+            return Some(Read::Value(value));
+        }
+
+        if self.closed.load(Acquire) {
+            // A value published right before the channel closed may not have
+            // been visible above; the `Acquire` load of `closed` makes it
+            // visible now.
+            if let Some(value) = self.take(*head) {
+                *head += 1;
+                return Some(Read::Value(value));
+            }
+
+            return Some(Read::Closed);
+        }
+
+        None
+    }
+
+    /// Returns a reference to the value at `head` without dequeuing it.
+    pub(crate) fn peek(&self, head: usize) -> Option<Read<&T>> {
+        if let Some(value) = self.peek_at(head) {
+            return Some(Read::Value(value));
+        }
+
+        if self.closed.load(Acquire) {
+            // See `pop` for why the slot is re-checked after observing the
+            // closed flag.
+            if let Some(value) = self.peek_at(head) {
+                return Some(Read::Value(value));
+            }
+
+            return Some(Read::Closed);
+        }
+
+        None
+    }
+
+    fn peek_at(&self, head: usize) -> Option<&T> {
+        let slot = &self.slots[head % self.slots.len()];
+
+        if !slot.ready.load(Acquire) {
+            return None;
+        }
+
+        // Safety: `ready` guards initialization, and only the receiver reads
+        // slots. The slot stays ready, so the value is not dropped while the
+        // reference is live.
+        Some(slot.value.with(|ptr| unsafe { &*(*ptr).as_ptr() }))
+    }
+
+    fn take(&self, head: usize) -> Option<T> {
+        let slot = &self.slots[head % self.slots.len()];
+
+        if !slot.ready.load(Acquire) {
+            return None;
+        }
+
+        // Safety: `ready` guards initialization, and only the receiver reads
+        // slots.
+        let value = slot.value.with(|ptr| unsafe { (*ptr).as_ptr().read() });
+
+        slot.ready.store(false, Release);
+
+        Some(value)
+    }
+}
@@ -1,4 +1,3 @@
-use crate::loom::sync::atomic::AtomicUsize;
 use crate::sync::mpsc::chan;
 use crate::sync::mpsc::error::SendError;
 
@@ -61,7 +60,7 @@ impl<T> fmt::Debug for UnboundedReceiver<T> {
 /// the channel. Using an `unbounded` channel has the ability of causing the
 /// process to run out of memory. In this case, the process will be aborted.
 pub fn unbounded_channel<T>() -> (UnboundedSender<T>, UnboundedReceiver<T>) {
-    let (tx, rx) = chan::channel(AtomicUsize::new(0));
+    let (tx, rx) = chan::channel(chan::UnboundedLimit::new());
 
     let tx = UnboundedSender::new(tx);
     let rx = UnboundedReceiver::new(rx);
@@ -69,8 +68,8 @@ pub fn unbounded_channel<T>() -> (UnboundedSender<T>, UnboundedReceiver<T>) {
     (tx, rx)
 }
 
-/// No capacity
-type Semaphore = AtomicUsize;
+/// No capacity, but a message count and an optional soft high-water mark.
+type Semaphore = chan::UnboundedLimit;
 
 impl<T> UnboundedReceiver<T> {
     pub(crate) fn new(chan: chan::Rx<T, Semaphore>) -> UnboundedReceiver<T> {
@@ -122,6 +121,40 @@ impl<T> UnboundedReceiver<T> {
         poll_fn(|cx| self.poll_recv(cx)).await
     }
 
+    /// Receives the next values for this receiver and extends `buffer`.
+    ///
+    /// This method extends `buffer` by no more than a fixed number of values
+    /// as specified by `limit`. If `limit` is zero, the method returns `0`
+    /// immediately. Otherwise the method waits until at least one value is
+    /// ready, appends everything ready up to `limit`, and returns the number
+    /// of values added. See [`Receiver::recv_many`] on the bounded channel
+    /// for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::unbounded_channel();
+    ///     let mut buffer = Vec::new();
+    ///
+    ///     tx.send(1).unwrap();
+    ///     tx.send(2).unwrap();
+    ///
+    ///     assert_eq!(rx.recv_many(&mut buffer, 10).await, 2);
+    ///     assert_eq!(buffer, vec![1, 2]);
+    /// }
+    /// ```
+    ///
+    /// [`Receiver::recv_many`]: crate::sync::mpsc::Receiver::recv_many
+    pub async fn recv_many(&mut self, buffer: &mut Vec<T>, limit: usize) -> usize {
+        use crate::future::poll_fn;
+
+        poll_fn(|cx| self.chan.recv_many(cx, buffer, limit)).await
+    }
+
     /// Blocking receive to call outside of asynchronous contexts.
     ///
     /// # Panics
@@ -160,6 +193,36 @@ impl<T> UnboundedReceiver<T> {
         self.chan.close();
     }
 
+    /// Closes the receiving half of a channel, recording a reason.
+    ///
+    /// This behaves exactly like [`close`], except that senders can retrieve
+    /// `reason` through [`UnboundedSender::close_reason`] after a send
+    /// fails. See [`Receiver::close_with`] for details.
+    ///
+    /// Calling this more than once keeps the first recorded reason.
+    ///
+    /// [`close`]: UnboundedReceiver::close
+    /// [`Receiver::close_with`]: crate::sync::mpsc::Receiver::close_with
+    pub fn close_with<E>(&mut self, reason: E)
+    where
+        E: Send + Sync + 'static,
+    {
+        self.chan.close_with_reason(std::sync::Arc::new(reason));
+    }
+
+    /// Closes the receiving half of a channel and returns all buffered
+    /// messages.
+    ///
+    /// This is equivalent to calling [`close`] and then `try_recv` in a loop,
+    /// except that the buffered messages are collected in a single operation.
+    /// See [`Receiver::drain`] for details.
+    ///
+    /// [`close`]: UnboundedReceiver::close
+    /// [`Receiver::drain`]: crate::sync::mpsc::Receiver::drain
+    pub fn drain(&mut self) -> Vec<T> {
+        self.chan.drain()
+    }
+
     /// Polls to receive the next message on this channel.
     ///
     /// This method returns:
@@ -207,33 +270,76 @@ impl<T> UnboundedSender<T> {
     }
 
     fn inc_num_messages(&self) -> bool {
-        use std::process;
-        use std::sync::atomic::Ordering::{AcqRel, Acquire};
-
-        let mut curr = self.chan.semaphore().load(Acquire);
-
-        loop {
-            if curr & 1 == 1 {
-                return false;
-            }
-
-            if curr == usize::MAX ^ 1 {
-                // Overflowed the ref count. There is no safe way to recover, so
-                // abort the process. In practice, this should never happen.
-                process::abort()
-            }
-
-            match self
-                .chan
-                .semaphore()
-                .compare_exchange(curr, curr + 2, AcqRel, Acquire)
-            {
-                Ok(_) => return true,
-                Err(actual) => {
-                    curr = actual;
-                }
-            }
-        }
+        self.chan.semaphore().inc_num_messages()
+    }
+
+    /// Returns the number of messages currently queued in the channel.
+    ///
+    /// Since senders run without backpressure, this is the primary signal for
+    /// detecting a runaway producer; see [`set_high_watermark`].
+    ///
+    /// [`set_high_watermark`]: UnboundedSender::set_high_watermark
+    pub fn len(&self) -> usize {
+        self.chan.semaphore().num_messages()
+    }
+
+    /// Returns `true` if the channel has no queued messages.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Sets the channel's soft high-water mark.
+    ///
+    /// The mark does not limit the channel in any way; it only determines
+    /// when [`weak_backpressure`] resolves. A mark of zero (the default)
+    /// disables it. The mark is shared by all clones of the sender.
+    ///
+    /// [`weak_backpressure`]: UnboundedSender::weak_backpressure
+    pub fn set_high_watermark(&self, mark: usize) {
+        self.chan.semaphore().set_watermark(mark);
+    }
+
+    /// Completes when the number of queued messages is below the high-water
+    /// mark.
+    ///
+    /// This provides a weak, opt-in form of backpressure for unbounded
+    /// channels: a producer can await it between batches to let the receiver
+    /// catch up, without the channel ever rejecting a send. The future
+    /// resolves immediately if no mark has been set with
+    /// [`set_high_watermark`], if the queue is already below the mark, or if
+    /// the receiver has been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::mpsc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = mpsc::unbounded_channel();
+    ///     tx.set_high_watermark(2);
+    ///
+    ///     tx.send(1).unwrap();
+    ///     tx.send(2).unwrap();
+    ///     assert_eq!(tx.len(), 2);
+    ///
+    ///     let producer = tx.clone();
+    ///     let waiter = tokio::spawn(async move {
+    ///         // Above the mark: waits until the receiver drains a message.
+    ///         producer.weak_backpressure().await;
+    ///         producer.len()
+    ///     });
+    ///
+    ///     assert_eq!(rx.recv().await, Some(1));
+    ///     assert!(waiter.await.unwrap() < 2);
+    /// }
+    /// ```
+    ///
+    /// [`set_high_watermark`]: UnboundedSender::set_high_watermark
+    pub async fn weak_backpressure(&self) {
+        use crate::future::poll_fn;
+
+        poll_fn(|cx| self.chan.semaphore().poll_below_watermark(cx)).await
     }
 
     /// Completes when the receiver has dropped.
@@ -292,6 +398,22 @@ impl<T> UnboundedSender<T> {
         self.chan.is_closed()
     }
 
+    /// Returns the reason the receiver closed the channel, if one was
+    /// recorded with [`UnboundedReceiver::close_with`].
+    ///
+    /// See [`Sender::close_reason`] for details.
+    ///
+    /// [`UnboundedReceiver::close_with`]: UnboundedReceiver::close_with
+    /// [`Sender::close_reason`]: crate::sync::mpsc::Sender::close_reason
+    pub fn close_reason<E>(&self) -> Option<E>
+    where
+        E: Clone + Send + Sync + 'static,
+    {
+        self.chan
+            .close_reason()
+            .and_then(|reason| reason.downcast_ref::<E>().cloned())
+    }
+
     /// Returns `true` if senders belong to the same channel.
     ///
     /// # Examples
@@ -308,3 +430,16 @@ impl<T> UnboundedSender<T> {
         self.chan.same_channel(&other.chan)
     }
 }
+
+cfg_stream! {
+    impl<T> futures_core::Stream for UnboundedReceiver<T> {
+        type Item = T;
+
+        fn poll_next(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<T>> {
+            self.poll_recv(cx)
+        }
+    }
+}
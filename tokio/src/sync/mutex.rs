@@ -138,7 +138,7 @@ pub struct Mutex<T: ?Sized> {
 /// The lock is automatically released whenever the guard is dropped, at which
 /// point `lock` will succeed yet again.
 pub struct MutexGuard<'a, T: ?Sized> {
-    lock: &'a Mutex<T>,
+    pub(super) lock: &'a Mutex<T>,
 }
 
 /// An owned handle to a held `Mutex`.
@@ -173,6 +173,23 @@ pub struct MappedMutexGuard<'a, T: ?Sized> {
     marker: marker::PhantomData<&'a mut T>,
 }
 
+/// An owned handle to a held `Mutex` that has had a function applied to it via
+/// [`OwnedMutexGuard::map`].
+///
+/// This can be used to hold a subfield of the protected data. Like
+/// [`OwnedMutexGuard`], it keeps a reference-counted pointer to the original
+/// `Mutex`, so it has the `'static` lifetime and remains valid even if the
+/// lock goes away.
+///
+/// [`OwnedMutexGuard::map`]: method@OwnedMutexGuard::map
+#[must_use = "if unused the Mutex will immediately unlock"]
+pub struct OwnedMappedMutexGuard<T: ?Sized, U: ?Sized = T> {
+    data: *mut U,
+    // Keeps the mutex — and with it the pointee — alive; the lock is
+    // released through it on drop.
+    lock: Arc<Mutex<T>>,
+}
+
 // As long as T: Send, it's fine to send and share Mutex<T> between threads.
 // If T was not Send, sending and sharing a Mutex<T> would be bad, since you can
 // access T through Mutex<T>.
@@ -182,6 +199,18 @@ unsafe impl<T> Sync for MutexGuard<'_, T> where T: ?Sized + Send + Sync {}
 unsafe impl<T> Sync for OwnedMutexGuard<T> where T: ?Sized + Send + Sync {}
 unsafe impl<'a, T> Sync for MappedMutexGuard<'a, T> where T: ?Sized + Sync + 'a {}
 unsafe impl<'a, T> Send for MappedMutexGuard<'a, T> where T: ?Sized + Send + 'a {}
+unsafe impl<T, U> Sync for OwnedMappedMutexGuard<T, U>
+where
+    T: ?Sized + Send + Sync,
+    U: ?Sized + Send + Sync,
+{
+}
+unsafe impl<T, U> Send for OwnedMappedMutexGuard<T, U>
+where
+    T: ?Sized + Send,
+    U: ?Sized + Send,
+{
+}
 
 /// Error returned from the [`Mutex::try_lock`], [`RwLock::try_read`] and
 /// [`RwLock::try_write`] functions.
@@ -221,9 +250,11 @@ fn bounds() {
 
     check_send::<MutexGuard<'_, u32>>();
     check_send::<OwnedMutexGuard<u32>>();
+    check_send::<OwnedMappedMutexGuard<(u32, u32), u32>>();
     check_unpin::<Mutex<u32>>();
     check_send_sync::<Mutex<u32>>();
     check_static::<OwnedMutexGuard<u32>>();
+    check_static::<OwnedMappedMutexGuard<(u32, u32), u32>>();
 
     let mutex = Mutex::new(1);
     check_send_sync_val(mutex.lock());
@@ -327,11 +358,33 @@ impl<T: ?Sized> Mutex<T> {
     }
 
     async fn acquire(&self) {
+        #[cfg(all(tokio_unstable, feature = "tracing"))]
+        let contended_at = if self.s.available_permits() == 0 {
+            tracing::trace!(
+                target: "tokio::sync",
+                resource_id = self.s.resource_id(),
+                "mutex.contended",
+            );
+            Some(std::time::Instant::now())
+        } else {
+            None
+        };
+
         self.s.acquire(1).await.unwrap_or_else(|_| {
             // The semaphore was closed. but, we never explicitly close it, and
             // we own it exclusively, which means that this can never happen.
             unreachable!()
         });
+
+        #[cfg(all(tokio_unstable, feature = "tracing"))]
+        if let Some(contended_at) = contended_at {
+            tracing::trace!(
+                target: "tokio::sync",
+                resource_id = self.s.resource_id(),
+                wait_us = contended_at.elapsed().as_micros() as u64,
+                "mutex.acquired",
+            );
+        }
     }
 
     /// Attempts to acquire the lock, and returns [`TryLockError`] if the
@@ -435,6 +488,91 @@ impl<T: ?Sized> Mutex<T> {
     }
 }
 
+cfg_time! {
+    /// Error returned from the [`Mutex::lock_timeout`], [`RwLock::read_timeout`]
+    /// and [`RwLock::write_timeout`] functions: the lock could not be acquired
+    /// before the deadline.
+    ///
+    /// [`Mutex::lock_timeout`]: Mutex::lock_timeout
+    /// [`RwLock::read_timeout`]: fn@super::RwLock::read_timeout
+    /// [`RwLock::write_timeout`]: fn@super::RwLock::write_timeout
+    #[derive(Debug)]
+    pub struct LockTimeoutError(pub(super) ());
+
+    impl fmt::Display for LockTimeoutError {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(fmt, "timed out waiting for lock")
+        }
+    }
+
+    impl Error for LockTimeoutError {}
+
+    impl<T: ?Sized> Mutex<T> {
+        /// Locks this mutex, waiting at most `timeout` for the lock to become
+        /// available.
+        ///
+        /// The deadline is handled by the waiter itself rather than by
+        /// wrapping the acquisition in [`tokio::time::timeout`]: when the
+        /// deadline is reached, the waiter is unlinked from the lock's queue
+        /// in the same poll, so the lock passes to the next waiter without
+        /// waiting for a wrapped future to be dropped.
+        ///
+        /// Returns [`LockTimeoutError`] if the deadline elapsed before the
+        /// lock was acquired.
+        ///
+        /// [`tokio::time::timeout`]: crate::time::timeout
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use tokio::sync::Mutex;
+        /// use tokio::time::Duration;
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let mutex = Mutex::new(1);
+        ///
+        ///     let mut n = mutex.lock_timeout(Duration::from_millis(10)).await.unwrap();
+        ///     *n = 2;
+        /// }
+        /// ```
+        pub async fn lock_timeout(
+            &self,
+            timeout: crate::time::Duration,
+        ) -> Result<MutexGuard<'_, T>, LockTimeoutError> {
+            use std::future::Future;
+            use std::task::Poll;
+
+            let acquire = self.s.acquire(1);
+            let delay = crate::time::sleep(timeout);
+            crate::pin!(acquire, delay);
+
+            crate::future::poll_fn(|cx| {
+                if let Poll::Ready(res) = acquire.as_mut().poll(cx) {
+                    res.unwrap_or_else(|_| {
+                        // The semaphore was closed. but, we never explicitly close it, and
+                        // we own it exclusively, which means that this can never happen.
+                        unreachable!()
+                    });
+                    return Poll::Ready(Ok(()));
+                }
+
+                if delay.as_mut().poll(cx).is_ready() {
+                    // Unlink the waiter so the lock passes to the next waiter
+                    // in the queue before the timeout is reported.
+                    acquire.as_mut().cancel();
+                    return Poll::Ready(Err(LockTimeoutError(())));
+                }
+
+                Poll::Pending
+            })
+            .await?;
+
+            Ok(MutexGuard { lock: self })
+        }
+    }
+}
+
 impl<T> From<T> for Mutex<T> {
     fn from(s: T) -> Self {
         Self::new(s)
@@ -596,6 +734,103 @@ impl<T: ?Sized + fmt::Display> fmt::Display for MutexGuard<'_, T> {
 
 // === impl OwnedMutexGuard ===
 
+impl<T: ?Sized> OwnedMutexGuard<T> {
+    /// Makes a new [`OwnedMappedMutexGuard`] for a component of the locked data.
+    ///
+    /// This operation cannot fail as the [`OwnedMutexGuard`] passed in already locked the mutex.
+    ///
+    /// This is an associated function that needs to be used as `OwnedMutexGuard::map(...)`. A
+    /// method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tokio::sync::{Mutex, OwnedMutexGuard};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    /// struct Foo(u32);
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let foo = Arc::new(Mutex::new(Foo(1)));
+    ///
+    /// {
+    ///     let mut mapped = OwnedMutexGuard::map(foo.clone().lock_owned().await, |f| &mut f.0);
+    ///     *mapped = 2;
+    /// }
+    ///
+    /// assert_eq!(Foo(2), *foo.lock().await);
+    /// # }
+    /// ```
+    ///
+    /// [`OwnedMutexGuard`]: struct@OwnedMutexGuard
+    /// [`OwnedMappedMutexGuard`]: struct@OwnedMappedMutexGuard
+    #[inline]
+    pub fn map<U, F>(mut this: Self, f: F) -> OwnedMappedMutexGuard<T, U>
+    where
+        U: ?Sized,
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let data = f(&mut *this) as *mut U;
+        let this = mem::ManuallyDrop::new(this);
+        // Safety: the original guard is not dropped, so the `Arc` is moved
+        // out rather than duplicated.
+        let lock = unsafe { std::ptr::read(&this.lock) };
+        OwnedMappedMutexGuard { data, lock }
+    }
+
+    /// Attempts to make a new [`OwnedMappedMutexGuard`] for a component of the locked data. The
+    /// original guard is returned if the closure returns `None`.
+    ///
+    /// This operation cannot fail as the [`OwnedMutexGuard`] passed in already locked the mutex.
+    ///
+    /// This is an associated function that needs to be used as `OwnedMutexGuard::try_map(...)`. A
+    /// method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tokio::sync::{Mutex, OwnedMutexGuard};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    /// struct Foo(u32);
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let foo = Arc::new(Mutex::new(Foo(1)));
+    ///
+    /// {
+    ///     let mut mapped = OwnedMutexGuard::try_map(foo.clone().lock_owned().await, |f| Some(&mut f.0))
+    ///         .expect("should not fail");
+    ///     *mapped = 2;
+    /// }
+    ///
+    /// assert_eq!(Foo(2), *foo.lock().await);
+    /// # }
+    /// ```
+    ///
+    /// [`OwnedMutexGuard`]: struct@OwnedMutexGuard
+    /// [`OwnedMappedMutexGuard`]: struct@OwnedMappedMutexGuard
+    #[inline]
+    pub fn try_map<U, F>(mut this: Self, f: F) -> Result<OwnedMappedMutexGuard<T, U>, Self>
+    where
+        U: ?Sized,
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        let data = match f(&mut *this) {
+            Some(data) => data as *mut U,
+            None => return Err(this),
+        };
+        let this = mem::ManuallyDrop::new(this);
+        // Safety: the original guard is not dropped, so the `Arc` is moved
+        // out rather than duplicated.
+        let lock = unsafe { std::ptr::read(&this.lock) };
+        Ok(OwnedMappedMutexGuard { data, lock })
+    }
+}
+
 impl<T: ?Sized> Drop for OwnedMutexGuard<T> {
     fn drop(&mut self) {
         self.lock.s.release(1)
@@ -711,3 +946,89 @@ impl<'a, T: ?Sized + fmt::Display> fmt::Display for MappedMutexGuard<'a, T> {
         fmt::Display::fmt(&**self, f)
     }
 }
+
+// === impl OwnedMappedMutexGuard ===
+
+impl<T: ?Sized, U: ?Sized> OwnedMappedMutexGuard<T, U> {
+    /// Makes a new [`OwnedMappedMutexGuard`] for a component of the locked data.
+    ///
+    /// This operation cannot fail as the [`OwnedMappedMutexGuard`] passed in already locked the
+    /// mutex.
+    ///
+    /// This is an associated function that needs to be used as `OwnedMappedMutexGuard::map(...)`.
+    /// A method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// [`OwnedMappedMutexGuard`]: struct@OwnedMappedMutexGuard
+    #[inline]
+    pub fn map<S, F>(mut this: Self, f: F) -> OwnedMappedMutexGuard<T, S>
+    where
+        S: ?Sized,
+        F: FnOnce(&mut U) -> &mut S,
+    {
+        let data = f(&mut *this) as *mut S;
+        let this = mem::ManuallyDrop::new(this);
+        // Safety: the original guard is not dropped, so the `Arc` is moved
+        // out rather than duplicated.
+        let lock = unsafe { std::ptr::read(&this.lock) };
+        OwnedMappedMutexGuard { data, lock }
+    }
+
+    /// Attempts to make a new [`OwnedMappedMutexGuard`] for a component of the locked data. The
+    /// original guard is returned if the closure returns `None`.
+    ///
+    /// This operation cannot fail as the [`OwnedMappedMutexGuard`] passed in already locked the
+    /// mutex.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `OwnedMappedMutexGuard::try_map(...)`. A method would interfere with methods of the same
+    /// name on the contents of the locked data.
+    ///
+    /// [`OwnedMappedMutexGuard`]: struct@OwnedMappedMutexGuard
+    #[inline]
+    pub fn try_map<S, F>(mut this: Self, f: F) -> Result<OwnedMappedMutexGuard<T, S>, Self>
+    where
+        S: ?Sized,
+        F: FnOnce(&mut U) -> Option<&mut S>,
+    {
+        let data = match f(&mut *this) {
+            Some(data) => data as *mut S,
+            None => return Err(this),
+        };
+        let this = mem::ManuallyDrop::new(this);
+        // Safety: the original guard is not dropped, so the `Arc` is moved
+        // out rather than duplicated.
+        let lock = unsafe { std::ptr::read(&this.lock) };
+        Ok(OwnedMappedMutexGuard { data, lock })
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Drop for OwnedMappedMutexGuard<T, U> {
+    fn drop(&mut self) {
+        self.lock.s.release(1)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for OwnedMappedMutexGuard<T, U> {
+    type Target = U;
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.data }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> DerefMut for OwnedMappedMutexGuard<T, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.data }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Debug> fmt::Debug for OwnedMappedMutexGuard<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized + fmt::Display> fmt::Display for OwnedMappedMutexGuard<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
@@ -15,6 +15,7 @@ use std::marker::PhantomPinned;
 use std::pin::Pin;
 use std::ptr::NonNull;
 use std::sync::atomic::Ordering::SeqCst;
+use std::sync::Arc;
 use std::task::{Context, Poll, Waker};
 
 type WaitList = LinkedList<Waiter, <Waiter as linked_list::Link>::Target>;
@@ -156,6 +157,26 @@ pub struct Notified<'a> {
 unsafe impl<'a> Send for Notified<'a> {}
 unsafe impl<'a> Sync for Notified<'a> {}
 
+/// Future returned from [`Notify::notified_owned`].
+///
+/// This is the `'static` counterpart of [`Notified`]: it keeps the `Notify`
+/// alive through an [`Arc`] instead of borrowing it, so it can be stored in
+/// structs and moved into spawned tasks freely.
+#[derive(Debug)]
+pub struct OwnedNotified {
+    /// The `Notify` being received on.
+    notify: Arc<Notify>,
+
+    /// The current state of the receiving process.
+    state: State,
+
+    /// Entry in the waiter `LinkedList`.
+    waiter: UnsafeCell<Waiter>,
+}
+
+unsafe impl Send for OwnedNotified {}
+unsafe impl Sync for OwnedNotified {}
+
 #[derive(Debug)]
 enum State {
     Init(usize),
@@ -352,6 +373,160 @@ impl Notify {
         }
     }
 
+    /// Wait for a notification, keeping the `Notify` alive through an `Arc`.
+    ///
+    /// Equivalent to:
+    ///
+    /// ```ignore
+    /// async fn notified_owned(self: Arc<Self>);
+    /// ```
+    ///
+    /// This behaves exactly like [`notified()`], but the returned
+    /// [`OwnedNotified`] future holds a clone of the `Arc` rather than a
+    /// borrow, making it `'static`. This allows storing the pending future in
+    /// a struct or moving it into a spawned task without lifetime gymnastics.
+    ///
+    /// [`notified()`]: Notify::notified
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Notify;
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let notify = Arc::new(Notify::new());
+    ///
+    ///     // The future owns its handle and can be sent anywhere.
+    ///     let notified = notify.clone().notified_owned();
+    ///     let handle = tokio::spawn(notified);
+    ///
+    ///     notify.notify_one();
+    ///     handle.await.unwrap();
+    /// }
+    /// ```
+    pub fn notified_owned(self: Arc<Self>) -> OwnedNotified {
+        // we load the number of times notify_waiters
+        // was called and store that in our initial state
+        let state = self.state.load(SeqCst);
+        OwnedNotified {
+            notify: self,
+            state: State::Init(state >> NOTIFY_WAITERS_SHIFT),
+            waiter: UnsafeCell::new(Waiter {
+                pointers: linked_list::Pointers::new(),
+                waker: None,
+                notified: None,
+                _p: PhantomPinned,
+            }),
+        }
+    }
+
+    /// Notifies up to `n` waiting tasks
+    ///
+    /// This fills the space between [`notify_one()`] and [`notify_waiters()`]:
+    /// up to `n` currently registered waiters are woken, in FIFO order. If
+    /// fewer than `n` tasks are waiting, a **single** permit is stored for the
+    /// next call to [`notified().await`], exactly as [`notify_one()`] would
+    /// store one; a `Notify` never holds more than one permit. Use a
+    /// [`Semaphore`] when counted permits must accumulate.
+    ///
+    /// As with [`notify_one()`], a notification assigned to a waiter that is
+    /// dropped before receiving it is passed on to the next waiter in the
+    /// queue, so `notify_n(n)` reliably wakes `n` workers when at least `n`
+    /// are waiting.
+    ///
+    /// [`notify_one()`]: Notify::notify_one
+    /// [`notify_waiters()`]: Notify::notify_waiters
+    /// [`notified().await`]: Notify::notified()
+    /// [`Semaphore`]: crate::sync::Semaphore
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Notify;
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let notify = Arc::new(Notify::new());
+    ///     let mut workers = Vec::new();
+    ///
+    ///     for _ in 0..4 {
+    ///         let notify = notify.clone();
+    ///         workers.push(tokio::spawn(async move {
+    ///             notify.notified().await;
+    ///         }));
+    ///     }
+    ///
+    ///     // Allow the workers to register as waiters.
+    ///     tokio::task::yield_now().await;
+    ///
+    ///     // Wake two of the four workers.
+    ///     notify.notify_n(2);
+    /// }
+    /// ```
+    pub fn notify_n(&self, n: usize) {
+        const NUM_WAKERS: usize = 32;
+
+        if n == 0 {
+            return;
+        }
+
+        let mut wakers: [Option<Waker>; NUM_WAKERS] = Default::default();
+        let mut curr_waker = 0;
+        let mut remaining = n;
+
+        // There may be waiters; the lock must be acquired to notify them.
+        let mut waiters = self.waiters.lock();
+
+        'outer: loop {
+            while curr_waker < NUM_WAKERS {
+                if remaining == 0 {
+                    break 'outer;
+                }
+
+                // The state must be (re)loaded while the lock is held. The
+                // state may only transition out of `WAITING` while the lock
+                // is held.
+                let curr = self.state.load(SeqCst);
+
+                if get_state(curr) != WAITING {
+                    // The wait list is empty. Store the one permit a `Notify`
+                    // can hold and stop; the remaining notifications are
+                    // dropped.
+                    notify_locked(&mut waiters, &self.state, curr);
+                    break 'outer;
+                }
+
+                if let Some(waker) = notify_locked(&mut waiters, &self.state, curr) {
+                    wakers[curr_waker] = Some(waker);
+                    curr_waker += 1;
+                }
+
+                remaining -= 1;
+            }
+
+            drop(waiters);
+
+            for waker in wakers.iter_mut().take(curr_waker) {
+                waker.take().unwrap().wake();
+            }
+
+            curr_waker = 0;
+
+            // Acquire the lock again.
+            waiters = self.waiters.lock();
+        }
+
+        // Release the lock before notifying.
+        drop(waiters);
+
+        for waker in wakers.iter_mut().take(curr_waker) {
+            waker.take().unwrap().wake();
+        }
+    }
+
     /// Notifies all waiting tasks
     ///
     /// If a task is currently waiting, that task is notified. Unlike with
@@ -462,6 +637,7 @@ impl Default for Notify {
     }
 }
 
+#[allow(clippy::never_loop)]
 fn notify_locked(waiters: &mut WaitList, state: &AtomicUsize, curr: usize) -> Option<Waker> {
     loop {
         match get_state(curr) {
@@ -525,150 +701,301 @@ impl Notified<'_> {
             (&me.notify, &mut me.state, &me.waiter)
         }
     }
+
+    /// Re-arms this future so it can be awaited again.
+    ///
+    /// After the future has completed, a pinned `Notified` can be re-armed to
+    /// wait for the **next** notification instead of constructing a new future
+    /// with [`Notify::notified`]. The waiter node is stored inline in the
+    /// `Notified` value, so hot loops waiting on the same `Notify` can reuse a
+    /// single waiter without any per-iteration setup.
+    ///
+    /// Calling `re_arm` on a future that has not completed cancels the current
+    /// wait first, exactly as dropping the future would: if a [`notify_one`]
+    /// permit had already been assigned to this waiter, it is passed on to the
+    /// next waiter in the queue.
+    ///
+    /// [`notify_one`]: Notify::notify_one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Notify;
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let notify = Arc::new(Notify::new());
+    ///     let notify2 = notify.clone();
+    ///
+    ///     tokio::spawn(async move {
+    ///         for _ in 0..3 {
+    ///             notify2.notify_one();
+    ///             tokio::task::yield_now().await;
+    ///         }
+    ///     });
+    ///
+    ///     let notified = notify.notified();
+    ///     tokio::pin!(notified);
+    ///
+    ///     for _ in 0..3 {
+    ///         notified.as_mut().await;
+    ///         notified.as_mut().re_arm();
+    ///     }
+    /// }
+    /// ```
+    pub fn re_arm(mut self: Pin<&mut Self>) {
+        {
+            let (notify, state, waiter) = self.as_mut().project();
+
+            if let State::Waiting = *state {
+                release_waiter(notify, waiter);
+            }
+        }
+
+        let (notify, state, waiter) = self.project();
+
+        // The waiter is no longer in the list; it is safe to reset its fields
+        // without holding the lock.
+        let w = unsafe { &mut *waiter.get() };
+        w.waker = None;
+        w.notified = None;
+
+        // Start over, observing `notify_waiters` calls from this point on.
+        let curr = notify.state.load(SeqCst);
+        *state = State::Init(curr >> NOTIFY_WAITERS_SHIFT);
+    }
+}
+
+/// Removes a registered waiter from the wait list, handing a stray `notify_one`
+/// notification to the next waiter in the queue.
+fn release_waiter(notify: &Notify, waiter: &UnsafeCell<Waiter>) {
+    let mut waiters = notify.waiters.lock();
+    let mut notify_state = notify.state.load(SeqCst);
+
+    // remove the entry from the list (if not already removed)
+    //
+    // safety: the waiter is only added to `waiters` by virtue of it
+    // being the only `LinkedList` available to the type.
+    unsafe { waiters.remove(NonNull::new_unchecked(waiter.get())) };
+
+    if waiters.is_empty() {
+        if let WAITING = get_state(notify_state) {
+            notify_state = set_state(notify_state, EMPTY);
+            notify.state.store(notify_state, SeqCst);
+        }
+    }
+
+    // See if the node was notified but not received. In this case, if
+    // the notification was triggered via `notify_one`, it must be sent
+    // to the next waiter.
+    //
+    // Safety: with the entry removed from the linked list, there can be
+    // no concurrent access to the entry
+    if let Some(NotificationType::OneWaiter) = unsafe { (*waiter.get()).notified } {
+        if let Some(waker) = notify_locked(&mut waiters, &notify.state, notify_state) {
+            drop(waiters);
+            waker.wake();
+        }
+    }
 }
 
 impl Future for Notified<'_> {
     type Output = ();
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
-        use State::*;
-
         let (notify, state, waiter) = self.project();
+        poll_notified(notify, state, waiter, cx)
+    }
+}
 
-        loop {
-            match *state {
-                Init(initial_notify_waiters_calls) => {
-                    let curr = notify.state.load(SeqCst);
-
-                    // Optimistically try acquiring a pending notification
-                    let res = notify.state.compare_exchange(
-                        set_state(curr, NOTIFIED),
-                        set_state(curr, EMPTY),
-                        SeqCst,
-                        SeqCst,
-                    );
-
-                    if res.is_ok() {
-                        // Acquired the notification
-                        *state = Done;
-                        return Poll::Ready(());
-                    }
+/// Polls a waiter towards completion. Shared between [`Notified`] and
+/// [`OwnedNotified`]; the caller guarantees `waiter` stays pinned while in the
+/// `Waiting` state.
+fn poll_notified(
+    notify: &Notify,
+    state: &mut State,
+    waiter: &UnsafeCell<Waiter>,
+    cx: &mut Context<'_>,
+) -> Poll<()> {
+    use State::*;
 
-                    // Acquire the lock and attempt to transition to the waiting
-                    // state.
-                    let mut waiters = notify.waiters.lock();
+    loop {
+        match *state {
+            Init(initial_notify_waiters_calls) => {
+                let curr = notify.state.load(SeqCst);
+
+                // Optimistically try acquiring a pending notification
+                let res = notify.state.compare_exchange(
+                    set_state(curr, NOTIFIED),
+                    set_state(curr, EMPTY),
+                    SeqCst,
+                    SeqCst,
+                );
+
+                if res.is_ok() {
+                    // Acquired the notification
+                    *state = Done;
+                    return Poll::Ready(());
+                }
 
-                    // Reload the state with the lock held
-                    let mut curr = notify.state.load(SeqCst);
+                // Acquire the lock and attempt to transition to the waiting
+                // state.
+                let mut waiters = notify.waiters.lock();
 
-                    // if notify_waiters has been called after the future
-                    // was created, then we are done
-                    if get_num_notify_waiters_calls(curr) != initial_notify_waiters_calls {
-                        *state = Done;
-                        return Poll::Ready(());
-                    }
+                // Reload the state with the lock held
+                let mut curr = notify.state.load(SeqCst);
 
-                    // Transition the state to WAITING.
-                    loop {
-                        match get_state(curr) {
-                            EMPTY => {
-                                // Transition to WAITING
-                                let res = notify.state.compare_exchange(
-                                    set_state(curr, EMPTY),
-                                    set_state(curr, WAITING),
-                                    SeqCst,
-                                    SeqCst,
-                                );
-
-                                if let Err(actual) = res {
-                                    assert_eq!(get_state(actual), NOTIFIED);
-                                    curr = actual;
-                                } else {
-                                    break;
-                                }
+                // if notify_waiters has been called after the future
+                // was created, then we are done
+                if get_num_notify_waiters_calls(curr) != initial_notify_waiters_calls {
+                    *state = Done;
+                    return Poll::Ready(());
+                }
+
+                // Transition the state to WAITING.
+                loop {
+                    match get_state(curr) {
+                        EMPTY => {
+                            // Transition to WAITING
+                            let res = notify.state.compare_exchange(
+                                set_state(curr, EMPTY),
+                                set_state(curr, WAITING),
+                                SeqCst,
+                                SeqCst,
+                            );
+
+                            if let Err(actual) = res {
+                                assert_eq!(get_state(actual), NOTIFIED);
+                                curr = actual;
+                            } else {
+                                break;
                             }
-                            WAITING => break,
-                            NOTIFIED => {
-                                // Try consuming the notification
-                                let res = notify.state.compare_exchange(
-                                    set_state(curr, NOTIFIED),
-                                    set_state(curr, EMPTY),
-                                    SeqCst,
-                                    SeqCst,
-                                );
-
-                                match res {
-                                    Ok(_) => {
-                                        // Acquired the notification
-                                        *state = Done;
-                                        return Poll::Ready(());
-                                    }
-                                    Err(actual) => {
-                                        assert_eq!(get_state(actual), EMPTY);
-                                        curr = actual;
-                                    }
+                        }
+                        WAITING => break,
+                        NOTIFIED => {
+                            // Try consuming the notification
+                            let res = notify.state.compare_exchange(
+                                set_state(curr, NOTIFIED),
+                                set_state(curr, EMPTY),
+                                SeqCst,
+                                SeqCst,
+                            );
+
+                            match res {
+                                Ok(_) => {
+                                    // Acquired the notification
+                                    *state = Done;
+                                    return Poll::Ready(());
+                                }
+                                Err(actual) => {
+                                    assert_eq!(get_state(actual), EMPTY);
+                                    curr = actual;
                                 }
                             }
-                            _ => unreachable!(),
                         }
+                        _ => unreachable!(),
                     }
+                }
 
-                    // Safety: called while locked.
-                    unsafe {
-                        (*waiter.get()).waker = Some(cx.waker().clone());
-                    }
-
-                    // Insert the waiter into the linked list
-                    //
-                    // safety: pointers from `UnsafeCell` are never null.
-                    waiters.push_front(unsafe { NonNull::new_unchecked(waiter.get()) });
+                // Safety: called while locked.
+                unsafe {
+                    (*waiter.get()).waker = Some(cx.waker().clone());
+                }
 
-                    *state = Waiting;
+                // Insert the waiter into the linked list
+                //
+                // safety: pointers from `UnsafeCell` are never null.
+                waiters.push_front(unsafe { NonNull::new_unchecked(waiter.get()) });
 
-                    return Poll::Pending;
-                }
-                Waiting => {
-                    // Currently in the "Waiting" state, implying the caller has
-                    // a waiter stored in the waiter list (guarded by
-                    // `notify.waiters`). In order to access the waker fields,
-                    // we must hold the lock.
-
-                    let waiters = notify.waiters.lock();
-
-                    // Safety: called while locked
-                    let w = unsafe { &mut *waiter.get() };
-
-                    if w.notified.is_some() {
-                        // Our waker has been notified. Reset the fields and
-                        // remove it from the list.
-                        w.waker = None;
-                        w.notified = None;
-
-                        *state = Done;
-                    } else {
-                        // Update the waker, if necessary.
-                        if !w.waker.as_ref().unwrap().will_wake(cx.waker()) {
-                            w.waker = Some(cx.waker().clone());
-                        }
+                *state = Waiting;
 
-                        return Poll::Pending;
+                return Poll::Pending;
+            }
+            Waiting => {
+                // Currently in the "Waiting" state, implying the caller has
+                // a waiter stored in the waiter list (guarded by
+                // `notify.waiters`). In order to access the waker fields,
+                // we must hold the lock.
+
+                let waiters = notify.waiters.lock();
+
+                // Safety: called while locked
+                let w = unsafe { &mut *waiter.get() };
+
+                if w.notified.is_some() {
+                    // Our waker has been notified. Reset the fields and
+                    // remove it from the list.
+                    w.waker = None;
+                    w.notified = None;
+
+                    *state = Done;
+                } else {
+                    // Update the waker, if necessary.
+                    if !w.waker.as_ref().unwrap().will_wake(cx.waker()) {
+                        w.waker = Some(cx.waker().clone());
                     }
 
-                    // Explicit drop of the lock to indicate the scope that the
-                    // lock is held. Because holding the lock is required to
-                    // ensure safe access to fields not held within the lock, it
-                    // is helpful to visualize the scope of the critical
-                    // section.
-                    drop(waiters);
-                }
-                Done => {
-                    return Poll::Ready(());
+                    return Poll::Pending;
                 }
+
+                // Explicit drop of the lock to indicate the scope that the
+                // lock is held. Because holding the lock is required to
+                // ensure safe access to fields not held within the lock, it
+                // is helpful to visualize the scope of the critical
+                // section.
+                drop(waiters);
+            }
+            Done => {
+                return Poll::Ready(());
             }
         }
     }
 }
 
+// ===== impl OwnedNotified =====
+
+impl OwnedNotified {
+    /// A custom `project` implementation is used in place of `pin-project-lite`
+    /// as a custom drop implementation is needed.
+    fn project(self: Pin<&mut Self>) -> (&Notify, &mut State, &UnsafeCell<Waiter>) {
+        unsafe {
+            // Safety: `notify` and `state` are `Unpin`, and the waiter is
+            // never moved while in the `Waiting` state.
+
+            is_unpin::<Arc<Notify>>();
+            is_unpin::<AtomicUsize>();
+
+            let me = self.get_unchecked_mut();
+            (&me.notify, &mut me.state, &me.waiter)
+        }
+    }
+}
+
+impl Future for OwnedNotified {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let (notify, state, waiter) = self.project();
+        poll_notified(notify, state, waiter, cx)
+    }
+}
+
+impl Drop for OwnedNotified {
+    fn drop(&mut self) {
+        use State::*;
+
+        // Safety: The type only transitions to a "Waiting" state when pinned.
+        let (notify, state, waiter) = unsafe { Pin::new_unchecked(self).project() };
+
+        // See `Drop for Notified`: the waiter entry must be removed from the
+        // linked list before the memory is released.
+        if let Waiting = *state {
+            release_waiter(notify, waiter);
+        }
+    }
+}
+
 impl Drop for Notified<'_> {
     fn drop(&mut self) {
         use State::*;
@@ -680,34 +1007,7 @@ impl Drop for Notified<'_> {
         // dropped, which means we must ensure that the waiter entry is no
         // longer stored in the linked list.
         if let Waiting = *state {
-            let mut waiters = notify.waiters.lock();
-            let mut notify_state = notify.state.load(SeqCst);
-
-            // remove the entry from the list (if not already removed)
-            //
-            // safety: the waiter is only added to `waiters` by virtue of it
-            // being the only `LinkedList` available to the type.
-            unsafe { waiters.remove(NonNull::new_unchecked(waiter.get())) };
-
-            if waiters.is_empty() {
-                if let WAITING = get_state(notify_state) {
-                    notify_state = set_state(notify_state, EMPTY);
-                    notify.state.store(notify_state, SeqCst);
-                }
-            }
-
-            // See if the node was notified but not received. In this case, if
-            // the notification was triggered via `notify_one`, it must be sent
-            // to the next waiter.
-            //
-            // Safety: with the entry removed from the linked list, there can be
-            // no concurrent access to the entry
-            if let Some(NotificationType::OneWaiter) = unsafe { (*waiter.get()).notified } {
-                if let Some(waker) = notify_locked(&mut waiters, &notify.state, notify_state) {
-                    drop(waiters);
-                    waker.wake();
-                }
-            }
+            release_waiter(notify, waiter);
         }
     }
 }
@@ -319,6 +319,86 @@ impl<T> OnceCell<T> {
         }
     }
 
+    /// Tries to initialize the value of the OnceCell using the async function
+    /// `f`, retrying `attempts` times before giving up. If the value of the
+    /// OnceCell was already initialized prior to this call, a reference to
+    /// that initialized value is returned.
+    ///
+    /// Unlike [`OnceCell::get_or_try_init`], a failed call to `f` does not
+    /// return immediately: the init slot is handed to the next waiting task
+    /// and this call re-queues behind it, so at most one initialization
+    /// attempt is in flight at a time even when many tasks retry. The error
+    /// of the final attempt is returned once `attempts` calls to `f` have
+    /// failed.
+    ///
+    /// This will deadlock if `f` tries to initialize the cell itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `attempts` is zero.
+    pub async fn get_or_try_init_with_retry<E, F, Fut>(
+        &self,
+        mut attempts: u32,
+        mut f: F,
+    ) -> Result<&T, E>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        assert!(attempts > 0, "`attempts` must be at least one");
+
+        loop {
+            if self.initialized() {
+                // SAFETY: once the value is initialized, no mutable references are given out, so
+                // we can give out arbitrarily many immutable references
+                return unsafe { Ok(self.get_unchecked()) };
+            }
+
+            match self.semaphore.acquire().await {
+                Ok(_permit) => {
+                    if !self.initialized() {
+                        // If `f()` panics or `select!` is called, this call
+                        // is aborted and the semaphore permit is dropped.
+                        match f().await {
+                            Ok(value) => {
+                                // SAFETY: There is only one permit on the semaphore, hence only one
+                                // mutable reference is created
+                                unsafe { self.set_value(value) };
+
+                                // SAFETY: once the value is initialized, no mutable references are given out, so
+                                // we can give out arbitrarily many immutable references
+                                return unsafe { Ok(self.get_unchecked()) };
+                            }
+                            Err(e) => {
+                                attempts -= 1;
+                                if attempts == 0 {
+                                    return Err(e);
+                                }
+
+                                // Dropping the permit hands the init slot to
+                                // the next waiting task; loop around to
+                                // re-queue behind it.
+                            }
+                        }
+                    } else {
+                        unreachable!("acquired semaphore after value was already initialized.");
+                    }
+                }
+                Err(_) => {
+                    if self.initialized() {
+                        // SAFETY: once the value is initialized, no mutable references are given out, so
+                        // we can give out arbitrarily many immutable references
+                        return unsafe { Ok(self.get_unchecked()) };
+                    } else {
+                        unreachable!(
+                            "Semaphore closed, but the OnceCell has not been initialized."
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     /// Moves the value out of the cell, destroying the cell in the process.
     ///
     /// Returns `None` if the cell is uninitialized.
@@ -340,6 +420,86 @@ impl<T> OnceCell<T> {
     }
 }
 
+cfg_time! {
+    use super::AcquireTimeoutError;
+
+    /// Error returned from [`OnceCell::get_or_init_timeout`]: the cell was
+    /// not initialized before the deadline.
+    ///
+    /// [`OnceCell::get_or_init_timeout`]: crate::sync::OnceCell::get_or_init_timeout
+    #[derive(Debug, PartialEq)]
+    pub struct InitTimeoutError(());
+
+    impl fmt::Display for InitTimeoutError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "timed out waiting for initialization")
+        }
+    }
+
+    impl Error for InitTimeoutError {}
+
+    impl<T> OnceCell<T> {
+        /// Tries to initialize the value of the OnceCell using the async
+        /// function `f`, waiting at most `timeout` for the init slot. If the
+        /// value of the OnceCell was already initialized prior to this call,
+        /// a reference to that initialized value is returned.
+        ///
+        /// The deadline bounds the wait for the init slot, including the time
+        /// spent waiting on initializations run by other tasks. Once `f` has
+        /// started, it runs to completion; this method does not abort the
+        /// caller's own initializer.
+        ///
+        /// This will deadlock if `f` tries to initialize the cell itself.
+        pub async fn get_or_init_timeout<F, Fut>(
+            &self,
+            timeout: crate::time::Duration,
+            f: F,
+        ) -> Result<&T, InitTimeoutError>
+        where
+            F: FnOnce() -> Fut,
+            Fut: Future<Output = T>,
+        {
+            if self.initialized() {
+                // SAFETY: once the value is initialized, no mutable references are given out, so
+                // we can give out arbitrarily many immutable references
+                return unsafe { Ok(self.get_unchecked()) };
+            }
+
+            match self.semaphore.acquire_timeout(1, timeout).await {
+                Ok(_permit) => {
+                    if !self.initialized() {
+                        // If `f()` panics or `select!` is called, this call
+                        // is aborted and the semaphore permit is dropped.
+                        let value = f().await;
+
+                        // SAFETY: There is only one permit on the semaphore, hence only one
+                        // mutable reference is created
+                        unsafe { self.set_value(value) };
+
+                        // SAFETY: once the value is initialized, no mutable references are given out, so
+                        // we can give out arbitrarily many immutable references
+                        unsafe { Ok(self.get_unchecked()) }
+                    } else {
+                        unreachable!("acquired semaphore after value was already initialized.");
+                    }
+                }
+                Err(AcquireTimeoutError::Closed) => {
+                    if self.initialized() {
+                        // SAFETY: once the value is initialized, no mutable references are given out, so
+                        // we can give out arbitrarily many immutable references
+                        unsafe { Ok(self.get_unchecked()) }
+                    } else {
+                        unreachable!(
+                            "Semaphore closed, but the OnceCell has not been initialized."
+                        );
+                    }
+                }
+                Err(AcquireTimeoutError::TimedOut) => Err(InitTimeoutError(())),
+            }
+        }
+    }
+}
+
 // Since `get` gives us access to immutable references of the
 // OnceCell, OnceCell can only be Sync if T is Sync, otherwise
 // OnceCell would allow sharing references of !Sync values across
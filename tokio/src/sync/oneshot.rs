@@ -125,6 +125,40 @@ pub struct Receiver<T> {
     inner: Option<Arc<Inner<T>>>,
 }
 
+/// A oneshot channel whose shared state is allocated once and reused.
+///
+/// Each call to [`pair`] hands out a fresh [`Sender`]/[`Receiver`] pair
+/// backed by the same allocation, resetting any state left over from the
+/// previous cycle. This avoids one `Arc` allocation per request in hot
+/// request/response paths that would otherwise call [`channel`] millions of
+/// times.
+///
+/// A new cycle can only start once both handles from the previous cycle have
+/// been dropped; [`pair`] returns `None` until then.
+///
+/// [`pair`]: Reusable::pair
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::oneshot;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut slot = oneshot::Reusable::new();
+///
+///     for i in 0..3 {
+///         let (tx, rx) = slot.pair().unwrap();
+///         tx.send(i).unwrap();
+///         assert_eq!(rx.await.unwrap(), i);
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Reusable<T> {
+    inner: Arc<Inner<T>>,
+}
+
 pub mod error {
     //! Oneshot error types
 
@@ -144,6 +178,17 @@ pub mod error {
         Closed,
     }
 
+    /// Error returned by the `send_with` function on `Sender`.
+    pub enum SendWithError<T, F> {
+        /// The receive half of the channel was already closed; the closure
+        /// was never invoked and is handed back.
+        Closed(F),
+
+        /// The receive half closed while the value was being constructed.
+        /// The constructed value is handed back.
+        Unsent(T),
+    }
+
     // ===== impl RecvError =====
 
     impl fmt::Display for RecvError {
@@ -166,6 +211,30 @@ pub mod error {
     }
 
     impl std::error::Error for TryRecvError {}
+
+    // ===== impl SendWithError =====
+
+    // Implemented by hand so the error carries no `Debug` bounds; the
+    // closure variant could not satisfy a derived one.
+    impl<T, F> fmt::Debug for SendWithError<T, F> {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                SendWithError::Closed(..) => fmt.debug_struct("Closed").finish(),
+                SendWithError::Unsent(..) => fmt.debug_struct("Unsent").finish(),
+            }
+        }
+    }
+
+    impl<T, F> fmt::Display for SendWithError<T, F> {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                SendWithError::Closed(..) => write!(fmt, "channel closed"),
+                SendWithError::Unsent(..) => write!(fmt, "channel closed while constructing the value"),
+            }
+        }
+    }
+
+    impl<T, F> std::error::Error for SendWithError<T, F> {}
 }
 
 use self::error::*;
@@ -323,6 +392,51 @@ impl<T> Sender<T> {
         Ok(())
     }
 
+    /// Lazily constructs and sends a value on this channel.
+    ///
+    /// The closure is only invoked if the [`Receiver`] is still around, so a
+    /// value that is expensive to produce — a serialized response buffer, for
+    /// example — is never constructed for a caller that already went away.
+    /// Apart from that, this behaves like [`send`].
+    ///
+    /// # Errors
+    ///
+    /// * [`SendWithError::Closed`] if the receiver was already gone. The
+    ///   closure is handed back uninvoked.
+    /// * [`SendWithError::Unsent`] if the receiver disappeared while the
+    ///   value was being constructed. The constructed value is handed back.
+    ///
+    /// [`send`]: Sender::send
+    /// [`SendWithError::Closed`]: error::SendWithError::Closed
+    /// [`SendWithError::Unsent`]: error::SendWithError::Unsent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::oneshot;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, rx) = oneshot::channel::<Vec<u8>>();
+    ///     drop(rx);
+    ///
+    ///     // The serialization closure never runs.
+    ///     assert!(tx.send_with(|| vec![0; 1024 * 1024]).is_err());
+    /// }
+    /// ```
+    pub fn send_with<F>(self, f: F) -> Result<(), error::SendWithError<T, F>>
+    where
+        F: FnOnce() -> T,
+    {
+        if self.is_closed() {
+            return Err(error::SendWithError::Closed(f));
+        }
+
+        // The receiver can still disappear between the check and the send;
+        // in that case `send` hands the constructed value back.
+        self.send(f()).map_err(error::SendWithError::Unsent)
+    }
+
     /// Waits for the associated [`Receiver`] handle to close.
     ///
     /// A [`Receiver`] is closed by either calling [`close`] explicitly or the
@@ -678,6 +792,50 @@ impl<T> Receiver<T> {
         self.inner = None;
         result
     }
+
+    /// Returns `true` if a value has been sent and is ready to be received.
+    ///
+    /// When this returns `true`, the next call to [`try_recv`] returns the
+    /// value and awaiting the `Receiver` completes immediately. A `false`
+    /// result means either that no value has been sent yet, that the value
+    /// was already received, or that the [`Sender`] was dropped without
+    /// sending.
+    ///
+    /// [`try_recv`]: Receiver::try_recv
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::oneshot;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = oneshot::channel();
+    ///
+    ///     assert!(!rx.is_ready());
+    ///
+    ///     tx.send("hello").unwrap();
+    ///     assert!(rx.is_ready());
+    ///
+    ///     rx.try_recv().unwrap();
+    ///     assert!(!rx.is_ready());
+    /// }
+    /// ```
+    pub fn is_ready(&self) -> bool {
+        let inner = match self.inner.as_ref() {
+            Some(inner) => inner,
+            // The value was already received.
+            None => return false,
+        };
+
+        let state = State::load(&inner.state, Acquire);
+
+        // `complete` is also set when the sender drops without sending, in
+        // which case no value is present. The `Acquire` load above
+        // synchronizes with the sender's write of the value, and only the
+        // receive half reads it, so peeking here cannot race.
+        state.is_complete() && inner.value.with(|ptr| unsafe { (*ptr).is_some() })
+    }
 }
 
 impl<T> Drop for Receiver<T> {
@@ -704,6 +862,47 @@ impl<T> Future for Receiver<T> {
     }
 }
 
+impl<T> Reusable<T> {
+    /// Creates a reusable oneshot channel with no outstanding handles.
+    pub fn new() -> Reusable<T> {
+        Reusable {
+            inner: Arc::new(Inner {
+                state: AtomicUsize::new(State::new().as_usize()),
+                value: UnsafeCell::new(None),
+                tx_task: Task(UnsafeCell::new(MaybeUninit::uninit())),
+                rx_task: Task(UnsafeCell::new(MaybeUninit::uninit())),
+            }),
+        }
+    }
+
+    /// Starts a new cycle, handing out the [`Sender`] and [`Receiver`].
+    ///
+    /// Any state left from the previous cycle is cleared, including a value
+    /// that was sent but never received. Returns `None` while a handle from
+    /// the previous cycle is still alive, since the shared state cannot be
+    /// reset while it may be observed.
+    pub fn pair(&mut self) -> Option<(Sender<T>, Receiver<T>)> {
+        // Unique access proves both previous handles are gone and makes the
+        // reset race-free.
+        Arc::get_mut(&mut self.inner)?.reset();
+
+        let tx = Sender {
+            inner: Some(self.inner.clone()),
+        };
+        let rx = Receiver {
+            inner: Some(self.inner.clone()),
+        };
+
+        Some((tx, rx))
+    }
+}
+
+impl<T> Default for Reusable<T> {
+    fn default() -> Reusable<T> {
+        Reusable::new()
+    }
+}
+
 impl<T> Inner<T> {
     fn complete(&self) -> bool {
         let prev = State::set_complete(&self.state);
@@ -827,6 +1026,31 @@ impl<T> Drop for Inner<T> {
     }
 }
 
+impl<T> Inner<T> {
+    /// Returns the cell to its freshly-constructed state for the next
+    /// [`Reusable`] cycle. Requires unique access, which guarantees no
+    /// handle from the previous cycle can observe the reset.
+    fn reset(&mut self) {
+        let state = State(mut_load(&mut self.state));
+
+        // Release the wakers exactly as `drop` would.
+        if state.is_rx_task_set() {
+            unsafe {
+                self.rx_task.drop_task();
+            }
+        }
+
+        if state.is_tx_task_set() {
+            unsafe {
+                self.tx_task.drop_task();
+            }
+        }
+
+        self.state = AtomicUsize::new(State::new().as_usize());
+        self.value = UnsafeCell::new(None);
+    }
+}
+
 impl<T: fmt::Debug> fmt::Debug for Inner<T> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         use std::sync::atomic::Ordering::Relaxed;
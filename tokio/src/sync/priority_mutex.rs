@@ -0,0 +1,504 @@
+use crate::loom::cell::UnsafeCell;
+use crate::loom::sync::Mutex;
+use crate::sync::mutex::TryLockError;
+use crate::util::linked_list::{self, LinkedList};
+
+use std::cell::{Cell, UnsafeCell as StdUnsafeCell};
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomPinned;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::task::{Context, Poll, Waker};
+
+/// An asynchronous `Mutex`-like type that grants the lock to waiters by
+/// priority.
+///
+/// Where [`Mutex`] queues contending tasks strictly first-in-first-out, a
+/// `PriorityMutex` is locked with [`lock_with_priority`], and when the lock
+/// is released it is handed to the waiter with the **highest** priority.
+/// Waiters with equal priority are served in FIFO order, so a stream of
+/// high-priority lockers cannot reorder among themselves, but it can starve
+/// lower-priority waiters indefinitely.
+///
+/// This is useful when a shared resource has a latency-sensitive path and a
+/// background path: for example, a write-ahead-log lock where commit-path
+/// lockers must be admitted before background compaction.
+///
+/// The lock is handed off directly: releasing the lock while tasks are
+/// waiting transfers ownership to the chosen waiter rather than leaving the
+/// lock open to be stolen by a new arrival.
+///
+/// [`Mutex`]: crate::sync::Mutex
+/// [`lock_with_priority`]: PriorityMutex::lock_with_priority
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::PriorityMutex;
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let wal = Arc::new(PriorityMutex::new(Vec::new()));
+///
+///     // The commit path locks with a high priority...
+///     let commit = wal.clone();
+///     let commit = tokio::spawn(async move {
+///         commit.lock_with_priority(10).await.push("commit");
+///     });
+///
+///     // ...and is admitted before background compaction under contention.
+///     let compact = wal.clone();
+///     let compact = tokio::spawn(async move {
+///         compact.lock_with_priority(0).await.push("compact");
+///     });
+///
+///     commit.await.unwrap();
+///     compact.await.unwrap();
+///
+///     assert_eq!(2, wal.lock_with_priority(0).await.len());
+/// }
+/// ```
+pub struct PriorityMutex<T: ?Sized> {
+    /// The lock state and the list of waiting tasks.
+    waiters: Mutex<Waitlist>,
+
+    /// The data protected by the mutex.
+    c: StdUnsafeCell<T>,
+}
+
+/// A handle to a held `PriorityMutex`.
+///
+/// The guard can be held across any `.await` point as it is [`Send`].
+///
+/// As long as you have this guard, you have exclusive access to the
+/// underlying `T`. The guard internally borrows the `PriorityMutex`, so the
+/// mutex will not be dropped while a guard exists. The lock is automatically
+/// released whenever the guard is dropped.
+pub struct PriorityMutexGuard<'a, T: ?Sized> {
+    lock: &'a PriorityMutex<T>,
+}
+
+/// State protected by the waiters lock.
+struct Waitlist {
+    /// True when the lock is held, either by a guard or because it was
+    /// handed off to a waiter that has not been polled yet.
+    locked: bool,
+
+    /// Waiting tasks, in no particular order: the release path scans the
+    /// list for the highest-priority waiter.
+    queue: LinkedList<Waiter, <Waiter as linked_list::Link>::Target>,
+}
+
+/// An entry in the wait queue.
+struct Waiter {
+    /// Priority this waiter locked with.
+    priority: usize,
+
+    /// True while the waiter is in the queue.
+    ///
+    /// # Safety
+    ///
+    /// May only be accessed while the wait queue is locked.
+    queued: Cell<bool>,
+
+    /// Set to true when the lock is handed off to this waiter.
+    ///
+    /// # Safety
+    ///
+    /// May only be accessed while the wait queue is locked. `Cell` is used
+    /// as the release path only holds a shared reference to the waiter.
+    granted: Cell<bool>,
+
+    /// The waker to notify when the lock is handed off.
+    ///
+    /// # Safety
+    ///
+    /// May only be accessed while the wait queue is locked.
+    waker: UnsafeCell<Option<Waker>>,
+
+    /// Intrusive linked-list pointers.
+    ///
+    /// # Safety
+    ///
+    /// May only be accessed while the wait queue is locked.
+    pointers: linked_list::Pointers<Waiter>,
+
+    /// Should not be `Unpin`.
+    _p: PhantomPinned,
+}
+
+/// Future returned by [`PriorityMutex::lock_with_priority`].
+struct Acquire<'a, T: ?Sized> {
+    /// The mutex being locked.
+    mutex: &'a PriorityMutex<T>,
+
+    /// True once the future completed and ownership of the lock moved into
+    /// the returned guard.
+    acquired: bool,
+
+    /// Entry in the waiter `LinkedList`.
+    waiter: UnsafeCell<Waiter>,
+}
+
+// As long as T: Send, it's fine to send and share PriorityMutex<T> between
+// threads. The waiter list is only touched under the internal lock.
+unsafe impl<T> Send for PriorityMutex<T> where T: ?Sized + Send {}
+unsafe impl<T> Sync for PriorityMutex<T> where T: ?Sized + Send {}
+unsafe impl<T> Sync for PriorityMutexGuard<'_, T> where T: ?Sized + Send + Sync {}
+
+unsafe impl<'a, T: ?Sized + Send> Send for Acquire<'a, T> {}
+unsafe impl<'a, T: ?Sized + Send> Sync for Acquire<'a, T> {}
+
+impl<T: Sized> PriorityMutex<T> {
+    /// Creates a new priority mutex in an unlocked state ready for use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::PriorityMutex;
+    ///
+    /// let lock = PriorityMutex::new(5);
+    /// ```
+    pub fn new(t: T) -> PriorityMutex<T> {
+        PriorityMutex {
+            waiters: Mutex::new(Waitlist {
+                locked: false,
+                queue: LinkedList::new(),
+            }),
+            c: StdUnsafeCell::new(t),
+        }
+    }
+
+    /// Consumes the mutex, returning the underlying data.
+    pub fn into_inner(self) -> T {
+        self.c.into_inner()
+    }
+}
+
+impl<T: ?Sized> PriorityMutex<T> {
+    /// Locks this mutex with the given priority, causing the current task to
+    /// yield until the lock has been acquired.
+    ///
+    /// When the lock is released while several tasks are waiting, it is
+    /// handed to the waiter that locked with the **largest** `priority`
+    /// value. Waiters with equal priority acquire the lock in the order they
+    /// called `lock_with_priority`. Note that a steady stream of
+    /// high-priority lockers starves lower-priority waiters.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method uses a queue to fairly distribute locks among waiters of
+    /// equal priority. Cancelling a call to `lock_with_priority` removes the
+    /// calling task from the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::PriorityMutex;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mutex = PriorityMutex::new(1);
+    ///
+    ///     let mut n = mutex.lock_with_priority(0).await;
+    ///     *n = 2;
+    /// }
+    /// ```
+    pub async fn lock_with_priority(&self, priority: usize) -> PriorityMutexGuard<'_, T> {
+        Acquire::new(self, priority).await
+    }
+
+    /// Attempts to acquire the lock, and returns [`TryLockError`] if the
+    /// lock is currently held somewhere else.
+    ///
+    /// This succeeds even if tasks are waiting for the lock: a waiter is
+    /// only entitled to the lock once it has been handed off at release
+    /// time.
+    ///
+    /// [`TryLockError`]: crate::sync::TryLockError
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::PriorityMutex;
+    ///
+    /// # fn main() {
+    /// let mutex = PriorityMutex::new(1);
+    ///
+    /// let n = mutex.try_lock().unwrap();
+    /// assert_eq!(*n, 1);
+    /// # }
+    /// ```
+    pub fn try_lock(&self) -> Result<PriorityMutexGuard<'_, T>, TryLockError> {
+        let mut waiters = self.waiters.lock();
+
+        if waiters.locked {
+            return Err(TryLockError(()));
+        }
+
+        waiters.locked = true;
+
+        Ok(PriorityMutexGuard { lock: self })
+    }
+
+    /// Returns a mutable reference to the underlying data.
+    ///
+    /// Since this call borrows the `PriorityMutex` mutably, no actual
+    /// locking needs to take place -- the mutable borrow statically
+    /// guarantees no locks exist.
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe {
+            // Safety: This is https://github.com/rust-lang/rust/pull/76936
+            &mut *self.c.get()
+        }
+    }
+
+    /// Releases the lock, handing it to the highest-priority waiter if any.
+    fn release(&self) {
+        let mut waiters = self.waiters.lock();
+
+        let waker = match waiters.highest_priority_waiter() {
+            Some(mut waiter) => {
+                // Safety: the wait queue is locked and the waiter was just
+                // removed from the queue, so nothing else references it.
+                let waiter = unsafe { waiter.as_mut() };
+
+                // The lock stays marked as held: ownership is transferred
+                // directly to the waiter.
+                waiter.queued.set(false);
+                waiter.granted.set(true);
+                waiter.waker.with_mut(|ptr| unsafe { (*ptr).take() })
+            }
+            None => {
+                waiters.locked = false;
+                None
+            }
+        };
+
+        // Wake without holding the lock.
+        drop(waiters);
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl Waitlist {
+    /// Removes and returns the waiter that should acquire the lock next.
+    ///
+    /// This is the waiter with the largest priority; ties are broken in
+    /// favor of the waiter that has been queued the longest.
+    fn highest_priority_waiter(&mut self) -> Option<NonNull<Waiter>> {
+        let mut best: Option<NonNull<Waiter>> = None;
+
+        // Waiters are pushed onto the front of the queue, so iterating from
+        // the head visits the newest waiter first. Using `>=` when comparing
+        // lets older waiters of equal priority win.
+        for waiter in self.queue.iter() {
+            let better = match best {
+                Some(best) => {
+                    // Safety: the wait queue is locked.
+                    waiter.priority >= unsafe { best.as_ref() }.priority
+                }
+                None => true,
+            };
+
+            if better {
+                best = Some(NonNull::from(waiter));
+            }
+        }
+
+        let best = best?;
+
+        // Safety: the wait queue is locked and `best` is a member of it.
+        unsafe { self.queue.remove(best) }
+    }
+}
+
+impl<'a, T: ?Sized> Acquire<'a, T> {
+    fn new(mutex: &'a PriorityMutex<T>, priority: usize) -> Acquire<'a, T> {
+        Acquire {
+            mutex,
+            acquired: false,
+            waiter: UnsafeCell::new(Waiter {
+                priority,
+                queued: Cell::new(false),
+                granted: Cell::new(false),
+                waker: UnsafeCell::new(None),
+                pointers: linked_list::Pointers::new(),
+                _p: PhantomPinned,
+            }),
+        }
+    }
+
+    /// A custom `project` implementation is used in place of
+    /// `pin-project-lite` as a custom drop implementation is needed.
+    fn project(self: Pin<&mut Self>) -> (&'a PriorityMutex<T>, &mut bool, &UnsafeCell<Waiter>) {
+        unsafe {
+            // Safety: neither field requires structural pinning; the waiter
+            // cell is never moved as `Acquire` itself is pinned.
+            let me = self.get_unchecked_mut();
+            (me.mutex, &mut me.acquired, &me.waiter)
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Future for Acquire<'a, T> {
+    type Output = PriorityMutexGuard<'a, T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<PriorityMutexGuard<'a, T>> {
+        let (mutex, acquired, waiter) = self.project();
+
+        let mut waiters = mutex.waiters.lock();
+
+        // Safety: the wait queue is locked.
+        let granted = waiter.with(|ptr| unsafe { (*ptr).granted.get() });
+
+        if granted {
+            // The lock was handed off by `release`; it is already marked as
+            // held on this waiter's behalf.
+            *acquired = true;
+            return Poll::Ready(PriorityMutexGuard { lock: mutex });
+        }
+
+        if !waiters.locked {
+            waiters.locked = true;
+            *acquired = true;
+            return Poll::Ready(PriorityMutexGuard { lock: mutex });
+        }
+
+        // Safety: called while locked; the waiter is pinned for the
+        // lifetime of this future and removed from the queue on drop.
+        unsafe {
+            waiter.with_mut(|ptr| {
+                (*ptr).waker.with_mut(|waker| {
+                    // Only clone a new waker if the stored one would not
+                    // wake the current task.
+                    match *waker {
+                        Some(ref w) if w.will_wake(cx.waker()) => {}
+                        _ => {
+                            *waker = Some(cx.waker().clone());
+                        }
+                    }
+                });
+
+                if !(*ptr).queued.get() {
+                    (*ptr).queued.set(true);
+                    waiters.queue.push_front(NonNull::new_unchecked(&mut *ptr));
+                }
+            });
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<'a, T: ?Sized> Drop for Acquire<'a, T> {
+    fn drop(&mut self) {
+        // If the future completed, the waiter was already dequeued and the
+        // lock belongs to the returned guard.
+        if self.acquired {
+            return;
+        }
+
+        let mut waiters = self.mutex.waiters.lock();
+
+        // Safety: the wait queue is locked.
+        let granted = self.waiter.with_mut(|ptr| unsafe {
+            if (*ptr).queued.get() {
+                (*ptr).queued.set(false);
+                waiters.queue.remove(NonNull::new_unchecked(&mut *ptr));
+            }
+            (*ptr).granted.get()
+        });
+
+        drop(waiters);
+
+        if granted {
+            // The lock was handed off but the future was cancelled before
+            // observing it; release the lock again.
+            self.mutex.release();
+        }
+    }
+}
+
+impl<T> From<T> for PriorityMutex<T> {
+    fn from(s: T) -> Self {
+        Self::new(s)
+    }
+}
+
+impl<T> Default for PriorityMutex<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for PriorityMutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("PriorityMutex");
+        match self.try_lock() {
+            Ok(inner) => d.field("data", &&*inner),
+            Err(_) => d.field("data", &format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+impl<T: ?Sized> Drop for PriorityMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.release()
+    }
+}
+
+impl<T: ?Sized> Deref for PriorityMutexGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.lock.c.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for PriorityMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut *self.lock.c.get() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for PriorityMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for PriorityMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+/// # Safety
+///
+/// `Waiter` is forced to be !Unpin.
+unsafe impl linked_list::Link for Waiter {
+    type Handle = NonNull<Waiter>;
+    type Target = Waiter;
+
+    fn as_raw(handle: &NonNull<Waiter>) -> NonNull<Waiter> {
+        *handle
+    }
+
+    unsafe fn from_raw(ptr: NonNull<Waiter>) -> NonNull<Waiter> {
+        ptr
+    }
+
+    unsafe fn pointers(mut target: NonNull<Waiter>) -> NonNull<linked_list::Pointers<Waiter>> {
+        NonNull::from(&mut target.as_mut().pointers)
+    }
+}
@@ -0,0 +1,242 @@
+use crate::loom::sync::atomic::AtomicUsize;
+use crate::loom::sync::Mutex;
+use crate::sync::batch_semaphore as semaphore;
+use crate::time::{Duration, Instant};
+
+use std::cmp;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::Ordering::SeqCst;
+use std::task::Poll;
+
+/// An asynchronous token-bucket rate limiter.
+///
+/// A `RateLimiter` hands out up to `rate` tokens per `period`, with up to
+/// [`burst`] tokens saved up for spiky callers. Tokens accrue continuously:
+/// a limiter configured with 100 tokens per second makes one token
+/// available every 10 milliseconds. Unlike a [`Semaphore`] permit, an
+/// acquired token is consumed, not returned.
+///
+/// Tokens are handed to waiting tasks in the order the tasks called
+/// [`acquire`], so a large request cannot be starved by a stream of small
+/// ones.
+///
+/// [`burst`]: RateLimiter::burst
+/// [`acquire`]: RateLimiter::acquire
+/// [`Semaphore`]: crate::sync::Semaphore
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::RateLimiter;
+/// use tokio::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Two requests per hour, both available up front.
+///     let limiter = RateLimiter::new(2, Duration::from_secs(3600));
+///
+///     limiter.acquire(1).await;
+///     assert!(limiter.try_acquire(1));
+///
+///     // The bucket is now empty; the next token is an half hour away.
+///     assert!(!limiter.try_acquire(1));
+/// }
+/// ```
+pub struct RateLimiter {
+    /// Available tokens. Tokens are added by `refill` and never exceed
+    /// `burst`; `acquire` queues on this semaphore when the bucket is empty.
+    s: semaphore::Semaphore,
+
+    /// Tokens added per `period`.
+    rate: u64,
+
+    /// The interval over which `rate` tokens accrue.
+    period: Duration,
+
+    /// Maximum number of tokens the bucket holds; accrual beyond this is
+    /// discarded.
+    burst: AtomicUsize,
+
+    /// The instant up to which accrued tokens have been credited. Guarded by
+    /// a mutex so that concurrent refills do not credit the same elapsed
+    /// time twice.
+    refilled: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter releasing `rate` tokens per `period`, with
+    /// a burst size of `rate`.
+    ///
+    /// The bucket starts full: the first `rate` tokens are available
+    /// immediately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` is zero or `period` is zero.
+    pub fn new(rate: u64, period: Duration) -> RateLimiter {
+        assert!(rate > 0, "a rate limiter requires a non-zero rate");
+        assert!(
+            period > Duration::from_nanos(0),
+            "a rate limiter requires a non-zero period"
+        );
+
+        RateLimiter {
+            s: semaphore::Semaphore::new(rate as usize),
+            rate,
+            period,
+            burst: AtomicUsize::new(rate as usize),
+            refilled: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Returns the maximum number of tokens the bucket holds.
+    pub fn burst(&self) -> usize {
+        self.burst.load(SeqCst)
+    }
+
+    /// Sets the maximum number of tokens the bucket holds.
+    ///
+    /// Growing the burst size takes effect as tokens accrue; it does not
+    /// create tokens retroactively. Shrinking it discards already-accrued
+    /// tokens above the new limit. Tasks already waiting in [`acquire`] for
+    /// more tokens than the new burst size will never complete.
+    ///
+    /// [`acquire`]: RateLimiter::acquire
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    pub fn set_burst(&self, burst: usize) {
+        assert!(burst > 0, "a rate limiter requires a non-zero burst size");
+
+        // The `refilled` lock serializes this against `refill`, so the
+        // available count cannot grow between the load and the reduction.
+        let refilled = self.refilled.lock();
+        self.burst.store(burst, SeqCst);
+
+        let available = self.s.available_permits();
+        if available > burst {
+            // A concurrent `acquire` may have taken tokens since the load;
+            // the resulting underflow is paid down by the next refill.
+            self.s.reduce_permits(available - burst);
+        }
+        drop(refilled);
+    }
+
+    /// Acquires `n` tokens, waiting until they have accrued.
+    ///
+    /// Tokens are granted to callers in the order they arrived.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If the future is dropped before
+    /// completing, no tokens were consumed and the task's place in the
+    /// queue is given up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero or exceeds the configured burst size, in which
+    /// case the tokens could never all be available at once.
+    pub async fn acquire(&self, n: u32) {
+        assert!(n > 0, "cannot acquire 0 tokens from a rate limiter");
+        assert!(
+            n as usize <= self.burst(),
+            "cannot acquire more tokens ({}) than the burst size ({})",
+            n,
+            self.burst()
+        );
+
+        self.refill();
+
+        let acquire = self.s.acquire(n);
+        let interval = self.token_interval();
+        let delay = crate::time::sleep(interval);
+        crate::pin!(acquire, delay);
+
+        crate::future::poll_fn(|cx| {
+            loop {
+                if let Poll::Ready(res) = acquire.as_mut().poll(cx) {
+                    res.unwrap_or_else(|_| {
+                        // The semaphore was closed. but, we never explicitly
+                        // close it, and we own it exclusively, which means
+                        // that this can never happen.
+                        unreachable!()
+                    });
+                    return Poll::Ready(());
+                }
+
+                // While queued, the waiter itself drives the refill: each
+                // time a token's worth of time elapses, credit it and poll
+                // the semaphore again.
+                if delay.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+
+                self.refill();
+                let deadline = delay.deadline() + interval;
+                delay.as_mut().reset(cmp::max(deadline, Instant::now()));
+            }
+        })
+        .await
+    }
+
+    /// Acquires `n` tokens without waiting, returning whether they were
+    /// available.
+    ///
+    /// On `false`, no tokens are consumed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn try_acquire(&self, n: u32) -> bool {
+        assert!(n > 0, "cannot acquire 0 tokens from a rate limiter");
+
+        self.refill();
+        self.s.try_acquire(n).is_ok()
+    }
+
+    /// Credits the tokens that have accrued since the last refill, capped at
+    /// the burst size.
+    fn refill(&self) {
+        let mut refilled = self.refilled.lock();
+
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(*refilled).as_nanos();
+        let period = self.period.as_nanos();
+        let tokens = elapsed * u128::from(self.rate) / period;
+
+        if tokens == 0 {
+            return;
+        }
+
+        // Advance the refill point by exactly the time those tokens took to
+        // accrue, so the fractional remainder keeps counting.
+        let credited = tokens * period / u128::from(self.rate);
+        *refilled += Duration::from_nanos(credited as u64);
+
+        // Only refills add permits, and refills are serialized by the lock,
+        // so capping against the current count cannot overshoot the burst.
+        let room = self.burst().saturating_sub(self.s.available_permits());
+        let add = cmp::min(tokens, room as u128) as usize;
+        if add > 0 {
+            self.s.release(add);
+        }
+    }
+
+    /// The time it takes for a single token to accrue.
+    fn token_interval(&self) -> Duration {
+        let nanos = cmp::max(self.period.as_nanos() / u128::from(self.rate), 1);
+        Duration::from_nanos(nanos as u64)
+    }
+}
+
+impl fmt::Debug for RateLimiter {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("RateLimiter")
+            .field("rate", &self.rate)
+            .field("period", &self.period)
+            .field("burst", &self.burst())
+            .finish()
+    }
+}
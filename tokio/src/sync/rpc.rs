@@ -0,0 +1,230 @@
+//! A multi-producer, single-consumer request/response channel.
+//!
+//! An [`rpc::channel`][channel] pairs a bounded [`mpsc`] channel carrying
+//! requests with a per-request [`oneshot`] channel carrying the typed
+//! response. Calling [`Sender::call`] sends the request, applies the bounded
+//! channel's backpressure, and waits for the response, so the common actor
+//! request pattern does not have to be wired up by hand at every call site.
+//!
+//! The consumer receives each request together with a [`Responder`] used to
+//! send the reply:
+//!
+//! ```
+//! use tokio::sync::rpc;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (tx, mut rx) = rpc::channel::<u32, u32>(16);
+//!
+//!     tokio::spawn(async move {
+//!         while let Some((request, responder)) = rx.recv().await {
+//!             let _ = responder.respond(request * 2);
+//!         }
+//!     });
+//!
+//!     assert_eq!(tx.call(21).await.unwrap(), 42);
+//! }
+//! ```
+//!
+//! # Cancellation
+//!
+//! Dropping the future returned by [`Sender::call`] before it completes
+//! cancels the request cleanly. If the request was not yet sent, it is never
+//! observed by the consumer. If it was sent, the consumer still receives it,
+//! but [`Responder::respond`] returns the response back as an error, letting
+//! the consumer detect that the caller went away.
+//!
+//! [channel]: fn@channel
+//! [`mpsc`]: crate::sync::mpsc
+//! [`oneshot`]: crate::sync::oneshot
+
+use crate::sync::{mpsc, oneshot};
+
+use std::fmt;
+
+/// Creates a bounded request/response channel for communicating with an
+/// actor-style consumer.
+///
+/// At most `buffer` requests can be in flight at once; further calls to
+/// [`Sender::call`] wait for capacity, exactly like [`mpsc::Sender::send`].
+///
+/// A `buffer` of 0 creates a rendezvous channel: each call waits until the
+/// consumer is ready to receive the request. See the [`mpsc::channel`]
+/// documentation for details.
+///
+/// [`mpsc::channel`]: fn@crate::sync::mpsc::channel
+pub fn channel<Req, Resp>(buffer: usize) -> (Sender<Req, Resp>, Receiver<Req, Resp>) {
+    let (tx, rx) = mpsc::channel(buffer);
+    (Sender { inner: tx }, Receiver { inner: rx })
+}
+
+/// Sends requests and awaits their responses.
+///
+/// Instances are created by the [`channel`](fn@channel) function and may be
+/// cloned to allow multiple callers.
+pub struct Sender<Req, Resp> {
+    inner: mpsc::Sender<(Req, Responder<Resp>)>,
+}
+
+/// Receives requests paired with the [`Responder`] used to answer them.
+///
+/// Instances are created by the [`channel`](fn@channel) function.
+pub struct Receiver<Req, Resp> {
+    inner: mpsc::Receiver<(Req, Responder<Resp>)>,
+}
+
+/// Sends the response for a single received request.
+///
+/// Each request handed out by [`Receiver::recv`] comes with its own
+/// `Responder`. Dropping it without calling [`respond`] completes the
+/// caller's [`Sender::call`] with [`error::CallError::NoResponse`].
+///
+/// [`respond`]: Responder::respond
+pub struct Responder<Resp> {
+    inner: oneshot::Sender<Resp>,
+}
+
+impl<Req, Resp> Sender<Req, Resp> {
+    /// Sends a request and waits for the response.
+    ///
+    /// Waits for channel capacity first, providing the same backpressure as
+    /// [`mpsc::Sender::send`]. Once the consumer answers via
+    /// [`Responder::respond`], the typed response is returned.
+    ///
+    /// # Errors
+    ///
+    /// * [`error::CallError::Closed`] if the [`Receiver`] was dropped before
+    ///   the request could be sent. The request is handed back in the error.
+    /// * [`error::CallError::NoResponse`] if the consumer dropped the
+    ///   [`Responder`] without answering, including when the receiver is
+    ///   dropped while requests are in flight.
+    ///
+    /// # Cancel safety
+    ///
+    /// If the returned future is dropped before completion, the request is
+    /// either never seen by the consumer or surfaces there as a `Responder`
+    /// whose [`respond`](Responder::respond) returns an error. No capacity is
+    /// permanently lost.
+    pub async fn call(&self, request: Req) -> Result<Resp, error::CallError<Req>> {
+        let (tx, rx) = oneshot::channel();
+        let responder = Responder { inner: tx };
+
+        self.inner
+            .send((request, responder))
+            .await
+            .map_err(|mpsc::error::SendError((request, _))| error::CallError::Closed(request))?;
+
+        rx.await.map_err(|_| error::CallError::NoResponse)
+    }
+
+    /// Returns `true` if the [`Receiver`] has been dropped.
+    ///
+    /// Calls on a closed channel fail with [`error::CallError::Closed`].
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    /// Returns the number of requests that can currently be started without
+    /// waiting.
+    ///
+    /// Each in-flight [`call`](Sender::call) holds one permit on the
+    /// underlying channel from the moment the request is sent until the
+    /// consumer receives it.
+    pub fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    /// Returns the buffer size the channel was created with.
+    pub fn max_capacity(&self) -> usize {
+        self.inner.max_capacity()
+    }
+}
+
+impl<Req, Resp> Clone for Sender<Req, Resp> {
+    fn clone(&self) -> Self {
+        Sender {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<Req, Resp> fmt::Debug for Sender<Req, Resp> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("rpc::Sender").finish()
+    }
+}
+
+impl<Req, Resp> Receiver<Req, Resp> {
+    /// Receives the next request, along with the [`Responder`] for its reply.
+    ///
+    /// Returns `None` when every [`Sender`] has been dropped and all
+    /// in-flight requests have been received.
+    pub async fn recv(&mut self) -> Option<(Req, Responder<Resp>)> {
+        self.inner.recv().await
+    }
+
+    /// Closes the channel without dropping the receiver.
+    ///
+    /// Subsequent calls fail with [`error::CallError::Closed`], while
+    /// requests already in flight can still be received and answered.
+    pub fn close(&mut self) {
+        self.inner.close()
+    }
+}
+
+impl<Req, Resp> fmt::Debug for Receiver<Req, Resp> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("rpc::Receiver").finish()
+    }
+}
+
+impl<Resp> Responder<Resp> {
+    /// Sends the response for the associated request.
+    ///
+    /// If the caller stopped waiting — its [`Sender::call`] future was
+    /// dropped — the response is handed back as the error value.
+    pub fn respond(self, response: Resp) -> Result<(), Resp> {
+        self.inner.send(response)
+    }
+
+    /// Returns `true` if the caller has stopped waiting for the response.
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+}
+
+impl<Resp> fmt::Debug for Responder<Resp> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("rpc::Responder").finish()
+    }
+}
+
+pub mod error {
+    //! RPC channel error types
+
+    use std::error::Error;
+    use std::fmt;
+
+    /// Error returned by [`Sender::call`](super::Sender::call).
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum CallError<Req> {
+        /// The receiver was dropped before the request could be sent. The
+        /// request is handed back to the caller.
+        Closed(Req),
+
+        /// The consumer dropped the [`Responder`](super::Responder) without
+        /// sending a response.
+        NoResponse,
+    }
+
+    impl<Req> fmt::Display for CallError<Req> {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                CallError::Closed(..) => write!(fmt, "channel closed"),
+                CallError::NoResponse => write!(fmt, "no response received"),
+            }
+        }
+    }
+
+    impl<Req: fmt::Debug> Error for CallError<Req> {}
+}
@@ -25,6 +25,28 @@ const MAX_READS: u32 = std::u32::MAX >> 3;
 #[cfg(loom)]
 const MAX_READS: u32 = 10;
 
+/// Priority policy of an [`RwLock`], set with [`RwLock::with_policy`].
+///
+/// The policy decides how the underlying semaphore hands out permits when
+/// both readers and writers are waiting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RwLockPolicy {
+    /// First-in, first-out: readers and writers share one queue, so neither
+    /// side can starve the other. This is the default policy.
+    Fair,
+
+    /// Waiting readers are served before waiting writers. Read-mostly
+    /// workloads gain throughput from reader batching, at the cost of
+    /// writers waiting until no reader is queued.
+    ReaderPriority,
+
+    /// Waiting writers are served before waiting readers. Keeps data fresh
+    /// under write bursts, at the cost of readers waiting until no writer is
+    /// queued.
+    WriterPriority,
+}
+
 /// An asynchronous reader-writer lock.
 ///
 /// This type of lock allows a number of readers or at most one writer at any
@@ -89,6 +111,9 @@ pub struct RwLock<T: ?Sized> {
     // maximum number of concurrent readers
     mr: u32,
 
+    // how permits are handed out when readers and writers are both waiting
+    policy: RwLockPolicy,
+
     //semaphore to coordinate read and write access to T
     s: Semaphore,
 
@@ -203,11 +228,56 @@ impl<T: ?Sized> RwLock<T> {
     {
         RwLock {
             mr: MAX_READS,
+            policy: RwLockPolicy::Fair,
             c: UnsafeCell::new(value),
             s: Semaphore::new(MAX_READS as usize),
         }
     }
 
+    /// Creates a new instance of an `RwLock<T>` with the given priority
+    /// policy.
+    ///
+    /// The default [`Fair`] policy serves readers and writers from a single
+    /// first-in, first-out queue. [`ReaderPriority`] and [`WriterPriority`]
+    /// instead give the underlying semaphore one wait queue per side and
+    /// serve the preferred side first whenever both are waiting. The
+    /// preferred side can starve the other under sustained load; choose a
+    /// non-fair policy only when the workload is known to be lopsided, such
+    /// as a read-mostly metadata map.
+    ///
+    /// [`Fair`]: RwLockPolicy::Fair
+    /// [`ReaderPriority`]: RwLockPolicy::ReaderPriority
+    /// [`WriterPriority`]: RwLockPolicy::WriterPriority
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::{RwLock, RwLockPolicy};
+    ///
+    /// let lock = RwLock::with_policy(5, RwLockPolicy::ReaderPriority);
+    /// ```
+    pub fn with_policy(value: T, policy: RwLockPolicy) -> RwLock<T>
+    where
+        T: Sized,
+    {
+        let s = match policy {
+            RwLockPolicy::Fair => Semaphore::new(MAX_READS as usize),
+            // The preferred side is class 0 and its weight normalizes a full
+            // write (`MAX_READS` permits) against single-permit reads, so
+            // waiting class 0 is always the least served and goes first.
+            RwLockPolicy::ReaderPriority | RwLockPolicy::WriterPriority => {
+                Semaphore::new_weighted(MAX_READS as usize, &[MAX_READS, 1])
+            }
+        };
+
+        RwLock {
+            mr: MAX_READS,
+            policy,
+            c: UnsafeCell::new(value),
+            s,
+        }
+    }
+
     /// Creates a new instance of an `RwLock<T>` which is unlocked
     /// and allows a maximum of `max_reads` concurrent readers.
     ///
@@ -233,6 +303,7 @@ impl<T: ?Sized> RwLock<T> {
         );
         RwLock {
             mr: max_reads,
+            policy: RwLockPolicy::Fair,
             c: UnsafeCell::new(value),
             s: Semaphore::new(max_reads as usize),
         }
@@ -255,6 +326,7 @@ impl<T: ?Sized> RwLock<T> {
     {
         RwLock {
             mr: MAX_READS,
+            policy: RwLockPolicy::Fair,
             c: UnsafeCell::new(value),
             s: Semaphore::const_new(MAX_READS as usize),
         }
@@ -279,11 +351,63 @@ impl<T: ?Sized> RwLock<T> {
         max_reads &= MAX_READS;
         RwLock {
             mr: max_reads,
+            policy: RwLockPolicy::Fair,
             c: UnsafeCell::new(value),
             s: Semaphore::const_new(max_reads as usize),
         }
     }
 
+    /// Returns the semaphore class for readers and writers. The preferred
+    /// side of a non-fair policy is class 0; the fair policy has one queue.
+    fn classes(&self) -> (u32, u32) {
+        match self.policy {
+            RwLockPolicy::Fair => (0, 0),
+            RwLockPolicy::ReaderPriority => (0, 1),
+            RwLockPolicy::WriterPriority => (1, 0),
+        }
+    }
+
+    /// Acquires `permits` permits from the underlying semaphore. Read locks
+    /// acquire a single permit, write locks acquire all of them.
+    async fn acquire(&self, permits: u32) {
+        let (read_class, write_class) = self.classes();
+        let class = if permits == self.mr && self.mr != 1 {
+            write_class
+        } else {
+            read_class
+        };
+
+        #[cfg(all(tokio_unstable, feature = "tracing"))]
+        let contended_at = if self.s.available_permits() < permits as usize {
+            tracing::trace!(
+                target: "tokio::sync",
+                resource_id = self.s.resource_id(),
+                kind = if permits == 1 { "read" } else { "write" },
+                "rwlock.contended",
+            );
+            Some(std::time::Instant::now())
+        } else {
+            None
+        };
+
+        self.s.acquire_for_class(permits, class).await.unwrap_or_else(|_| {
+            // The semaphore was closed. but, we never explicitly close it, and we have a
+            // handle to it through the Arc, which means that this can never happen.
+            unreachable!()
+        });
+
+        #[cfg(all(tokio_unstable, feature = "tracing"))]
+        if let Some(contended_at) = contended_at {
+            tracing::trace!(
+                target: "tokio::sync",
+                resource_id = self.s.resource_id(),
+                kind = if permits == 1 { "read" } else { "write" },
+                wait_us = contended_at.elapsed().as_micros() as u64,
+                "rwlock.acquired",
+            );
+        }
+    }
+
     /// Locks this `RwLock` with shared read access, causing the current task
     /// to yield until the lock has been acquired.
     ///
@@ -324,18 +448,63 @@ impl<T: ?Sized> RwLock<T> {
     ///}
     /// ```
     pub async fn read(&self) -> RwLockReadGuard<'_, T> {
-        self.s.acquire(1).await.unwrap_or_else(|_| {
-            // The semaphore was closed. but, we never explicitly close it, and we have a
-            // handle to it through the Arc, which means that this can never happen.
-            unreachable!()
-        });
+        self.acquire(1).await;
         RwLockReadGuard {
+            mr: self.mr,
             s: &self.s,
             data: self.c.get(),
             marker: marker::PhantomData,
         }
     }
 
+    /// Blockingly locks this `RwLock` with shared read access.
+    ///
+    /// This method is intended for use cases where you need to use this rwlock
+    /// in asynchronous code as well as in synchronous code, such as `Drop`
+    /// implementations or callbacks invoked by FFI.
+    ///
+    /// Returns an RAII guard which will drop the read access of this `RwLock`
+    /// when dropped.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called within an asynchronous execution
+    /// context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tokio::sync::RwLock;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let rwlock = Arc::new(RwLock::new(1));
+    ///     let mut write_lock = rwlock.write().await;
+    ///
+    ///     let blocking_task = tokio::task::spawn_blocking({
+    ///         let rwlock = Arc::clone(&rwlock);
+    ///         move || {
+    ///             // This shall block until the `write_lock` is released.
+    ///             let read_lock = rwlock.blocking_read();
+    ///             assert_eq!(*read_lock, 0);
+    ///         }
+    ///     });
+    ///
+    ///     *write_lock = 0;
+    ///     drop(write_lock);
+    ///
+    ///     // Await the completion of the blocking task.
+    ///     blocking_task.await.unwrap();
+    ///
+    ///     // Assert uncontended.
+    ///     assert!(rwlock.try_write().is_ok());
+    /// }
+    /// ```
+    pub fn blocking_read(&self) -> RwLockReadGuard<'_, T> {
+        crate::future::block_on(self.read())
+    }
+
     /// Locks this `RwLock` with shared read access, causing the current task
     /// to yield until the lock has been acquired.
     ///
@@ -382,11 +551,7 @@ impl<T: ?Sized> RwLock<T> {
     ///}
     /// ```
     pub async fn read_owned(self: Arc<Self>) -> OwnedRwLockReadGuard<T> {
-        self.s.acquire(1).await.unwrap_or_else(|_| {
-            // The semaphore was closed. but, we never explicitly close it, and we have a
-            // handle to it through the Arc, which means that this can never happen.
-            unreachable!()
-        });
+        self.acquire(1).await;
         OwnedRwLockReadGuard {
             data: self.c.get(),
             lock: ManuallyDrop::new(self),
@@ -434,6 +599,7 @@ impl<T: ?Sized> RwLock<T> {
         }
 
         Ok(RwLockReadGuard {
+            mr: self.mr,
             s: &self.s,
             data: self.c.get(),
             marker: marker::PhantomData,
@@ -515,11 +681,7 @@ impl<T: ?Sized> RwLock<T> {
     ///}
     /// ```
     pub async fn write(&self) -> RwLockWriteGuard<'_, T> {
-        self.s.acquire(self.mr).await.unwrap_or_else(|_| {
-            // The semaphore was closed. but, we never explicitly close it, and we have a
-            // handle to it through the Arc, which means that this can never happen.
-            unreachable!()
-        });
+        self.acquire(self.mr).await;
         RwLockWriteGuard {
             permits_acquired: self.mr,
             s: &self.s,
@@ -528,6 +690,56 @@ impl<T: ?Sized> RwLock<T> {
         }
     }
 
+    /// Blockingly locks this `RwLock` with exclusive write access.
+    ///
+    /// This method is intended for use cases where you need to use this rwlock
+    /// in asynchronous code as well as in synchronous code, such as `Drop`
+    /// implementations or callbacks invoked by FFI.
+    ///
+    /// Returns an RAII guard which will drop the write access of this
+    /// `RwLock` when dropped.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if called within an asynchronous execution
+    /// context.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tokio::sync::RwLock;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let rwlock = Arc::new(RwLock::new(1));
+    ///     let read_lock = rwlock.read().await;
+    ///
+    ///     let blocking_task = tokio::task::spawn_blocking({
+    ///         let rwlock = Arc::clone(&rwlock);
+    ///         move || {
+    ///             // This shall block until the `read_lock` is released.
+    ///             let mut write_lock = rwlock.blocking_write();
+    ///             *write_lock = 2;
+    ///         }
+    ///     });
+    ///
+    ///     assert_eq!(*read_lock, 1);
+    ///     // Release the last outstanding read lock.
+    ///     drop(read_lock);
+    ///
+    ///     // Await the completion of the blocking task.
+    ///     blocking_task.await.unwrap();
+    ///
+    ///     // Assert uncontended.
+    ///     let read_lock = rwlock.try_read().unwrap();
+    ///     assert_eq!(*read_lock, 2);
+    /// }
+    /// ```
+    pub fn blocking_write(&self) -> RwLockWriteGuard<'_, T> {
+        crate::future::block_on(self.write())
+    }
+
     /// Locks this `RwLock` with exclusive write access, causing the current
     /// task to yield until the lock has been acquired.
     ///
@@ -558,11 +770,7 @@ impl<T: ?Sized> RwLock<T> {
     ///}
     /// ```
     pub async fn write_owned(self: Arc<Self>) -> OwnedRwLockWriteGuard<T> {
-        self.s.acquire(self.mr).await.unwrap_or_else(|_| {
-            // The semaphore was closed. but, we never explicitly close it, and we have a
-            // handle to it through the Arc, which means that this can never happen.
-            unreachable!()
-        });
+        self.acquire(self.mr).await;
         OwnedRwLockWriteGuard {
             permits_acquired: self.mr,
             data: self.c.get(),
@@ -687,6 +895,138 @@ impl<T: ?Sized> RwLock<T> {
     }
 }
 
+cfg_time! {
+    use crate::sync::mutex::LockTimeoutError;
+
+    impl<T: ?Sized> RwLock<T> {
+        /// Locks this `RwLock` with shared read access, waiting at most
+        /// `timeout` for the lock to become available.
+        ///
+        /// The deadline is handled by the waiter itself rather than by
+        /// wrapping the acquisition in [`tokio::time::timeout`]: when the
+        /// deadline is reached, the waiter is unlinked from the lock's queue
+        /// in the same poll, and any permits that were already assigned to it
+        /// are handed to the next waiters before this method returns.
+        ///
+        /// Returns [`LockTimeoutError`] if the deadline elapsed before the
+        /// lock was acquired.
+        ///
+        /// [`tokio::time::timeout`]: crate::time::timeout
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use tokio::sync::RwLock;
+        /// use tokio::time::Duration;
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let lock = RwLock::new(1);
+        ///
+        ///     let n = lock.read_timeout(Duration::from_millis(10)).await.unwrap();
+        ///     assert_eq!(*n, 1);
+        /// }
+        /// ```
+        pub async fn read_timeout(
+            &self,
+            timeout: crate::time::Duration,
+        ) -> Result<RwLockReadGuard<'_, T>, LockTimeoutError> {
+            self.acquire_timeout(1, timeout).await?;
+            Ok(RwLockReadGuard {
+                mr: self.mr,
+                s: &self.s,
+                data: self.c.get(),
+                marker: marker::PhantomData,
+            })
+        }
+
+        /// Locks this `RwLock` with exclusive write access, waiting at most
+        /// `timeout` for the lock to become available.
+        ///
+        /// The deadline is handled by the waiter itself rather than by
+        /// wrapping the acquisition in [`tokio::time::timeout`]: when the
+        /// deadline is reached, the waiter is unlinked from the lock's queue
+        /// in the same poll, and any permits that were already assigned to it
+        /// are handed to the next waiters before this method returns.
+        ///
+        /// Returns [`LockTimeoutError`] if the deadline elapsed before the
+        /// lock was acquired.
+        ///
+        /// [`tokio::time::timeout`]: crate::time::timeout
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use tokio::sync::RwLock;
+        /// use tokio::time::Duration;
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let lock = RwLock::new(1);
+        ///
+        ///     let mut n = lock.write_timeout(Duration::from_millis(10)).await.unwrap();
+        ///     *n = 2;
+        /// }
+        /// ```
+        pub async fn write_timeout(
+            &self,
+            timeout: crate::time::Duration,
+        ) -> Result<RwLockWriteGuard<'_, T>, LockTimeoutError> {
+            self.acquire_timeout(self.mr, timeout).await?;
+            Ok(RwLockWriteGuard {
+                permits_acquired: self.mr,
+                s: &self.s,
+                data: self.c.get(),
+                marker: marker::PhantomData,
+            })
+        }
+
+        /// Acquires `permits` permits from the underlying semaphore, waiting
+        /// at most `timeout`.
+        async fn acquire_timeout(
+            &self,
+            permits: u32,
+            timeout: crate::time::Duration,
+        ) -> Result<(), LockTimeoutError> {
+            use std::future::Future;
+            use std::task::Poll;
+
+            let (read_class, write_class) = self.classes();
+            let class = if permits == self.mr && self.mr != 1 {
+                write_class
+            } else {
+                read_class
+            };
+
+            let acquire = self.s.acquire_for_class(permits, class);
+            let delay = crate::time::sleep(timeout);
+            crate::pin!(acquire, delay);
+
+            crate::future::poll_fn(|cx| {
+                if let Poll::Ready(res) = acquire.as_mut().poll(cx) {
+                    res.unwrap_or_else(|_| {
+                        // The semaphore was closed. but, we never explicitly close it, and we have a
+                        // handle to it through the Arc, which means that this can never happen.
+                        unreachable!()
+                    });
+                    return Poll::Ready(Ok(()));
+                }
+
+                if delay.as_mut().poll(cx).is_ready() {
+                    // Unlink the waiter and hand any partially assigned
+                    // permits to the next waiters before reporting the
+                    // timeout.
+                    acquire.as_mut().cancel();
+                    return Poll::Ready(Err(LockTimeoutError(())));
+                }
+
+                Poll::Pending
+            })
+            .await
+        }
+    }
+}
+
 impl<T> From<T> for RwLock<T> {
     fn from(s: T) -> Self {
         Self::new(s)
@@ -1,4 +1,5 @@
 use crate::sync::batch_semaphore::Semaphore;
+use crate::sync::rwlock::write_guard::RwLockWriteGuard;
 use std::fmt;
 use std::marker;
 use std::mem;
@@ -13,6 +14,9 @@ use std::ops;
 /// [`read`]: method@crate::sync::RwLock::read
 /// [`RwLock`]: struct@crate::sync::RwLock
 pub struct RwLockReadGuard<'a, T: ?Sized> {
+    // Total number of permits the lock's semaphore holds; needed by
+    // `upgrade` to know how many more permits make the access exclusive.
+    pub(super) mr: u32,
     pub(super) s: &'a Semaphore,
     pub(super) data: *const T,
     pub(super) marker: marker::PhantomData<&'a T>,
@@ -59,9 +63,11 @@ impl<'a, T: ?Sized> RwLockReadGuard<'a, T> {
     {
         let data = f(&*this) as *const U;
         let s = this.s;
+        let mr = this.mr;
         // NB: Forget to avoid drop impl from being called.
         mem::forget(this);
         RwLockReadGuard {
+            mr,
             s,
             data,
             marker: marker::PhantomData,
@@ -113,14 +119,83 @@ impl<'a, T: ?Sized> RwLockReadGuard<'a, T> {
             None => return Err(this),
         };
         let s = this.s;
+        let mr = this.mr;
         // NB: Forget to avoid drop impl from being called.
         mem::forget(this);
         Ok(RwLockReadGuard {
+            mr,
             s,
             data,
             marker: marker::PhantomData,
         })
     }
+
+    /// Atomically upgrades this read lock into a write lock, keeping the
+    /// guard's position in the lock's fair queue.
+    ///
+    /// Instead of releasing the read lock and re-acquiring a write lock, the
+    /// remaining permits are acquired through the semaphore's multi-permit
+    /// acquire while the read permit stays held. No other writer can slip in
+    /// between the read and write access: once every other reader ahead of
+    /// this guard has released its lock, the upgrade completes.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `RwLockReadGuard::upgrade(..)`. A method would interfere with methods
+    /// of the same name on the contents of the locked data.
+    ///
+    /// # Deadlocks
+    ///
+    /// If two tasks holding read locks on the same `RwLock` both attempt to
+    /// upgrade, neither can complete: each waits for the other's read permit
+    /// to be released. Only one task should upgrade a given lock at a time.
+    ///
+    /// # Cancel safety
+    ///
+    /// If the returned future is dropped before the upgrade completes, the
+    /// read lock is released as if the guard had been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::{RwLock, RwLockReadGuard};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let lock = RwLock::new(1);
+    ///
+    /// let guard = lock.read().await;
+    /// assert_eq!(*guard, 1);
+    ///
+    /// let mut guard = RwLockReadGuard::upgrade(guard).await;
+    /// *guard += 1;
+    /// drop(guard);
+    ///
+    /// assert_eq!(*lock.read().await, 2);
+    /// # }
+    /// ```
+    pub async fn upgrade(this: Self) -> RwLockWriteGuard<'a, T> {
+        // Acquire the rest of the lock's permits. The held read permit keeps
+        // the guard's place in the FIFO queue; writers queued behind it
+        // cannot acquire in the meantime.
+        this.s.acquire(this.mr - 1).await.unwrap_or_else(|_| {
+            // The semaphore was closed. but, we never explicitly close it, and
+            // we have a handle to it through the Arc, which means that this
+            // can never happen.
+            unreachable!()
+        });
+
+        let permits_acquired = this.mr;
+        let s = this.s;
+        let data = this.data as *mut T;
+        // NB: Forget to avoid drop impl from being called.
+        mem::forget(this);
+        RwLockWriteGuard {
+            permits_acquired,
+            s,
+            data,
+            marker: marker::PhantomData,
+        }
+    }
 }
 
 impl<T: ?Sized> ops::Deref for RwLockReadGuard<'_, T> {
@@ -188,12 +188,78 @@ impl<'a, T: ?Sized> RwLockWriteGuard<'a, T> {
     /// [`RwLock`]: struct@crate::sync::RwLock
     pub fn downgrade(self) -> RwLockReadGuard<'a, T> {
         let RwLockWriteGuard { s, data, .. } = self;
+        let mr = self.permits_acquired;
 
         // Release all but one of the permits held by the write guard
         s.release((self.permits_acquired - 1) as usize);
         // NB: Forget to avoid drop impl from being called.
         mem::forget(self);
         RwLockReadGuard {
+            mr,
+            s,
+            data,
+            marker: marker::PhantomData,
+        }
+    }
+
+    /// Atomically downgrades a write lock into a read lock for a component of
+    /// the locked data, without allowing any writers to take exclusive access
+    /// of the lock in the meantime.
+    ///
+    /// This combines [`downgrade`] and [`RwLockReadGuard::map`] in a single
+    /// step: no other writer can observe or modify the data between the
+    /// mapping and the downgrade.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `RwLockWriteGuard::downgrade_map(..)`. A method would interfere with
+    /// methods of the same name on the contents of the locked data.
+    ///
+    /// **Note:** This won't *necessarily* allow any additional readers to acquire
+    /// locks, since [`RwLock`] is fair and it is possible that a writer is next
+    /// in line.
+    ///
+    /// [`downgrade`]: RwLockWriteGuard::downgrade
+    /// [`RwLockReadGuard::map`]: RwLockReadGuard::map
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::{RwLock, RwLockWriteGuard};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    /// struct Foo(u32);
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let lock = RwLock::new(Foo(1));
+    ///
+    /// let mut guard = lock.write().await;
+    /// guard.0 += 1;
+    ///
+    /// let guard = RwLockWriteGuard::downgrade_map(guard, |f| &f.0);
+    /// assert_eq!(2, *guard);
+    ///
+    /// // Other readers are admitted alongside the downgraded guard.
+    /// let other = lock.try_read().unwrap();
+    /// assert_eq!(2, other.0);
+    /// # }
+    /// ```
+    ///
+    /// [`RwLock`]: struct@crate::sync::RwLock
+    pub fn downgrade_map<F, U: ?Sized>(this: Self, f: F) -> RwLockReadGuard<'a, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let data = f(&*this) as *const U;
+        let s = this.s;
+        let mr = this.permits_acquired;
+
+        // Release all but one of the permits held by the write guard
+        s.release((this.permits_acquired - 1) as usize);
+        // NB: Forget to avoid drop impl from being called.
+        mem::forget(this);
+        RwLockReadGuard {
+            mr,
             s,
             data,
             marker: marker::PhantomData,
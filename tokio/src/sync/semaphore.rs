@@ -1,6 +1,9 @@
 use super::batch_semaphore as ll; // low level implementation
 use super::{AcquireError, TryAcquireError};
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 
 /// Counting semaphore performing asynchronous permit acquisition.
 ///
@@ -43,6 +46,22 @@ pub struct SemaphorePermit<'a> {
     permits: u32,
 }
 
+pin_project_lite::pin_project! {
+    /// A future that acquires permits from a [`Semaphore`] and allows the
+    /// requested amount to be changed while waiting.
+    ///
+    /// This type is created by the [`acquire_many_adjustable`] method.
+    ///
+    /// [`acquire_many_adjustable`]: crate::sync::Semaphore::acquire_many_adjustable()
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct AcquireMany<'a> {
+        sem: &'a Semaphore,
+        #[pin]
+        acquire: ll::Acquire<'a>,
+        permits: u32,
+    }
+}
+
 /// An owned permit from the semaphore.
 ///
 /// This type is created by the [`acquire_owned`] method.
@@ -71,6 +90,9 @@ fn bounds() {
 }
 
 impl Semaphore {
+    /// The maximum number of permits which a semaphore can hold.
+    pub const MAX_PERMITS: usize = super::batch_semaphore::Semaphore::MAX_PERMITS;
+
     /// Creates a new semaphore with the initial number of permits.
     pub fn new(permits: usize) -> Self {
         Self {
@@ -78,6 +100,107 @@ impl Semaphore {
         }
     }
 
+    /// Creates a new semaphore with the initial number of permits and a
+    /// weighted fairness policy.
+    ///
+    /// Each entry in `weights` defines a priority class; callers tag
+    /// themselves with a class index via [`acquire_class`]. When waiters from
+    /// several classes are queued, permits are distributed between the
+    /// classes proportionally to their weights instead of in strict request
+    /// order, while waiters within a class are still served FIFO. This
+    /// prevents one class — for example, one tenant of a shared proxy — from
+    /// monopolizing the queue.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weights` is empty or contains a zero weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Semaphore;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     // Class 0 gets three quarters of contended permits, class 1 one
+    ///     // quarter.
+    ///     let semaphore = Semaphore::new_weighted(10, &[3, 1]);
+    ///
+    ///     let permit = semaphore.acquire_class(0).await.unwrap();
+    ///     drop(permit);
+    /// }
+    /// ```
+    ///
+    /// [`acquire_class`]: Semaphore::acquire_class
+    pub fn new_weighted(permits: usize, weights: &[u32]) -> Self {
+        Self {
+            ll_sem: ll::Semaphore::new_weighted(permits, weights),
+        }
+    }
+
+    /// Creates a new semaphore that wakes queued waiters in LIFO order.
+    ///
+    /// When permits are released while tasks are waiting, the *most recently*
+    /// queued waiter receives them first, rather than the oldest one. This is
+    /// unfair — a steady stream of new waiters can starve an old one — but it
+    /// biases wakeups towards recency, which can significantly reduce tail
+    /// latency under bursty load compared to the strict FIFO order used by
+    /// [`new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Semaphore;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let semaphore = Semaphore::new_unfair(2);
+    ///
+    ///     let permit = semaphore.acquire().await.unwrap();
+    ///     drop(permit);
+    /// }
+    /// ```
+    ///
+    /// [`new`]: Semaphore::new
+    pub fn new_unfair(permits: usize) -> Self {
+        Self {
+            ll_sem: ll::Semaphore::new_unfair(permits),
+        }
+    }
+
+    /// Creates a new semaphore with a maximum capacity of `max_permits`.
+    ///
+    /// On such a semaphore, [`add_permits`] and dropped permits saturate at
+    /// `max_permits` instead of panicking when the number of available
+    /// permits would exceed it. This makes the semaphore usable as a token
+    /// bucket: replenishment can blindly add permits without tracking how
+    /// many are outstanding.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_permits` exceeds [`Semaphore::MAX_PERMITS`], or if
+    /// `permits` exceeds `max_permits`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new_with_max(2, 5);
+    ///
+    /// // Adding more permits than the maximum saturates rather than
+    /// // panicking.
+    /// semaphore.add_permits(100);
+    /// assert_eq!(semaphore.available_permits(), 5);
+    /// ```
+    ///
+    /// [`add_permits`]: Semaphore::add_permits
+    pub fn new_with_max(permits: usize, max_permits: usize) -> Self {
+        Self {
+            ll_sem: ll::Semaphore::new_with_max(permits, max_permits),
+        }
+    }
+
     /// Creates a new semaphore with the initial number of permits.
     #[cfg(all(feature = "parking_lot", not(all(loom, test))))]
     #[cfg_attr(docsrs, doc(cfg(feature = "parking_lot")))]
@@ -99,6 +222,125 @@ impl Semaphore {
         self.ll_sem.release(n);
     }
 
+    /// Adds `n` new permits to the semaphore, saturating at [`max_permits`].
+    ///
+    /// Unlike [`add_permits`], this never panics: permits that would push the
+    /// available count past the maximum are silently discarded.
+    ///
+    /// [`add_permits`]: Semaphore::add_permits
+    /// [`max_permits`]: Semaphore::max_permits
+    pub fn add_permits_saturating(&self, n: usize) {
+        self.ll_sem.release_saturating(n);
+    }
+
+    /// Returns the maximum number of permits the semaphore may hold.
+    ///
+    /// This is the value passed to [`new_with_max`], or
+    /// [`Semaphore::MAX_PERMITS`] for semaphores created without a maximum.
+    ///
+    /// [`new_with_max`]: Semaphore::new_with_max
+    pub fn max_permits(&self) -> usize {
+        self.ll_sem.max_permits()
+    }
+
+    /// Returns the number of tasks currently waiting to acquire permits.
+    ///
+    /// Together with [`queued_permits`], this exposes the demand on the
+    /// semaphore, e.g. for sizing decisions, without maintaining shadow
+    /// counters around every acquire site. The value is a snapshot and may
+    /// be outdated as soon as it is returned.
+    ///
+    /// [`queued_permits`]: Semaphore::queued_permits
+    pub fn waiters(&self) -> usize {
+        self.ll_sem.waiters()
+    }
+
+    /// Returns the total number of permits the queued waiters are still
+    /// waiting for.
+    ///
+    /// Permits already assigned to a waiter that has not yet completed are
+    /// not counted. The value is a snapshot and may be outdated as soon as
+    /// it is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Semaphore;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let semaphore = Semaphore::new(0);
+    ///     assert_eq!(semaphore.waiters(), 0);
+    ///     assert_eq!(semaphore.queued_permits(), 0);
+    ///
+    ///     let acquire = semaphore.acquire_many(3);
+    ///     tokio::pin!(acquire);
+    ///
+    ///     // Poll once so the waiter joins the queue.
+    ///     tokio::select! {
+    ///         biased;
+    ///         _ = &mut acquire => unreachable!(),
+    ///         _ = std::future::ready(()) => {}
+    ///     }
+    ///
+    ///     assert_eq!(semaphore.waiters(), 1);
+    ///     assert_eq!(semaphore.queued_permits(), 3);
+    /// }
+    /// ```
+    pub fn queued_permits(&self) -> usize {
+        self.ll_sem.queued_permits()
+    }
+
+    /// Removes `n` permits from the semaphore, without waiting for them to
+    /// become available.
+    ///
+    /// This is the shrinking counterpart of [`add_permits`], intended for
+    /// adjusting capacity dynamically. If fewer than `n` permits are
+    /// currently available, the semaphore enters a deficit: permits released
+    /// back pay down the deficit instead of waking waiters, until the
+    /// removal has been fully absorbed. The outstanding deficit can be
+    /// observed with [`permit_deficit`].
+    ///
+    /// [`add_permits`]: Semaphore::add_permits
+    /// [`permit_deficit`]: Semaphore::permit_deficit
+    pub fn reduce_permits(&self, n: usize) {
+        self.ll_sem.reduce_permits(n);
+    }
+
+    /// Returns the number of permits the semaphore is currently short after
+    /// a [`reduce_permits`] call removed more permits than were available.
+    ///
+    /// While the deficit is non-zero, released permits are consumed to pay
+    /// it down rather than becoming available to new acquisitions; new
+    /// acquire calls behave as if no permits were available. A deficit of
+    /// zero means the semaphore is operating normally.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Semaphore;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let semaphore = Semaphore::new(2);
+    ///     let permit = semaphore.acquire().await.unwrap();
+    ///
+    ///     // Remove both remaining permits plus one that is checked out.
+    ///     semaphore.reduce_permits(2);
+    ///     assert_eq!(semaphore.permit_deficit(), 1);
+    ///
+    ///     // Returning the outstanding permit pays down the deficit.
+    ///     drop(permit);
+    ///     assert_eq!(semaphore.permit_deficit(), 0);
+    ///     assert_eq!(semaphore.available_permits(), 0);
+    /// }
+    /// ```
+    ///
+    /// [`reduce_permits`]: Semaphore::reduce_permits
+    pub fn permit_deficit(&self) -> usize {
+        self.ll_sem.underflow()
+    }
+
     /// Acquires a permit from the semaphore.
     ///
     /// If the semaphore has been closed, this returns an [`AcquireError`].
@@ -131,6 +373,78 @@ impl Semaphore {
         })
     }
 
+    /// Acquires `n` permits from the semaphore, allowing the requested
+    /// amount to be adjusted while waiting.
+    ///
+    /// Unlike [`acquire_many`], the returned [`AcquireMany`] future exposes
+    /// [`set_permits`], which changes the number of permits being requested
+    /// without the waiter losing its place in the queue. This is useful when
+    /// the cost of an operation is re-estimated while the request is already
+    /// waiting, and dropping and re-acquiring would forfeit fairness
+    /// ordering.
+    ///
+    /// [`acquire_many`]: Semaphore::acquire_many
+    /// [`AcquireMany`]: crate::sync::AcquireMany
+    /// [`set_permits`]: crate::sync::AcquireMany::set_permits
+    pub fn acquire_many_adjustable(&self, n: u32) -> AcquireMany<'_> {
+        AcquireMany {
+            sem: self,
+            acquire: self.ll_sem.acquire(n),
+            permits: n,
+        }
+    }
+
+    /// Acquires a permit from the semaphore as a member of the given priority
+    /// class.
+    ///
+    /// On a semaphore created with [`new_weighted`], waiters of the same
+    /// class queue up FIFO, and permits are distributed between waiting
+    /// classes proportionally to their weights. On a semaphore created with
+    /// [`new`], only class `0` exists and this is equivalent to [`acquire`].
+    ///
+    /// If the semaphore has been closed, this returns an [`AcquireError`].
+    /// Otherwise, this returns a [`SemaphorePermit`] representing the
+    /// acquired permit.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `class` is not a valid class index for this semaphore.
+    ///
+    /// [`new`]: Semaphore::new
+    /// [`new_weighted`]: Semaphore::new_weighted
+    /// [`acquire`]: Semaphore::acquire
+    /// [`AcquireError`]: crate::sync::AcquireError
+    /// [`SemaphorePermit`]: crate::sync::SemaphorePermit
+    pub async fn acquire_class(&self, class: u32) -> Result<SemaphorePermit<'_>, AcquireError> {
+        self.ll_sem.acquire_for_class(1, class).await?;
+        Ok(SemaphorePermit {
+            sem: &self,
+            permits: 1,
+        })
+    }
+
+    /// Acquires `n` permits from the semaphore as a member of the given
+    /// priority class.
+    ///
+    /// See [`acquire_class`] for how classes affect queueing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `class` is not a valid class index for this semaphore.
+    ///
+    /// [`acquire_class`]: Semaphore::acquire_class
+    pub async fn acquire_many_class(
+        &self,
+        n: u32,
+        class: u32,
+    ) -> Result<SemaphorePermit<'_>, AcquireError> {
+        self.ll_sem.acquire_for_class(n, class).await?;
+        Ok(SemaphorePermit {
+            sem: &self,
+            permits: n,
+        })
+    }
+
     /// Tries to acquire a permit from the semaphore.
     ///
     /// If the semaphore has been closed, this returns a [`TryAcquireError::Closed`]
@@ -169,6 +483,42 @@ impl Semaphore {
         }
     }
 
+    /// Tries to acquire up to `n` permits from the semaphore.
+    ///
+    /// Unlike [`try_acquire_many`], this is not all-or-nothing: if fewer than
+    /// `n` permits are available, whatever is there is taken. The returned
+    /// permit holds between `1` and `n` permits; use
+    /// [`SemaphorePermit::num_permits`] to see how many were obtained.
+    ///
+    /// If the semaphore has been closed, this returns a
+    /// [`TryAcquireError::Closed`], and a [`TryAcquireError::NoPermits`] if
+    /// no permits at all are available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(3);
+    ///
+    /// let permit = semaphore.try_acquire_upto(5).unwrap();
+    /// assert_eq!(permit.num_permits(), 3);
+    /// assert_eq!(semaphore.available_permits(), 0);
+    /// ```
+    ///
+    /// [`try_acquire_many`]: Semaphore::try_acquire_many
+    /// [`TryAcquireError::Closed`]: crate::sync::TryAcquireError::Closed
+    /// [`TryAcquireError::NoPermits`]: crate::sync::TryAcquireError::NoPermits
+    pub fn try_acquire_upto(&self, n: u32) -> Result<SemaphorePermit<'_>, TryAcquireError> {
+        match self.ll_sem.try_acquire_upto(n) {
+            Ok(acquired) => Ok(SemaphorePermit {
+                sem: self,
+                permits: acquired,
+            }),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Acquires a permit from the semaphore.
     ///
     /// The semaphore must be wrapped in an [`Arc`] to call this method.
@@ -292,6 +642,203 @@ impl Semaphore {
     pub fn is_closed(&self) -> bool {
         self.ll_sem.is_closed()
     }
+
+    /// Completes when the semaphore is closed.
+    ///
+    /// Returns immediately if the semaphore has already been closed. This
+    /// lets a task react to closure without polling [`is_closed`] in a loop,
+    /// mirroring [`mpsc::Sender::closed`].
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. Once the semaphore is closed, all calls to
+    /// this method return immediately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tokio::sync::Semaphore;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let semaphore = Arc::new(Semaphore::new(1));
+    ///     let sem = semaphore.clone();
+    ///
+    ///     let task = tokio::spawn(async move {
+    ///         sem.closed().await;
+    ///     });
+    ///
+    ///     semaphore.close();
+    ///     task.await.unwrap();
+    /// }
+    /// ```
+    ///
+    /// [`is_closed`]: Semaphore::is_closed
+    /// [`mpsc::Sender::closed`]: crate::sync::mpsc::Sender::closed
+    pub async fn closed(&self) {
+        crate::future::poll_fn(|cx| self.ll_sem.poll_closed(cx)).await
+    }
+}
+
+cfg_time! {
+    /// Error returned from [`Semaphore::acquire_timeout`].
+    ///
+    /// [`Semaphore::acquire_timeout`]: crate::sync::Semaphore::acquire_timeout
+    #[derive(Debug, PartialEq)]
+    pub enum AcquireTimeoutError {
+        /// The semaphore was [closed] before the permits could be acquired.
+        ///
+        /// [closed]: crate::sync::Semaphore::close
+        Closed,
+
+        /// The permits could not be acquired before the deadline.
+        TimedOut,
+    }
+
+    impl std::fmt::Display for AcquireTimeoutError {
+        fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                AcquireTimeoutError::Closed => write!(fmt, "semaphore closed"),
+                AcquireTimeoutError::TimedOut => write!(fmt, "timed out waiting for permits"),
+            }
+        }
+    }
+
+    impl std::error::Error for AcquireTimeoutError {}
+
+    impl Semaphore {
+        /// Acquires `n` permits from the semaphore, waiting at most `timeout`.
+        ///
+        /// The deadline is handled by the waiter itself rather than by
+        /// wrapping the acquisition in [`tokio::time::timeout`]: when the
+        /// deadline is reached, permits that were already partially assigned
+        /// to the waiter are handed to the next waiters in the queue before
+        /// this method returns, rather than lingering until the wrapped
+        /// future is dropped.
+        ///
+        /// Returns [`AcquireTimeoutError::TimedOut`] if the deadline elapsed,
+        /// and [`AcquireTimeoutError::Closed`] if the semaphore was closed
+        /// while waiting.
+        ///
+        /// [`tokio::time::timeout`]: crate::time::timeout
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use tokio::sync::{AcquireTimeoutError, Semaphore};
+        /// use tokio::time::Duration;
+        ///
+        /// #[tokio::main]
+        /// async fn main() {
+        ///     let semaphore = Semaphore::new(1);
+        ///
+        ///     let permit = semaphore
+        ///         .acquire_timeout(1, Duration::from_millis(10))
+        ///         .await
+        ///         .unwrap();
+        ///
+        ///     // All permits are held, so a second request times out.
+        ///     let err = semaphore
+        ///         .acquire_timeout(1, Duration::from_millis(10))
+        ///         .await
+        ///         .unwrap_err();
+        ///     assert_eq!(err, AcquireTimeoutError::TimedOut);
+        ///
+        ///     drop(permit);
+        /// }
+        /// ```
+        pub async fn acquire_timeout(
+            &self,
+            n: u32,
+            timeout: crate::time::Duration,
+        ) -> Result<SemaphorePermit<'_>, AcquireTimeoutError> {
+            let acquire = self.ll_sem.acquire(n);
+            let delay = crate::time::sleep(timeout);
+            crate::pin!(acquire, delay);
+
+            crate::future::poll_fn(|cx| {
+                if let Poll::Ready(res) = acquire.as_mut().poll(cx) {
+                    return Poll::Ready(res.map_err(|_| AcquireTimeoutError::Closed));
+                }
+
+                if delay.as_mut().poll(cx).is_ready() {
+                    // Unlink the waiter and hand any partially assigned
+                    // permits to the next waiters before reporting the
+                    // timeout.
+                    acquire.as_mut().cancel();
+                    return Poll::Ready(Err(AcquireTimeoutError::TimedOut));
+                }
+
+                Poll::Pending
+            })
+            .await?;
+
+            Ok(SemaphorePermit {
+                sem: self,
+                permits: n,
+            })
+        }
+    }
+}
+
+impl<'a> AcquireMany<'a> {
+    /// Changes the number of permits this future is requesting.
+    ///
+    /// The waiter keeps its place in the semaphore's queue. Permits already
+    /// assigned to the waiter count against the new request: if the request
+    /// shrinks to no more than what has been assigned, the excess is released
+    /// back to the semaphore and the future completes on its next poll.
+    ///
+    /// Calling this after the future has completed has no effect.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Semaphore;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let semaphore = Semaphore::new(2);
+    ///
+    ///     // Request more permits than are available...
+    ///     let acquire = semaphore.acquire_many_adjustable(4);
+    ///     tokio::pin!(acquire);
+    ///
+    ///     // ...then scale the request down to fit.
+    ///     acquire.as_mut().set_permits(2);
+    ///
+    ///     let permit = acquire.await.unwrap();
+    ///     assert_eq!(semaphore.available_permits(), 0);
+    ///     drop(permit);
+    /// }
+    /// ```
+    pub fn set_permits(self: Pin<&mut Self>, n: u32) {
+        let me = self.project();
+        me.acquire.set_permits(n);
+        *me.permits = n;
+    }
+
+    /// Returns the number of permits currently being requested.
+    pub fn permits(&self) -> u32 {
+        self.permits
+    }
+}
+
+impl<'a> Future for AcquireMany<'a> {
+    type Output = Result<SemaphorePermit<'a>, AcquireError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let me = self.project();
+        match me.acquire.poll(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(SemaphorePermit {
+                sem: me.sem,
+                permits: *me.permits,
+            })),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
 }
 
 impl<'a> SemaphorePermit<'a> {
@@ -301,6 +848,82 @@ impl<'a> SemaphorePermit<'a> {
     pub fn forget(mut self) {
         self.permits = 0;
     }
+
+    /// Forgets up to `n` of the held permits **without** releasing them back
+    /// to the semaphore, keeping the rest.
+    ///
+    /// Returns the number of permits that were actually forgotten. Unlike
+    /// [`forget`], the permit stays usable with its remaining permits, so
+    /// capacity can be reduced gradually without giving everything up and
+    /// re-acquiring.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::Semaphore;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let semaphore = Semaphore::new(5);
+    ///
+    ///     let mut permit = semaphore.acquire_many(3).await.unwrap();
+    ///     assert_eq!(permit.forget_many(2), 2);
+    ///     assert_eq!(permit.num_permits(), 1);
+    ///
+    ///     // The two forgotten permits are gone for good.
+    ///     drop(permit);
+    ///     assert_eq!(semaphore.available_permits(), 3);
+    /// }
+    /// ```
+    ///
+    /// [`forget`]: SemaphorePermit::forget
+    pub fn forget_many(&mut self, n: u32) -> u32 {
+        let forgotten = std::cmp::min(n, self.permits);
+        self.permits -= forgotten;
+        forgotten
+    }
+
+    /// Splits `n` permits from the permit into a new one.
+    ///
+    /// Returns `None` if the permit doesn't hold enough permits. The permits
+    /// are moved, not duplicated: nothing is acquired from or released back
+    /// to the semaphore.
+    pub fn split(&mut self, n: u32) -> Option<SemaphorePermit<'a>> {
+        if n > self.permits {
+            return None;
+        }
+
+        self.permits -= n;
+
+        Some(SemaphorePermit {
+            sem: self.sem,
+            permits: n,
+        })
+    }
+
+    /// Merges the permits held by `other` into this permit.
+    ///
+    /// This is the inverse of [`split`]: the combined permits are released
+    /// together when this permit is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` was acquired from a different semaphore.
+    ///
+    /// [`split`]: SemaphorePermit::split
+    pub fn merge(&mut self, mut other: SemaphorePermit<'a>) {
+        assert!(
+            std::ptr::eq(self.sem, other.sem),
+            "merging permits from different semaphore instances"
+        );
+        self.permits += other.permits;
+        other.permits = 0;
+    }
+
+    /// Returns the number of permits held by this permit.
+    pub fn num_permits(&self) -> u32 {
+        self.permits
+    }
 }
 
 impl OwnedSemaphorePermit {
@@ -310,6 +933,88 @@ impl OwnedSemaphorePermit {
     pub fn forget(mut self) {
         self.permits = 0;
     }
+
+    /// Forgets up to `n` of the held permits **without** releasing them back
+    /// to the semaphore, keeping the rest.
+    ///
+    /// Returns the number of permits that were actually forgotten. See
+    /// [`SemaphorePermit::forget_many`].
+    pub fn forget_many(&mut self, n: u32) -> u32 {
+        let forgotten = std::cmp::min(n, self.permits);
+        self.permits -= forgotten;
+        forgotten
+    }
+
+    /// Splits `n` permits from the permit into a new one.
+    ///
+    /// Returns `None` if the permit doesn't hold enough permits. The permits
+    /// are moved, not duplicated: nothing is acquired from or released back
+    /// to the semaphore. The new permit references the same semaphore
+    /// through its own [`Arc`], so it can be handed to a spawned task and
+    /// recombined with [`merge`] later.
+    ///
+    /// [`Arc`]: std::sync::Arc
+    /// [`merge`]: OwnedSemaphorePermit::merge
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use tokio::sync::Semaphore;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let sem = Arc::new(Semaphore::new(3));
+    ///     let mut permit = sem.clone().acquire_many_owned(3).await.unwrap();
+    ///
+    ///     // Lend one permit to a child task...
+    ///     let lent = permit.split(1).unwrap();
+    ///     let child = tokio::spawn(async move { lent });
+    ///
+    ///     // ...and recombine it when the child is done.
+    ///     let lent = child.await.unwrap();
+    ///     permit.merge(lent);
+    ///
+    ///     drop(permit);
+    ///     assert_eq!(sem.available_permits(), 3);
+    /// }
+    /// ```
+    pub fn split(&mut self, n: u32) -> Option<OwnedSemaphorePermit> {
+        if n > self.permits {
+            return None;
+        }
+
+        self.permits -= n;
+
+        Some(OwnedSemaphorePermit {
+            sem: self.sem.clone(),
+            permits: n,
+        })
+    }
+
+    /// Merges the permits held by `other` into this permit.
+    ///
+    /// This is the inverse of [`split`]: the combined permits are released
+    /// together when this permit is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` was acquired from a different semaphore.
+    ///
+    /// [`split`]: OwnedSemaphorePermit::split
+    pub fn merge(&mut self, mut other: OwnedSemaphorePermit) {
+        assert!(
+            Arc::ptr_eq(&self.sem, &other.sem),
+            "merging permits from different semaphore instances"
+        );
+        self.permits += other.permits;
+        other.permits = 0;
+    }
+
+    /// Returns the number of permits held by this permit.
+    pub fn num_permits(&self) -> u32 {
+        self.permits
+    }
 }
 
 impl<'a> Drop for SemaphorePermit<'_> {
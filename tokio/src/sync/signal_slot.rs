@@ -0,0 +1,121 @@
+use crate::loom::sync::Mutex;
+use crate::sync::notify::Notify;
+
+use std::fmt;
+
+/// Notifies a task with a value.
+///
+/// `SignalSlot` is [`Notify`] with a payload: each call to
+/// [`notify`](Self::notify) deposits a value that the woken waiter receives
+/// from [`wait`](Self::wait). When nobody is waiting, a later `notify`
+/// overwrites the stored value — last write wins — so a waiter always
+/// observes the most recent reason it was woken, never a queue of stale
+/// ones.
+///
+/// This replaces the common pattern of pairing a `Notify` with a separate
+/// `Mutex<Option<T>>` to tell a worker task *why* it was kicked.
+///
+/// If all values must be delivered rather than coalesced, use an
+/// [`mpsc`](crate::sync::mpsc) channel instead. If both halves should be
+/// owned separately, see the [`slot`](crate::sync::slot) channel.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::SignalSlot;
+/// use std::sync::Arc;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Reason {
+///     Reload,
+///     Shutdown,
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let signal = Arc::new(SignalSlot::new());
+///     let signal2 = signal.clone();
+///
+///     let worker = tokio::spawn(async move {
+///         loop {
+///             match signal2.wait().await {
+///                 Reason::Reload => { /* reload configuration */ }
+///                 Reason::Shutdown => break,
+///             }
+///         }
+///     });
+///
+///     signal.notify(Reason::Reload);
+///     signal.notify(Reason::Shutdown);
+///     worker.await.unwrap();
+/// }
+/// ```
+pub struct SignalSlot<T> {
+    /// The most recently deposited value, taken by the woken waiter.
+    value: Mutex<Option<T>>,
+
+    /// Wakes the task waiting on the slot.
+    notify: Notify,
+}
+
+impl<T> SignalSlot<T> {
+    /// Creates a new `SignalSlot` without a stored value.
+    pub fn new() -> SignalSlot<T> {
+        SignalSlot {
+            value: Mutex::new(None),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Deposits a value and notifies a waiting task.
+    ///
+    /// If a task is currently waiting in [`wait`](Self::wait), it is woken
+    /// and receives the value. Otherwise the value is stored for the next
+    /// waiter, replacing any value already stored; the replaced value is
+    /// returned.
+    pub fn notify(&self, value: T) -> Option<T> {
+        let prev = self.value.lock().replace(value);
+        self.notify.notify_one();
+        prev
+    }
+
+    /// Waits until a value is deposited and receives it.
+    ///
+    /// If a value is already stored, it is returned immediately. At most one
+    /// waiter receives each deposited value; when several tasks wait on the
+    /// same slot, each `notify` wakes one of them.
+    pub async fn wait(&self) -> T {
+        loop {
+            // Request a notification before checking the slot, so a value
+            // deposited in between cannot be missed.
+            let notified = self.notify.notified();
+
+            if let Some(value) = self.value.lock().take() {
+                return value;
+            }
+
+            notified.await;
+        }
+    }
+
+    /// Takes the stored value without waiting.
+    ///
+    /// Returns `None` if no value has been deposited since the last take.
+    pub fn try_take(&self) -> Option<T> {
+        self.value.lock().take()
+    }
+}
+
+impl<T> Default for SignalSlot<T> {
+    fn default() -> SignalSlot<T> {
+        SignalSlot::new()
+    }
+}
+
+impl<T> fmt::Debug for SignalSlot<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("SignalSlot")
+            .field("occupied", &self.value.lock().is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,457 @@
+//! A single-value mailbox for sending values between tasks.
+//!
+//! Unlike [`oneshot`], both halves are reusable: a [`Sender`] may send any
+//! number of values and a [`Receiver`] may receive any number of values. The
+//! channel stores at most one value at a time; sending a value before the
+//! previous one was received **overwrites** it. This makes `slot` suited for
+//! repeatedly handing the latest piece of work to a peer task without
+//! recreating channel pairs in a loop.
+//!
+//! Unlike [`watch`], the value is **taken** by the receiver rather than
+//! observed in place, so `T` does not need to implement `Clone` and each
+//! value is received at most once.
+//!
+//! # Closing
+//!
+//! When the [`Sender`] is dropped, the channel is closed. The receiver may
+//! still take a value stored before the sender was dropped; afterwards,
+//! [`recv`] returns [`RecvError`]. When the [`Receiver`] is dropped, further
+//! calls to [`send`] fail, returning the value to the caller.
+//!
+//! [`oneshot`]: crate::sync::oneshot
+//! [`watch`]: crate::sync::watch
+//! [`Sender`]: crate::sync::slot::Sender
+//! [`Receiver`]: crate::sync::slot::Receiver
+//! [`send`]: crate::sync::slot::Sender::send
+//! [`recv`]: crate::sync::slot::Receiver::recv
+//! [`RecvError`]: crate::sync::slot::error::RecvError
+//!
+//! # Examples
+//!
+//! ```
+//! use tokio::sync::slot;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (tx, mut rx) = slot::channel();
+//!
+//!     tokio::spawn(async move {
+//!         for i in 0..3 {
+//!             tx.send(i).unwrap();
+//!             tx.notified().await;
+//!         }
+//!     });
+//!
+//!     for i in 0..3 {
+//!         assert_eq!(i, rx.recv().await.unwrap());
+//!     }
+//! }
+//! ```
+
+use crate::loom::sync::{Arc, Mutex};
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+/// Sending-half of the [`slot`] channel.
+///
+/// Values are sent with [`send`]. Sending overwrites any value that has not
+/// yet been received.
+///
+/// [`slot`]: crate::sync::slot
+/// [`send`]: crate::sync::slot::Sender::send
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// Receiving-half of the [`slot`] channel.
+///
+/// Values are received with [`recv`] or [`try_recv`], which take the stored
+/// value out of the slot.
+///
+/// [`slot`]: crate::sync::slot
+/// [`recv`]: crate::sync::slot::Receiver::recv
+/// [`try_recv`]: crate::sync::slot::Receiver::try_recv
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+pub mod error {
+    //! Slot error types
+
+    use std::fmt;
+
+    /// Error returned by the [`send`] function on a [`Sender`].
+    ///
+    /// A **send** operation can only fail if the receiving half of the
+    /// channel was dropped. The error contains the value being sent so it
+    /// can be recovered.
+    ///
+    /// [`send`]: crate::sync::slot::Sender::send
+    /// [`Sender`]: crate::sync::slot::Sender
+    #[derive(Debug)]
+    pub struct SendError<T>(pub T);
+
+    impl<T> fmt::Display for SendError<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "channel closed")
+        }
+    }
+
+    impl<T: fmt::Debug> std::error::Error for SendError<T> {}
+
+    /// Error returned by the [`recv`] function on a [`Receiver`].
+    ///
+    /// The sender was dropped and the slot holds no value, so no further
+    /// values can ever be received.
+    ///
+    /// [`recv`]: crate::sync::slot::Receiver::recv
+    /// [`Receiver`]: crate::sync::slot::Receiver
+    #[derive(Debug, PartialEq)]
+    pub struct RecvError(pub(super) ());
+
+    impl fmt::Display for RecvError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "channel closed")
+        }
+    }
+
+    impl std::error::Error for RecvError {}
+
+    /// Error returned by the [`try_recv`] function on a [`Receiver`].
+    ///
+    /// [`try_recv`]: crate::sync::slot::Receiver::try_recv
+    /// [`Receiver`]: crate::sync::slot::Receiver
+    #[derive(Debug, PartialEq)]
+    pub enum TryRecvError {
+        /// The slot is currently empty, but the sender has not yet been
+        /// dropped, so a value may still arrive.
+        Empty,
+
+        /// The sender was dropped and the slot holds no value, so no further
+        /// values can ever be received.
+        Closed,
+    }
+
+    impl fmt::Display for TryRecvError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                TryRecvError::Empty => write!(f, "channel empty"),
+                TryRecvError::Closed => write!(f, "channel closed"),
+            }
+        }
+    }
+
+    impl std::error::Error for TryRecvError {}
+}
+
+use self::error::*;
+
+/// State shared between the sender and the receiver.
+struct Shared<T> {
+    inner: Mutex<Inner<T>>,
+}
+
+struct Inner<T> {
+    /// The stored value, if any.
+    value: Option<T>,
+
+    /// Waker registered by `recv` waiting for a value.
+    rx_waker: Option<Waker>,
+
+    /// Waker registered by `notified` waiting for the slot to empty.
+    tx_waker: Option<Waker>,
+
+    /// True when the sender was dropped.
+    tx_dropped: bool,
+
+    /// True when the receiver was dropped.
+    rx_dropped: bool,
+}
+
+/// Creates a new slot channel, returning the sender and receiver halves.
+///
+/// The channel holds at most one value. Each value sent overwrites any value
+/// that has not yet been received, and each value is received at most once.
+/// Both halves may be used repeatedly.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::slot;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (tx, mut rx) = slot::channel();
+///
+///     // Only the latest value is kept.
+///     tx.send(1).unwrap();
+///     tx.send(2).unwrap();
+///
+///     assert_eq!(2, rx.recv().await.unwrap());
+/// }
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        inner: Mutex::new(Inner {
+            value: None,
+            rx_waker: None,
+            tx_waker: None,
+            tx_dropped: false,
+            rx_dropped: false,
+        }),
+    });
+
+    let rx = Receiver {
+        shared: shared.clone(),
+    };
+
+    let tx = Sender { shared };
+
+    (tx, rx)
+}
+
+impl<T> Sender<T> {
+    /// Stores a value in the slot, waking the receiver.
+    ///
+    /// Any value that has not yet been received is overwritten and returned
+    /// as `Ok(Some(value))`.
+    ///
+    /// An error is returned if the [`Receiver`] was dropped; the error
+    /// contains the value being sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::slot;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = slot::channel();
+    ///
+    ///     assert_eq!(None, tx.send(1).unwrap());
+    ///     assert_eq!(Some(1), tx.send(2).unwrap());
+    ///
+    ///     assert_eq!(2, rx.recv().await.unwrap());
+    /// }
+    /// ```
+    pub fn send(&self, value: T) -> Result<Option<T>, SendError<T>> {
+        let mut inner = self.shared.inner.lock();
+
+        if inner.rx_dropped {
+            return Err(SendError(value));
+        }
+
+        let prev = inner.value.replace(value);
+
+        if let Some(waker) = inner.rx_waker.take() {
+            // Release the lock before waking.
+            drop(inner);
+            waker.wake();
+        }
+
+        Ok(prev)
+    }
+
+    /// Completes when the value stored in the slot has been received.
+    ///
+    /// If the slot is currently empty, this completes immediately. This
+    /// allows a sender to wait for the receiver to catch up instead of
+    /// overwriting an unreceived value.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. Dropping the returned future does not
+    /// lose the notification.
+    pub async fn notified(&self) {
+        Notified { sender: self }.await
+    }
+
+    /// Returns `true` if the [`Receiver`] was dropped.
+    pub fn is_closed(&self) -> bool {
+        self.shared.inner.lock().rx_dropped
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut inner = self.shared.inner.lock();
+
+        inner.tx_dropped = true;
+
+        if let Some(waker) = inner.rx_waker.take() {
+            drop(inner);
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Receives the next value, waiting until one is stored in the slot.
+    ///
+    /// The value is taken out of the slot, leaving it empty. An error is
+    /// returned once the [`Sender`] was dropped and the slot is empty.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If `recv` is dropped before completing,
+    /// no value was taken out of the slot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::slot;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = slot::channel();
+    ///
+    ///     tokio::spawn(async move {
+    ///         tx.send("hello").unwrap();
+    ///     });
+    ///
+    ///     assert_eq!("hello", rx.recv().await.unwrap());
+    ///
+    ///     // The sender was dropped.
+    ///     assert!(rx.recv().await.is_err());
+    /// }
+    /// ```
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        Recv { receiver: self }.await
+    }
+
+    /// Attempts to take the value out of the slot without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::slot;
+    /// use tokio::sync::slot::error::TryRecvError;
+    ///
+    /// fn main() {
+    ///     let (tx, mut rx) = slot::channel();
+    ///
+    ///     assert_eq!(Err(TryRecvError::Empty), rx.try_recv());
+    ///
+    ///     tx.send(1).unwrap();
+    ///     assert_eq!(Ok(1), rx.try_recv());
+    ///
+    ///     drop(tx);
+    ///     assert_eq!(Err(TryRecvError::Closed), rx.try_recv());
+    /// }
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut inner = self.shared.inner.lock();
+
+        match inner.value.take() {
+            Some(value) => {
+                if let Some(waker) = inner.tx_waker.take() {
+                    drop(inner);
+                    waker.wake();
+                }
+
+                Ok(value)
+            }
+            None if inner.tx_dropped => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, RecvError>> {
+        let mut inner = self.shared.inner.lock();
+
+        if let Some(value) = inner.value.take() {
+            if let Some(waker) = inner.tx_waker.take() {
+                drop(inner);
+                waker.wake();
+            }
+
+            return Poll::Ready(Ok(value));
+        }
+
+        if inner.tx_dropped {
+            return Poll::Ready(Err(RecvError(())));
+        }
+
+        // Register the waker to be notified on the next send. Only clone if
+        // the stored waker would not wake the current task.
+        match inner.rx_waker {
+            Some(ref waker) if waker.will_wake(cx.waker()) => {}
+            _ => {
+                inner.rx_waker = Some(cx.waker().clone());
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut inner = self.shared.inner.lock();
+
+        inner.rx_dropped = true;
+
+        // Drop any unreceived value eagerly and wake a sender waiting in
+        // `notified`.
+        inner.value = None;
+
+        if let Some(waker) = inner.tx_waker.take() {
+            drop(inner);
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [`Receiver::recv`].
+struct Recv<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<'a, T> Future for Recv<'a, T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<T, RecvError>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Future returned by [`Sender::notified`].
+struct Notified<'a, T> {
+    sender: &'a Sender<T>,
+}
+
+impl<'a, T> Future for Notified<'a, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut inner = self.sender.shared.inner.lock();
+
+        if inner.value.is_none() || inner.rx_dropped {
+            return Poll::Ready(());
+        }
+
+        match inner.tx_waker {
+            Some(ref waker) if waker.will_wake(cx.waker()) => {}
+            _ => {
+                inner.tx_waker = Some(cx.waker().clone());
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "slot::Sender")
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "slot::Receiver")
+    }
+}
@@ -0,0 +1,392 @@
+//! A single-producer, single-consumer queue for sending values between
+//! asynchronous tasks.
+//!
+//! This is a specialized alternative to the [`mpsc`] bounded channel for the
+//! point-to-point case. Values live in a fixed ring buffer allocated up
+//! front, and each side is driven by a single task, so the implementation
+//! needs neither the linked list of blocks nor the sender reference counting
+//! of the mpsc path: a send or receive is an index bump and one atomic
+//! store.
+//!
+//! The restriction to one producer and one consumer is enforced by the API:
+//! [`Sender`] and [`Receiver`] are not `Clone`, and sending and receiving
+//! take `&mut self`.
+//!
+//! [`mpsc`]: crate::sync::mpsc
+//!
+//! # Examples
+//!
+//! ```
+//! use tokio::sync::spsc;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let (mut tx, mut rx) = spsc::channel(16);
+//!
+//!     tokio::spawn(async move {
+//!         for i in 0..10 {
+//!             if tx.send(i).await.is_err() {
+//!                 return;
+//!             }
+//!         }
+//!     });
+//!
+//!     let mut sum = 0;
+//!     while let Some(i) = rx.recv().await {
+//!         sum += i;
+//!     }
+//!
+//!     assert_eq!(sum, 45);
+//! }
+//! ```
+
+use crate::loom::cell::UnsafeCell;
+use crate::loom::sync::atomic::{AtomicBool, AtomicUsize};
+use crate::loom::sync::Arc;
+use crate::sync::task::AtomicWaker;
+
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use std::task::{Context, Poll};
+
+pub mod error {
+    //! SPSC channel error types
+
+    use std::error::Error;
+    use std::fmt;
+
+    /// Error returned by [`Sender::send`](super::Sender::send): the receiver
+    /// was dropped, and the value is handed back.
+    #[derive(Debug)]
+    pub struct SendError<T>(pub T);
+
+    impl<T> fmt::Display for SendError<T> {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(fmt, "channel closed")
+        }
+    }
+
+    impl<T: fmt::Debug> Error for SendError<T> {}
+
+    /// Error returned by [`Sender::try_send`](super::Sender::try_send).
+    #[derive(Debug)]
+    pub enum TrySendError<T> {
+        /// The channel's ring buffer is full.
+        Full(T),
+
+        /// The receiver was dropped.
+        Closed(T),
+    }
+
+    impl<T> fmt::Display for TrySendError<T> {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                fmt,
+                "{}",
+                match self {
+                    TrySendError::Full(..) => "no available capacity",
+                    TrySendError::Closed(..) => "channel closed",
+                }
+            )
+        }
+    }
+
+    impl<T: fmt::Debug> Error for TrySendError<T> {}
+
+    /// Error returned by [`Receiver::try_recv`](super::Receiver::try_recv).
+    #[derive(Debug, PartialEq)]
+    pub enum TryRecvError {
+        /// The ring buffer is currently empty, but the sender is still alive.
+        Empty,
+
+        /// The sender was dropped and all buffered values have been received.
+        Closed,
+    }
+
+    impl fmt::Display for TryRecvError {
+        fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                fmt,
+                "{}",
+                match self {
+                    TryRecvError::Empty => "channel empty",
+                    TryRecvError::Closed => "channel closed",
+                }
+            )
+        }
+    }
+
+    impl Error for TryRecvError {}
+}
+
+use error::{SendError, TryRecvError, TrySendError};
+
+/// Creates a single-producer, single-consumer channel with room for exactly
+/// `capacity` buffered values.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "spsc bounded channel requires capacity > 0");
+
+    let mut buffer = Vec::with_capacity(capacity);
+    buffer.resize_with(capacity, || UnsafeCell::new(MaybeUninit::uninit()));
+
+    let chan = Arc::new(Channel {
+        buffer: buffer.into_boxed_slice(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        tx_dropped: AtomicBool::new(false),
+        rx_dropped: AtomicBool::new(false),
+        rx_waker: AtomicWaker::new(),
+        tx_waker: AtomicWaker::new(),
+    });
+
+    (
+        Sender { chan: chan.clone() },
+        Receiver { chan },
+    )
+}
+
+/// Sends values to the associated [`Receiver`].
+///
+/// Created by the [`channel`] function. There is exactly one sender per
+/// channel; it cannot be cloned.
+pub struct Sender<T> {
+    chan: Arc<Channel<T>>,
+}
+
+/// Receives values from the associated [`Sender`].
+///
+/// Created by the [`channel`] function. There is exactly one receiver per
+/// channel; it cannot be cloned.
+pub struct Receiver<T> {
+    chan: Arc<Channel<T>>,
+}
+
+struct Channel<T> {
+    /// The ring buffer. A slot at `pos % capacity` is owned by the producer
+    /// while `pos` is in `tail - capacity..tail` and free, and by the
+    /// consumer while initialized and in `head..tail`.
+    buffer: Box<[UnsafeCell<MaybeUninit<T>>]>,
+
+    /// Position of the next value to read. Written by the consumer only.
+    head: AtomicUsize,
+
+    /// Position of the next value to write. Written by the producer only.
+    tail: AtomicUsize,
+
+    /// Set when the `Sender` is dropped.
+    tx_dropped: AtomicBool,
+
+    /// Set when the `Receiver` is dropped.
+    rx_dropped: AtomicBool,
+
+    /// Wakes the consumer after a value is written or the sender is dropped.
+    rx_waker: AtomicWaker,
+
+    /// Wakes the producer after a slot is freed or the receiver is dropped.
+    tx_waker: AtomicWaker,
+}
+
+// Safety: only one side accesses a given slot at a time, hand-off being
+// synchronized by the `head`/`tail` release stores.
+unsafe impl<T: Send> Send for Channel<T> {}
+unsafe impl<T: Send> Sync for Channel<T> {}
+
+impl<T> Channel<T> {
+    fn len(&self) -> usize {
+        self.tail.load(Acquire).wrapping_sub(self.head.load(Acquire))
+    }
+
+    /// Writes `value` at `tail` and publishes it. May only be called by the
+    /// producer with a free slot.
+    fn push(&self, tail: usize, value: T) {
+        let slot = &self.buffer[tail % self.buffer.len()];
+        slot.with_mut(|ptr| unsafe { (*ptr).as_mut_ptr().write(value) });
+        self.tail.store(tail.wrapping_add(1), Release);
+        self.rx_waker.wake();
+    }
+
+    /// Reads the value at `head` and frees the slot. May only be called by
+    /// the consumer with `head != tail`.
+    fn pop(&self, head: usize) -> T {
+        let slot = &self.buffer[head % self.buffer.len()];
+        let value = slot.with(|ptr| unsafe { ptr::read(ptr).assume_init() });
+        self.head.store(head.wrapping_add(1), Release);
+        self.tx_waker.wake();
+        value
+    }
+}
+
+impl<T> Drop for Channel<T> {
+    fn drop(&mut self) {
+        // Both halves are gone; values still in the buffer are dropped here.
+        let mut head = self.head.load(Relaxed);
+        let tail = self.tail.load(Relaxed);
+
+        while head != tail {
+            let slot = &self.buffer[head % self.buffer.len()];
+            slot.with_mut(|ptr| unsafe { ptr::drop_in_place((*ptr).as_mut_ptr()) });
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    /// Sends a value, waiting until there is capacity.
+    ///
+    /// An error is returned if the receiver was dropped; the value is handed
+    /// back in the error.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If the future is dropped before
+    /// completing, no slot was consumed and the value is dropped along with
+    /// the future.
+    pub async fn send(&mut self, value: T) -> Result<(), SendError<T>> {
+        let mut value = Some(value);
+        crate::future::poll_fn(|cx| self.poll_send(cx, &mut value)).await
+    }
+
+    /// Attempts to send a value without waiting.
+    pub fn try_send(&mut self, value: T) -> Result<(), TrySendError<T>> {
+        if self.chan.rx_dropped.load(Acquire) {
+            return Err(TrySendError::Closed(value));
+        }
+
+        let tail = self.chan.tail.load(Relaxed);
+        if tail.wrapping_sub(self.chan.head.load(Acquire)) == self.chan.buffer.len() {
+            return Err(TrySendError::Full(value));
+        }
+
+        self.chan.push(tail, value);
+        Ok(())
+    }
+
+    /// Returns `true` if the receiver has been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.chan.rx_dropped.load(Acquire)
+    }
+
+    fn poll_send(&mut self, cx: &mut Context<'_>, value: &mut Option<T>) -> Poll<Result<(), SendError<T>>> {
+        loop {
+            if self.chan.rx_dropped.load(Acquire) {
+                return Poll::Ready(Err(SendError(value.take().unwrap())));
+            }
+
+            let tail = self.chan.tail.load(Relaxed);
+            if tail.wrapping_sub(self.chan.head.load(Acquire)) < self.chan.buffer.len() {
+                self.chan.push(tail, value.take().unwrap());
+                return Poll::Ready(Ok(()));
+            }
+
+            self.chan.tx_waker.register_by_ref(cx.waker());
+
+            // Re-check after registering: a slot freed (or the receiver
+            // dropped) in the meantime may have missed the waker.
+            if self.chan.rx_dropped.load(Acquire)
+                || tail.wrapping_sub(self.chan.head.load(Acquire)) < self.chan.buffer.len()
+            {
+                continue;
+            }
+
+            return Poll::Pending;
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.chan.tx_dropped.store(true, Release);
+        self.chan.rx_waker.wake();
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Sender")
+            .field("len", &self.chan.len())
+            .finish()
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Receives the next value, waiting for one if the buffer is empty.
+    ///
+    /// Returns `None` once the sender has been dropped and every buffered
+    /// value has been received.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. If it is dropped before completing, no
+    /// value was taken out of the channel.
+    pub async fn recv(&mut self) -> Option<T> {
+        crate::future::poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    /// Attempts to receive the next value without waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let head = self.chan.head.load(Relaxed);
+        if head != self.chan.tail.load(Acquire) {
+            return Ok(self.chan.pop(head));
+        }
+
+        if self.chan.tx_dropped.load(Acquire) {
+            // The flag is set before the sender's last wake; the buffer was
+            // checked after the flag, so it is empty for good.
+            if head != self.chan.tail.load(Acquire) {
+                return Ok(self.chan.pop(head));
+            }
+            return Err(TryRecvError::Closed);
+        }
+
+        Err(TryRecvError::Empty)
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        loop {
+            let head = self.chan.head.load(Relaxed);
+            if head != self.chan.tail.load(Acquire) {
+                return Poll::Ready(Some(self.chan.pop(head)));
+            }
+
+            if self.chan.tx_dropped.load(Acquire) {
+                // A value published between the emptiness check and the flag
+                // load is caught by looping once more.
+                if head != self.chan.tail.load(Acquire) {
+                    continue;
+                }
+                return Poll::Ready(None);
+            }
+
+            self.chan.rx_waker.register_by_ref(cx.waker());
+
+            // Re-check after registering: a value published (or the sender
+            // dropped) in the meantime may have missed the waker.
+            if head != self.chan.tail.load(Acquire) || self.chan.tx_dropped.load(Acquire) {
+                continue;
+            }
+
+            return Poll::Pending;
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.chan.rx_dropped.store(true, Release);
+        self.chan.tx_waker.wake();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Receiver")
+            .field("len", &self.chan.len())
+            .finish()
+    }
+}
@@ -0,0 +1,129 @@
+use crate::runtime;
+use crate::sync::{diagnostics, Mutex};
+use crate::task;
+
+use std::ptr;
+use std::sync::atomic::{
+    AtomicPtr,
+    Ordering::{AcqRel, Acquire},
+};
+use std::sync::{Arc, MutexGuard};
+
+/// Serializes the tests in this file: the tracking state is global, so a
+/// deadlock staged by one test must not leak into another's assertions.
+fn serial() -> MutexGuard<'static, ()> {
+    static LOCK: AtomicPtr<std::sync::Mutex<()>> = AtomicPtr::new(ptr::null_mut());
+
+    let mut curr = LOCK.load(Acquire);
+    if curr.is_null() {
+        let init = Box::into_raw(Box::new(std::sync::Mutex::new(())));
+        curr = match LOCK.compare_exchange(ptr::null_mut(), init, AcqRel, Acquire) {
+            Ok(_) => init,
+            Err(existing) => {
+                drop(unsafe { Box::from_raw(init) });
+                existing
+            }
+        };
+    }
+
+    // Safety: the pointer, once published, is never replaced or freed. The
+    // lock is not poisoned by a failing test.
+    match unsafe { &*curr }.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Two tasks acquiring two mutexes in opposite order deadlock; the cycle
+/// must name both tasks and both resources.
+#[test]
+fn detect_cycles_reports_deadlocked_tasks() {
+    let _serial = serial();
+    let rt = runtime::Builder::new_current_thread().build().unwrap();
+
+    let a = Arc::new(Mutex::new(()));
+    let b = Arc::new(Mutex::new(()));
+
+    diagnostics::enable_tracking();
+
+    let (h1, h2, cycle) = rt.block_on(async {
+        let (a1, b1) = (a.clone(), b.clone());
+        let h1 = crate::spawn(async move {
+            let _a = a1.lock().await;
+            // Let the other task take `b` before going for it.
+            task::yield_now().await;
+            let _b = b1.lock().await;
+        });
+
+        let (a2, b2) = (a.clone(), b.clone());
+        let h2 = crate::spawn(async move {
+            let _b = b2.lock().await;
+            task::yield_now().await;
+            let _a = a2.lock().await;
+        });
+
+        // Drive the scheduler until both tasks are blocked on each other.
+        let mut remaining = 1000;
+        let cycle = loop {
+            if let Some(cycle) = diagnostics::detect_cycles().into_iter().find(|cycle| {
+                cycle.tasks().contains(&h1.task_id()) && cycle.tasks().contains(&h2.task_id())
+            }) {
+                break cycle;
+            }
+
+            remaining -= 1;
+            assert!(remaining > 0, "deadlock was not detected");
+            task::yield_now().await;
+        };
+
+        (h1, h2, cycle)
+    });
+
+    assert_eq!(cycle.tasks().len(), 2);
+    assert!(cycle.tasks().contains(&h1.task_id()));
+    assert!(cycle.tasks().contains(&h2.task_id()));
+
+    // Each task waits for a distinct resource.
+    assert_eq!(cycle.resources().len(), 2);
+    assert_ne!(cycle.resources()[0], cycle.resources()[1]);
+
+    // Dropping the runtime drops the deadlocked tasks and their guards.
+    drop(rt);
+    assert!(diagnostics::detect_cycles().is_empty());
+
+    diagnostics::disable_tracking();
+}
+
+/// Contention that resolves on its own must not be reported, and nothing is
+/// left behind once the guards are dropped.
+#[test]
+fn detect_cycles_empty_after_guards_drop() {
+    let _serial = serial();
+    let rt = runtime::Builder::new_current_thread().build().unwrap();
+
+    let lock = Arc::new(Mutex::new(()));
+
+    diagnostics::enable_tracking();
+
+    rt.block_on(async {
+        let lock1 = lock.clone();
+        let h1 = crate::spawn(async move {
+            let _g = lock1.lock().await;
+            task::yield_now().await;
+        });
+
+        let lock2 = lock.clone();
+        let h2 = crate::spawn(async move {
+            let _g = lock2.lock().await;
+            task::yield_now().await;
+        });
+
+        h1.await.unwrap();
+        h2.await.unwrap();
+    });
+
+    // Both guards dropped; the wait-for graph must be empty again.
+    assert!(diagnostics::detect_cycles().is_empty());
+
+    diagnostics::disable_tracking();
+}
@@ -11,7 +11,7 @@ fn smoke() {
     const NUM_MSG: usize = 2;
 
     loom::model(|| {
-        let (tx, mut rx) = list::channel();
+        let (tx, mut rx) = list::channel_with_block_size(crate::sync::mpsc::BLOCK_CAP, None);
         let tx = Arc::new(tx);
 
         for th in 0..NUM_TX {
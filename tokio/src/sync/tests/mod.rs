@@ -1,5 +1,7 @@
 cfg_not_loom! {
     mod atomic_waker;
+    #[cfg(all(tokio_unstable, feature = "rt"))]
+    mod diagnostics;
     mod semaphore_batch;
 }
 
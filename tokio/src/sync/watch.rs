@@ -57,7 +57,8 @@ use crate::sync::notify::Notify;
 
 use crate::loom::sync::atomic::AtomicUsize;
 use crate::loom::sync::atomic::Ordering::{Relaxed, SeqCst};
-use crate::loom::sync::{Arc, RwLock, RwLockReadGuard};
+use crate::loom::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use std::collections::VecDeque;
 use std::ops;
 
 /// Receives values from the associated [`Sender`](struct@Sender).
@@ -114,6 +115,28 @@ struct Shared<T> {
 
     /// Notifies any task listening for `Receiver` dropped events
     notify_tx: Notify,
+
+    /// Bounded replay buffer, set by [`channel_with_history`].
+    history: Option<History<T>>,
+}
+
+/// Ring of recently sent values, keyed by the version they were sent at.
+struct History<T> {
+    /// Buffered `(version, value)` pairs, oldest first.
+    entries: Mutex<VecDeque<(usize, T)>>,
+
+    /// Maximum number of buffered values.
+    depth: usize,
+
+    /// Clones values into the buffer. Storing the fn pointer here lets `send`
+    /// stay free of a `T: Clone` bound on the plain channel.
+    clone: fn(&T) -> T,
+}
+
+impl<T> std::fmt::Debug for History<T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("History").field("depth", &self.depth).finish()
+    }
 }
 
 pub mod error {
@@ -188,6 +211,71 @@ pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
         ref_count_rx: AtomicUsize::new(1),
         notify_rx: Notify::new(),
         notify_tx: Notify::new(),
+        history: None,
+    });
+
+    let tx = Sender {
+        shared: shared.clone(),
+    };
+
+    let rx = Receiver { shared, version: 0 };
+
+    (tx, rx)
+}
+
+/// Creates a new watch channel that retains the last `depth` sent values.
+///
+/// Unlike [`channel`], intermediate values are not immediately dropped: the
+/// channel buffers up to `depth` values, and [`Receiver::changed`] observes
+/// each buffered transition in order rather than skipping straight to the
+/// latest. A receiver that falls more than `depth` values behind loses the
+/// evicted transitions and resumes at the oldest buffered one. New receivers
+/// created with [`Sender::subscribe`] start with the whole buffer unseen, so
+/// they can replay the last `depth` transitions.
+///
+/// [`Receiver::recv`] waits for the next transition and returns its value,
+/// and [`Receiver::history`] returns a snapshot of the buffered values.
+///
+/// # Panics
+///
+/// Panics if `depth` is zero.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::sync::watch;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let (tx, mut rx) = watch::channel_with_history(0, 4);
+///
+///     tx.send(1).unwrap();
+///     tx.send(2).unwrap();
+///
+///     // Every transition is observed, not just the latest value.
+///     assert_eq!(rx.recv().await.unwrap(), 1);
+///     assert_eq!(rx.recv().await.unwrap(), 2);
+/// }
+/// ```
+///
+/// [`channel`]: fn@channel
+pub fn channel_with_history<T: Clone>(init: T, depth: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(depth > 0, "history depth must be greater than 0");
+
+    let mut entries = VecDeque::with_capacity(depth.min(64));
+    entries.push_back((0, init.clone()));
+
+    let shared = Arc::new(Shared {
+        value: RwLock::new(init),
+        version: AtomicUsize::new(0),
+        ref_count_rx: AtomicUsize::new(1),
+        notify_rx: Notify::new(),
+        notify_tx: Notify::new(),
+        history: Some(History {
+            entries: Mutex::new(entries),
+            depth,
+            clone: T::clone,
+        }),
     });
 
     let tx = Sender {
@@ -276,6 +364,154 @@ impl<T> Receiver<T> {
             maybe_changed(&self.shared, &mut self.version)
         }
     }
+
+    /// Waits for a value that satisfies the provided condition.
+    ///
+    /// The current value is checked immediately, so this can return without
+    /// waiting; otherwise the predicate is re-run every time a new value is
+    /// sent. On success, a borrow of the first matching value is returned and
+    /// that value is marked as seen. When the `Sender` half is dropped before
+    /// a matching value is observed, `Err` is returned.
+    ///
+    /// The check-then-wait race is handled internally: a notification is
+    /// requested before each check, so a send between the check and the wait
+    /// is never missed.
+    ///
+    /// On a channel created with [`channel_with_history`], this checks the
+    /// latest value only and marks all buffered transitions as seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::watch;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = watch::channel(0);
+    ///
+    ///     tokio::spawn(async move {
+    ///         for i in 1..=5 {
+    ///             tx.send(i).unwrap();
+    ///         }
+    ///     });
+    ///
+    ///     let seen = rx.wait_for(|v| *v >= 3).await.unwrap();
+    ///     assert!(*seen >= 3);
+    /// }
+    /// ```
+    ///
+    /// [`channel_with_history`]: fn@channel_with_history
+    pub async fn wait_for(
+        &mut self,
+        mut f: impl FnMut(&T) -> bool,
+    ) -> Result<Ref<'_, T>, error::RecvError> {
+        loop {
+            // As in `changed`, request a notification before checking so a
+            // send arriving between the check and the await is not missed.
+            let notified = self.shared.notify_rx.notified();
+
+            let state = self.shared.version.load(SeqCst);
+
+            {
+                let inner = self.shared.value.read().unwrap();
+
+                if f(&inner) {
+                    self.version = state & !CLOSED;
+                    return Ok(Ref { inner });
+                }
+            }
+
+            if CLOSED == state & CLOSED {
+                // The sender dropped without producing a matching value.
+                return Err(error::RecvError(()));
+            }
+
+            notified.await;
+            // loop around again in case the wake-up was spurious
+        }
+    }
+
+    /// Waits for the next change and returns the new value.
+    ///
+    /// On a channel created with [`channel_with_history`], this returns each
+    /// buffered transition in order: the value that was current when the
+    /// observed version was sent, even if newer values have been sent since.
+    /// On a plain [`channel`], this is equivalent to awaiting
+    /// [`changed`](Self::changed) and cloning [`borrow`](Self::borrow).
+    ///
+    /// When the `Sender` half is dropped, `Err` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::watch;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, mut rx) = watch::channel_with_history("hello", 2);
+    ///
+    ///     tx.send("inter").unwrap();
+    ///     tx.send("goodbye").unwrap();
+    ///
+    ///     assert_eq!(rx.recv().await.unwrap(), "inter");
+    ///     assert_eq!(rx.recv().await.unwrap(), "goodbye");
+    /// }
+    /// ```
+    ///
+    /// [`channel_with_history`]: fn@channel_with_history
+    /// [`channel`]: fn@channel
+    pub async fn recv(&mut self) -> Result<T, error::RecvError>
+    where
+        T: Clone,
+    {
+        self.changed().await?;
+
+        if let Some(history) = &self.shared.history {
+            let entries = history.entries.lock();
+
+            if let Some((_, value)) = entries.iter().find(|(v, _)| *v == self.version) {
+                return Ok(value.clone());
+            }
+        }
+
+        // Either a plain channel, or the observed version was evicted from
+        // the buffer before we got here; fall back to the latest value.
+        Ok(self.shared.value.read().unwrap().clone())
+    }
+
+    /// Returns a snapshot of the values retained in the history buffer,
+    /// oldest first.
+    ///
+    /// On a channel created with [`channel`], the buffer does not exist and
+    /// an empty `Vec` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::watch;
+    ///
+    /// let (tx, rx) = watch::channel_with_history(0, 2);
+    ///
+    /// tx.send(1).unwrap();
+    /// tx.send(2).unwrap();
+    ///
+    /// assert_eq!(rx.history(), vec![1, 2]);
+    /// ```
+    ///
+    /// [`channel_with_history`]: fn@channel_with_history
+    /// [`channel`]: fn@channel
+    pub fn history(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        match &self.shared.history {
+            Some(history) => {
+                let entries = history.entries.lock();
+                entries.iter().map(|(_, value)| value.clone()).collect()
+            }
+            None => Vec::new(),
+        }
+    }
 }
 
 fn maybe_changed<T>(
@@ -287,6 +523,18 @@ fn maybe_changed<T>(
     let new_version = state & !CLOSED;
 
     if *version != new_version {
+        // On a history channel, advance to the oldest unseen buffered version
+        // so no retained transition is skipped. Transitions evicted from the
+        // buffer are lost; the receiver resumes at the oldest retained one.
+        if let Some(history) = &shared.history {
+            let entries = history.entries.lock();
+
+            if let Some((next, _)) = entries.iter().find(|(v, _)| is_after(*v, *version)) {
+                *version = *next;
+                return Some(Ok(()));
+            }
+        }
+
         // Observe the new version and return
         *version = new_version;
         return Some(Ok(()));
@@ -300,6 +548,15 @@ fn maybe_changed<T>(
     None
 }
 
+/// Returns `true` if version `v` was produced after `seen`.
+///
+/// Versions advance by two per send, so a wrapping subtraction keeps ordering
+/// correct even across version counter wrap-around.
+fn is_after(v: usize, seen: usize) -> bool {
+    let diff = v.wrapping_sub(seen);
+    diff != 0 && diff <= usize::MAX / 2
+}
+
 impl<T> Clone for Receiver<T> {
     fn clone(&self) -> Self {
         let version = self.version;
@@ -328,10 +585,27 @@ impl<T> Sender<T> {
             return Err(error::SendError { inner: value });
         }
 
-        *self.shared.value.write().unwrap() = value;
+        {
+            let mut slot = self.shared.value.write().unwrap();
+
+            if let Some(history) = &self.shared.history {
+                // The value write lock is held, so the version cannot move
+                // under us and entries stay in version order.
+                let version = (self.shared.version.load(SeqCst) & !CLOSED).wrapping_add(2);
+                let mut entries = history.entries.lock();
 
-        // Update the version. 2 is used so that the CLOSED bit is not set.
-        self.shared.version.fetch_add(2, SeqCst);
+                entries.push_back((version, (history.clone)(&value)));
+
+                while entries.len() > history.depth {
+                    entries.pop_front();
+                }
+            }
+
+            *slot = value;
+
+            // Update the version. 2 is used so that the CLOSED bit is not set.
+            self.shared.version.fetch_add(2, SeqCst);
+        }
 
         // Notify all watchers
         self.shared.notify_rx.notify_waiters();
@@ -339,6 +613,91 @@ impl<T> Sender<T> {
         Ok(())
     }
 
+    /// Modifies the watched value in place, notifying all receivers.
+    ///
+    /// Unlike [`send`](Self::send), this applies even when no receivers
+    /// exist, and does not require constructing a whole new value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::watch;
+    ///
+    /// let (tx, rx) = watch::channel(0);
+    ///
+    /// tx.send_modify(|v| *v += 1);
+    /// assert_eq!(*rx.borrow(), 1);
+    /// ```
+    pub fn send_modify<F>(&self, modify: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        self.send_if_modified(|value| {
+            modify(value);
+            true
+        });
+    }
+
+    /// Modifies the watched value in place, notifying all receivers only if
+    /// the closure reports a material change.
+    ///
+    /// The closure mutates the value under the write lock and returns whether
+    /// receivers should be woken. Returning `false` keeps the mutation but
+    /// skips both the version bump and the notification, so high-frequency
+    /// updates that do not matter to consumers cause no wakeups at all.
+    /// Returns the closure's result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::watch;
+    ///
+    /// let (tx, rx) = watch::channel(1u64);
+    ///
+    /// // Only notify when the value changes by an order of magnitude.
+    /// let notify = |old: u64, new: u64| new / 10 >= old || old / 10 >= new;
+    ///
+    /// let notified = tx.send_if_modified(|v| {
+    ///     let old = *v;
+    ///     *v = 5;
+    ///     notify(old, 5)
+    /// });
+    ///
+    /// assert!(!notified);
+    /// assert_eq!(*rx.borrow(), 5);
+    /// ```
+    pub fn send_if_modified<F>(&self, modify: F) -> bool
+    where
+        F: FnOnce(&mut T) -> bool,
+    {
+        {
+            let mut slot = self.shared.value.write().unwrap();
+
+            if !modify(&mut slot) {
+                // The mutation is kept, but receivers are not woken and the
+                // version is unchanged so `changed()` does not fire.
+                return false;
+            }
+
+            if let Some(history) = &self.shared.history {
+                let version = (self.shared.version.load(SeqCst) & !CLOSED).wrapping_add(2);
+                let mut entries = history.entries.lock();
+
+                entries.push_back((version, (history.clone)(&slot)));
+
+                while entries.len() > history.depth {
+                    entries.pop_front();
+                }
+            }
+
+            self.shared.version.fetch_add(2, SeqCst);
+        }
+
+        self.shared.notify_rx.notify_waiters();
+
+        true
+    }
+
     /// Returns a reference to the most recently sent value
     ///
     /// Outstanding borrows hold a read lock. This means that long lived borrows
@@ -409,13 +768,102 @@ impl<T> Sender<T> {
         debug_assert_eq!(0, self.shared.ref_count_rx.load(Relaxed));
     }
 
-    cfg_signal_internal! {
-        pub(crate) fn subscribe(&self) -> Receiver<T> {
-            let shared = self.shared.clone();
-            let version = shared.version.load(SeqCst);
+    /// Creates a new [`Receiver`] connected to this `Sender`.
+    ///
+    /// All messages sent before this call to `subscribe` are initially marked
+    /// as seen by the new `Receiver`. On a channel created with
+    /// [`channel_with_history`], the buffered values are instead marked as
+    /// unseen so the new receiver can replay them.
+    ///
+    /// This method can be called even if there are no other receivers. In
+    /// this case, the channel is reopened.
+    ///
+    /// # Examples
+    ///
+    /// The new channel will receive messages sent on it.
+    ///
+    /// ```
+    /// use tokio::sync::watch;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, _rx) = watch::channel(0u64);
+    ///
+    ///     tx.send(5).unwrap();
+    ///
+    ///     let rx = tx.subscribe();
+    ///     assert_eq!(5, *rx.borrow());
+    ///
+    ///     tx.send(10).unwrap();
+    ///     assert_eq!(10, *rx.borrow());
+    /// }
+    /// ```
+    ///
+    /// The most recent message is considered seen by the new receiver.
+    ///
+    /// ```
+    /// use tokio::sync::watch;
+    /// use tokio::time::Duration;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, _rx) = watch::channel(0u64);
+    ///     tx.send(5).unwrap();
+    ///     let mut rx = tx.subscribe();
+    ///
+    ///     tokio::spawn(async move {
+    ///         // by spawning and sleeping, the message is sent after `changed`
+    ///         // is called.
+    ///         tokio::time::sleep(Duration::from_millis(10)).await;
+    ///         tx.send(100).unwrap();
+    ///     });
+    ///
+    ///     rx.changed().await.unwrap();
+    ///     assert_eq!(100, *rx.borrow());
+    /// }
+    /// ```
+    ///
+    /// [`channel_with_history`]: fn@channel_with_history
+    pub fn subscribe(&self) -> Receiver<T> {
+        let shared = self.shared.clone();
 
-            Receiver::from_shared(version, shared)
-        }
+        // On a history channel, start before the oldest buffered version so
+        // the new receiver replays the whole buffer. See
+        // `channel_with_history`.
+        let version = match &shared.history {
+            Some(history) => {
+                let entries = history.entries.lock();
+                match entries.front() {
+                    Some((oldest, _)) => oldest.wrapping_sub(2),
+                    None => shared.version.load(SeqCst),
+                }
+            }
+            None => shared.version.load(SeqCst),
+        };
+
+        Receiver::from_shared(version, shared)
+    }
+
+    /// Returns the number of receivers that currently exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::sync::watch;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (tx, rx1) = watch::channel("hello");
+    ///
+    ///     assert_eq!(1, tx.receiver_count());
+    ///
+    ///     let mut _rx2 = rx1.clone();
+    ///
+    ///     assert_eq!(2, tx.receiver_count());
+    /// }
+    /// ```
+    pub fn receiver_count(&self) -> usize {
+        self.shared.ref_count_rx.load(Relaxed)
     }
 }
 
@@ -140,4 +140,43 @@ cfg_rt! {
     {
         crate::runtime::spawn_blocking(f)
     }
+
+    /// Runs the provided closure on a named blocking pool of the current
+    /// runtime.
+    ///
+    /// Named pools are registered with
+    /// [`Handle::create_blocking_pool`][create]. If no pool was registered
+    /// under `name`, the closure runs on the default blocking pool, exactly
+    /// as if [`spawn_blocking`] had been called; libraries can therefore
+    /// target a pool unconditionally and leave the decision of whether it
+    /// gets dedicated threads to the application.
+    ///
+    /// [create]: crate::runtime::Handle::create_blocking_pool
+    /// [`spawn_blocking`]: fn@crate::task::spawn_blocking
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Handle;
+    /// use tokio::task;
+    ///
+    /// # async fn docs() -> Result<(), Box<dyn std::error::Error>>{
+    /// Handle::current().create_blocking_pool("rocksdb", 4);
+    ///
+    /// let res = task::spawn_blocking_on("rocksdb", move || {
+    ///     // perform storage-engine work on its dedicated pool
+    ///     "done"
+    /// }).await?;
+    ///
+    /// assert_eq!(res, "done");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spawn_blocking_on<F, R>(name: &str, f: F) -> JoinHandle<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        crate::runtime::spawn_blocking_on(name, f)
+    }
 }
@@ -217,11 +217,44 @@ cfg_rt! {
         /// State available from thread-local
         context: Context,
 
+        /// Total number of scheduler ticks, reported by `metrics_snapshot`.
+        #[cfg(tokio_unstable)]
+        ticks: Cell<u64>,
+
+        /// Total number of task polls, reported by `metrics_snapshot`.
+        #[cfg(tokio_unstable)]
+        task_polls: Cell<u64>,
+
         /// This type should not be Send.
         _not_send: PhantomData<*const ()>,
     }
 }
 
+/// A point-in-time snapshot of a [`LocalSet`]'s counters.
+///
+/// Returned by [`LocalSet::metrics_snapshot`]. The counters mirror the
+/// per-worker counters in [`runtime::metrics`](crate::runtime::metrics) so
+/// that single-threaded embedders can observe their local scheduler the same
+/// way.
+#[cfg(tokio_unstable)]
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct LocalSetMetrics {
+    /// Number of tasks currently alive on the local set, whether or not they
+    /// are scheduled to run.
+    pub alive_tasks: usize,
+
+    /// Number of notified tasks waiting in the local run queues.
+    pub queue_depth: usize,
+
+    /// Total number of scheduler ticks. A tick polls a batch of scheduled
+    /// tasks.
+    pub ticks: u64,
+
+    /// Total number of times a task was polled by this local set.
+    pub task_polls: u64,
+}
+
 /// State available from the thread-local
 struct Context {
     /// Owned task set and local run queue
@@ -235,6 +268,11 @@ struct Tasks {
     /// Collection of all active tasks spawned onto this executor.
     owned: LinkedList<Task<Arc<Shared>>, <Task<Arc<Shared>> as Link>::Target>,
 
+    /// Number of tasks in `owned`, as the linked list cannot be counted
+    /// without walking it.
+    #[cfg(tokio_unstable)]
+    alive: usize,
+
     /// Local run queue sender and receiver.
     queue: VecDeque<task::Notified<Arc<Shared>>>,
 }
@@ -328,6 +366,8 @@ impl LocalSet {
             context: Context {
                 tasks: RefCell::new(Tasks {
                     owned: LinkedList::new(),
+                    #[cfg(tokio_unstable)]
+                    alive: 0,
                     queue: VecDeque::with_capacity(INITIAL_CAPACITY),
                 }),
                 shared: Arc::new(Shared {
@@ -335,10 +375,48 @@ impl LocalSet {
                     waker: AtomicWaker::new(),
                 }),
             },
+            #[cfg(tokio_unstable)]
+            ticks: Cell::new(0),
+            #[cfg(tokio_unstable)]
+            task_polls: Cell::new(0),
             _not_send: PhantomData,
         }
     }
 
+    /// Returns a point-in-time snapshot of the local set's counters.
+    ///
+    /// See [`LocalSetMetrics`] for the meaning of each field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::task::LocalSet;
+    ///
+    /// #[tokio::main(flavor = "current_thread")]
+    /// async fn main() {
+    ///     let local = LocalSet::new();
+    ///
+    ///     local.run_until(async {
+    ///         tokio::task::spawn_local(async {}).await.unwrap();
+    ///     }).await;
+    ///
+    ///     let metrics = local.metrics_snapshot();
+    ///     assert!(metrics.task_polls >= 1);
+    /// }
+    /// ```
+    #[cfg(tokio_unstable)]
+    pub fn metrics_snapshot(&self) -> LocalSetMetrics {
+        let tasks = self.context.tasks.borrow();
+        let queue_depth = tasks.queue.len() + self.context.shared.queue.lock().unwrap().len();
+
+        LocalSetMetrics {
+            alive_tasks: tasks.alive,
+            queue_depth,
+            ticks: self.ticks.get(),
+            task_polls: self.task_polls.get(),
+        }
+    }
+
     /// Spawns a `!Send` task onto the local task set.
     ///
     /// This task is guaranteed to be run on the current thread.
@@ -501,6 +579,9 @@ impl LocalSet {
     /// Tick the scheduler, returning whether the local future needs to be
     /// notified again.
     fn tick(&self) -> bool {
+        #[cfg(tokio_unstable)]
+        self.ticks.set(self.ticks.get() + 1);
+
         for _ in 0..MAX_TASKS_PER_TICK {
             match self.next_task() {
                 // Run the task
@@ -511,7 +592,12 @@ impl LocalSet {
                 // task initially. Because `LocalSet` itself is `!Send`, and
                 // `spawn_local` spawns into the `LocalSet` on the current
                 // thread, the invariant is maintained.
-                Some(task) => crate::coop::budget(|| task.run()),
+                Some(task) => {
+                    #[cfg(tokio_unstable)]
+                    self.task_polls.set(self.task_polls.get() + 1);
+
+                    crate::coop::budget(|| task.run())
+                }
                 // We have fully drained the queue of notified tasks, so the
                 // local future doesn't need to be notified again — it can wait
                 // until something else wakes a task in the local set.
@@ -669,7 +755,12 @@ impl task::Schedule for Arc<Shared> {
     fn bind(task: Task<Self>) -> Arc<Shared> {
         CURRENT.with(|maybe_cx| {
             let cx = maybe_cx.expect("scheduler context missing");
-            cx.tasks.borrow_mut().owned.push_front(task);
+            let mut tasks = cx.tasks.borrow_mut();
+            tasks.owned.push_front(task);
+            #[cfg(tokio_unstable)]
+            {
+                tasks.alive += 1;
+            }
             cx.shared.clone()
         })
     }
@@ -685,7 +776,13 @@ impl task::Schedule for Arc<Shared> {
             let ptr = NonNull::from(task.header());
             // safety: task must be contained by list. It is inserted into the
             // list in `bind`.
-            unsafe { cx.tasks.borrow_mut().owned.remove(ptr) }
+            let mut tasks = cx.tasks.borrow_mut();
+            let task = unsafe { tasks.owned.remove(ptr) };
+            #[cfg(tokio_unstable)]
+            if task.is_some() {
+                tasks.alive -= 1;
+            }
+            task
         })
     }
 
@@ -275,14 +275,18 @@
 //! [`task::unconstrained`]: crate::task::unconstrained()
 //! [`poll`]: method@std::future::Future::poll
 
+pub use crate::coop::{budget_remaining, has_budget_remaining};
+
 cfg_rt! {
-    pub use crate::runtime::task::{JoinError, JoinHandle};
+    pub use crate::runtime::task::{AndThenSpawn, JoinError, JoinHandle, MappedJoinHandle};
 
     mod blocking;
-    pub use blocking::spawn_blocking;
+    pub use blocking::{spawn_blocking, spawn_blocking_on};
 
     mod spawn;
-    pub use spawn::spawn;
+    pub use spawn::{spawn, spawn_with_hint, SpawnHint};
+    #[cfg(feature = "rt-multi-thread")]
+    pub(crate) use spawn::HintKind;
 
     cfg_rt_multi_thread! {
         pub use blocking::block_in_place;
@@ -293,6 +297,8 @@ cfg_rt! {
 
     mod local;
     pub use local::{spawn_local, LocalSet};
+    #[cfg(tokio_unstable)]
+    pub use local::LocalSetMetrics;
 
     mod task_local;
     pub use task_local::LocalKey;
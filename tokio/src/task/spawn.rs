@@ -129,9 +129,104 @@ cfg_rt! {
         T: Future + Send + 'static,
         T::Output: Send + 'static,
     {
-        let spawn_handle = runtime::context::spawn_handle()
+        let (spawn_handle, runtime_id) = runtime::context::spawn_handle()
         .expect(CONTEXT_MISSING_ERROR);
         let task = crate::util::trace::task(task, "task");
-        spawn_handle.spawn(task)
+        let mut handle = spawn_handle.spawn(task);
+        handle.set_runtime_id(runtime_id);
+        handle
+    }
+
+    /// A hint describing which worker thread a spawned task would prefer to
+    /// run on.
+    ///
+    /// Used with [`spawn_with_hint`]. A hint is advisory: the scheduler makes
+    /// an effort to run the task on the preferred worker so that related
+    /// tasks stay close for cache locality, but remains free to run it
+    /// elsewhere if the preferred worker is busy. On the current-thread
+    /// runtime, hints are ignored.
+    #[derive(Debug, Clone, Copy)]
+    pub struct SpawnHint {
+        // Only the multi-thread scheduler acts on the hint.
+        #[cfg_attr(not(feature = "rt-multi-thread"), allow(dead_code))]
+        pub(crate) kind: HintKind,
+    }
+
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) enum HintKind {
+        /// Prefer the worker the spawning task is currently running on.
+        CurrentWorker,
+
+        /// Prefer the worker with the given index, modulo the number of
+        /// workers.
+        #[cfg_attr(not(feature = "rt-multi-thread"), allow(dead_code))]
+        Worker(usize),
+    }
+
+    impl SpawnHint {
+        /// Prefer the worker thread the task is spawned from.
+        ///
+        /// When spawning from outside the runtime, this behaves like a plain
+        /// [`spawn`](spawn()).
+        pub fn prefer_current_worker() -> SpawnHint {
+            SpawnHint {
+                kind: HintKind::CurrentWorker,
+            }
+        }
+
+        /// Prefer the worker thread with the given index.
+        ///
+        /// The index is taken modulo the number of worker threads, so any
+        /// `usize` value is valid. Tasks hinted with equal indices prefer the
+        /// same worker.
+        pub fn worker(index: usize) -> SpawnHint {
+            SpawnHint {
+                kind: HintKind::Worker(index),
+            }
+        }
+    }
+
+    /// Spawns a new asynchronous task with a worker placement hint, returning
+    /// a [`JoinHandle`](super::JoinHandle) for it.
+    ///
+    /// This is identical to [`spawn`](spawn()) except that `hint` expresses a
+    /// preference for which worker thread should run the task. Spawning
+    /// related tasks with the same hint colocates them on one worker, keeping
+    /// their data warm in that worker's cache. The hint is only a preference:
+    /// if the runtime is imbalanced, another worker may steal the task.
+    ///
+    /// This function must be called from the context of a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::task::{self, SpawnHint};
+    ///
+    /// # #[tokio::main(flavor = "multi_thread")] async fn main() {
+    /// // Keep both halves of a connection on the same worker.
+    /// let reader = task::spawn_with_hint(async {
+    ///     // ...
+    /// }, SpawnHint::worker(0));
+    ///
+    /// let writer = task::spawn_with_hint(async {
+    ///     // ...
+    /// }, SpawnHint::worker(0));
+    ///
+    /// reader.await.unwrap();
+    /// writer.await.unwrap();
+    /// # }
+    /// ```
+    #[cfg_attr(tokio_track_caller, track_caller)]
+    pub fn spawn_with_hint<T>(task: T, hint: SpawnHint) -> JoinHandle<T::Output>
+    where
+        T: Future + Send + 'static,
+        T::Output: Send + 'static,
+    {
+        let (spawn_handle, runtime_id) = runtime::context::spawn_handle()
+        .expect(CONTEXT_MISSING_ERROR);
+        let task = crate::util::trace::task(task, "task");
+        let mut handle = spawn_handle.spawn_hinted(task, hint);
+        handle.set_runtime_id(runtime_id);
+        handle
     }
 }
@@ -12,7 +12,6 @@ cfg_rt! {
     /// continue.
     ///
     /// See also the usage example in the [task module](index.html#yield_now).
-    #[must_use = "yield_now does nothing unless polled/`await`-ed"]
     pub async fn yield_now() {
         /// Yield implementation
         struct YieldNow {
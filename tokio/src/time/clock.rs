@@ -13,7 +13,13 @@ cfg_not_test_util! {
     pub(crate) struct Clock {}
 
     pub(crate) fn now() -> Instant {
-        Instant::from_std(std::time::Instant::now())
+        // `Instant::now` reads the target's native clock; on
+        // `wasm32-unknown-unknown` that is the host-provided clock.
+        Instant::now()
+    }
+
+    pub(crate) fn is_paused() -> bool {
+        false
     }
 
     impl Clock {
@@ -136,6 +142,11 @@ cfg_test_util! {
         }
     }
 
+    /// Returns `true` if the current execution context's clock is frozen.
+    pub(crate) fn is_paused() -> bool {
+        clock().map_or(false, |clock| clock.is_paused())
+    }
+
     impl Clock {
         /// Return a new `Clock` instance that uses the current execution context's
         /// source of time.
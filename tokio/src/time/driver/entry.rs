@@ -281,7 +281,7 @@ impl StateCell {
 /// timer. As this participates in intrusive data structures, it must be pinned
 /// before polling.
 #[derive(Debug)]
-pub(super) struct TimerEntry {
+pub(crate) struct TimerEntry {
     /// Arc reference to the driver. We can only free the driver after
     /// deregistering everything from their respective timer wheels.
     driver: Handle,
@@ -543,6 +543,17 @@ impl TimerEntry {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Result<(), super::Error>> {
+        // In debug builds, catch timers that were moved to a different
+        // runtime, which otherwise hang because the driver they are
+        // registered with is no longer the one being driven.
+        #[cfg(all(debug_assertions, feature = "rt"))]
+        if let Some(Some(current)) = crate::runtime::context::try_time_handle() {
+            assert!(
+                std::ptr::eq(self.driver.get(), current.get()),
+                "timer polled on a runtime other than the one it was created on",
+            );
+        }
+
         if self.driver.is_shutdown() {
             panic!("{}", crate::util::error::RUNTIME_SHUTTING_DOWN_ERROR);
         }
@@ -7,7 +7,8 @@
 //! Time driver
 
 mod entry;
-pub(self) use self::entry::{EntryList, TimerEntry, TimerHandle, TimerShared};
+pub(crate) use self::entry::TimerEntry;
+pub(self) use self::entry::{EntryList, TimerHandle, TimerShared};
 
 mod handle;
 pub(crate) use self::handle::Handle;
@@ -269,6 +270,9 @@ impl Handle {
         while let Some(entry) = lock.wheel.poll(now) {
             debug_assert!(unsafe { entry.is_pending() });
 
+            #[cfg(all(tokio_unstable, feature = "rt"))]
+            crate::runtime::console::timer_fired();
+
             // SAFETY: We hold the driver lock, and just removed the entry from any linked lists.
             if let Some(waker) = unsafe { entry.fire(Ok(())) } {
                 waker_list[waker_idx] = Some(waker);
@@ -32,9 +32,19 @@ use std::time::Duration;
 /// take advantage of `time::pause()` and `time::advance()`.
 #[derive(Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub struct Instant {
-    std: std::time::Instant,
+    inner: Inner,
 }
 
+// On most targets the inner representation is `std::time::Instant`. On
+// `wasm32-unknown-unknown` there is no OS clock, so the inner representation
+// is backed by the embedder-provided host clock instead; see the `wasm`
+// module below.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+type Inner = std::time::Instant;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+type Inner = wasm::Instant;
+
 impl Instant {
     /// Returns an instant corresponding to "now".
     ///
@@ -50,8 +60,16 @@ impl Instant {
     }
 
     /// Create a `tokio::time::Instant` from a `std::time::Instant`.
+    ///
+    /// This method is unavailable on `wasm32-unknown-unknown`, where the
+    /// clock is not backed by `std`.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn from_std(std: std::time::Instant) -> Instant {
-        Instant { std }
+        Instant { inner: std }
+    }
+
+    fn from_inner(inner: Inner) -> Instant {
+        Instant { inner }
     }
 
     pub(crate) fn far_future() -> Instant {
@@ -63,8 +81,12 @@ impl Instant {
     }
 
     /// Convert the value into a `std::time::Instant`.
+    ///
+    /// This method is unavailable on `wasm32-unknown-unknown`, where the
+    /// clock is not backed by `std`.
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
     pub fn into_std(self) -> std::time::Instant {
-        self.std
+        self.inner
     }
 
     /// Returns the amount of time elapsed from another instant to this one.
@@ -73,7 +95,7 @@ impl Instant {
     ///
     /// This function will panic if `earlier` is later than `self`.
     pub fn duration_since(&self, earlier: Instant) -> Duration {
-        self.std.duration_since(earlier.std)
+        self.inner.duration_since(earlier.inner)
     }
 
     /// Returns the amount of time elapsed from another instant to this one, or
@@ -94,7 +116,7 @@ impl Instant {
     /// }
     /// ```
     pub fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
-        self.std.checked_duration_since(earlier.std)
+        self.inner.checked_duration_since(earlier.inner)
     }
 
     /// Returns the amount of time elapsed from another instant to this one, or
@@ -115,7 +137,7 @@ impl Instant {
     /// }
     /// ```
     pub fn saturating_duration_since(&self, earlier: Instant) -> Duration {
-        self.std.saturating_duration_since(earlier.std)
+        self.inner.saturating_duration_since(earlier.inner)
     }
 
     /// Returns the amount of time elapsed since this instant was created.
@@ -147,23 +169,25 @@ impl Instant {
     /// represented as `Instant` (which means it's inside the bounds of the
     /// underlying data structure), `None` otherwise.
     pub fn checked_add(&self, duration: Duration) -> Option<Instant> {
-        self.std.checked_add(duration).map(Instant::from_std)
+        self.inner.checked_add(duration).map(Instant::from_inner)
     }
 
     /// Returns `Some(t)` where `t` is the time `self - duration` if `t` can be
     /// represented as `Instant` (which means it's inside the bounds of the
     /// underlying data structure), `None` otherwise.
     pub fn checked_sub(&self, duration: Duration) -> Option<Instant> {
-        self.std.checked_sub(duration).map(Instant::from_std)
+        self.inner.checked_sub(duration).map(Instant::from_inner)
     }
 }
 
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 impl From<std::time::Instant> for Instant {
     fn from(time: std::time::Instant) -> Instant {
         Instant::from_std(time)
     }
 }
 
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
 impl From<Instant> for std::time::Instant {
     fn from(time: Instant) -> std::time::Instant {
         time.into_std()
@@ -174,7 +198,7 @@ impl ops::Add<Duration> for Instant {
     type Output = Instant;
 
     fn add(self, other: Duration) -> Instant {
-        Instant::from_std(self.std + other)
+        Instant::from_inner(self.inner + other)
     }
 }
 
@@ -188,7 +212,7 @@ impl ops::Sub for Instant {
     type Output = Duration;
 
     fn sub(self, rhs: Instant) -> Duration {
-        self.std - rhs.std
+        self.inner - rhs.inner
     }
 }
 
@@ -196,7 +220,7 @@ impl ops::Sub<Duration> for Instant {
     type Output = Instant;
 
     fn sub(self, rhs: Duration) -> Instant {
-        Instant::from_std(self.std - rhs)
+        Instant::from_inner(self.inner - rhs)
     }
 }
 
@@ -208,7 +232,7 @@ impl ops::SubAssign<Duration> for Instant {
 
 impl fmt::Debug for Instant {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.std.fmt(fmt)
+        self.inner.fmt(fmt)
     }
 }
 
@@ -217,7 +241,7 @@ mod variant {
     use super::Instant;
 
     pub(super) fn now() -> Instant {
-        Instant::from_std(std::time::Instant::now())
+        Instant::from_inner(super::Inner::now())
     }
 }
 
@@ -229,3 +253,78 @@ mod variant {
         crate::time::clock::now()
     }
 }
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod wasm {
+    //! An `Instant` backed by the embedder-provided host clock, mirroring
+    //! the parts of the `std::time::Instant` API used above. The value is
+    //! the time elapsed since the host clock's arbitrary origin (e.g. the
+    //! origin of `performance.now()`).
+
+    use std::ops;
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+    pub(super) struct Instant {
+        since_origin: Duration,
+    }
+
+    impl Instant {
+        pub(super) fn now() -> Instant {
+            Instant {
+                since_origin: crate::util::wasm_host::now(),
+            }
+        }
+
+        pub(super) fn duration_since(&self, earlier: Instant) -> Duration {
+            self.checked_duration_since(earlier)
+                .expect("supplied instant is later than self")
+        }
+
+        pub(super) fn checked_duration_since(&self, earlier: Instant) -> Option<Duration> {
+            self.since_origin.checked_sub(earlier.since_origin)
+        }
+
+        pub(super) fn saturating_duration_since(&self, earlier: Instant) -> Duration {
+            self.checked_duration_since(earlier).unwrap_or_default()
+        }
+
+        pub(super) fn checked_add(&self, duration: Duration) -> Option<Instant> {
+            self.since_origin
+                .checked_add(duration)
+                .map(|since_origin| Instant { since_origin })
+        }
+
+        pub(super) fn checked_sub(&self, duration: Duration) -> Option<Instant> {
+            self.since_origin
+                .checked_sub(duration)
+                .map(|since_origin| Instant { since_origin })
+        }
+    }
+
+    impl ops::Add<Duration> for Instant {
+        type Output = Instant;
+
+        fn add(self, other: Duration) -> Instant {
+            self.checked_add(other)
+                .expect("overflow when adding duration to instant")
+        }
+    }
+
+    impl ops::Sub for Instant {
+        type Output = Duration;
+
+        fn sub(self, rhs: Instant) -> Duration {
+            self.duration_since(rhs)
+        }
+    }
+
+    impl ops::Sub<Duration> for Instant {
+        type Output = Instant;
+
+        fn sub(self, rhs: Duration) -> Instant {
+            self.checked_sub(rhs)
+                .expect("overflow when subtracting duration from instant")
+        }
+    }
+}
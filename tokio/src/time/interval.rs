@@ -182,3 +182,16 @@ impl Interval {
         self.period
     }
 }
+
+cfg_stream! {
+    impl futures_core::Stream for Interval {
+        type Item = Instant;
+
+        fn poll_next(
+            mut self: std::pin::Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Instant>> {
+            self.poll_tick(cx).map(Some)
+        }
+    }
+}
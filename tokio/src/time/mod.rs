@@ -102,10 +102,16 @@ pub use self::instant::Instant;
 mod interval;
 pub use interval::{interval, interval_at, Interval};
 
+mod sleep_precise;
+pub use sleep_precise::{sleep_precise, SleepPrecise};
+
 mod timeout;
 #[doc(inline)]
 pub use timeout::{timeout, timeout_at, Timeout};
 
+mod virtual_clock;
+pub use virtual_clock::{Deadline, VirtualClock};
+
 #[cfg(test)]
 #[cfg(not(loom))]
 mod tests;
@@ -0,0 +1,181 @@
+use crate::time::driver::sleep::{sleep_until, Sleep};
+use crate::time::{Duration, Instant};
+
+use pin_project_lite::pin_project;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{self, Poll};
+
+/// The default precision target: the granularity of the timer wheel.
+const DEFAULT_PRECISION: Duration = Duration::from_millis(1);
+
+/// Waits until `duration` has elapsed, with sub-millisecond precision.
+///
+/// The timer wheel operates at millisecond granularity, so a plain
+/// [`sleep`](crate::time::sleep) can complete up to a millisecond late.
+/// `sleep_precise` sleeps on the wheel for the bulk of the duration, then
+/// yields to the scheduler for the final remainder, re-checking the clock on
+/// every pass until the deadline is reached. This trades CPU time close to the
+/// deadline for tighter wakeups, as needed by audio and robotics loops.
+///
+/// The size of the remainder handled outside the wheel is set with
+/// [`SleepPrecise::precision`] and defaults to one millisecond. Busy-spinning
+/// instead of yielding for the remainder can be requested with
+/// [`SleepPrecise::spin`].
+///
+/// The final remainder is timed against the real clock. Under a
+/// [paused](crate::time::pause) test clock the future instead completes
+/// through the timer wheel when auto-advance reaches the deadline, exactly
+/// like [`sleep`](crate::time::sleep); the sub-millisecond behavior cannot be
+/// observed in tests that pause time.
+///
+/// # Cancellation
+///
+/// Canceling a precise sleep is done by dropping the returned future. No
+/// additional cleanup work is required.
+///
+/// # Examples
+///
+/// ```
+/// use tokio::time::{self, Duration};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     time::sleep_precise(Duration::from_micros(1500)).await;
+///     println!("1.5 ms have elapsed");
+/// }
+/// ```
+pub fn sleep_precise(duration: Duration) -> SleepPrecise {
+    let deadline = match Instant::now().checked_add(duration) {
+        Some(deadline) => deadline,
+        None => Instant::far_future(),
+    };
+
+    SleepPrecise {
+        deadline,
+        precision: DEFAULT_PRECISION,
+        spin: false,
+        sleep: sleep_until(coarse_deadline(deadline, DEFAULT_PRECISION)),
+    }
+}
+
+/// The deadline handed to the timer wheel: the precision target before the
+/// real deadline, so the wheel cannot overshoot it.
+fn coarse_deadline(deadline: Instant, precision: Duration) -> Instant {
+    deadline
+        .checked_sub(precision)
+        .unwrap_or_else(Instant::now)
+}
+
+pin_project! {
+    /// Future returned by [`sleep_precise`](sleep_precise).
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct SleepPrecise {
+        deadline: Instant,
+
+        // How far before the deadline to leave the timer wheel.
+        precision: Duration,
+
+        // Whether to busy-spin for the final remainder instead of yielding.
+        spin: bool,
+
+        // Wheel sleep covering the bulk of the duration.
+        #[pin]
+        sleep: Sleep,
+    }
+}
+
+impl SleepPrecise {
+    /// Returns the instant at which the future will complete.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Sets the precision target: how far before the deadline the future
+    /// leaves the timer wheel and starts re-checking the clock.
+    ///
+    /// Larger targets wake up more precisely at the cost of more scheduler
+    /// passes (or spinning); a target of zero is equivalent to a plain
+    /// [`sleep`](crate::time::sleep). The default is one millisecond, the
+    /// granularity of the timer wheel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::time::{self, Duration};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// time::sleep_precise(Duration::from_millis(5))
+    ///     .precision(Duration::from_micros(250))
+    ///     .await;
+    /// # }
+    /// ```
+    pub fn precision(mut self, target: Duration) -> SleepPrecise {
+        self.precision = target;
+        self.sleep = sleep_until(coarse_deadline(self.deadline, target));
+        self
+    }
+
+    /// Busy-spins for the final remainder instead of yielding to the
+    /// scheduler.
+    ///
+    /// Spinning occupies the worker thread until the deadline is reached and
+    /// blocks other tasks for up to the precision target, but delivers the
+    /// tightest wakeups. Only enable this on runtimes dedicated to the timing
+    /// loop.
+    pub fn spin(mut self, spin: bool) -> SleepPrecise {
+        self.spin = spin;
+        self
+    }
+}
+
+impl Future for SleepPrecise {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Self::Output> {
+        let mut me = self.project();
+        let now = Instant::now();
+
+        if now >= *me.deadline {
+            return Poll::Ready(());
+        }
+
+        // Under a paused test clock the clock only moves when the runtime
+        // auto-advances it, which happens once all tasks are idle.
+        // Re-checking the clock from a yield loop would keep this task
+        // runnable and the deadline out of reach forever. Complete through
+        // the timer wheel at the real deadline instead; sub-millisecond
+        // precision is meaningless on a paused clock anyway.
+        if crate::time::clock::is_paused() {
+            if me.sleep.deadline() < *me.deadline {
+                me.sleep.as_mut().reset(*me.deadline);
+            }
+            return me.sleep.poll(cx);
+        }
+
+        // Sleep on the wheel until within the precision target of the
+        // deadline. The wheel deadline is in the past on every later poll, so
+        // the completed `Sleep` is never polled again.
+        if now < coarse_deadline(*me.deadline, *me.precision) {
+            ready!(me.sleep.as_mut().poll(cx));
+        }
+
+        if *me.spin {
+            while Instant::now() < *me.deadline {
+                std::hint::spin_loop();
+            }
+
+            return Poll::Ready(());
+        }
+
+        if Instant::now() >= *me.deadline {
+            Poll::Ready(())
+        } else {
+            // Yield, re-checking the clock once the scheduler polls us again.
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use crate::time::driver::{Handle, TimerEntry};
+use crate::time::{error::Error, Instant};
+
+use pin_project_lite::pin_project;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A handle to a runtime's timer, detached from any task.
+///
+/// `VirtualClock` gives external executors and protocol drivers (QUIC
+/// connection timers, MQTT keepalive managers, ...) direct access to the
+/// runtime's timer wheel: [`deadline`] registers a wakeup without creating a
+/// [`Sleep`] future, and the resulting [`Deadline`] is driven with an
+/// explicit [`Waker`] through its poll API. Registration and reset are O(1)
+/// operations on the wheel, so a driver can maintain thousands of deadlines
+/// cheaply.
+///
+/// The handle is obtained from [`runtime::Handle::timer`], is cheap to
+/// clone, and may be moved to other threads, including threads that are not
+/// owned by the runtime.
+///
+/// The clock is "virtual" in that it follows the runtime's notion of time:
+/// when the runtime was built with test-time support and time is
+/// [paused](crate::time::pause), deadlines fire according to the mocked
+/// clock.
+///
+/// [`deadline`]: method@VirtualClock::deadline
+/// [`Sleep`]: struct@crate::time::Sleep
+/// [`Waker`]: struct@std::task::Waker
+/// [`runtime::Handle::timer`]: method@crate::runtime::Handle::timer
+///
+/// # Examples
+///
+/// ```
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+/// use tokio::time::{Deadline, Duration, Instant};
+///
+/// /// A future driving a single protocol deadline by hand.
+/// struct Keepalive {
+///     deadline: Pin<Box<Deadline>>,
+/// }
+///
+/// impl Future for Keepalive {
+///     type Output = ();
+///
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+///         match self.deadline.as_mut().poll_elapsed(cx) {
+///             Poll::Ready(res) => {
+///                 res.unwrap();
+///                 Poll::Ready(())
+///             }
+///             Poll::Pending => Poll::Pending,
+///         }
+///     }
+/// }
+///
+/// #[tokio::main(flavor = "current_thread", start_paused = true)]
+/// async fn main() {
+///     let clock = tokio::runtime::Handle::current().timer();
+///
+///     let keepalive = Keepalive {
+///         deadline: Box::pin(clock.deadline(Instant::now() + Duration::from_secs(30))),
+///     };
+///
+///     keepalive.await;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct VirtualClock {
+    handle: Handle,
+}
+
+impl VirtualClock {
+    pub(crate) fn new(handle: Handle) -> VirtualClock {
+        VirtualClock { handle }
+    }
+
+    /// Returns the current instant according to this clock.
+    pub fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Registers a wakeup at `when` on the runtime's timer wheel.
+    ///
+    /// The returned [`Deadline`] must be pinned before it can be polled. It
+    /// is not bound to the task context of the calling thread: unlike
+    /// [`Sleep`], it can be created from outside the runtime.
+    ///
+    /// [`Sleep`]: struct@crate::time::Sleep
+    pub fn deadline(&self, when: Instant) -> Deadline {
+        Deadline {
+            deadline: when,
+            entry: TimerEntry::new(&self.handle, when),
+        }
+    }
+}
+
+pin_project! {
+    /// A wakeup registered on a runtime's timer wheel via [`VirtualClock`].
+    ///
+    /// This is the poll-level equivalent of [`Sleep`]: it completes when its
+    /// deadline is reached, waking the [`Waker`] most recently passed to
+    /// [`poll_elapsed`]. It does not implement [`Future`], does not consume
+    /// task budget, and can be reset and reused indefinitely, which makes it
+    /// suitable for drivers that multiplex many deadlines behind a single
+    /// task or thread.
+    ///
+    /// [`Sleep`]: struct@crate::time::Sleep
+    /// [`Waker`]: struct@std::task::Waker
+    /// [`poll_elapsed`]: method@Deadline::poll_elapsed
+    /// [`Future`]: trait@std::future::Future
+    #[derive(Debug)]
+    pub struct Deadline {
+        deadline: Instant,
+
+        #[pin]
+        entry: TimerEntry,
+    }
+}
+
+impl Deadline {
+    /// Returns the instant at which this deadline fires.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns `true` if the deadline has been reached.
+    pub fn is_elapsed(&self) -> bool {
+        self.entry.is_elapsed()
+    }
+
+    /// Resets the deadline to a new instant.
+    ///
+    /// Like [`Sleep::reset`], this reuses the existing timer wheel entry and
+    /// may be called both before and after the deadline has fired.
+    ///
+    /// [`Sleep::reset`]: method@crate::time::Sleep::reset
+    pub fn reset(self: Pin<&mut Self>, when: Instant) {
+        let me = self.project();
+        me.entry.reset(when);
+        *me.deadline = when;
+    }
+
+    /// Polls for the deadline having been reached, registering the current
+    /// task's waker for a wakeup otherwise.
+    ///
+    /// Only the [`Waker`] from the most recent call is woken.
+    ///
+    /// Returns an error if the wakeup could not be registered with the
+    /// runtime's timer, for example because too many timers are pending.
+    ///
+    /// # Panics
+    ///
+    /// Panics when polled after the runtime this deadline belongs to has
+    /// shut down.
+    ///
+    /// [`Waker`]: struct@std::task::Waker
+    pub fn poll_elapsed(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        self.project().entry.poll_elapsed(cx)
+    }
+
+    /// Cancels the deadline, deregistering it from the timer wheel.
+    ///
+    /// This operation is irreversible; further polls will not complete.
+    /// Dropping a `Deadline` cancels it implicitly.
+    pub fn cancel(self: Pin<&mut Self>) {
+        self.project().entry.cancel();
+    }
+}
@@ -1,4 +1,7 @@
 #![cfg_attr(not(feature = "full"), allow(dead_code))]
+// The list is exported publicly when `--cfg tokio_unstable` is set; in other
+// builds these `pub` items are only reachable within the crate.
+#![allow(unreachable_pub)]
 
 //! An intrusive double linked list of data
 //!
@@ -16,7 +19,7 @@ use core::ptr::{self, NonNull};
 ///
 /// Currently, the list is not emptied on drop. It is the caller's
 /// responsibility to ensure the list is empty before dropping it.
-pub(crate) struct LinkedList<L, T> {
+pub struct LinkedList<L, T> {
     /// Linked list head
     head: Option<NonNull<T>>,
 
@@ -40,7 +43,7 @@ unsafe impl<L: Link> Sync for LinkedList<L, L::Target> where L::Target: Sync {}
 /// Implementations must guarantee that `Target` types are pinned in memory. In
 /// other words, when a node is inserted, the value will not be moved as long as
 /// it is stored in the list.
-pub(crate) unsafe trait Link {
+pub unsafe trait Link {
     /// Handle to the list entry.
     ///
     /// This is usually a pointer-ish type.
@@ -60,7 +63,7 @@ pub(crate) unsafe trait Link {
 }
 
 /// Previous / next pointers
-pub(crate) struct Pointers<T> {
+pub struct Pointers<T> {
     inner: UnsafeCell<PointersInner<T>>,
 }
 /// We do not want the compiler to put the `noalias` attribute on mutable
@@ -104,7 +107,7 @@ unsafe impl<T: Sync> Sync for Pointers<T> {}
 
 impl<L, T> LinkedList<L, T> {
     /// Creates an empty linked list.
-    pub(crate) const fn new() -> LinkedList<L, T> {
+    pub const fn new() -> LinkedList<L, T> {
         LinkedList {
             head: None,
             tail: None,
@@ -115,7 +118,7 @@ impl<L, T> LinkedList<L, T> {
 
 impl<L: Link> LinkedList<L, L::Target> {
     /// Adds an element first in the list.
-    pub(crate) fn push_front(&mut self, val: L::Handle) {
+    pub fn push_front(&mut self, val: L::Handle) {
         // The value should not be dropped, it is being inserted into the list
         let val = ManuallyDrop::new(val);
         let ptr = L::as_raw(&*val);
@@ -138,7 +141,7 @@ impl<L: Link> LinkedList<L, L::Target> {
 
     /// Removes the last element from a list and returns it, or None if it is
     /// empty.
-    pub(crate) fn pop_back(&mut self) -> Option<L::Handle> {
+    pub fn pop_back(&mut self) -> Option<L::Handle> {
         unsafe {
             let last = self.tail?;
             self.tail = L::pointers(last).as_ref().get_prev();
@@ -157,7 +160,7 @@ impl<L: Link> LinkedList<L, L::Target> {
     }
 
     /// Returns whether the linked list does not contain any node
-    pub(crate) fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         if self.head.is_some() {
             return false;
         }
@@ -172,7 +175,7 @@ impl<L: Link> LinkedList<L, L::Target> {
     ///
     /// The caller **must** ensure that `node` is currently contained by
     /// `self` or not contained by any other list.
-    pub(crate) unsafe fn remove(&mut self, node: NonNull<L::Target>) -> Option<L::Handle> {
+    pub unsafe fn remove(&mut self, node: NonNull<L::Target>) -> Option<L::Handle> {
         if let Some(prev) = L::pointers(node).as_ref().get_prev() {
             debug_assert_eq!(L::pointers(prev).as_ref().get_next(), Some(node));
             L::pointers(prev)
@@ -229,6 +232,35 @@ impl<L: Link> LinkedList<L, L::Target> {
     }
 }
 
+#[cfg(any(feature = "rt", feature = "sync"))]
+impl<L: Link> LinkedList<L, L::Target> {
+    /// Returns a reference to the first element.
+    pub(crate) fn first(&self) -> Option<&L::Target> {
+        let head = self.head.as_ref()?;
+        unsafe { Some(&*head.as_ptr()) }
+    }
+
+    /// Removes the first element from a list and returns it, or None if it is
+    /// empty.
+    pub(crate) fn pop_front(&mut self) -> Option<L::Handle> {
+        unsafe {
+            let first = self.head?;
+            self.head = L::pointers(first).as_ref().get_next();
+
+            if let Some(next) = L::pointers(first).as_ref().get_next() {
+                L::pointers(next).as_mut().set_prev(None);
+            } else {
+                self.tail = None
+            }
+
+            L::pointers(first).as_mut().set_prev(None);
+            L::pointers(first).as_mut().set_next(None);
+
+            Some(L::from_raw(first))
+        }
+    }
+}
+
 impl<L: Link> Default for LinkedList<L, L::Target> {
     fn default() -> Self {
         Self::new()
@@ -237,7 +269,10 @@ impl<L: Link> Default for LinkedList<L, L::Target> {
 
 // ===== impl Iter =====
 
-cfg_rt_multi_thread! {
+#[cfg(any(feature = "rt-multi-thread", feature = "sync"))]
+mod iter {
+    use super::*;
+
     pub(crate) struct Iter<'a, T: Link> {
         curr: Option<NonNull<T::Target>>,
         _p: core::marker::PhantomData<&'a T>,
@@ -316,7 +351,7 @@ cfg_io_readiness! {
 
 impl<T> Pointers<T> {
     /// Create a new set of empty pointers
-    pub(crate) fn new() -> Pointers<T> {
+    pub fn new() -> Pointers<T> {
         Pointers {
             inner: UnsafeCell::new(PointersInner {
                 prev: None,
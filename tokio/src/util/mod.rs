@@ -1,19 +1,44 @@
+//! Internal utilities.
+//!
+//! With `--cfg tokio_unstable` set, the intrusive [`linked_list`] and the
+//! [`wake_list`] modules are exported so that ecosystem crates can build
+//! custom synchronization primitives using the same zero-allocation waiter
+//! pattern as `Semaphore` and `Notify`. These APIs are unstable and may
+//! change in any release.
+
 cfg_io_driver! {
     pub(crate) mod bit;
     pub(crate) mod slab;
 }
 
-#[cfg(any(
-    feature = "fs",
-    feature = "net",
-    feature = "process",
-    feature = "rt",
-    feature = "sync",
-    feature = "signal",
-    feature = "time",
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub(crate) mod wasm_host;
+
+#[cfg(all(
+    not(tokio_unstable),
+    any(
+        feature = "fs",
+        feature = "net",
+        feature = "process",
+        feature = "rt",
+        feature = "sync",
+        feature = "signal",
+        feature = "time",
+    )
 ))]
 pub(crate) mod linked_list;
 
+/// An intrusive doubly-linked list used to build waiter queues.
+#[cfg(tokio_unstable)]
+pub mod linked_list;
+
+#[cfg(all(not(tokio_unstable), feature = "sync"))]
+pub(crate) mod wake_list;
+
+/// Batched waking of tasks outside a critical section.
+#[cfg(all(tokio_unstable, feature = "sync"))]
+pub mod wake_list;
+
 #[cfg(any(feature = "rt-multi-thread", feature = "macros"))]
 mod rand;
 
@@ -34,6 +59,7 @@ pub(crate) mod trace;
 
 #[cfg(any(feature = "macros"))]
 #[cfg_attr(not(feature = "macros"), allow(unreachable_pub))]
+#[doc(hidden)]
 pub use self::rand::thread_rng_n;
 
 #[cfg(any(
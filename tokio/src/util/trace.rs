@@ -1,4 +1,16 @@
 cfg_trace! {
+    /// Returns a unique ID for a resource (semaphore, lock, channel, ...).
+    ///
+    /// The ID is embedded in every event the resource emits so that events can
+    /// be correlated across the lifetime of the resource.
+    pub(crate) fn resource_id() -> u64 {
+        use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+        NEXT_ID.fetch_add(1, Relaxed)
+    }
+
     cfg_rt! {
         pub(crate) use tracing::instrument::Instrumented;
 
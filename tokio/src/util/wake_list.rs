@@ -0,0 +1,65 @@
+// Exported publicly when `--cfg tokio_unstable` is set.
+#![allow(unreachable_pub)]
+
+use std::task::Waker;
+
+/// The number of wakers held inline before spilling to the heap.
+///
+/// Waking more waiters than this in one batch is rare, so the common case
+/// performs no allocation.
+const NUM_INLINE_WAKERS: usize = 32;
+
+/// A list of wakers to be woken after a lock is released.
+///
+/// Collecting wakers while holding a lock and invoking them afterwards avoids
+/// waking tasks into a lock they would immediately contend on. The list holds
+/// a fixed number of wakers inline and spills to a `Vec` beyond that, so an
+/// arbitrarily large batch can be collected in a single critical section.
+pub struct WakeList {
+    inline: [Option<Waker>; NUM_INLINE_WAKERS],
+    len: usize,
+    overflow: Vec<Waker>,
+}
+
+impl WakeList {
+    /// Creates an empty list.
+    pub fn new() -> WakeList {
+        WakeList {
+            inline: Default::default(),
+            len: 0,
+            overflow: Vec::new(),
+        }
+    }
+
+    /// Adds a waker to the list.
+    pub fn push(&mut self, waker: Waker) {
+        if self.len < NUM_INLINE_WAKERS {
+            self.inline[self.len] = Some(waker);
+            self.len += 1;
+        } else {
+            self.overflow.push(waker);
+        }
+    }
+
+    /// Wakes every task in the list, draining it.
+    pub fn wake_all(&mut self) {
+        for waker in self.inline[..self.len].iter_mut() {
+            if let Some(waker) = waker.take() {
+                waker.wake();
+            }
+        }
+        self.len = 0;
+
+        for waker in self.overflow.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl std::fmt::Debug for WakeList {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("WakeList")
+            .field("len", &(self.len + self.overflow.len()))
+            .finish()
+    }
+}
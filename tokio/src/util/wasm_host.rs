@@ -0,0 +1,42 @@
+//! Bindings to the host environment on `wasm32-unknown-unknown`.
+//!
+//! This target has no operating system: there is no monotonic clock and no
+//! way to block the calling thread. Instead, the embedder supplies two
+//! imports in the `tokio` import module, and the clock and timer are built
+//! on top of them:
+//!
+//! * `now_millis() -> f64` — milliseconds elapsed since an arbitrary but
+//!   fixed origin, e.g. JavaScript's `performance.now()`.
+//! * `park_millis(millis: f64)` — return after approximately `millis`
+//!   milliseconds, or earlier if the host wants the runtime to wake up. In
+//!   a worker this is typically `Atomics.wait` driven by `setTimeout`; a
+//!   non-browser host can simply sleep. `millis` may be infinite, meaning
+//!   "until woken".
+//!
+//! See the "WASM support" section of the crate documentation for an example
+//! of the JavaScript glue.
+
+use std::time::Duration;
+
+#[link(wasm_import_module = "tokio")]
+extern "C" {
+    fn now_millis() -> f64;
+    fn park_millis(millis: f64);
+}
+
+/// Returns the time elapsed since the host clock's origin.
+pub(crate) fn now() -> Duration {
+    // `performance.now()` has microsecond resolution at best; nanoseconds
+    // cannot overflow here for any realistic host uptime.
+    Duration::from_nanos((unsafe { now_millis() } * 1_000_000.0) as u64)
+}
+
+/// Blocks through the host for at most `duration`, or until woken when
+/// `None`.
+pub(crate) fn park(duration: Option<Duration>) {
+    let millis = match duration {
+        Some(duration) => duration.as_secs_f64() * 1_000.0,
+        None => f64::INFINITY,
+    };
+    unsafe { park_millis(millis) }
+}
@@ -82,7 +82,7 @@ async fn coop() {
         let mut buf = [0; 1024];
 
         loop {
-            file.read(&mut buf).await.unwrap();
+            let _ = file.read(&mut buf).await.unwrap();
             file.seek(std::io::SeekFrom::Start(0)).await.unwrap();
         }
     });
@@ -119,3 +119,65 @@ async fn windows_handle() {
     let file = File::create(tempfile.path()).await.unwrap();
     assert!(file.as_raw_handle() as u64 > 0);
 }
+
+#[tokio::test]
+#[cfg(any(unix, windows))]
+async fn read_at() {
+    let mut tempfile = tempfile();
+    tempfile.write_all(HELLO).unwrap();
+
+    let file = File::open(tempfile.path()).await.unwrap();
+
+    let mut buf = [0; 5];
+    let n = file.read_at(&mut buf, 6).await.unwrap();
+
+    assert_eq!(n, 5);
+    assert_eq!(&buf[..n], &HELLO[6..11]);
+
+    // Past the end of the file.
+    let n = file.read_at(&mut buf, HELLO.len() as u64 + 10).await.unwrap();
+    assert_eq!(n, 0);
+}
+
+#[tokio::test]
+#[cfg(any(unix, windows))]
+async fn write_at() {
+    let mut tempfile = tempfile();
+    tempfile.write_all(HELLO).unwrap();
+
+    let file = tokio::fs::OpenOptions::new()
+        .write(true)
+        .open(tempfile.path())
+        .await
+        .unwrap();
+
+    let n = file.write_at(b"WORLD", 6).await.unwrap();
+    assert_eq!(n, 5);
+
+    let contents = std::fs::read(tempfile.path()).unwrap();
+    assert_eq!(&contents, b"hello WORLD...");
+}
+
+#[tokio::test]
+#[cfg(any(unix, windows))]
+async fn positional_io_is_concurrent() {
+    let mut tempfile = tempfile();
+    tempfile.write_all(HELLO).unwrap();
+
+    let file = std::sync::Arc::new(File::open(tempfile.path()).await.unwrap());
+
+    let mut handles = Vec::new();
+    for offset in 0..HELLO.len() as u64 {
+        let file = file.clone();
+        handles.push(tokio::spawn(async move {
+            let mut buf = [0; 1];
+            let n = file.read_at(&mut buf, offset).await.unwrap();
+            assert_eq!(n, 1);
+            assert_eq!(buf[0], HELLO[offset as usize]);
+        }));
+    }
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+}
@@ -1,5 +1,7 @@
 #![warn(rust_2018_idioms)]
-#![cfg(feature = "full")]
+// This harness compiles `src/fs/file.rs` against a mocked blocking pool, so
+// it cannot be built when the io_uring backend is compiled in.
+#![cfg(all(feature = "full", not(feature = "io-uring")))]
 
 macro_rules! ready {
     ($e:expr $(,)?) => {
@@ -20,6 +22,11 @@ macro_rules! cfg_io_std {
     ($($item:item)*) => { $($item)* }
 }
 
+#[macro_export]
+macro_rules! cfg_io_uring {
+    ($($item:item)*) => {};
+}
+
 use futures::future;
 
 // Load source
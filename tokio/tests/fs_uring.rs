@@ -0,0 +1,63 @@
+#![warn(rust_2018_idioms)]
+#![cfg(all(feature = "io-uring", feature = "full", target_os = "linux"))]
+
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use tempfile::NamedTempFile;
+
+const HELLO: &[u8] = b"hello world...";
+
+// These exercise the same public API as `fs_file.rs`; with the `io-uring`
+// feature enabled the operations are served by the ring when the kernel
+// supports it, and by the blocking pool otherwise. Either way the observable
+// behavior must be identical.
+
+#[tokio::test]
+async fn write_read_roundtrip() {
+    let tempfile = NamedTempFile::new().unwrap();
+
+    let mut file = File::create(tempfile.path()).await.unwrap();
+    file.write_all(HELLO).await.unwrap();
+    file.sync_all().await.unwrap();
+
+    let mut file = File::open(tempfile.path()).await.unwrap();
+    let mut buf = vec![];
+    file.read_to_end(&mut buf).await.unwrap();
+
+    assert_eq!(buf, HELLO);
+}
+
+#[tokio::test]
+async fn sync_data() {
+    let tempfile = NamedTempFile::new().unwrap();
+
+    let mut file = File::create(tempfile.path()).await.unwrap();
+    file.write_all(HELLO).await.unwrap();
+    file.sync_data().await.unwrap();
+}
+
+#[tokio::test]
+async fn open_missing_file() {
+    let err = File::open("/definitely/does/not/exist")
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[tokio::test]
+async fn large_write() {
+    // Larger than the 16KB chunk buffer, forcing several operations.
+    let data = vec![0x42u8; 1024 * 1024];
+    let tempfile = NamedTempFile::new().unwrap();
+
+    let mut file = File::create(tempfile.path()).await.unwrap();
+    file.write_all(&data).await.unwrap();
+    file.flush().await.unwrap();
+
+    let mut file = File::open(tempfile.path()).await.unwrap();
+    let mut buf = vec![];
+    file.read_to_end(&mut buf).await.unwrap();
+
+    assert_eq!(buf, data);
+}
@@ -610,3 +610,125 @@ fn driver_shutdown_wakes_poll_race() {
         assert_err!(futures::executor::block_on(poll_writable(&afd_a)));
     }
 }
+
+#[tokio::test]
+async fn ready_reports_observed_readiness() {
+    use tokio::io::{Interest, Ready};
+
+    let (a, mut b) = socketpair();
+
+    let afd_a = AsyncFd::new(a).unwrap();
+
+    // A single-direction wait only ever reports that direction, even if the
+    // descriptor is also ready in the other one.
+    b.write_all(b"0").unwrap();
+
+    let guard = afd_a.readable().await.unwrap();
+    assert!(guard.ready().is_readable());
+    assert!(!guard.ready().is_writable());
+    drop(guard);
+
+    let guard = afd_a.writable().await.unwrap();
+    assert!(guard.ready().is_writable());
+    assert!(!guard.ready().is_readable());
+    drop(guard);
+
+    // A combined wait reports whichever requested states were observed.
+    let guard = afd_a
+        .ready(Interest::READABLE | Interest::WRITABLE)
+        .await
+        .unwrap();
+    assert!(!guard.ready().is_empty());
+    drop(guard);
+
+    // Clearing both directions leaves nothing in the guard.
+    let mut guard = afd_a
+        .ready(Interest::READABLE | Interest::WRITABLE)
+        .await
+        .unwrap();
+    guard.clear_ready_matching(Ready::ALL);
+    assert!(guard.ready().is_empty());
+}
+
+#[tokio::test]
+async fn clear_ready_matching_clears_one_direction() {
+    use tokio::io::{Interest, Ready};
+
+    let (a, mut b) = socketpair();
+
+    b.write_all(b"0").unwrap();
+
+    let afd_a = AsyncFd::new(a).unwrap();
+
+    // Wait until the read direction has been observed; the initial event may
+    // only carry writability.
+    let mut guard = loop {
+        let mut guard = afd_a
+            .ready(Interest::READABLE | Interest::WRITABLE)
+            .await
+            .unwrap();
+
+        if guard.ready().is_readable() {
+            break guard;
+        }
+
+        guard.clear_ready_matching(Ready::WRITABLE | Ready::WRITE_CLOSED);
+    };
+
+    let was_writable = guard.ready().is_writable();
+
+    // Clear only the read direction.
+    guard.clear_ready_matching(Ready::READABLE | Ready::READ_CLOSED);
+    assert!(!guard.ready().is_readable());
+    drop(guard);
+
+    // The data is still buffered, but read readiness was cleared, so a
+    // read-only wait must now block until the next edge.
+    let readable = afd_a.readable();
+    tokio::pin!(readable);
+
+    tokio::select! {
+        _ = readable.as_mut() => panic!("readable after clear_ready_matching"),
+        _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+    }
+
+    if was_writable {
+        // Write readiness was retained and is still immediately observable.
+        afd_a.writable().await.unwrap().retain_ready();
+    }
+}
+
+#[tokio::test]
+async fn ready_mut_combined_interest() {
+    use tokio::io::{Interest, Ready};
+
+    let (a, mut b) = socketpair();
+
+    let mut afd_a = AsyncFd::new(a).unwrap();
+
+    b.write_all(b"0").unwrap();
+
+    let mut buf = [0u8; 16];
+
+    let mut guard = loop {
+        let mut guard = afd_a
+            .ready_mut(Interest::READABLE | Interest::WRITABLE)
+            .await
+            .unwrap();
+
+        if guard.ready().is_readable() {
+            break guard;
+        }
+
+        guard.clear_ready_matching(Ready::WRITABLE | Ready::WRITE_CLOSED);
+    };
+
+    let n = guard
+        .try_io(|afd| afd.get_mut().read(&mut buf))
+        .unwrap()
+        .unwrap();
+    assert_eq!(n, 1);
+
+    guard.clear_ready_matching(Ready::READABLE | Ready::READ_CLOSED);
+    assert!(!guard.ready().is_readable());
+}
@@ -0,0 +1,93 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt, RateLimitedReader, RateLimitedWriter};
+use tokio::time::{Duration, Instant};
+
+#[tokio::test(start_paused = true)]
+async fn read_within_burst_is_immediate() {
+    let (mut tx, rx) = duplex(64);
+    tx.write_all(b"abcd").await.unwrap();
+
+    let mut rx = RateLimitedReader::with_burst(rx, 8, 4);
+
+    let start = Instant::now();
+    let mut buf = [0; 4];
+    rx.read_exact(&mut buf).await.unwrap();
+
+    assert_eq!(&buf, b"abcd");
+    assert!(start.elapsed() < Duration::from_millis(100));
+}
+
+#[tokio::test(start_paused = true)]
+async fn read_beyond_burst_is_paced() {
+    let (mut tx, rx) = duplex(64);
+    tx.write_all(&[0; 12]).await.unwrap();
+
+    // 4 bytes up front, then 8 more at 8 bytes/s: one second in total.
+    let mut rx = RateLimitedReader::with_burst(rx, 8, 4);
+
+    let start = Instant::now();
+    let mut buf = [0; 12];
+    rx.read_exact(&mut buf).await.unwrap();
+
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(900), "read too fast: {:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(2), "read too slow: {:?}", elapsed);
+}
+
+#[tokio::test(start_paused = true)]
+async fn write_beyond_burst_is_paced() {
+    let (tx, mut rx) = duplex(64);
+
+    let mut tx = RateLimitedWriter::with_burst(tx, 8, 4);
+
+    let start = Instant::now();
+    tx.write_all(&[7; 12]).await.unwrap();
+    tx.flush().await.unwrap();
+
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(900), "write too fast: {:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(2), "write too slow: {:?}", elapsed);
+
+    let mut buf = [0; 12];
+    rx.read_exact(&mut buf).await.unwrap();
+    assert_eq!(buf, [7; 12]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn tokens_accrue_while_idle() {
+    let (mut tx, rx) = duplex(64);
+
+    let mut rx = RateLimitedReader::with_burst(rx, 8, 8);
+
+    // Drain the initial burst.
+    tx.write_all(&[0; 8]).await.unwrap();
+    let mut buf = [0; 8];
+    rx.read_exact(&mut buf).await.unwrap();
+
+    // After sitting idle for a second the bucket is full again.
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    tx.write_all(&[0; 8]).await.unwrap();
+
+    let start = Instant::now();
+    rx.read_exact(&mut buf).await.unwrap();
+    assert!(start.elapsed() < Duration::from_millis(100));
+}
+
+#[tokio::test(start_paused = true)]
+async fn unlimited_direction_passes_through() {
+    let (a, mut b) = duplex(64);
+
+    // Read-limited adapter: writes are not paced.
+    let mut a = RateLimitedReader::with_burst(a, 8, 4);
+
+    let start = Instant::now();
+    a.write_all(&[1; 32]).await.unwrap();
+    a.flush().await.unwrap();
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    let mut buf = [0; 32];
+    b.read_exact(&mut buf).await.unwrap();
+    assert_eq!(buf, [1; 32]);
+}
@@ -0,0 +1,103 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use std::io::ErrorKind;
+use tokio::io::{AsyncBufReadExt, BufReader, Error, ReadLineState};
+use tokio_test::{assert_ok, assert_pending, io::Builder, task};
+
+use std::io::Cursor;
+use std::time::Duration;
+
+#[tokio::test]
+async fn read_line_resumable() {
+    let mut rd = Cursor::new(b"hello\nworld\n\n");
+    let mut state = ReadLineState::new();
+
+    let line = assert_ok!(rd.read_line_resumable(&mut state).await);
+    assert_eq!(line.as_deref(), Some("hello\n"));
+    let line = assert_ok!(rd.read_line_resumable(&mut state).await);
+    assert_eq!(line.as_deref(), Some("world\n"));
+    let line = assert_ok!(rd.read_line_resumable(&mut state).await);
+    assert_eq!(line.as_deref(), Some("\n"));
+    let line = assert_ok!(rd.read_line_resumable(&mut state).await);
+    assert_eq!(line, None);
+    assert!(state.is_empty());
+}
+
+#[tokio::test]
+async fn resumes_after_future_dropped() {
+    let mock = Builder::new()
+        .read(b"Hello ")
+        .wait(Duration::from_millis(1))
+        .read(b"World\nrest\n")
+        .build();
+
+    let mut read = BufReader::new(mock);
+    let mut state = ReadLineState::new();
+
+    {
+        // The mock returns the first chunk then goes pending; drop the
+        // future mid-line as a `select!` race would.
+        let mut fut = task::spawn(Box::pin(read.read_line_resumable(&mut state)));
+        assert_pending!(fut.poll());
+    }
+
+    assert_eq!(state.buffer(), b"Hello ");
+
+    // A new future picks up where the dropped one left off.
+    let line = assert_ok!(read.read_line_resumable(&mut state).await);
+    assert_eq!(line.as_deref(), Some("Hello World\n"));
+
+    let line = assert_ok!(read.read_line_resumable(&mut state).await);
+    assert_eq!(line.as_deref(), Some("rest\n"));
+}
+
+#[tokio::test]
+async fn final_line_without_newline() {
+    let mut rd = Cursor::new(b"no newline");
+    let mut state = ReadLineState::new();
+
+    let line = assert_ok!(rd.read_line_resumable(&mut state).await);
+    assert_eq!(line.as_deref(), Some("no newline"));
+    let line = assert_ok!(rd.read_line_resumable(&mut state).await);
+    assert_eq!(line, None);
+}
+
+#[tokio::test]
+async fn io_error_keeps_progress() {
+    let mock = Builder::new()
+        .read(b"Hello Wor")
+        .read_error(Error::new(ErrorKind::Other, "The world has no end"))
+        .read(b"ld\n")
+        .build();
+
+    let mut read = BufReader::new(mock);
+    let mut state = ReadLineState::new();
+
+    let err = read
+        .read_line_resumable(&mut state)
+        .await
+        .expect_err("Should fail");
+    assert_eq!(err.kind(), ErrorKind::Other);
+    assert_eq!(state.buffer(), b"Hello Wor");
+
+    // Retrying after the transient error completes the line.
+    let line = assert_ok!(read.read_line_resumable(&mut state).await);
+    assert_eq!(line.as_deref(), Some("Hello World\n"));
+}
+
+#[tokio::test]
+async fn invalid_utf8_keeps_bytes() {
+    let mock = Builder::new().read(b"Hello Wor\xffld.\n").build();
+
+    let mut read = BufReader::new(mock);
+    let mut state = ReadLineState::new();
+
+    let err = read
+        .read_line_resumable(&mut state)
+        .await
+        .expect_err("Should fail");
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "stream did not contain valid UTF-8");
+    assert_eq!(state.into_bytes(), b"Hello Wor\xffld.\n");
+}
@@ -0,0 +1,37 @@
+#![warn(rust_2018_idioms)]
+#![cfg(all(feature = "full", unix))]
+
+use tokio::io;
+
+#[tokio::test]
+async fn is_tty_does_not_panic() {
+    // The test harness may or may not be attached to a terminal; either
+    // answer is fine as long as the query itself works.
+    let _ = io::stdin().is_tty();
+    let _ = io::stdout().is_tty();
+}
+
+#[tokio::test]
+async fn window_size_errors_without_tty() {
+    let stdin = io::stdin();
+
+    if !stdin.is_tty() {
+        assert!(stdin.window_size().is_err());
+    }
+}
+
+#[tokio::test]
+async fn raw_mode_errors_without_tty() {
+    let stdin = io::stdin();
+
+    if !stdin.is_tty() {
+        assert!(stdin.enter_raw_mode().is_err());
+    }
+}
+
+#[tokio::test]
+async fn resize_events_registers() {
+    // Registering the SIGWINCH handler works whether or not a terminal is
+    // attached; only querying the size requires one.
+    let _ = io::stdout().resize_events().unwrap();
+}
@@ -537,3 +537,67 @@ async fn biased_eventually_ready() {
 
     assert_eq!(count, 3);
 }
+
+#[tokio::test]
+async fn index_mode_returns_branch_index() {
+    let (index, value) = tokio::select! {
+        index;
+        v = async { 1 } => v,
+        v = std::future::pending::<i32>() => v,
+    };
+
+    assert_eq!(index, 0);
+    assert_eq!(value, 1);
+
+    let (index, value) = tokio::select! {
+        index;
+        v = std::future::pending::<i32>() => v,
+        v = async { 2 } => v,
+    };
+
+    assert_eq!(index, 1);
+    assert_eq!(value, 2);
+}
+
+#[tokio::test]
+async fn index_mode_biased() {
+    let (index, value) = tokio::select! {
+        index;
+        biased;
+
+        v = async { 1 } => v,
+        v = async { 2 } => v,
+    };
+
+    assert_eq!(index, 0);
+    assert_eq!(value, 1);
+}
+
+#[tokio::test]
+async fn index_mode_match_on_branch() {
+    let out = tokio::select! {
+        index;
+        _ = std::future::pending::<()>() => "pending",
+        _ = async {} => "ready",
+    };
+
+    let label = match out {
+        (0, s) => s,
+        (1, s) => s,
+        _ => unreachable!(),
+    };
+
+    assert_eq!(label, "ready");
+}
+
+#[tokio::test]
+async fn index_mode_with_else() {
+    let (index, value) = tokio::select! {
+        index;
+        v = async { 1 }, if false => v,
+        else => (u32::MAX, -1),
+    };
+
+    assert_eq!(index, u32::MAX);
+    assert_eq!(value, -1);
+}
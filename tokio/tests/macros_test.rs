@@ -26,6 +26,24 @@ pub async fn unused_braces_main() { println!("hello") }
 #[tokio::test]
 async fn unused_braces_test() { assert_eq!(1 + 1, 2) }
 
+#[tokio::test(max_blocking_threads = 1)]
+async fn test_macro_max_blocking_threads() {
+    tokio::task::spawn_blocking(|| assert_eq!(1 + 1, 2))
+        .await
+        .unwrap();
+}
+
+fn customize(builder: &mut tokio::runtime::Builder) {
+    builder.max_blocking_threads(1);
+}
+
+#[tokio::test(builder = "customize")]
+async fn test_macro_builder_fn() {
+    tokio::task::spawn_blocking(|| assert_eq!(1 + 1, 2))
+        .await
+        .unwrap();
+}
+
 // https://github.com/tokio-rs/tokio/pull/3766#issuecomment-835508651
 #[std::prelude::v1::test]
 fn trait_method() {
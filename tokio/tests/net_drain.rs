@@ -0,0 +1,70 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{Drain, TcpListener, TcpStream};
+use tokio::time::{timeout, Duration};
+
+#[tokio::test]
+async fn drain_resolves_immediately_when_empty() {
+    let drain = Drain::new();
+    assert_eq!(drain.tracked(), 0);
+
+    timeout(Duration::from_secs(1), drain.drain())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn drain_waits_for_tracked_connections() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let drain = Drain::new();
+
+    let client = tokio::spawn(async move {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let mut buf = Vec::new();
+        stream.read_to_end(&mut buf).await.unwrap();
+        buf
+    });
+
+    let (socket, _) = listener.accept().await.unwrap();
+    let mut socket = drain.track(socket);
+    assert_eq!(drain.tracked(), 1);
+
+    // Not drained while the connection is open.
+    assert!(!drain.drain_timeout(Duration::from_millis(10)).await);
+
+    let server = tokio::spawn(async move {
+        socket.write_all(b"bye").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(socket);
+    });
+
+    drop(listener);
+    assert!(drain.drain_timeout(Duration::from_secs(5)).await);
+    assert_eq!(drain.tracked(), 0);
+
+    server.await.unwrap();
+    assert_eq!(client.await.unwrap(), b"bye");
+}
+
+#[tokio::test]
+async fn clones_share_the_tracker() {
+    let drain = Drain::new();
+    let tracked = drain.clone().track(());
+
+    assert_eq!(drain.tracked(), 1);
+    drop(tracked);
+    assert_eq!(drain.tracked(), 0);
+}
+
+#[tokio::test]
+async fn into_inner_stops_tracking() {
+    let drain = Drain::new();
+    let tracked = drain.track("connection");
+
+    assert_eq!(drain.tracked(), 1);
+    assert_eq!(tracked.into_inner(), "connection");
+    assert_eq!(drain.tracked(), 0);
+}
@@ -0,0 +1,63 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use socket2::{Domain, Socket, Type};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, UdpSocket};
+
+use std::net::SocketAddr;
+
+fn any_addr() -> SocketAddr {
+    "127.0.0.1:0".parse().unwrap()
+}
+
+#[tokio::test]
+async fn tcp_listener_from_socket2() {
+    let socket = Socket::new(Domain::IPV4, Type::STREAM, None).unwrap();
+    socket.bind(&any_addr().into()).unwrap();
+    socket.listen(128).unwrap();
+    socket.set_nonblocking(true).unwrap();
+
+    let listener = TcpListener::from_socket2(socket).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let (connected, (mut accepted, _)) =
+        tokio::try_join!(TcpStream::connect(addr), listener.accept()).unwrap();
+
+    let mut connected = connected;
+    connected.write_all(b"ping").await.unwrap();
+    drop(connected);
+
+    let mut buf = Vec::new();
+    accepted.read_to_end(&mut buf).await.unwrap();
+    assert_eq!(buf, b"ping");
+}
+
+#[tokio::test]
+async fn udp_socket_from_socket2() {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, None).unwrap();
+    socket.bind(&any_addr().into()).unwrap();
+    socket.set_nonblocking(true).unwrap();
+
+    let receiver = UdpSocket::from_socket2(socket).unwrap();
+    let addr = receiver.local_addr().unwrap();
+
+    let sender = UdpSocket::bind(any_addr()).await.unwrap();
+    sender.send_to(b"ping", addr).await.unwrap();
+
+    let mut buf = [0; 16];
+    let (n, _) = receiver.recv_from(&mut buf).await.unwrap();
+    assert_eq!(&buf[..n], b"ping");
+}
+
+#[tokio::test]
+#[cfg(unix)]
+async fn blocking_socket_rejected() {
+    let socket = Socket::new(Domain::IPV4, Type::STREAM, None).unwrap();
+    socket.bind(&any_addr().into()).unwrap();
+    socket.listen(128).unwrap();
+
+    // Never switched to non-blocking mode.
+    let err = TcpListener::from_socket2(socket).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
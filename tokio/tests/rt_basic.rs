@@ -150,6 +150,165 @@ fn timeout_panics_when_no_time_handle() {
     });
 }
 
+#[test]
+#[should_panic(
+    expected = "a spawned task panicked and the runtime is configured to shut down on unhandled panic"
+)]
+fn shutdown_on_unhandled_panic() {
+    use std::future::pending;
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .unhandled_panic(tokio::runtime::UnhandledPanic::ShutdownRuntime)
+        .build()
+        .unwrap();
+
+    rt.spawn(async {
+        panic!("boom");
+    });
+
+    rt.block_on(async {
+        pending::<()>().await;
+    });
+}
+
+#[test]
+fn ignore_unhandled_panic_by_default() {
+    let rt = rt();
+
+    let handle = rt.spawn(async {
+        panic!("boom");
+    });
+
+    rt.block_on(async {
+        let err = handle.await.unwrap_err();
+        assert!(err.is_panic());
+
+        // The runtime is still running.
+        tokio::task::yield_now().await;
+    });
+}
+
+#[test]
+fn runtime_identity() {
+    use tokio::runtime::{Handle, RuntimeFlavor};
+
+    let first = rt();
+    let second = rt();
+
+    assert_eq!(first.handle().runtime_flavor(), RuntimeFlavor::CurrentThread);
+    assert_eq!(first.handle().id(), first.handle().id());
+    assert_ne!(first.handle().id(), second.handle().id());
+
+    let handle = first.handle().clone();
+    assert!(!handle.is_current());
+
+    first.block_on(async move {
+        assert!(handle.is_current());
+        assert_eq!(Handle::current().id(), handle.id());
+    });
+}
+
+#[test]
+fn handle_owns_spawned_tasks() {
+    let first = rt();
+    let second = rt();
+
+    let task = first.spawn(async { 1 });
+    let blocking = first.spawn_blocking(|| 2);
+
+    assert!(first.handle().owns(&task));
+    assert!(first.handle().owns(&blocking));
+    assert!(!second.handle().owns(&task));
+
+    first.block_on(async {
+        assert_eq!(task.await.unwrap(), 1);
+        assert_eq!(blocking.await.unwrap(), 2);
+
+        let inner = tokio::spawn(async { 3 });
+        assert!(tokio::runtime::Handle::current().owns(&inner));
+        assert_eq!(inner.await.unwrap(), 3);
+    });
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "timer polled on a runtime other than the one it was created on")]
+fn cross_runtime_timer_polled() {
+    let first = rt();
+    let second = rt();
+
+    // Deliberately return the un-awaited sleep so it can be polled on the
+    // wrong runtime below.
+    #[allow(clippy::async_yields_async)]
+    let sleep = first.block_on(async { tokio::time::sleep(Duration::from_millis(5)) });
+
+    second.block_on(sleep);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn cross_runtime_io_resource_errors() {
+    let first = rt();
+    let second = rt();
+
+    let listener = first.block_on(async {
+        tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .unwrap()
+    });
+
+    second.block_on(async {
+        let err = listener.accept().await.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("runtime other than the one it was registered with"));
+    });
+}
+
+#[test]
+fn try_current_missing_context() {
+    let err = tokio::runtime::Handle::try_current().unwrap_err();
+    assert!(err.is_missing_context());
+    assert!(!err.is_thread_local_destroyed());
+
+    let rt = rt();
+    rt.block_on(async {
+        assert!(tokio::runtime::Handle::try_current().is_ok());
+    });
+}
+
+#[test]
+fn idle_busy_callbacks() {
+    use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+    use std::sync::Arc;
+
+    let idle = Arc::new(AtomicUsize::new(0));
+    let busy = Arc::new(AtomicUsize::new(0));
+
+    let idle2 = idle.clone();
+    let busy2 = busy.clone();
+
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .on_runtime_idle(move || {
+            idle2.fetch_add(1, SeqCst);
+        })
+        .on_runtime_busy(move || {
+            busy2.fetch_add(1, SeqCst);
+        })
+        .build()
+        .unwrap();
+
+    // Waiting on the timer parks the thread with no work available, then
+    // wakes it when the timer fires.
+    rt.block_on(async {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    });
+
+    assert!(idle.load(SeqCst) >= 1);
+    assert!(busy.load(SeqCst) >= 1);
+}
+
 fn rt() -> Runtime {
     tokio::runtime::Builder::new_current_thread()
         .enable_all()
@@ -214,6 +214,38 @@ rt_test! {
         }
     }
 
+    #[test]
+    fn spawn_iter_batch() {
+        const ITER: usize = 500;
+
+        let rt = rt();
+
+        // Spawn from outside the runtime so the batch goes through the
+        // injection queue.
+        let handles = rt.handle().spawn_iter((0..ITER).map(|i| async move { i }));
+        assert_eq!(handles.len(), ITER);
+
+        let total = rt.block_on(async move {
+            let mut total = 0;
+            for handle in handles {
+                total += handle.await.unwrap();
+            }
+            total
+        });
+
+        assert_eq!(total, ITER * (ITER - 1) / 2);
+    }
+
+    #[test]
+    fn spawn_iter_empty() {
+        let rt = rt();
+
+        let handles = rt
+            .handle()
+            .spawn_iter(std::iter::empty::<std::future::Ready<()>>());
+        assert!(handles.is_empty());
+    }
+
     #[test]
     fn spawn_many_from_task() {
         use tokio::sync::mpsc;
@@ -5,6 +5,7 @@ use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::runtime::{self, Runtime};
 use tokio::sync::oneshot;
+use tokio::task::SpawnHint;
 use tokio_test::{assert_err, assert_ok};
 
 use futures::future::poll_fn;
@@ -405,6 +406,159 @@ async fn hang_on_shutdown() {
     tokio::time::sleep(std::time::Duration::from_secs(1)).await;
 }
 
+#[test]
+fn spawn_with_hint_completes() {
+    let rt = rt();
+
+    let out = rt.block_on(async {
+        let handles: Vec<_> = (0..100)
+            .map(|i| tokio::task::spawn_with_hint(async move { i }, SpawnHint::worker(i)))
+            .collect();
+
+        let mut sum = 0;
+        for handle in handles {
+            sum += handle.await.unwrap();
+        }
+        sum
+    });
+
+    assert_eq!(out, 4950);
+}
+
+#[test]
+fn spawn_with_hint_colocates_tasks() {
+    use std::time::Duration;
+
+    // On an otherwise idle runtime, tasks hinted to the same worker run on
+    // the same thread.
+    let rt = runtime::Builder::new_multi_thread()
+        .worker_threads(4)
+        .enable_all()
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        // Let the workers park so the hinted worker is woken directly rather
+        // than the task being claimed by a searching worker.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let a = tokio::task::spawn_with_hint(
+            async { std::thread::current().id() },
+            SpawnHint::worker(2),
+        )
+        .await
+        .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let b = tokio::task::spawn_with_hint(
+            async { std::thread::current().id() },
+            SpawnHint::worker(2),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(a, b);
+    });
+}
+
+#[test]
+fn spawn_with_hint_current_worker() {
+    let rt = rt();
+
+    rt.block_on(async {
+        tokio::task::spawn_with_hint(async {}, SpawnHint::prefer_current_worker())
+            .await
+            .unwrap();
+    });
+
+    // Spawning from outside a worker falls back on the inject queue.
+    rt.block_on(async {})
+}
+
+#[test]
+fn idle_busy_callbacks() {
+    use std::sync::atomic::Ordering::SeqCst;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    let (idle_tx, idle_rx) = mpsc::channel();
+    let idle_tx = Mutex::new(idle_tx);
+
+    let busy = Arc::new(AtomicUsize::new(0));
+    let busy2 = busy.clone();
+
+    let rt = runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .on_runtime_idle(move || {
+            let _ = idle_tx.lock().unwrap().send(());
+        })
+        .on_runtime_busy(move || {
+            busy2.fetch_add(1, SeqCst);
+        })
+        .build()
+        .unwrap();
+
+    // With no work submitted, all workers park and the runtime reports idle.
+    idle_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    let busy_before = busy.load(SeqCst);
+
+    rt.block_on(async {
+        tokio::spawn(async {}).await.unwrap();
+    });
+
+    // Spawning onto the idle runtime unparked a worker.
+    assert!(busy.load(SeqCst) > busy_before);
+
+    // Once the task completes, the runtime goes idle again.
+    idle_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+}
+
+#[test]
+fn min_blocking_threads_are_pre_spawned() {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let (tx, rx) = mpsc::channel();
+    let tx = std::sync::Mutex::new(tx);
+
+    let rt = runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .min_blocking_threads(3)
+        .on_thread_start(move || {
+            let _ = tx.lock().unwrap().send(());
+        })
+        .build()
+        .unwrap();
+
+    // 1 worker thread + 3 warm blocking threads start before any work is
+    // submitted.
+    for _ in 0..4 {
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    // The warm threads pick up work as usual.
+    rt.block_on(async {
+        let out = tokio::task::spawn_blocking(|| "hello").await.unwrap();
+        assert_eq!(out, "hello");
+    });
+}
+
+#[test]
+fn min_blocking_threads_clamped_to_max() {
+    let rt = runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .max_blocking_threads(2)
+        .min_blocking_threads(16)
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        tokio::task::spawn_blocking(|| ()).await.unwrap();
+    });
+}
+
 fn rt() -> Runtime {
     Runtime::new().unwrap()
 }
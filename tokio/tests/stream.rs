@@ -0,0 +1,40 @@
+#![warn(rust_2018_idioms)]
+#![cfg(all(feature = "full", feature = "stream"))]
+
+use tokio::sync::mpsc;
+use tokio::time::{self, Duration};
+
+use futures::StreamExt;
+
+#[tokio::test]
+async fn mpsc_bounded_stream() {
+    let (tx, rx) = mpsc::channel(4);
+
+    tx.send(1).await.unwrap();
+    tx.send(2).await.unwrap();
+    drop(tx);
+
+    let values: Vec<i32> = rx.collect().await;
+    assert_eq!(values, vec![1, 2]);
+}
+
+#[tokio::test]
+async fn mpsc_unbounded_stream() {
+    let (tx, rx) = mpsc::unbounded_channel();
+
+    tx.send("hello").unwrap();
+    drop(tx);
+
+    let values: Vec<&str> = rx.collect().await;
+    assert_eq!(values, vec!["hello"]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn interval_stream() {
+    let mut interval = time::interval(Duration::from_millis(10));
+
+    // An interval stream never terminates.
+    for _ in 0..3 {
+        assert!(interval.next().await.is_some());
+    }
+}
@@ -135,6 +135,14 @@ impl File {
         unimplemented!();
     }
 
+    pub fn read_at(&self, _dst: &mut [u8], _offset: u64) -> io::Result<usize> {
+        unimplemented!("positional reads are not mocked");
+    }
+
+    pub fn write_at(&self, _src: &[u8], _offset: u64) -> io::Result<usize> {
+        unimplemented!("positional writes are not mocked");
+    }
+
     pub fn mock() -> (Handle, File) {
         let shared = Arc::new(Mutex::new(Shared {
             calls: VecDeque::new(),
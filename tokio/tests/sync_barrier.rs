@@ -94,3 +94,56 @@ fn lots() {
         assert!(found_leader);
     }
 }
+
+#[test]
+fn add_party_raises_requirement() {
+    let b = Barrier::new(2);
+
+    let mut w1 = spawn(b.wait());
+    assert_pending!(w1.poll());
+
+    // A third party joins, so two waiters are no longer enough.
+    b.add_party();
+
+    let mut w2 = spawn(b.wait());
+    assert_pending!(w2.poll());
+    assert_pending!(w1.poll());
+
+    let mut w3 = spawn(b.wait());
+    let wr3 = assert_ready!(w3.poll());
+    assert!(wr3.is_leader());
+
+    assert!(!assert_ready!(w1.poll()).is_leader());
+    assert!(!assert_ready!(w2.poll()).is_leader());
+}
+
+#[test]
+fn remove_party_releases_waiters() {
+    let b = Barrier::new(3);
+
+    let mut w1 = spawn(b.wait());
+    assert_pending!(w1.poll());
+    let mut w2 = spawn(b.wait());
+    assert_pending!(w2.poll());
+
+    // Dropping to two parties releases the generation; no waiter is the
+    // leader since none of them was the final arriver.
+    b.remove_party();
+
+    assert!(!assert_ready!(w1.poll()).is_leader());
+    assert!(!assert_ready!(w2.poll()).is_leader());
+
+    // The next generation requires two arrivals.
+    let mut w3 = spawn(b.wait());
+    assert_pending!(w3.poll());
+    let mut w4 = spawn(b.wait());
+    assert!(assert_ready!(w4.poll()).is_leader());
+    assert!(!assert_ready!(w3.poll()).is_leader());
+}
+
+#[test]
+#[should_panic = "cannot remove the last party from a barrier"]
+fn remove_last_party_panics() {
+    let b = Barrier::new(1);
+    b.remove_party();
+}
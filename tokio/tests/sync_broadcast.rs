@@ -454,3 +454,285 @@ fn lagging_receiver_recovers_after_wrap_open() {
 fn is_closed(err: broadcast::error::RecvError) -> bool {
     matches!(err, broadcast::error::RecvError::Closed)
 }
+
+#[test]
+fn receiver_buffer_slow_consumer_isolated() {
+    let (tx, mut fast) = broadcast::channel_with_receiver_buffer(2, 8);
+    let mut slow = tx.subscribe();
+
+    for i in 1..=6 {
+        assert_ok!(tx.send(i));
+        assert_eq!(assert_recv!(fast), i);
+    }
+
+    // The slow receiver fell four values behind a two-slot ring, but its
+    // side buffer kept the overwritten values: no lag.
+    for i in 1..=6 {
+        assert_eq!(assert_recv!(slow), i);
+    }
+
+    assert_empty!(slow);
+    assert_empty!(fast);
+}
+
+#[test]
+fn receiver_buffer_lags_only_on_own_overflow() {
+    let (tx, mut fast) = broadcast::channel_with_receiver_buffer(2, 2);
+    let mut slow = tx.subscribe();
+
+    // Seven sends on a two-slot ring evict values 1..=5. The slow
+    // receiver's two-slot side buffer keeps 4 and 5 and misses 1..=3.
+    for i in 1..=7 {
+        assert_ok!(tx.send(i));
+        assert_eq!(assert_recv!(fast), i);
+    }
+
+    assert_lagged!(slow.try_recv(), 3);
+    for i in 4..=7 {
+        assert_eq!(assert_recv!(slow), i);
+    }
+    assert_empty!(slow);
+}
+
+#[test]
+fn receiver_buffer_recv_wakes_on_send() {
+    let (tx, mut rx) = broadcast::channel_with_receiver_buffer(4, 4);
+
+    let mut recv = task::spawn(rx.recv());
+    assert_pending!(recv.poll());
+
+    assert_ok!(tx.send("hello"));
+
+    assert!(recv.is_woken());
+    let val = assert_ready_ok!(recv.poll());
+    assert_eq!(val, "hello");
+}
+
+#[test]
+fn receiver_buffer_closed() {
+    let (tx, mut rx) = broadcast::channel_with_receiver_buffer(2, 2);
+
+    assert_ok!(tx.send(1));
+    drop(tx);
+
+    assert_eq!(assert_recv!(rx), 1);
+    assert_closed!(rx.try_recv());
+}
+
+#[test]
+fn receiver_buffer_dropped_receiver_not_fed() {
+    let (tx, mut rx1) = broadcast::channel_with_receiver_buffer(2, 2);
+    let rx2 = tx.subscribe();
+    drop(rx2);
+
+    for i in 1..=4 {
+        assert_ok!(tx.send(i));
+    }
+
+    for i in 1..=4 {
+        assert_eq!(assert_recv!(rx1), i);
+    }
+    assert_empty!(rx1);
+}
+
+#[test]
+#[should_panic]
+fn zero_receiver_buffer_capacity() {
+    broadcast::channel_with_receiver_buffer::<()>(2, 0);
+}
+
+#[cfg(all(feature = "stream", feature = "full"))]
+mod stream {
+    use super::*;
+    use futures_core::Stream;
+    use std::pin::Pin;
+
+    fn poll_next<T: Clone>(
+        stream: &mut task::Spawn<Pin<Box<broadcast::IntoStream<T>>>>,
+    ) -> std::task::Poll<Option<Result<T, broadcast::error::Lagged>>> {
+        stream.enter(|cx, mut stream| stream.as_mut().poll_next(cx))
+    }
+
+    #[test]
+    fn stream_yields_sent_values() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut stream = task::spawn(Box::pin(rx.into_stream()));
+
+        assert_pending!(poll_next(&mut stream));
+
+        assert_ok!(tx.send("one"));
+        assert!(stream.is_woken());
+
+        assert_eq!(assert_ready!(poll_next(&mut stream)), Some(Ok("one")));
+        assert_pending!(poll_next(&mut stream));
+    }
+
+    #[test]
+    fn stream_ends_when_closed() {
+        let (tx, rx) = broadcast::channel(16);
+        let mut stream = task::spawn(Box::pin(rx.into_stream()));
+
+        assert_ok!(tx.send("one"));
+        drop(tx);
+
+        assert_eq!(assert_ready!(poll_next(&mut stream)), Some(Ok("one")));
+        assert_eq!(assert_ready!(poll_next(&mut stream)), None);
+    }
+
+    #[test]
+    fn stream_reports_lag() {
+        let (tx, rx) = broadcast::channel(2);
+        let mut stream = task::spawn(Box::pin(rx.into_stream()));
+
+        assert_ok!(tx.send("one"));
+        assert_ok!(tx.send("two"));
+        assert_ok!(tx.send("three"));
+
+        assert_eq!(
+            assert_ready!(poll_next(&mut stream)),
+            Some(Err(broadcast::error::Lagged(1)))
+        );
+        assert_eq!(assert_ready!(poll_next(&mut stream)), Some(Ok("two")));
+        assert_eq!(assert_ready!(poll_next(&mut stream)), Some(Ok("three")));
+    }
+
+    #[test]
+    fn stream_resumes_on_lag() {
+        let (tx, rx) = broadcast::channel(2);
+        let stream = rx.into_stream().lag_policy(broadcast::LagPolicy::Resume);
+        let mut stream = task::spawn(Box::pin(stream));
+
+        assert_ok!(tx.send("one"));
+        assert_ok!(tx.send("two"));
+        assert_ok!(tx.send("three"));
+
+        // The skipped message is not reported.
+        assert_eq!(assert_ready!(poll_next(&mut stream)), Some(Ok("two")));
+        assert_eq!(assert_ready!(poll_next(&mut stream)), Some(Ok("three")));
+    }
+}
+
+#[test]
+fn lag_policy_report_default() {
+    let (tx, mut rx) = broadcast::channel(2);
+    assert_eq!(rx.lag_policy(), broadcast::LagPolicy::Report);
+
+    for i in 0..4 {
+        assert_ok!(tx.send(i));
+    }
+
+    assert_lagged!(rx.try_recv(), 2);
+    assert_eq!(assert_recv!(rx), 2);
+}
+
+#[test]
+fn lag_policy_resume() {
+    let (tx, mut rx) = broadcast::channel(2);
+    rx.set_lag_policy(broadcast::LagPolicy::Resume);
+
+    for i in 0..4 {
+        assert_ok!(tx.send(i));
+    }
+
+    // No lag error; reception resumes at the oldest retained value.
+    assert_eq!(assert_recv!(rx), 2);
+    assert_eq!(assert_recv!(rx), 3);
+    assert_empty!(rx);
+}
+
+#[test]
+fn lag_policy_latest() {
+    let (tx, mut rx) = broadcast::channel(2);
+    rx.set_lag_policy(broadcast::LagPolicy::Latest);
+
+    for i in 0..5 {
+        assert_ok!(tx.send(i));
+    }
+
+    // Everything before the most recent value is skipped silently.
+    assert_eq!(assert_recv!(rx), 4);
+    assert_empty!(rx);
+}
+
+#[test]
+fn lag_policy_latest_after_close() {
+    let (tx, mut rx) = broadcast::channel(2);
+    rx.set_lag_policy(broadcast::LagPolicy::Latest);
+
+    for i in 0..5 {
+        assert_ok!(tx.send(i));
+    }
+    drop(tx);
+
+    assert_eq!(assert_recv!(rx), 4);
+    assert_closed!(rx.try_recv());
+}
+
+#[test]
+fn lag_policy_is_per_receiver() {
+    let (tx, mut rx1) = broadcast::channel(2);
+    let mut rx2 = tx.subscribe();
+    rx1.set_lag_policy(broadcast::LagPolicy::Latest);
+
+    for i in 0..4 {
+        assert_ok!(tx.send(i));
+    }
+
+    assert_eq!(assert_recv!(rx1), 3);
+    assert_lagged!(rx2.try_recv(), 2);
+}
+
+#[test]
+fn receiver_count_watch_tracks_subscribers() {
+    let (tx, rx1) = broadcast::channel::<i32>(16);
+    let count = tx.receiver_count_watch();
+
+    assert_eq!(*count.borrow(), 1);
+
+    let rx2 = tx.subscribe();
+    assert_eq!(*count.borrow(), 2);
+
+    drop(rx1);
+    assert_eq!(*count.borrow(), 1);
+
+    drop(rx2);
+    assert_eq!(*count.borrow(), 0);
+}
+
+#[test]
+fn subscribed_ready_with_existing_receiver() {
+    let (tx, _rx) = broadcast::channel::<i32>(16);
+
+    let mut subscribed = task::spawn(tx.subscribed());
+    assert_ready!(subscribed.poll());
+}
+
+#[test]
+fn subscribed_wakes_on_subscribe() {
+    let (tx, rx) = broadcast::channel::<i32>(16);
+    drop(rx);
+
+    let mut subscribed = task::spawn(tx.subscribed());
+    assert_pending!(subscribed.poll());
+
+    let _rx = tx.subscribe();
+
+    assert!(subscribed.is_woken());
+    assert_ready!(subscribed.poll());
+}
+
+#[test]
+fn subscribed_stays_pending_after_drop() {
+    let (tx, rx) = broadcast::channel::<i32>(16);
+    drop(rx);
+
+    let mut subscribed = task::spawn(tx.subscribed());
+    assert_pending!(subscribed.poll());
+
+    let rx = tx.subscribe();
+    drop(rx);
+
+    // The count went back to zero before the future observed it.
+    assert!(subscribed.is_woken());
+    assert_pending!(subscribed.poll());
+}
@@ -0,0 +1,100 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::sync::CancellationToken;
+
+use tokio_test::task::spawn;
+use tokio_test::{assert_pending, assert_ready};
+
+#[test]
+fn cancelled_immediately_after_cancel() {
+    let token = CancellationToken::new();
+    token.cancel();
+
+    assert!(token.is_cancelled());
+    let mut t = spawn(token.cancelled());
+    assert_ready!(t.poll());
+}
+
+#[test]
+fn cancel_wakes_waiter() {
+    let token = CancellationToken::new();
+
+    let mut t = spawn(token.cancelled());
+    assert_pending!(t.poll());
+
+    token.cancel();
+
+    assert!(t.is_woken());
+    assert_ready!(t.poll());
+}
+
+#[test]
+fn clone_shares_state() {
+    let token = CancellationToken::new();
+    let clone = token.clone();
+
+    let mut t = spawn(clone.cancelled());
+    assert_pending!(t.poll());
+
+    token.cancel();
+
+    assert!(clone.is_cancelled());
+    assert_ready!(t.poll());
+}
+
+#[test]
+fn cancel_propagates_to_child() {
+    let token = CancellationToken::new();
+    let child = token.child_token();
+    let grandchild = child.child_token();
+
+    let mut t = spawn(grandchild.cancelled());
+    assert_pending!(t.poll());
+
+    token.cancel();
+
+    assert!(child.is_cancelled());
+    assert!(grandchild.is_cancelled());
+    assert_ready!(t.poll());
+}
+
+#[test]
+fn cancel_child_leaves_parent_untouched() {
+    let token = CancellationToken::new();
+    let child = token.child_token();
+
+    child.cancel();
+
+    assert!(child.is_cancelled());
+    assert!(!token.is_cancelled());
+}
+
+#[test]
+fn child_of_cancelled_token_starts_cancelled() {
+    let token = CancellationToken::new();
+    token.cancel();
+
+    assert!(token.child_token().is_cancelled());
+}
+
+#[tokio::test]
+async fn run_until_cancelled_completes() {
+    let token = CancellationToken::new();
+
+    let result = token.run_until_cancelled(async { 5 }).await;
+    assert_eq!(result, Some(5));
+}
+
+#[test]
+fn run_until_cancelled_cut_short() {
+    let token = CancellationToken::new();
+
+    let mut t = spawn(token.run_until_cancelled(std::future::pending::<()>()));
+    assert_pending!(t.poll());
+
+    token.cancel();
+
+    assert!(t.is_woken());
+    assert_eq!(assert_ready!(t.poll()), None);
+}
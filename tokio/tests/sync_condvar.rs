@@ -0,0 +1,130 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use std::sync::Arc;
+use tokio::sync::{Condvar, Mutex};
+
+use tokio_test::task::spawn;
+use tokio_test::{assert_pending, assert_ready};
+
+#[test]
+fn wait_until_notified() {
+    let lock = Mutex::new(0);
+    let cvar = Condvar::new();
+
+    let guard = {
+        let mut t = spawn(lock.lock());
+        assert_ready!(t.poll())
+    };
+
+    let mut w = spawn(cvar.wait(guard));
+    assert_pending!(w.poll());
+
+    // The lock was released while waiting.
+    {
+        let mut t = spawn(lock.lock());
+        let mut g = assert_ready!(t.poll());
+        *g += 1;
+    }
+
+    cvar.notify_one();
+    assert!(w.is_woken());
+    let g = assert_ready!(w.poll());
+    assert_eq!(*g, 1);
+}
+
+#[test]
+fn notify_one_wakes_one() {
+    let lock = Mutex::new(());
+    let cvar = Condvar::new();
+
+    let g1 = assert_ready!(spawn(lock.lock()).poll());
+    let mut w1 = spawn(cvar.wait(g1));
+    assert_pending!(w1.poll());
+
+    let g2 = assert_ready!(spawn(lock.lock()).poll());
+    let mut w2 = spawn(cvar.wait(g2));
+    assert_pending!(w2.poll());
+
+    cvar.notify_one();
+    assert!(w1.is_woken());
+    assert!(!w2.is_woken());
+    assert_ready!(w1.poll());
+    assert_pending!(w2.poll());
+}
+
+#[test]
+fn notify_all_wakes_every_waiter() {
+    let lock = Mutex::new(());
+    let cvar = Condvar::new();
+
+    let g1 = assert_ready!(spawn(lock.lock()).poll());
+    let mut w1 = spawn(cvar.wait(g1));
+    assert_pending!(w1.poll());
+
+    let g2 = assert_ready!(spawn(lock.lock()).poll());
+    let mut w2 = spawn(cvar.wait(g2));
+    assert_pending!(w2.poll());
+
+    cvar.notify_all();
+    assert!(w1.is_woken());
+    assert!(w2.is_woken());
+
+    // The waiters reacquire the mutex one at a time.
+    let g1 = assert_ready!(w1.poll());
+    assert_pending!(w2.poll());
+    drop(g1);
+    assert_ready!(w2.poll());
+}
+
+#[test]
+fn notify_with_no_waiters_is_not_stored() {
+    let lock = Mutex::new(());
+    let cvar = Condvar::new();
+
+    cvar.notify_one();
+    cvar.notify_all();
+
+    let g = assert_ready!(spawn(lock.lock()).poll());
+    let mut w = spawn(cvar.wait(g));
+    assert_pending!(w.poll());
+}
+
+#[test]
+fn cancelled_wait_leaves_no_waiter_count() {
+    let lock = Mutex::new(());
+    let cvar = Condvar::new();
+
+    let g = assert_ready!(spawn(lock.lock()).poll());
+    let mut w = spawn(cvar.wait(g));
+    assert_pending!(w.poll());
+    drop(w);
+
+    // The cancelled waiter no longer counts, so this notification is a
+    // no-op rather than a stored permit.
+    cvar.notify_one();
+
+    let g = assert_ready!(spawn(lock.lock()).poll());
+    let mut w = spawn(cvar.wait(g));
+    assert_pending!(w.poll());
+}
+
+#[tokio::test]
+async fn wait_while_checks_condition() {
+    let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    let pair2 = pair.clone();
+
+    let handle = tokio::spawn(async move {
+        let (lock, cvar) = &*pair2;
+        let mut started = lock.lock().await;
+        *started = true;
+        cvar.notify_one();
+    });
+
+    let (lock, cvar) = &*pair;
+    let guard = lock.lock().await;
+    let guard = cvar.wait_while(guard, |started| !*started).await;
+    assert!(*guard);
+
+    handle.await.unwrap();
+}
@@ -0,0 +1,194 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::sync::mpmc::{self, error::TryRecvError, error::TrySendError};
+
+use tokio_test::task::spawn;
+use tokio_test::{assert_pending, assert_ready};
+
+#[test]
+fn send_recv_in_order() {
+    let (tx, rx) = mpmc::channel(4);
+
+    assert!(tx.try_send(1).is_ok());
+    assert!(tx.try_send(2).is_ok());
+
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Ok(2));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn try_send_full() {
+    let (tx, rx) = mpmc::channel(1);
+
+    assert!(tx.try_send(1).is_ok());
+    match tx.try_send(2) {
+        Err(TrySendError::Full(2)) => {}
+        other => panic!("expected Full, got {:?}", other),
+    }
+
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert!(tx.try_send(3).is_ok());
+}
+
+#[test]
+fn each_value_received_once() {
+    let (tx, rx1) = mpmc::channel(4);
+    let rx2 = rx1.clone();
+
+    assert!(tx.try_send(1).is_ok());
+    assert!(tx.try_send(2).is_ok());
+
+    assert_eq!(rx1.try_recv(), Ok(1));
+    assert_eq!(rx2.try_recv(), Ok(2));
+    assert_eq!(rx1.try_recv(), Err(TryRecvError::Empty));
+    assert_eq!(rx2.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn recv_waits_for_value() {
+    let (tx, rx) = mpmc::channel(1);
+
+    let mut t = spawn(rx.recv());
+    assert_pending!(t.poll());
+
+    assert!(tx.try_send(7).is_ok());
+
+    assert!(t.is_woken());
+    assert_eq!(assert_ready!(t.poll()), Some(7));
+}
+
+#[test]
+fn send_waits_for_capacity() {
+    let (tx, rx) = mpmc::channel(1);
+
+    assert!(tx.try_send(1).is_ok());
+
+    let mut t = spawn(tx.send(2));
+    assert_pending!(t.poll());
+
+    assert_eq!(rx.try_recv(), Ok(1));
+
+    assert!(t.is_woken());
+    assert!(assert_ready!(t.poll()).is_ok());
+    drop(t);
+
+    assert_eq!(rx.try_recv(), Ok(2));
+}
+
+#[test]
+fn recv_none_after_senders_drop() {
+    let (tx, rx) = mpmc::channel(2);
+    let tx2 = tx.clone();
+
+    assert!(tx.try_send(1).is_ok());
+    drop(tx);
+
+    // A surviving clone keeps the channel open.
+    assert!(tx2.try_send(2).is_ok());
+    drop(tx2);
+
+    // Buffered values are still delivered before the channel reports closed.
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Ok(2));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Closed));
+
+    let mut t = spawn(rx.recv());
+    assert_eq!(assert_ready!(t.poll()), None);
+}
+
+#[test]
+fn sender_drop_wakes_receivers() {
+    let (tx, rx1) = mpmc::channel::<i32>(1);
+    let rx2 = rx1.clone();
+
+    let mut t1 = spawn(rx1.recv());
+    let mut t2 = spawn(rx2.recv());
+    assert_pending!(t1.poll());
+    assert_pending!(t2.poll());
+
+    drop(tx);
+
+    assert!(t1.is_woken());
+    assert!(t2.is_woken());
+    assert_eq!(assert_ready!(t1.poll()), None);
+    assert_eq!(assert_ready!(t2.poll()), None);
+}
+
+#[test]
+fn send_errors_after_receivers_drop() {
+    let (tx, rx) = mpmc::channel(1);
+    let rx2 = rx.clone();
+
+    drop(rx);
+    assert!(!tx.is_closed());
+    drop(rx2);
+    assert!(tx.is_closed());
+
+    match tx.try_send(1) {
+        Err(TrySendError::Closed(1)) => {}
+        other => panic!("expected Closed, got {:?}", other),
+    }
+
+    let mut t = spawn(tx.send(2));
+    let err = assert_ready!(t.poll()).unwrap_err();
+    assert_eq!(err.0, 2);
+}
+
+#[test]
+fn receiver_drop_wakes_sender() {
+    let (tx, rx) = mpmc::channel(1);
+
+    assert!(tx.try_send(1).is_ok());
+    let mut t = spawn(tx.send(2));
+    assert_pending!(t.poll());
+
+    drop(rx);
+
+    assert!(t.is_woken());
+    assert!(assert_ready!(t.poll()).is_err());
+}
+
+#[tokio::test]
+async fn stress_multi_producer_multi_consumer() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const PER_SENDER: usize = 250;
+
+    let (tx, rx) = mpmc::channel(8);
+    let total = Arc::new(AtomicUsize::new(0));
+
+    let mut producers = Vec::new();
+    for _ in 0..4 {
+        let tx = tx.clone();
+        producers.push(tokio::spawn(async move {
+            for i in 0..PER_SENDER {
+                tx.send(i).await.unwrap();
+            }
+        }));
+    }
+    drop(tx);
+
+    let mut consumers = Vec::new();
+    for _ in 0..4 {
+        let rx = rx.clone();
+        let total = total.clone();
+        consumers.push(tokio::spawn(async move {
+            while rx.recv().await.is_some() {
+                total.fetch_add(1, Ordering::SeqCst);
+            }
+        }));
+    }
+    drop(rx);
+
+    for producer in producers {
+        producer.await.unwrap();
+    }
+    for consumer in consumers {
+        consumer.await.unwrap();
+    }
+
+    assert_eq!(total.load(Ordering::SeqCst), 4 * PER_SENDER);
+}
@@ -145,10 +145,58 @@ async fn start_send_past_cap() {
     assert!(rx.recv().await.is_none());
 }
 
+#[tokio::test]
+async fn rendezvous_send_waits_for_receiver() {
+    let (tx, mut rx) = mpsc::channel::<i32>(0);
+
+    // With no receiver waiting, there is no capacity.
+    assert!(tx.try_send(1).is_err());
+
+    let mut send = task::spawn(tx.send(2));
+    assert_pending!(send.poll());
+
+    let mut recv = task::spawn(rx.recv());
+    assert_pending!(recv.poll());
+
+    // The parked receiver unblocks the sender; the handoff completes.
+    assert!(send.is_woken());
+    assert_ready_ok!(send.poll());
+    assert_eq!(assert_ready!(recv.poll()), Some(2));
+
+    // The channel is back to zero capacity.
+    assert!(tx.try_send(3).is_err());
+}
+
+#[tokio::test]
+async fn rendezvous_try_send_with_waiting_receiver() {
+    let (tx, mut rx) = mpsc::channel::<i32>(0);
+
+    let mut recv = task::spawn(rx.recv());
+    assert_pending!(recv.poll());
+
+    assert_ok!(tx.try_send(1));
+    assert_eq!(assert_ready!(recv.poll()), Some(1));
+}
+
+#[tokio::test]
+async fn rendezvous_close_with_parked_receiver() {
+    let (tx, mut rx) = mpsc::channel::<i32>(0);
+
+    let mut recv = task::spawn(rx.recv());
+    assert_pending!(recv.poll());
+    drop(recv);
+
+    // The receiver's grant is unclaimed; dropping the senders must still
+    // close the channel cleanly.
+    drop(tx);
+    assert_eq!(rx.recv().await, None);
+}
+
 #[test]
 #[should_panic]
-fn buffer_gteq_one() {
-    mpsc::channel::<i32>(0);
+fn rendezvous_resize_panics() {
+    let (_tx, rx) = mpsc::channel::<i32>(0);
+    rx.resize(1);
 }
 
 #[tokio::test]
@@ -522,3 +570,1052 @@ async fn bounded_resize_zero() {
     let (_, rx) = mpsc::channel::<()>(1);
     rx.resize(0);
 }
+
+#[tokio::test]
+async fn bounded_resize_drop_newest() {
+    let (tx, mut rx) = mpsc::channel(2);
+
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+
+    rx.resize_with_policy(1, mpsc::OverflowPolicy::DropNewest);
+
+    // The channel is over capacity; new values are shed instead of blocking.
+    assert_ok!(tx.send(3).await);
+    assert_ok!(tx.try_send(4));
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(rx.recv().await, Some(2));
+
+    // One message consumed; the channel is back at capacity and sends are
+    // buffered again.
+    assert_ok!(tx.send(5).await);
+    assert_eq!(rx.recv().await, Some(5));
+}
+
+#[tokio::test]
+async fn bounded_resize_overflow_callback() {
+    use std::sync::atomic::{AtomicUsize, Ordering::SeqCst};
+    use std::sync::Arc;
+
+    let (tx, mut rx) = mpsc::channel(2);
+
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+
+    let shed = Arc::new(AtomicUsize::new(0));
+    let shed2 = shed.clone();
+    rx.resize_with_policy(
+        1,
+        mpsc::OverflowPolicy::Callback(Arc::new(move |value| {
+            shed2.fetch_add(value, SeqCst);
+        })),
+    );
+
+    assert_ok!(tx.send(10).await);
+    assert_ok!(tx.try_send(20));
+    assert_eq!(shed.load(SeqCst), 30);
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(rx.recv().await, Some(2));
+}
+
+#[tokio::test]
+async fn bounded_resize_drain_policy_blocks() {
+    let (tx, rx) = mpsc::channel(2);
+
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+
+    // The default policy keeps the old behavior: senders see a full channel
+    // until the excess drains.
+    rx.resize_with_policy(1, mpsc::OverflowPolicy::Drain);
+    assert_err!(tx.try_send(3));
+}
+
+#[tokio::test]
+async fn send_recv_with_block_size_one() {
+    let (tx, mut rx) = mpsc::channel_with_block_size(1, 1);
+
+    // Cross many block boundaries.
+    for i in 0..100 {
+        assert_ok!(tx.send(i).await);
+        assert_eq!(rx.recv().await, Some(i));
+    }
+
+    drop(tx);
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn send_recv_many_with_small_blocks() {
+    let (tx, mut rx) = mpsc::channel_with_block_size(16, 2);
+
+    tokio::spawn(async move {
+        for i in 0..1000 {
+            assert_ok!(tx.send(i).await);
+        }
+    });
+
+    for i in 0..1000 {
+        assert_eq!(rx.recv().await, Some(i));
+    }
+
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+#[should_panic]
+async fn block_size_not_power_of_two() {
+    let (_, _) = mpsc::channel_with_block_size::<()>(1, 3);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn block_size_zero() {
+    let (_, _) = mpsc::channel_with_block_size::<()>(1, 0);
+}
+
+#[tokio::test]
+async fn block_pool_recycles_blocks() {
+    let pool = mpsc::BlockPool::new();
+    assert_eq!(pool.cached_blocks(), 0);
+
+    {
+        let (tx, mut rx) = mpsc::channel_with_pool(1, 1, &pool);
+        assert_ok!(tx.send(1).await);
+        assert_eq!(rx.recv().await, Some(1));
+    }
+
+    // The channel's blocks were returned to the pool on drop.
+    let cached = pool.cached_blocks();
+    assert!(cached > 0);
+
+    // A new channel with the same layout draws from the pool.
+    let (tx, mut rx) = mpsc::channel_with_pool(1, 1, &pool);
+    assert!(pool.cached_blocks() < cached);
+
+    assert_ok!(tx.send(2).await);
+    assert_eq!(rx.recv().await, Some(2));
+}
+
+#[tokio::test]
+async fn block_pool_shared_across_types() {
+    let pool = mpsc::BlockPool::new();
+
+    {
+        let (tx, mut rx) = mpsc::channel_with_pool(4, 4, &pool);
+        for i in 0..100u64 {
+            assert_ok!(tx.send(i).await);
+            assert_eq!(rx.recv().await, Some(i));
+        }
+    }
+
+    {
+        let (tx, mut rx) = mpsc::channel_with_pool::<String>(4, 4, &pool);
+        assert_ok!(tx.send("hi".to_string()).await);
+        assert_eq!(rx.recv().await.as_deref(), Some("hi"));
+    }
+
+    assert!(pool.cached_blocks() > 0);
+}
+
+#[tokio::test]
+async fn small_capacity_send_recv() {
+    let (tx, mut rx) = mpsc::channel(2);
+
+    tokio::spawn(async move {
+        // Wrap the inline ring many times over.
+        for i in 0..100 {
+            assert_ok!(tx.send(i).await);
+        }
+    });
+
+    for i in 0..100 {
+        assert_eq!(rx.recv().await, Some(i));
+    }
+
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn small_capacity_enforces_backpressure() {
+    let (tx, mut rx) = mpsc::channel(1);
+
+    assert_ok!(tx.try_send(1));
+    assert_err!(tx.try_send(2));
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert_ok!(tx.try_send(2));
+}
+
+#[tokio::test]
+async fn small_capacity_close_drains_buffered() {
+    let (tx, mut rx) = mpsc::channel(4);
+
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+
+    rx.close();
+    assert_err!(tx.try_send(3));
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(rx.recv().await, Some(2));
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn small_capacity_drops_buffered_values() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Debug)]
+    struct Count(Arc<AtomicUsize>);
+
+    impl Drop for Count {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (tx, rx) = mpsc::channel(4);
+
+    assert_ok!(tx.send(Count(drops.clone())).await);
+    assert_ok!(tx.send(Count(drops.clone())).await);
+
+    drop(rx);
+    drop(tx);
+
+    // Both buffered values were dropped exactly once.
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+#[should_panic]
+async fn small_capacity_resize_past_ring() {
+    let (_, rx) = mpsc::channel::<()>(1);
+    rx.resize(64);
+}
+
+#[tokio::test]
+async fn channel_stats_track_deficit() {
+    let (tx, mut rx) = mpsc::channel(3);
+
+    let stats = tx.stats();
+    assert_eq!(stats.capacity, 3);
+    assert_eq!(stats.available, 3);
+    assert_eq!(stats.permit_deficit, 0);
+
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+    assert_ok!(tx.send(3).await);
+
+    // Shrink below the three in-flight messages: two of them are excess.
+    rx.resize(1);
+
+    let stats = rx.stats();
+    assert_eq!(stats.capacity, 1);
+    assert_eq!(stats.available, 0);
+    assert_eq!(stats.permit_deficit, 2);
+
+    // Each received message pays down the deficit before capacity frees up.
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(tx.stats().permit_deficit, 1);
+
+    assert_eq!(rx.recv().await, Some(2));
+    assert_eq!(tx.stats().permit_deficit, 0);
+    assert_eq!(tx.stats().available, 0);
+
+    assert_eq!(rx.recv().await, Some(3));
+    assert_eq!(tx.stats().available, 1);
+}
+
+#[tokio::test]
+async fn set_capacity_updates_max_capacity() {
+    let (tx, mut rx) = mpsc::channel::<i32>(2);
+    assert_eq!(tx.max_capacity(), 2);
+    assert_eq!(tx.capacity(), 2);
+
+    // Growing wakes blocked senders once capacity becomes available.
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+    assert!(tx.try_send(3).is_err());
+
+    rx.set_capacity(4);
+    assert_eq!(tx.max_capacity(), 4);
+    assert_eq!(tx.capacity(), 2);
+    assert_ok!(tx.send(3).await);
+
+    // Shrinking leaves max_capacity at the new limit while the in-flight
+    // excess drains.
+    rx.set_capacity(1);
+    assert_eq!(tx.max_capacity(), 1);
+    assert_eq!(tx.capacity(), 0);
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(rx.recv().await, Some(2));
+    assert_eq!(rx.recv().await, Some(3));
+    assert_eq!(tx.capacity(), 1);
+}
+
+#[tokio::test]
+async fn recv_many_drains_in_batches() {
+    let (tx, mut rx) = mpsc::channel(16);
+    let mut buffer = Vec::new();
+
+    for i in 0..5 {
+        assert_ok!(tx.send(i).await);
+    }
+
+    assert_eq!(rx.recv_many(&mut buffer, 3).await, 3);
+    assert_eq!(buffer, vec![0, 1, 2]);
+
+    // The batch's permits come back all at once.
+    assert_eq!(tx.capacity(), 14);
+
+    assert_eq!(rx.recv_many(&mut buffer, 10).await, 2);
+    assert_eq!(buffer, vec![0, 1, 2, 3, 4]);
+    assert_eq!(tx.capacity(), 16);
+}
+
+#[tokio::test]
+async fn recv_many_zero_limit() {
+    let (tx, mut rx) = mpsc::channel(4);
+    let mut buffer = Vec::new();
+
+    assert_ok!(tx.send(1).await);
+    assert_eq!(rx.recv_many(&mut buffer, 0).await, 0);
+    assert!(buffer.is_empty());
+}
+
+#[tokio::test]
+async fn recv_many_on_closed_channel() {
+    let (tx, mut rx) = mpsc::channel(4);
+    let mut buffer = Vec::new();
+
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+    drop(tx);
+
+    // Buffered values are still drained before the closed signal.
+    assert_eq!(rx.recv_many(&mut buffer, 8).await, 2);
+    assert_eq!(buffer, vec![1, 2]);
+
+    assert_eq!(rx.recv_many(&mut buffer, 8).await, 0);
+}
+
+#[tokio::test]
+async fn recv_many_waits_for_first_value() {
+    let (tx, mut rx) = mpsc::channel(4);
+
+    let handle = tokio::spawn(async move {
+        let mut buffer = Vec::new();
+        let n = rx.recv_many(&mut buffer, 4).await;
+        (n, buffer)
+    });
+
+    tokio::task::yield_now().await;
+    assert_ok!(tx.send("hi").await);
+    drop(tx);
+
+    let (n, buffer) = handle.await.unwrap();
+    assert_eq!(n, 1);
+    assert_eq!(buffer, vec!["hi"]);
+}
+
+#[tokio::test]
+async fn recv_many_unbounded() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut buffer = Vec::new();
+
+    assert_ok!(tx.send(7));
+    assert_ok!(tx.send(8));
+
+    assert_eq!(rx.recv_many(&mut buffer, 10).await, 2);
+    assert_eq!(buffer, vec![7, 8]);
+}
+
+#[tokio::test]
+async fn priority_channel_orders_by_ord() {
+    let (tx, mut rx) = mpsc::priority_channel(8);
+
+    assert_ok!(tx.send(2).await);
+    assert_ok!(tx.send(5).await);
+    assert_ok!(tx.send(1).await);
+    drop(tx);
+
+    assert_eq!(rx.recv().await, Some(5));
+    assert_eq!(rx.recv().await, Some(2));
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(rx.recv().await, None);
+}
+
+#[tokio::test]
+async fn priority_channel_backpressure() {
+    let (tx, mut rx) = mpsc::priority_channel(2);
+    assert_eq!(tx.capacity(), 2);
+
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+    assert!(matches!(
+        tx.try_send(3),
+        Err(mpsc::error::TrySendError::Full(3))
+    ));
+
+    // Receiving frees capacity shared across all priorities.
+    assert_eq!(rx.recv().await, Some(2));
+    assert_ok!(tx.try_send(3));
+}
+
+#[tokio::test]
+async fn priority_channel_send_blocks_until_recv() {
+    let (tx, mut rx) = mpsc::priority_channel(1);
+    assert_ok!(tx.send(10).await);
+
+    let sender = tokio::spawn(async move {
+        assert_ok!(tx.send(20).await);
+    });
+
+    tokio::task::yield_now().await;
+    assert_eq!(rx.recv().await, Some(10));
+    assert_ok!(sender.await);
+    assert_eq!(rx.recv().await, Some(20));
+}
+
+#[tokio::test]
+async fn priority_channel_rx_close_drains_buffered() {
+    let (tx, mut rx) = mpsc::priority_channel(4);
+
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+
+    rx.close();
+    assert!(tx.is_closed());
+    assert!(matches!(
+        tx.try_send(3),
+        Err(mpsc::error::TrySendError::Closed(3))
+    ));
+
+    assert_eq!(rx.recv().await, Some(2));
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(rx.recv().await, None);
+}
+
+#[tokio::test]
+async fn priority_channel_rx_drop_errors_senders() {
+    let (tx, rx) = mpsc::priority_channel(1);
+    drop(rx);
+
+    assert!(tx.send(1).await.is_err());
+}
+
+#[tokio::test(start_paused = true)]
+async fn send_timeout_returns_value_on_timeout() {
+    use tokio::sync::mpsc::error::SendTimeoutError;
+    use tokio::time::Duration;
+
+    let (tx, mut rx) = mpsc::channel(1);
+    assert_ok!(tx.send(1).await);
+
+    assert!(matches!(
+        tx.send_timeout(2, Duration::from_millis(10)).await,
+        Err(SendTimeoutError::Timeout(2))
+    ));
+
+    // The timed-out waiter is unlinked; the queue is not wedged.
+    assert_eq!(rx.recv().await, Some(1));
+    assert_ok!(tx.send_timeout(3, Duration::from_millis(10)).await);
+    assert_eq!(rx.recv().await, Some(3));
+}
+
+#[tokio::test(start_paused = true)]
+async fn send_timeout_closed_returns_value() {
+    use tokio::sync::mpsc::error::SendTimeoutError;
+    use tokio::time::Duration;
+
+    let (tx, rx) = mpsc::channel(1);
+    drop(rx);
+
+    assert!(matches!(
+        tx.send_timeout(7, Duration::from_secs(1)).await,
+        Err(SendTimeoutError::Closed(7))
+    ));
+}
+
+#[tokio::test]
+async fn peek_does_not_consume() {
+    let (tx, mut rx) = mpsc::channel(16);
+
+    assert_ok!(tx.send(1).await);
+    assert_ok!(tx.send(2).await);
+
+    assert_eq!(rx.peek().await, Some(&1));
+    assert_eq!(rx.peek().await, Some(&1));
+    // Peeking releases no permits.
+    assert_eq!(tx.capacity(), 14);
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(rx.peek().await, Some(&2));
+    assert_eq!(rx.recv().await, Some(2));
+
+    drop(tx);
+    assert_eq!(rx.peek().await, None);
+}
+
+#[tokio::test]
+async fn peek_waits_for_value() {
+    let (tx, mut rx) = mpsc::channel(4);
+
+    let handle = tokio::spawn(async move {
+        let head = rx.peek().await.copied();
+        (head, rx)
+    });
+
+    tokio::task::yield_now().await;
+    assert_ok!(tx.send(42).await);
+
+    let (head, mut rx) = handle.await.unwrap();
+    assert_eq!(head, Some(42));
+    assert_eq!(rx.recv().await, Some(42));
+}
+
+#[tokio::test]
+async fn peek_small_capacity_channel() {
+    // Capacity <= 8 uses the inline ring rather than the block list.
+    let (tx, mut rx) = mpsc::channel(2);
+
+    assert_ok!(tx.send("a").await);
+    assert_eq!(rx.peek().await, Some(&"a"));
+    assert_eq!(rx.recv().await, Some("a"));
+
+    drop(tx);
+    assert_eq!(rx.peek().await, None);
+}
+
+#[tokio::test]
+async fn unbounded_len_tracks_queue() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    assert_eq!(tx.len(), 0);
+
+    assert_ok!(tx.send(1));
+    assert_ok!(tx.send(2));
+    assert_eq!(tx.len(), 2);
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(tx.len(), 1);
+}
+
+#[tokio::test]
+async fn weak_backpressure_resolves_below_watermark() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    // No mark set: resolves immediately.
+    tx.weak_backpressure().await;
+
+    tx.set_high_watermark(2);
+    assert_ok!(tx.send(1));
+    tx.weak_backpressure().await;
+
+    assert_ok!(tx.send(2));
+
+    let producer = tx.clone();
+    let waiter = tokio::spawn(async move {
+        producer.weak_backpressure().await;
+        producer.len()
+    });
+
+    tokio::task::yield_now().await;
+    assert_eq!(rx.recv().await, Some(1));
+
+    assert!(waiter.await.unwrap() < 2);
+}
+
+#[tokio::test]
+async fn weak_backpressure_resolves_on_rx_drop() {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tx.set_high_watermark(1);
+    assert_ok!(tx.send(1));
+
+    let producer = tx.clone();
+    let waiter = tokio::spawn(async move {
+        producer.weak_backpressure().await;
+    });
+
+    tokio::task::yield_now().await;
+    drop(rx);
+    assert_ok!(waiter.await);
+}
+
+#[tokio::test]
+async fn tap_observes_received_values() {
+    let (tx, mut rx) = mpsc::channel(4);
+    let mut tap = rx.tap();
+
+    tx.send(1).await.unwrap();
+    tx.send(2).await.unwrap();
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert_eq!(rx.recv().await, Some(2));
+
+    assert_eq!(tap.recv().await, Some(1));
+    assert_eq!(tap.recv().await, Some(2));
+
+    drop(rx);
+    assert_eq!(tap.recv().await, None);
+}
+
+#[tokio::test]
+async fn tap_observes_recv_many_batches() {
+    let (tx, mut rx) = mpsc::channel(8);
+    let mut tap = rx.tap();
+    let mut buffer = Vec::new();
+
+    for i in 0..4 {
+        tx.send(i).await.unwrap();
+    }
+
+    assert_eq!(rx.recv_many(&mut buffer, 4).await, 4);
+
+    for i in 0..4 {
+        assert_eq!(tap.recv().await, Some(i));
+    }
+}
+
+#[tokio::test]
+async fn tap_does_not_hold_channel_capacity() {
+    let (tx, mut rx) = mpsc::channel(1);
+    let tap = rx.tap();
+
+    // The tap never consumes; the main receiver alone frees capacity.
+    for i in 0..3 {
+        tx.send(i).await.unwrap();
+        assert_eq!(rx.recv().await, Some(i));
+    }
+
+    drop(tap);
+
+    // A dropped tap is detached and does not affect the channel.
+    tx.send(3).await.unwrap();
+    assert_eq!(rx.recv().await, Some(3));
+}
+
+#[tokio::test]
+async fn reserve_many_atomic_batch() {
+    let (tx, mut rx) = mpsc::channel(4);
+
+    let permits = tx.reserve_many(3).await.unwrap();
+    assert_eq!(permits.len(), 3);
+    assert_eq!(tx.capacity(), 1);
+
+    for (i, permit) in permits.enumerate() {
+        permit.send(i);
+    }
+
+    for i in 0..3 {
+        assert_eq!(rx.recv().await, Some(i));
+    }
+    assert_eq!(tx.capacity(), 4);
+}
+
+#[tokio::test]
+async fn reserve_many_unused_permits_released_on_drop() {
+    let (tx, _rx) = mpsc::channel::<i32>(4);
+
+    let mut permits = tx.reserve_many(4).await.unwrap();
+    let taken = permits.next().unwrap();
+    drop(permits);
+
+    // Only the permit taken from the iterator is still held.
+    assert_eq!(tx.capacity(), 3);
+    drop(taken);
+    assert_eq!(tx.capacity(), 4);
+}
+
+#[tokio::test]
+async fn try_reserve_many_all_or_nothing() {
+    let (tx, mut rx) = mpsc::channel::<i32>(4);
+
+    tx.send(1).await.unwrap();
+    tx.send(2).await.unwrap();
+
+    // Two slots remain; a batch of three must not partially reserve.
+    assert!(tx.try_reserve_many(3).is_err());
+    assert_eq!(tx.capacity(), 2);
+
+    let permits = tx.try_reserve_many(2).unwrap();
+    drop(permits);
+
+    assert_eq!(rx.recv().await, Some(1));
+}
+
+#[tokio::test]
+async fn reserve_many_closed() {
+    let (tx, rx) = mpsc::channel::<i32>(4);
+    drop(rx);
+
+    assert!(tx.reserve_many(2).await.is_err());
+}
+
+#[tokio::test]
+async fn close_with_reason_visible_to_senders() {
+    let (tx, mut rx) = mpsc::channel::<i32>(8);
+
+    assert_eq!(tx.close_reason::<String>(), None);
+
+    rx.close_with("rx failed".to_string());
+
+    assert!(tx.send(1).await.is_err());
+    assert_eq!(tx.close_reason::<String>().as_deref(), Some("rx failed"));
+
+    // The wrong type does not match.
+    assert_eq!(tx.close_reason::<u32>(), None);
+}
+
+#[tokio::test]
+async fn close_with_keeps_first_reason() {
+    let (tx, mut rx) = mpsc::channel::<i32>(8);
+
+    rx.close_with(1u32);
+    rx.close_with(2u32);
+
+    assert_eq!(tx.close_reason::<u32>(), Some(1));
+}
+
+#[tokio::test]
+async fn close_without_reason_reports_none() {
+    let (tx, mut rx) = mpsc::channel::<i32>(8);
+
+    rx.close();
+
+    assert!(tx.send(1).await.is_err());
+    assert_eq!(tx.close_reason::<String>(), None);
+}
+
+#[tokio::test]
+async fn unbounded_close_with_reason() {
+    let (tx, mut rx) = mpsc::unbounded_channel::<i32>();
+
+    rx.close_with("done".to_string());
+
+    assert!(tx.send(1).is_err());
+    assert_eq!(tx.close_reason::<String>().as_deref(), Some("done"));
+}
+
+#[test]
+fn blocking_recv_many_batches() {
+    let (tx, mut rx) = mpsc::channel::<u8>(8);
+
+    Runtime::new().unwrap().block_on(async {
+        for i in 1..=3 {
+            tx.send(i).await.unwrap();
+        }
+    });
+    drop(tx);
+
+    let mut buffer = Vec::new();
+    assert_eq!(rx.blocking_recv_many(&mut buffer, 4), 3);
+    assert_eq!(buffer, vec![1, 2, 3]);
+
+    // Closed and drained.
+    assert_eq!(rx.blocking_recv_many(&mut buffer, 4), 0);
+}
+
+#[test]
+fn blocking_send_all_waits_for_capacity() {
+    let (tx, mut rx) = mpsc::channel::<u8>(1);
+
+    let sync_code = thread::spawn(move || {
+        tx.blocking_send_all(1..=4).unwrap();
+    });
+
+    Runtime::new().unwrap().block_on(async move {
+        for i in 1..=4 {
+            assert_eq!(rx.recv().await, Some(i));
+        }
+        assert_eq!(rx.recv().await, None);
+    });
+    sync_code.join().unwrap()
+}
+
+#[test]
+fn blocking_send_all_closed_returns_failed_value() {
+    let (tx, rx) = mpsc::channel::<u8>(1);
+    drop(rx);
+
+    assert!(matches!(
+        tx.blocking_send_all(1..=3),
+        Err(mpsc::error::SendError(1))
+    ));
+}
+
+#[tokio::test]
+async fn poll_reserve_ready_path() {
+    let (mut tx, mut rx) = mpsc::channel(1);
+
+    let mut reserve = task::spawn(());
+    assert_ready_ok!(reserve.enter(|cx, _| tx.poll_reserve(cx)));
+
+    // The reservation holds the only slot.
+    assert!(tx.try_send(1).is_err());
+
+    // Repolling while the reservation is held is a no-op.
+    assert_ready_ok!(reserve.enter(|cx, _| tx.poll_reserve(cx)));
+
+    tx.send_reserved(2).unwrap();
+    assert_eq!(rx.recv().await, Some(2));
+}
+
+#[tokio::test]
+async fn poll_reserve_wakes_when_capacity_frees() {
+    let (tx, mut rx) = mpsc::channel(1);
+    let mut tx2 = tx.clone();
+
+    tx.send(1).await.unwrap();
+
+    let mut reserve = task::spawn(());
+    assert_pending!(reserve.enter(|cx, _| tx2.poll_reserve(cx)));
+
+    assert_eq!(rx.recv().await, Some(1));
+    assert!(reserve.is_woken());
+
+    assert_ready_ok!(reserve.enter(|cx, _| tx2.poll_reserve(cx)));
+    tx2.send_reserved(2).unwrap();
+    assert_eq!(rx.recv().await, Some(2));
+}
+
+#[tokio::test]
+async fn poll_reserve_closed() {
+    let (mut tx, rx) = mpsc::channel::<i32>(1);
+    drop(rx);
+
+    let mut reserve = task::spawn(());
+    assert_ready_err!(reserve.enter(|cx, _| tx.poll_reserve(cx)));
+}
+
+#[tokio::test]
+async fn abort_reserve_releases_capacity() {
+    let (mut tx, _rx) = mpsc::channel::<i32>(1);
+
+    let mut reserve = task::spawn(());
+    assert_ready_ok!(reserve.enter(|cx, _| tx.poll_reserve(cx)));
+    assert_eq!(tx.capacity(), 0);
+
+    assert!(tx.abort_reserve());
+    assert!(!tx.abort_reserve());
+    assert_eq!(tx.capacity(), 1);
+
+    // Without a reservation, send_reserved hands the value back.
+    assert!(tx.send_reserved(1).is_err());
+}
+
+#[tokio::test]
+async fn drain_returns_buffered_and_closes() {
+    let (tx, mut rx) = mpsc::channel(8);
+
+    tx.send(1).await.unwrap();
+    tx.send(2).await.unwrap();
+    tx.send(3).await.unwrap();
+
+    assert_eq!(rx.drain(), vec![1, 2, 3]);
+
+    assert!(tx.send(4).await.is_err());
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn drain_credits_permits() {
+    let (tx, mut rx) = mpsc::channel(2);
+
+    tx.send(1).await.unwrap();
+    tx.send(2).await.unwrap();
+    assert_eq!(tx.capacity(), 0);
+
+    assert_eq!(rx.drain(), vec![1, 2]);
+    assert_eq!(tx.capacity(), 2);
+}
+
+#[tokio::test]
+async fn drain_with_outstanding_permit() {
+    let (tx, mut rx) = mpsc::channel(4);
+
+    tx.send(1).await.unwrap();
+    let permit = tx.reserve().await.unwrap();
+
+    assert_eq!(rx.drain(), vec![1]);
+
+    // The reserved permit can still push its value; it is collected by
+    // `recv`, not by `drain`.
+    permit.send(2);
+    assert_eq!(rx.recv().await, Some(2));
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn drain_empty() {
+    let (tx, mut rx) = mpsc::channel::<i32>(4);
+
+    assert!(rx.drain().is_empty());
+    assert!(tx.send(1).await.is_err());
+}
+
+#[tokio::test]
+async fn drain_unbounded() {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+
+    assert_eq!(rx.drain(), vec![1, 2]);
+    assert!(tx.send(3).is_err());
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn weak_sender_upgrade() {
+    let (tx, mut rx) = mpsc::channel(8);
+    let weak_tx = tx.downgrade();
+
+    let strong_tx = weak_tx.upgrade().unwrap();
+    strong_tx.send(1).await.unwrap();
+    assert_eq!(rx.recv().await, Some(1));
+
+    drop(tx);
+    drop(strong_tx);
+
+    assert!(weak_tx.upgrade().is_none());
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn weak_sender_counts() {
+    let (tx, _rx) = mpsc::channel::<i32>(8);
+
+    assert_eq!(tx.strong_count(), 1);
+    assert_eq!(tx.weak_count(), 0);
+
+    let tx2 = tx.clone();
+    let weak_tx = tx.downgrade();
+    let weak_tx2 = weak_tx.clone();
+
+    assert_eq!(tx.strong_count(), 2);
+    assert_eq!(weak_tx.strong_count(), 2);
+    assert_eq!(weak_tx.weak_count(), 2);
+
+    drop(tx2);
+    drop(weak_tx2);
+
+    assert_eq!(weak_tx.strong_count(), 1);
+    assert_eq!(weak_tx.weak_count(), 1);
+
+    drop(tx);
+    assert_eq!(weak_tx.strong_count(), 0);
+}
+
+#[tokio::test]
+async fn weak_sender_closed() {
+    let (tx, _rx) = mpsc::channel::<i32>(8);
+    let weak_tx = tx.downgrade();
+
+    let mut closed = task::spawn(weak_tx.closed());
+    assert_pending!(closed.poll());
+
+    let tx2 = tx.clone();
+    drop(tx);
+    assert_pending!(closed.poll());
+
+    drop(tx2);
+    assert!(closed.is_woken());
+    assert_ready!(closed.poll());
+}
+
+#[tokio::test]
+async fn weak_sender_closed_immediate() {
+    let (tx, _rx) = mpsc::channel::<i32>(8);
+    let weak_tx = tx.downgrade();
+    drop(tx);
+
+    weak_tx.closed().await;
+    assert!(weak_tx.upgrade().is_none());
+}
+
+#[tokio::test]
+async fn weak_sender_does_not_hold_channel_open() {
+    let (tx, mut rx) = mpsc::channel::<i32>(8);
+    let _weak_tx = tx.downgrade();
+
+    drop(tx);
+    assert!(rx.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn cost_channel_budget() {
+    let (tx, mut rx) = mpsc::channel_with_cost(100, |msg: &Vec<u8>| msg.len());
+
+    tx.send(vec![0; 60]).await.unwrap();
+    assert_eq!(tx.capacity(), 40);
+
+    // The next message does not fit in the remaining budget.
+    assert!(matches!(
+        tx.try_send(vec![0; 50]),
+        Err(TrySendError::Full(_))
+    ));
+
+    // Receiving the first message frees its cost.
+    assert_eq!(rx.recv().await.unwrap().len(), 60);
+    assert_eq!(tx.capacity(), 100);
+
+    tx.send(vec![0; 50]).await.unwrap();
+    assert_eq!(tx.capacity(), 50);
+}
+
+#[tokio::test]
+async fn cost_channel_send_waits_for_budget() {
+    let (tx, mut rx) = mpsc::channel_with_cost(10, |msg: &Vec<u8>| msg.len());
+
+    tx.send(vec![0; 8]).await.unwrap();
+
+    let mut send = task::spawn(tx.send(vec![0; 4]));
+    assert_pending!(send.poll());
+
+    assert_eq!(rx.recv().await.unwrap().len(), 8);
+    assert!(send.is_woken());
+    assert_ready_ok!(send.poll());
+}
+
+#[tokio::test]
+async fn cost_channel_clamps_cost() {
+    let (tx, mut rx) = mpsc::channel_with_cost(10, |msg: &Vec<u8>| msg.len());
+
+    // A zero-cost message still occupies one unit.
+    tx.send(Vec::new()).await.unwrap();
+    assert_eq!(tx.capacity(), 9);
+    rx.recv().await.unwrap();
+    assert_eq!(tx.capacity(), 10);
+
+    // An oversized message takes the whole budget rather than waiting
+    // forever.
+    tx.send(vec![0; 100]).await.unwrap();
+    assert_eq!(tx.capacity(), 0);
+    assert_eq!(rx.recv().await.unwrap().len(), 100);
+    assert_eq!(tx.capacity(), 10);
+}
+
+#[tokio::test]
+async fn cost_channel_recv_many() {
+    let (tx, mut rx) = mpsc::channel_with_cost(100, |msg: &Vec<u8>| msg.len());
+
+    tx.send(vec![0; 30]).await.unwrap();
+    tx.send(vec![0; 20]).await.unwrap();
+
+    let mut buffer = Vec::new();
+    assert_eq!(rx.recv_many(&mut buffer, 10).await, 2);
+    assert_eq!(tx.capacity(), 100);
+}
+
+#[tokio::test]
+#[should_panic = "cannot reserve capacity on a cost-based channel"]
+async fn cost_channel_reserve_panics() {
+    let (tx, _rx) = mpsc::channel_with_cost(10, |msg: &Vec<u8>| msg.len());
+    let _ = tx.reserve().await;
+}
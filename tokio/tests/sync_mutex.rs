@@ -162,3 +162,31 @@ async fn mutex_debug() {
     let _guard = m.lock().await;
     assert_eq!(format!("{:?}", m), r#"Mutex { data: <locked> }"#)
 }
+
+#[tokio::test(start_paused = true)]
+async fn lock_timeout_uncontended() {
+    use tokio::time::Duration;
+
+    let m = Mutex::new(1);
+
+    let mut n = m.lock_timeout(Duration::from_millis(10)).await.unwrap();
+    *n = 2;
+    drop(n);
+
+    assert_eq!(*m.lock().await, 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn lock_timeout_expires() {
+    use tokio::time::Duration;
+
+    let m = Mutex::new(0);
+
+    let g = m.lock().await;
+    assert!(m.lock_timeout(Duration::from_millis(10)).await.is_err());
+
+    // The timed-out waiter left no trace: releasing the lock makes it
+    // available to the next caller.
+    drop(g);
+    assert!(m.try_lock().is_ok());
+}
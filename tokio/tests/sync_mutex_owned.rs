@@ -1,7 +1,7 @@
 #![warn(rust_2018_idioms)]
 #![cfg(feature = "full")]
 
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, OwnedMutexGuard};
 use tokio::time::{interval, timeout};
 use tokio_test::task::spawn;
 use tokio_test::{assert_pending, assert_ready};
@@ -114,6 +114,32 @@ fn try_lock_owned() {
     assert_eq!(g3.is_ok(), true);
 }
 
+#[tokio::test]
+async fn mapped_guard_releases_lock() {
+    let m = Arc::new(Mutex::new((1u32, 2u32)));
+    {
+        let mut mapped = OwnedMutexGuard::map(m.clone().lock_owned().await, |t| &mut t.1);
+        *mapped = 20;
+
+        // The mapped guard still holds the lock.
+        assert!(m.clone().try_lock_owned().is_err());
+    }
+    assert_eq!(*m.lock().await, (1, 20));
+}
+
+#[tokio::test]
+async fn try_map_returns_original_guard() {
+    let m = Arc::new(Mutex::new(1u32));
+
+    let guard = m.clone().lock_owned().await;
+    let guard = OwnedMutexGuard::try_map(guard, |_| Option::<&mut u32>::None).unwrap_err();
+
+    // The original guard came back and the lock is still held.
+    assert!(m.clone().try_lock_owned().is_err());
+    drop(guard);
+    assert!(m.try_lock_owned().is_ok());
+}
+
 #[tokio::test]
 async fn debug_format() {
     let s = "debug";
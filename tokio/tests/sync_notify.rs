@@ -151,3 +151,179 @@ fn notify_one_after_dropped_all() {
 
     assert_ready!(notified2.poll());
 }
+
+#[test]
+fn re_arm_after_complete() {
+    let notify = Notify::new();
+    let mut notified = spawn(notify.notified());
+
+    assert_pending!(notified.poll());
+
+    notify.notify_one();
+    assert!(notified.is_woken());
+    assert_ready!(notified.poll());
+
+    // Reuse the same waiter for the next notification.
+    notified.enter(|_, me| me.re_arm());
+    assert_pending!(notified.poll());
+
+    notify.notify_one();
+    assert!(notified.is_woken());
+    assert_ready!(notified.poll());
+}
+
+#[test]
+fn re_arm_while_waiting_passes_permit() {
+    let notify = Notify::new();
+    let mut notified1 = spawn(notify.notified());
+    let mut notified2 = spawn(notify.notified());
+
+    assert_pending!(notified1.poll());
+    assert_pending!(notified2.poll());
+
+    notify.notify_one();
+    assert!(notified1.is_woken());
+
+    // The permit was assigned to `notified1` but not yet consumed. Re-arming
+    // hands it to the next waiter, just like dropping the future would.
+    notified1.enter(|_, me| me.re_arm());
+
+    assert!(notified2.is_woken());
+    assert_ready!(notified2.poll());
+    assert_pending!(notified1.poll());
+}
+
+#[test]
+fn re_arm_waits_for_next_notify_waiters() {
+    let notify = Notify::new();
+    let mut notified = spawn(notify.notified());
+
+    assert_pending!(notified.poll());
+
+    notify.notify_waiters();
+    assert_ready!(notified.poll());
+
+    // The `notify_waiters` call consumed above must not satisfy the re-armed
+    // future.
+    notified.enter(|_, me| me.re_arm());
+    assert_pending!(notified.poll());
+
+    notify.notify_waiters();
+    assert!(notified.is_woken());
+    assert_ready!(notified.poll());
+}
+
+#[test]
+fn notify_n_wakes_up_to_n() {
+    let notify = Notify::new();
+    let mut notified1 = spawn(async { notify.notified().await });
+    let mut notified2 = spawn(async { notify.notified().await });
+    let mut notified3 = spawn(async { notify.notified().await });
+
+    assert_pending!(notified1.poll());
+    assert_pending!(notified2.poll());
+    assert_pending!(notified3.poll());
+
+    notify.notify_n(2);
+
+    assert!(notified1.is_woken());
+    assert!(notified2.is_woken());
+    assert!(!notified3.is_woken());
+
+    assert_ready!(notified1.poll());
+    assert_ready!(notified2.poll());
+    assert_pending!(notified3.poll());
+}
+
+#[test]
+fn notify_n_stores_single_permit() {
+    let notify = Notify::new();
+
+    // No waiters: only a single permit is stored, as with `notify_one`.
+    notify.notify_n(3);
+
+    let mut notified1 = spawn(async { notify.notified().await });
+    let mut notified2 = spawn(async { notify.notified().await });
+
+    assert_ready!(notified1.poll());
+    assert_pending!(notified2.poll());
+}
+
+#[test]
+fn notify_n_partial_waiters_stores_permit() {
+    let notify = Notify::new();
+    let mut notified1 = spawn(async { notify.notified().await });
+
+    assert_pending!(notified1.poll());
+
+    notify.notify_n(2);
+
+    assert!(notified1.is_woken());
+    assert_ready!(notified1.poll());
+
+    // One notification was left over; it was stored as a permit.
+    let mut notified2 = spawn(async { notify.notified().await });
+    let mut notified3 = spawn(async { notify.notified().await });
+    assert_ready!(notified2.poll());
+    assert_pending!(notified3.poll());
+}
+
+#[test]
+fn notify_n_zero_is_noop() {
+    let notify = Notify::new();
+    let mut notified = spawn(async { notify.notified().await });
+
+    assert_pending!(notified.poll());
+
+    notify.notify_n(0);
+
+    assert!(!notified.is_woken());
+    assert_pending!(notified.poll());
+}
+
+#[test]
+fn notified_owned_is_static() {
+    fn assert_static<T: 'static>(_: &T) {}
+
+    let notify = std::sync::Arc::new(Notify::new());
+    let notified = notify.clone().notified_owned();
+    assert_static(&notified);
+
+    let mut notified = spawn(notified);
+    assert_pending!(notified.poll());
+
+    notify.notify_one();
+    assert!(notified.is_woken());
+    assert_ready!(notified.poll());
+}
+
+#[test]
+fn notified_owned_drop_passes_permit() {
+    let notify = std::sync::Arc::new(Notify::new());
+
+    let mut notified1 = spawn(notify.clone().notified_owned());
+    let mut notified2 = spawn(notify.clone().notified_owned());
+
+    assert_pending!(notified1.poll());
+    assert_pending!(notified2.poll());
+
+    notify.notify_one();
+
+    // Dropping the first waiter hands its permit to the second.
+    drop(notified1);
+    assert!(notified2.is_woken());
+    assert_ready!(notified2.poll());
+}
+
+#[test]
+fn notified_owned_keeps_notify_alive() {
+    let notify = std::sync::Arc::new(Notify::new());
+    notify.notify_one();
+
+    let notified = notify.clone().notified_owned();
+    drop(notify);
+
+    // The future holds the only remaining handle.
+    let mut notified = spawn(notified);
+    assert_ready!(notified.poll());
+}
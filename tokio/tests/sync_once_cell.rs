@@ -266,3 +266,75 @@ fn drop_into_inner_new_with() {
     let count = NUM_DROPS.load(Ordering::Acquire);
     assert!(count == 1);
 }
+
+#[tokio::test(start_paused = true)]
+async fn get_or_init_timeout_uncontended() {
+    let cell: OnceCell<u32> = OnceCell::new();
+
+    let value = cell
+        .get_or_init_timeout(Duration::from_millis(10), func1)
+        .await
+        .unwrap();
+    assert_eq!(*value, 5);
+}
+
+#[tokio::test(start_paused = true)]
+async fn get_or_init_timeout_expires_while_initializing() {
+    static ONCE: OnceCell<u32> = OnceCell::const_new();
+
+    // Occupy the init slot with a slow initializer.
+    let slow = tokio::spawn(async {
+        ONCE.get_or_init(|| async {
+            time::sleep(Duration::from_millis(50)).await;
+            5
+        })
+        .await
+    });
+
+    time::advance(Duration::from_millis(1)).await;
+
+    // The waiter's deadline elapses before the slow initializer finishes.
+    assert!(ONCE
+        .get_or_init_timeout(Duration::from_millis(10), func1)
+        .await
+        .is_err());
+
+    assert_eq!(*slow.await.unwrap(), 5);
+}
+
+#[tokio::test]
+async fn get_or_try_init_with_retry_eventually_succeeds() {
+    let cell: OnceCell<u32> = OnceCell::new();
+    let attempts = AtomicU32::new(0);
+
+    let value = cell
+        .get_or_try_init_with_retry(3, || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(())
+            } else {
+                Ok(7)
+            }
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(*value, 7);
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn get_or_try_init_with_retry_returns_last_error() {
+    let cell: OnceCell<u32> = OnceCell::new();
+    let attempts = AtomicU32::new(0);
+
+    let err = cell
+        .get_or_try_init_with_retry(2, || async {
+            Err::<u32, u32>(attempts.fetch_add(1, Ordering::SeqCst))
+        })
+        .await
+        .unwrap_err();
+
+    // The error of the final attempt is returned.
+    assert_eq!(err, 1);
+    assert!(cell.get().is_none());
+}
@@ -267,3 +267,102 @@ fn sender_changes_task() {
 
     assert_ready!(task2.enter(|cx, _| tx.poll_closed(cx)));
 }
+
+#[test]
+fn send_with_invokes_closure() {
+    let (tx, rx) = oneshot::channel();
+
+    assert_ok!(tx.send_with(|| 1));
+    let mut rx = task::spawn(rx);
+    assert_eq!(assert_ready_ok!(rx.poll()), 1);
+}
+
+#[test]
+fn send_with_skips_closure_when_closed() {
+    use oneshot::error::SendWithError;
+
+    let (tx, rx) = oneshot::channel::<i32>();
+    drop(rx);
+
+    match tx.send_with(|| panic!("closure invoked for a closed channel")) {
+        Err(SendWithError::Closed(_)) => {}
+        _ => panic!("expected SendWithError::Closed"),
+    }
+}
+
+#[test]
+fn is_ready() {
+    let (tx, mut rx) = oneshot::channel();
+
+    assert!(!rx.is_ready());
+
+    assert_ok!(tx.send(1));
+    assert!(rx.is_ready());
+
+    assert_ok!(rx.try_recv());
+    assert!(!rx.is_ready());
+}
+
+#[test]
+fn is_ready_sender_dropped() {
+    let (tx, rx) = oneshot::channel::<i32>();
+    drop(tx);
+
+    // The channel completed without a value.
+    assert!(!rx.is_ready());
+}
+
+#[tokio::test]
+async fn reusable_cycles() {
+    let mut slot = oneshot::Reusable::new();
+
+    for i in 0..3 {
+        let (tx, rx) = slot.pair().unwrap();
+        assert_ok!(tx.send(i));
+        assert_eq!(rx.await.unwrap(), i);
+    }
+}
+
+#[test]
+fn reusable_pair_while_outstanding() {
+    let mut slot = oneshot::Reusable::<i32>::new();
+
+    let (tx, rx) = slot.pair().unwrap();
+    assert!(slot.pair().is_none());
+
+    drop(tx);
+    assert!(slot.pair().is_none());
+
+    drop(rx);
+    assert!(slot.pair().is_some());
+}
+
+#[test]
+fn reusable_clears_unconsumed_value() {
+    let mut slot = oneshot::Reusable::new();
+
+    let (tx, rx) = slot.pair().unwrap();
+    assert_ok!(tx.send(1));
+    drop(rx);
+
+    // The unreceived value from the previous cycle is discarded.
+    let (tx, mut rx) = slot.pair().unwrap();
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    assert_ok!(tx.send(2));
+    assert_eq!(rx.try_recv(), Ok(2));
+}
+
+#[test]
+fn reusable_after_closed_cycle() {
+    let mut slot = oneshot::Reusable::<i32>::new();
+
+    let (tx, rx) = slot.pair().unwrap();
+    drop(rx);
+    assert!(tx.send(1).is_err());
+
+    let (tx, rx) = slot.pair().unwrap();
+    assert_ok!(tx.send(2));
+
+    let mut rx = task::spawn(rx);
+    assert_eq!(assert_ready_ok!(rx.poll()), 2);
+}
@@ -0,0 +1,200 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::sync::PriorityMutex;
+use tokio_test::task::spawn;
+use tokio_test::{assert_pending, assert_ready};
+
+use std::sync::Arc;
+
+trait AssertSend: Send + Sync {}
+impl AssertSend for PriorityMutex<i32> {}
+
+#[test]
+fn straight_execution() {
+    let l = PriorityMutex::new(100);
+
+    {
+        let mut t = spawn(l.lock_with_priority(0));
+        let mut g = assert_ready!(t.poll());
+        assert_eq!(&*g, &100);
+        *g = 99;
+    }
+    {
+        let mut t = spawn(l.lock_with_priority(0));
+        let mut g = assert_ready!(t.poll());
+        assert_eq!(&*g, &99);
+        *g = 98;
+    }
+    {
+        let mut t = spawn(l.lock_with_priority(0));
+        let g = assert_ready!(t.poll());
+        assert_eq!(&*g, &98);
+    }
+}
+
+#[test]
+fn readiness() {
+    let l = Arc::new(PriorityMutex::new(100));
+    let mut t1 = spawn(l.lock_with_priority(0));
+    let g = assert_ready!(t1.poll());
+
+    // We can't lock, the lock is held.
+    let mut t2 = spawn(l.lock_with_priority(0));
+    assert_pending!(t2.poll());
+
+    drop(g);
+
+    assert!(t2.is_woken());
+    assert_ready!(t2.poll());
+}
+
+#[test]
+fn higher_priority_waiter_wins() {
+    let l = PriorityMutex::new(());
+    let mut t1 = spawn(l.lock_with_priority(0));
+    let g = assert_ready!(t1.poll());
+
+    let mut low = spawn(l.lock_with_priority(1));
+    assert_pending!(low.poll());
+
+    let mut high = spawn(l.lock_with_priority(5));
+    assert_pending!(high.poll());
+
+    // The lock is handed to the high-priority waiter even though the
+    // low-priority waiter queued first.
+    drop(g);
+
+    assert!(high.is_woken());
+    assert!(!low.is_woken());
+
+    let g = assert_ready!(high.poll());
+    assert_pending!(low.poll());
+
+    drop(g);
+
+    assert!(low.is_woken());
+    assert_ready!(low.poll());
+}
+
+#[test]
+fn equal_priority_is_fifo() {
+    let l = PriorityMutex::new(());
+    let mut t1 = spawn(l.lock_with_priority(0));
+    let g = assert_ready!(t1.poll());
+
+    let mut first = spawn(l.lock_with_priority(3));
+    assert_pending!(first.poll());
+
+    let mut second = spawn(l.lock_with_priority(3));
+    assert_pending!(second.poll());
+
+    drop(g);
+
+    assert!(first.is_woken());
+    assert!(!second.is_woken());
+
+    drop(assert_ready!(first.poll()));
+
+    assert!(second.is_woken());
+    assert_ready!(second.poll());
+}
+
+#[test]
+fn cancelled_waiter_is_dequeued() {
+    let l = PriorityMutex::new(());
+    let mut t1 = spawn(l.lock_with_priority(0));
+    let g = assert_ready!(t1.poll());
+
+    let mut waiting = spawn(l.lock_with_priority(9));
+    assert_pending!(waiting.poll());
+    drop(waiting);
+
+    drop(g);
+
+    let mut t2 = spawn(l.lock_with_priority(0));
+    assert_ready!(t2.poll());
+}
+
+#[test]
+fn handed_off_lock_released_on_cancel() {
+    let l = PriorityMutex::new(());
+    let mut t1 = spawn(l.lock_with_priority(0));
+    let g = assert_ready!(t1.poll());
+
+    let mut waiting = spawn(l.lock_with_priority(9));
+    assert_pending!(waiting.poll());
+
+    // The lock is handed off to `waiting`, which is then dropped without
+    // being polled again.
+    drop(g);
+    assert!(waiting.is_woken());
+    drop(waiting);
+
+    let mut t2 = spawn(l.lock_with_priority(0));
+    assert_ready!(t2.poll());
+}
+
+#[test]
+fn try_lock() {
+    let m = PriorityMutex::new(1);
+    {
+        let n = m.try_lock();
+        assert!(n.is_ok());
+        let n2 = m.try_lock();
+        assert!(n2.is_err());
+    }
+    let n3 = m.try_lock();
+    assert!(n3.is_ok());
+}
+
+#[tokio::test]
+async fn aborted_future_released() {
+    let m1: Arc<PriorityMutex<usize>> = Arc::new(PriorityMutex::new(0));
+    let m2 = m1.clone();
+
+    // Try to lock mutex in a future that is aborted prematurely
+    tokio::time::timeout(std::time::Duration::from_millis(1u64), async move {
+        let _g = m2.lock_with_priority(0).await;
+        futures::future::pending::<()>().await;
+    })
+    .await
+    .unwrap_err();
+
+    // This should succeed as there is no lock left for the mutex.
+    tokio::time::timeout(std::time::Duration::from_millis(1u64), async move {
+        let _g = m1.lock_with_priority(0).await;
+    })
+    .await
+    .expect("Mutex is locked");
+}
+
+#[tokio::test]
+async fn contended_commit_path_first() {
+    let wal = Arc::new(PriorityMutex::new(Vec::new()));
+
+    let g = wal.lock_with_priority(0).await;
+
+    let mut handles = Vec::new();
+    for i in 0..4 {
+        let wal = wal.clone();
+        // Even ids are "commit path", odd ids are "compaction".
+        let priority = if i % 2 == 0 { 10 } else { 0 };
+        handles.push(tokio::spawn(async move {
+            wal.lock_with_priority(priority).await.push(priority);
+        }));
+        // Ensure the task is queued before spawning the next one.
+        tokio::task::yield_now().await;
+    }
+
+    // Let all spawned tasks reach the wait queue.
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    drop(g);
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    let order = wal.lock_with_priority(0).await.clone();
+    assert_eq!(order, vec![10, 10, 0, 0]);
+}
@@ -0,0 +1,99 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::sync::RateLimiter;
+use tokio::time::{self, Duration, Instant};
+
+#[tokio::test(start_paused = true)]
+async fn initial_burst_is_available() {
+    let limiter = RateLimiter::new(5, Duration::from_secs(1));
+
+    assert!(limiter.try_acquire(5));
+    assert!(!limiter.try_acquire(1));
+}
+
+#[tokio::test(start_paused = true)]
+async fn tokens_accrue_over_time() {
+    let limiter = RateLimiter::new(10, Duration::from_secs(1));
+
+    assert!(limiter.try_acquire(10));
+    assert!(!limiter.try_acquire(1));
+
+    // One token per 100ms.
+    time::sleep(Duration::from_millis(100)).await;
+    assert!(limiter.try_acquire(1));
+    assert!(!limiter.try_acquire(1));
+
+    time::sleep(Duration::from_millis(300)).await;
+    assert!(limiter.try_acquire(3));
+    assert!(!limiter.try_acquire(1));
+}
+
+#[tokio::test(start_paused = true)]
+async fn acquire_waits_for_accrual() {
+    let limiter = RateLimiter::new(10, Duration::from_secs(1));
+
+    limiter.acquire(10).await;
+
+    let start = Instant::now();
+    limiter.acquire(2).await;
+    assert_eq!(start.elapsed(), Duration::from_millis(200));
+}
+
+#[tokio::test(start_paused = true)]
+async fn accrual_caps_at_burst() {
+    let limiter = RateLimiter::new(4, Duration::from_secs(1));
+
+    time::sleep(Duration::from_secs(60)).await;
+
+    assert!(limiter.try_acquire(4));
+    assert!(!limiter.try_acquire(1));
+}
+
+#[tokio::test(start_paused = true)]
+async fn set_burst_discards_excess_tokens() {
+    let limiter = RateLimiter::new(10, Duration::from_secs(1));
+    assert_eq!(limiter.burst(), 10);
+
+    limiter.set_burst(3);
+    assert_eq!(limiter.burst(), 3);
+
+    assert!(limiter.try_acquire(3));
+    assert!(!limiter.try_acquire(1));
+
+    // Accrual is now capped at the new burst size.
+    time::sleep(Duration::from_secs(10)).await;
+    assert!(limiter.try_acquire(3));
+    assert!(!limiter.try_acquire(1));
+}
+
+#[tokio::test(start_paused = true)]
+async fn waiters_are_served_in_order() {
+    use std::sync::Arc;
+
+    let limiter = Arc::new(RateLimiter::new(10, Duration::from_secs(1)));
+    limiter.acquire(10).await;
+
+    let l1 = limiter.clone();
+    let first = tokio::spawn(async move {
+        l1.acquire(5).await;
+        Instant::now()
+    });
+    time::sleep(Duration::from_millis(10)).await;
+
+    let l2 = limiter.clone();
+    let second = tokio::spawn(async move {
+        l2.acquire(1).await;
+        Instant::now()
+    });
+
+    // The small late request must not jump the queue.
+    assert!(first.await.unwrap() <= second.await.unwrap());
+}
+
+#[tokio::test(start_paused = true)]
+#[should_panic = "cannot acquire more tokens"]
+async fn acquire_beyond_burst_panics() {
+    let limiter = RateLimiter::new(2, Duration::from_secs(1));
+    limiter.acquire(3).await;
+}
@@ -0,0 +1,147 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::sync::rpc;
+use tokio::sync::rpc::error::CallError;
+use tokio_test::task::spawn;
+use tokio_test::{assert_pending, assert_ready};
+
+trait AssertSend: Send {}
+impl AssertSend for rpc::Sender<i32, i32> {}
+impl AssertSend for rpc::Receiver<i32, i32> {}
+
+#[tokio::test]
+async fn call_and_respond() {
+    let (tx, mut rx) = rpc::channel::<u32, u32>(4);
+
+    tokio::spawn(async move {
+        while let Some((request, responder)) = rx.recv().await {
+            responder.respond(request + 1).unwrap();
+        }
+    });
+
+    assert_eq!(tx.call(1).await.unwrap(), 2);
+    assert_eq!(tx.call(41).await.unwrap(), 42);
+}
+
+#[tokio::test]
+async fn cloned_senders() {
+    let (tx, mut rx) = rpc::channel::<u32, u32>(4);
+    let tx2 = tx.clone();
+
+    tokio::spawn(async move {
+        while let Some((request, responder)) = rx.recv().await {
+            responder.respond(request * 10).unwrap();
+        }
+    });
+
+    assert_eq!(tx.call(1).await.unwrap(), 10);
+    assert_eq!(tx2.call(2).await.unwrap(), 20);
+}
+
+#[test]
+fn call_applies_backpressure() {
+    let (tx, mut rx) = rpc::channel::<u32, u32>(1);
+
+    let mut first = spawn(tx.call(1));
+    assert_pending!(first.poll());
+
+    // The channel is full: a second call waits for capacity.
+    let mut second = spawn(tx.call(2));
+    assert_pending!(second.poll());
+
+    let mut recv = spawn(rx.recv());
+    let (request, responder) = assert_ready!(recv.poll()).unwrap();
+    assert_eq!(request, 1);
+    drop(recv);
+
+    // Receiving the first request makes room for the second.
+    assert!(second.is_woken());
+    assert_pending!(second.poll());
+
+    responder.respond(10).unwrap();
+    assert!(first.is_woken());
+    assert_eq!(assert_ready!(first.poll()).unwrap(), 10);
+}
+
+#[tokio::test]
+async fn closed_returns_request() {
+    let (tx, rx) = rpc::channel::<u32, u32>(1);
+    drop(rx);
+
+    assert!(tx.is_closed());
+
+    match tx.call(7).await {
+        Err(CallError::Closed(request)) => assert_eq!(request, 7),
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[tokio::test]
+async fn dropped_responder_is_no_response() {
+    let (tx, mut rx) = rpc::channel::<u32, u32>(1);
+
+    tokio::spawn(async move {
+        let (_request, responder) = rx.recv().await.unwrap();
+        drop(responder);
+    });
+
+    assert_eq!(tx.call(1).await, Err(CallError::NoResponse));
+}
+
+#[test]
+fn cancelled_call_surfaces_to_responder() {
+    let (tx, mut rx) = rpc::channel::<u32, u32>(1);
+
+    {
+        let mut call = spawn(tx.call(1));
+        assert_pending!(call.poll());
+        // The caller goes away with the request already sent.
+    }
+
+    let mut recv = spawn(rx.recv());
+    let (request, responder) = assert_ready!(recv.poll()).unwrap();
+    assert_eq!(request, 1);
+
+    assert!(responder.is_closed());
+    assert_eq!(responder.respond(10), Err(10));
+}
+
+#[test]
+fn close_drains_in_flight_requests() {
+    let (tx, mut rx) = rpc::channel::<u32, u32>(2);
+
+    let mut call = spawn(tx.call(1));
+    assert_pending!(call.poll());
+
+    rx.close();
+    assert!(tx.is_closed());
+
+    let mut recv = spawn(rx.recv());
+    let (request, responder) = assert_ready!(recv.poll()).unwrap();
+    assert_eq!(request, 1);
+    responder.respond(2).unwrap();
+
+    assert_eq!(assert_ready!(call.poll()).unwrap(), 2);
+}
+
+#[tokio::test]
+async fn capacity_tracks_in_flight_calls() {
+    let (tx, mut rx) = rpc::channel::<u32, u32>(2);
+
+    assert_eq!(tx.capacity(), 2);
+    assert_eq!(tx.max_capacity(), 2);
+
+    let call = tokio::spawn({
+        let tx = tx.clone();
+        async move { tx.call(1).await }
+    });
+
+    let (request, responder) = rx.recv().await.unwrap();
+    assert_eq!(request, 1);
+    assert_eq!(tx.capacity(), 2);
+    assert_eq!(tx.max_capacity(), 2);
+
+    responder.respond(2).unwrap();
+    assert_eq!(call.await.unwrap(), Ok(2));
+}
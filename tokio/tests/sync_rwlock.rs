@@ -268,3 +268,257 @@ fn try_read_try_write() {
 
     assert_eq!(*lock.try_read().unwrap(), 1515);
 }
+
+// Acquire a read lock from synchronous code while async writers contend.
+#[tokio::test(flavor = "multi_thread")]
+async fn blocking_read_from_spawn_blocking() {
+    let rwlock = Arc::new(RwLock::new(1));
+    let write_lock = rwlock.write().await;
+
+    let blocking_task = tokio::task::spawn_blocking({
+        let rwlock = Arc::clone(&rwlock);
+        move || {
+            let read_lock = rwlock.blocking_read();
+            assert_eq!(*read_lock, 0);
+        }
+    });
+
+    let mut write_lock = write_lock;
+    *write_lock = 0;
+    drop(write_lock);
+
+    blocking_task.await.unwrap();
+
+    assert!(rwlock.try_write().is_ok());
+}
+
+// Acquire a write lock from synchronous code while async readers contend.
+#[tokio::test(flavor = "multi_thread")]
+async fn blocking_write_from_spawn_blocking() {
+    let rwlock = Arc::new(RwLock::new(1));
+    let read_lock = rwlock.read().await;
+
+    let blocking_task = tokio::task::spawn_blocking({
+        let rwlock = Arc::clone(&rwlock);
+        move || {
+            let mut write_lock = rwlock.blocking_write();
+            *write_lock = 2;
+        }
+    });
+
+    assert_eq!(*read_lock, 1);
+    drop(read_lock);
+
+    blocking_task.await.unwrap();
+
+    assert_eq!(*rwlock.try_read().unwrap(), 2);
+}
+
+#[test]
+#[should_panic]
+fn blocking_read_in_async_context_panics() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let rwlock = RwLock::new(1);
+        let _ = rwlock.blocking_read();
+    });
+}
+
+// Upgrading while other readers hold the lock waits for them, and no writer
+// can slip in between the read and write access.
+#[test]
+fn upgrade_waits_for_other_readers() {
+    use tokio::sync::RwLockReadGuard;
+
+    let rwlock = RwLock::new(0);
+    let mut t1 = spawn(rwlock.read());
+    let g1 = assert_ready!(t1.poll());
+    let mut t2 = spawn(rwlock.read());
+    let g2 = assert_ready!(t2.poll());
+
+    let mut upgrade = spawn(RwLockReadGuard::upgrade(g1));
+    assert_pending!(upgrade.poll());
+
+    // A writer queued behind the upgrade cannot acquire first.
+    let mut write = spawn(rwlock.write());
+    assert_pending!(write.poll());
+
+    drop(g2);
+    assert!(upgrade.is_woken());
+    let mut wg = assert_ready!(upgrade.poll());
+    assert_pending!(write.poll());
+
+    *wg = 1;
+    drop(wg);
+
+    let wg2 = assert_ready!(write.poll());
+    assert_eq!(*wg2, 1);
+}
+
+#[tokio::test]
+async fn upgrade_uncontested() {
+    use tokio::sync::RwLockReadGuard;
+
+    let rwlock = RwLock::new(1);
+    let guard = rwlock.read().await;
+    let mut guard = RwLockReadGuard::upgrade(guard).await;
+    *guard += 1;
+    drop(guard);
+
+    assert_eq!(*rwlock.read().await, 2);
+}
+
+#[test]
+fn upgrade_cancelled_releases_read_lock() {
+    use tokio::sync::RwLockReadGuard;
+
+    let rwlock = RwLock::new(0);
+    let mut t1 = spawn(rwlock.read());
+    let g1 = assert_ready!(t1.poll());
+    let mut t2 = spawn(rwlock.read());
+    let g2 = assert_ready!(t2.poll());
+
+    let mut upgrade = spawn(RwLockReadGuard::upgrade(g1));
+    assert_pending!(upgrade.poll());
+    drop(upgrade);
+    drop(g2);
+
+    // Both read permits were returned.
+    let mut write = spawn(rwlock.write());
+    assert_ready!(write.poll());
+}
+
+#[tokio::test]
+async fn downgrade_map_shares_component() {
+    use tokio::sync::RwLockWriteGuard;
+
+    let rwlock = RwLock::new((1, String::from("a")));
+
+    let mut guard = rwlock.write().await;
+    guard.0 = 2;
+
+    let guard = RwLockWriteGuard::downgrade_map(guard, |v| &v.1);
+    assert_eq!(*guard, "a");
+
+    // Other readers are admitted while the mapped read guard is held.
+    let other = rwlock.try_read().unwrap();
+    assert_eq!(other.0, 2);
+    drop(other);
+    drop(guard);
+
+    assert!(rwlock.try_write().is_ok());
+}
+
+// Under reader priority, a reader queued after a writer is served first once
+// the current readers release the lock.
+#[test]
+fn reader_priority_serves_readers_first() {
+    use tokio::sync::RwLockPolicy;
+
+    let rwlock = RwLock::with_policy(0, RwLockPolicy::ReaderPriority);
+
+    let mut t1 = spawn(rwlock.read());
+    let g1 = assert_ready!(t1.poll());
+
+    let mut write = spawn(rwlock.write());
+    assert_pending!(write.poll());
+
+    // Queued after the writer, but preferred by the policy.
+    let mut read = spawn(rwlock.read());
+    assert_pending!(read.poll());
+
+    drop(g1);
+
+    let g2 = assert_ready!(read.poll());
+    assert_pending!(write.poll());
+
+    drop(g2);
+    assert_ready!(write.poll());
+}
+
+// Under writer priority, a writer queued after readers is served first once
+// the lock is released.
+#[test]
+fn writer_priority_serves_writers_first() {
+    use tokio::sync::RwLockPolicy;
+
+    let rwlock = RwLock::with_policy(0, RwLockPolicy::WriterPriority);
+
+    let mut t1 = spawn(rwlock.write());
+    let g1 = assert_ready!(t1.poll());
+
+    let mut read = spawn(rwlock.read());
+    assert_pending!(read.poll());
+
+    // Queued after the reader, but preferred by the policy.
+    let mut write = spawn(rwlock.write());
+    assert_pending!(write.poll());
+
+    drop(g1);
+
+    let mut g2 = assert_ready!(write.poll());
+    assert_pending!(read.poll());
+
+    *g2 = 1;
+    drop(g2);
+    assert_eq!(*assert_ready!(read.poll()), 1);
+}
+
+#[tokio::test]
+async fn with_policy_fair_matches_default() {
+    use tokio::sync::RwLockPolicy;
+
+    let rwlock = RwLock::with_policy(1, RwLockPolicy::Fair);
+
+    let r1 = rwlock.read().await;
+    let r2 = rwlock.read().await;
+    assert_eq!(*r1, 1);
+    drop((r1, r2));
+
+    let mut w = rwlock.write().await;
+    *w += 1;
+    drop(w);
+
+    assert_eq!(*rwlock.read().await, 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn read_timeout_expires_while_write_held() {
+    use tokio::time::Duration;
+
+    let rwlock = RwLock::new(0);
+
+    let g = rwlock.write().await;
+    assert!(rwlock
+        .read_timeout(Duration::from_millis(10))
+        .await
+        .is_err());
+
+    // The timed-out waiter left no trace: releasing the write lock makes
+    // the lock available again.
+    drop(g);
+    assert!(rwlock.try_read().is_ok());
+}
+
+#[tokio::test(start_paused = true)]
+async fn write_timeout_releases_partial_permits() {
+    use tokio::time::Duration;
+
+    let rwlock = RwLock::new(0);
+
+    // A reader blocks the writer, which times out after having been
+    // assigned part of the lock's permits.
+    let g = rwlock.read().await;
+    assert!(rwlock
+        .write_timeout(Duration::from_millis(10))
+        .await
+        .is_err());
+
+    // The partially assigned permits were handed back: another writer can
+    // acquire the lock once the reader is gone.
+    drop(g);
+    assert!(rwlock.try_write().is_ok());
+}
@@ -93,3 +93,525 @@ fn add_more_than_max_amount_permits() {
     let s = tokio::sync::Semaphore::new(1);
     s.add_permits(usize::MAX >> 3);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn release_acquire_stress() {
+    let sem = Arc::new(Semaphore::new(1));
+    let mut handles = Vec::new();
+
+    // Hammer the acquire/release handoff so releases interleave with waiters
+    // enqueueing, covering both the contended and uncontended release paths.
+    for _ in 0..8 {
+        let sem = sem.clone();
+        handles.push(tokio::spawn(async move {
+            for _ in 0..10_000 {
+                let _permit = sem.acquire().await.unwrap();
+            }
+        }));
+    }
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(sem.available_permits(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn add_permits_stress() {
+    let sem = Arc::new(Semaphore::new(0));
+    let mut handles = Vec::new();
+
+    for _ in 0..4 {
+        let sem = sem.clone();
+        handles.push(tokio::spawn(async move {
+            for _ in 0..10_000 {
+                sem.acquire().await.unwrap().forget();
+            }
+        }));
+    }
+
+    let releaser = {
+        let sem = sem.clone();
+        tokio::spawn(async move {
+            for _ in 0..40_000 {
+                sem.add_permits(1);
+            }
+        })
+    };
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    releaser.await.unwrap();
+
+    assert_eq!(sem.available_permits(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn release_large_batch() {
+    let sem = Arc::new(Semaphore::new(0));
+    let mut handles = Vec::new();
+
+    // More waiters than the inline wake-batch capacity, so a single release
+    // wakes them all in one critical section.
+    for _ in 0..100 {
+        let sem = sem.clone();
+        handles.push(tokio::spawn(async move {
+            sem.acquire().await.unwrap().forget();
+        }));
+    }
+
+    tokio::task::yield_now().await;
+    sem.add_permits(100);
+
+    for handle in handles {
+        handle.await.unwrap();
+    }
+
+    assert_eq!(sem.available_permits(), 0);
+}
+
+#[tokio::test]
+async fn reduce_permits_deficit() {
+    let sem = Semaphore::new(2);
+    let permit = sem.acquire_many(2).await.unwrap();
+
+    // Remove more permits than are available: the semaphore goes into
+    // deficit until enough permits are returned.
+    sem.reduce_permits(1);
+    assert_eq!(sem.permit_deficit(), 1);
+    assert!(sem.try_acquire().is_err());
+
+    drop(permit);
+    assert_eq!(sem.permit_deficit(), 0);
+    assert_eq!(sem.available_permits(), 1);
+    assert!(sem.try_acquire().is_ok());
+}
+
+#[test]
+fn reduce_available_permits_only() {
+    let sem = Semaphore::new(3);
+
+    // Removing no more than the available permits leaves no deficit.
+    sem.reduce_permits(2);
+    assert_eq!(sem.permit_deficit(), 0);
+    assert_eq!(sem.available_permits(), 1);
+}
+
+#[tokio::test]
+async fn weighted_distributes_proportionally() {
+    use tokio_test::{assert_pending, task::spawn};
+
+    let sem = Semaphore::new_weighted(0, &[3, 1]);
+
+    let mut class0: Vec<_> = (0..4).map(|_| spawn(sem.acquire_class(0))).collect();
+    let mut class1: Vec<_> = (0..4).map(|_| spawn(sem.acquire_class(1))).collect();
+
+    for waiter in class0.iter_mut().chain(class1.iter_mut()) {
+        assert_pending!(waiter.poll());
+    }
+
+    // Three quarters of the contended permits go to class 0, one quarter to
+    // class 1; within a class, waiters are served FIFO.
+    sem.add_permits(4);
+
+    let woken0: Vec<_> = class0.iter().map(|w| w.is_woken()).collect();
+    let woken1: Vec<_> = class1.iter().map(|w| w.is_woken()).collect();
+    assert_eq!(woken0, [true, true, true, false]);
+    assert_eq!(woken1, [true, false, false, false]);
+
+    // The remaining waiters drain once more permits arrive.
+    sem.add_permits(4);
+    assert!(class0[3].is_woken());
+    assert!(class1.iter().all(|w| w.is_woken()));
+}
+
+#[tokio::test]
+async fn weighted_idle_class_gets_no_backlog_credit() {
+    use tokio_test::{assert_pending, assert_ready_ok, task::spawn};
+
+    let sem = Semaphore::new_weighted(0, &[1, 1]);
+
+    // Class 0 consumes permits alone for a while.
+    let mut early: Vec<_> = (0..4).map(|_| spawn(sem.acquire_class(0))).collect();
+    for waiter in early.iter_mut() {
+        assert_pending!(waiter.poll());
+    }
+    sem.add_permits(4);
+    for mut waiter in early {
+        assert_ready_ok!(waiter.poll()).forget();
+    }
+
+    // When class 1 shows up, it is not owed the permits class 0 used while
+    // it was idle: with equal weights the next permits are split evenly.
+    let mut c0: Vec<_> = (0..2).map(|_| spawn(sem.acquire_class(0))).collect();
+    let mut c1: Vec<_> = (0..2).map(|_| spawn(sem.acquire_class(1))).collect();
+    for waiter in c0.iter_mut().chain(c1.iter_mut()) {
+        assert_pending!(waiter.poll());
+    }
+
+    sem.add_permits(2);
+    assert_eq!(c0.iter().filter(|w| w.is_woken()).count(), 1);
+    assert_eq!(c1.iter().filter(|w| w.is_woken()).count(), 1);
+}
+
+#[tokio::test]
+async fn acquire_class_on_unweighted() {
+    let sem = Semaphore::new(1);
+    let permit = sem.acquire_class(0).await.unwrap();
+    drop(permit);
+}
+
+#[tokio::test]
+#[should_panic = "class index 1 out of bounds"]
+async fn acquire_class_out_of_bounds() {
+    let sem = Semaphore::new(1);
+    let _ = sem.acquire_class(1).await;
+}
+
+#[tokio::test]
+async fn adjustable_shrink_completes() {
+    use tokio_test::{assert_pending, assert_ready_ok, task::spawn};
+
+    let sem = Semaphore::new(1);
+
+    // Request more than is available, then scale the request down to what
+    // has already been assigned.
+    let mut acquire = spawn(sem.acquire_many_adjustable(3));
+    assert_pending!(acquire.poll());
+
+    acquire.enter(|_, f| f.set_permits(1));
+    let permit = assert_ready_ok!(acquire.poll());
+
+    assert_eq!(sem.available_permits(), 0);
+    drop(permit);
+    assert_eq!(sem.available_permits(), 1);
+}
+
+#[tokio::test]
+async fn adjustable_shrink_releases_excess() {
+    use tokio_test::{assert_pending, assert_ready_ok, task::spawn};
+
+    let sem = Semaphore::new(2);
+
+    let mut big = spawn(sem.acquire_many_adjustable(5));
+    assert_pending!(big.poll());
+
+    let mut small = spawn(sem.acquire());
+    assert_pending!(small.poll());
+
+    // `big` holds two assigned permits; shrinking to one frees the other,
+    // which flows to the next waiter.
+    big.enter(|_, f| f.set_permits(1));
+    assert!(small.is_woken());
+    let small_permit = assert_ready_ok!(small.poll());
+    let big_permit = assert_ready_ok!(big.poll());
+
+    drop((small_permit, big_permit));
+    assert_eq!(sem.available_permits(), 2);
+}
+
+#[tokio::test]
+async fn adjustable_grow_keeps_queue_position() {
+    use tokio_test::{assert_pending, assert_ready_ok, task::spawn};
+
+    let sem = Semaphore::new(0);
+
+    let mut first = spawn(sem.acquire_many_adjustable(1));
+    assert_pending!(first.poll());
+
+    let mut second = spawn(sem.acquire());
+    assert_pending!(second.poll());
+
+    // Growing the request does not send the waiter to the back of the queue.
+    first.enter(|_, f| f.set_permits(2));
+
+    sem.add_permits(2);
+    assert!(first.is_woken());
+    let permit = assert_ready_ok!(first.poll());
+    assert_pending!(second.poll());
+
+    drop(permit);
+    assert!(second.is_woken());
+    let _ = assert_ready_ok!(second.poll());
+}
+
+#[tokio::test(start_paused = true)]
+async fn acquire_timeout_expires() {
+    use tokio::sync::AcquireTimeoutError;
+    use tokio::time::{Duration, Instant};
+
+    let sem = Semaphore::new(0);
+
+    let start = Instant::now();
+    let err = sem
+        .acquire_timeout(1, Duration::from_millis(50))
+        .await
+        .unwrap_err();
+
+    assert_eq!(err, AcquireTimeoutError::TimedOut);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    // The timed-out waiter left no trace: a released permit is available to
+    // the next caller.
+    sem.add_permits(1);
+    assert!(sem.try_acquire().is_ok());
+}
+
+#[tokio::test(start_paused = true)]
+async fn acquire_timeout_succeeds_before_deadline() {
+    use tokio::time::Duration;
+
+    let sem = Arc::new(Semaphore::new(0));
+
+    let handle = {
+        let sem = sem.clone();
+        tokio::spawn(async move {
+            sem.acquire_timeout(1, Duration::from_secs(1))
+                .await
+                .map(|permit| permit.forget())
+                .is_ok()
+        })
+    };
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    sem.add_permits(1);
+
+    assert!(handle.await.unwrap());
+}
+
+#[tokio::test(start_paused = true)]
+async fn acquire_timeout_releases_partial_permits() {
+    use tokio::sync::AcquireTimeoutError;
+    use tokio::time::Duration;
+
+    let sem = Arc::new(Semaphore::new(1));
+
+    let handle = {
+        let sem = sem.clone();
+        tokio::spawn(async move {
+            // Takes the single available permit, then waits for the second.
+            sem.acquire_timeout(2, Duration::from_millis(50)).await.err()
+        })
+    };
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(sem.available_permits(), 0);
+
+    // After the deadline, the partially assigned permit is returned.
+    assert_eq!(handle.await.unwrap(), Some(AcquireTimeoutError::TimedOut));
+    assert_eq!(sem.available_permits(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn acquire_timeout_closed() {
+    use tokio::sync::AcquireTimeoutError;
+    use tokio::time::Duration;
+
+    let sem = Arc::new(Semaphore::new(0));
+
+    let handle = {
+        let sem = sem.clone();
+        tokio::spawn(async move { sem.acquire_timeout(1, Duration::from_secs(1)).await.err() })
+    };
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    sem.close();
+
+    assert_eq!(handle.await.unwrap(), Some(AcquireTimeoutError::Closed));
+}
+
+#[tokio::test]
+async fn waiters_and_queued_permits() {
+    use tokio_test::{assert_pending, task::spawn};
+
+    let sem = Semaphore::new(1);
+    assert_eq!(sem.waiters(), 0);
+    assert_eq!(sem.queued_permits(), 0);
+
+    // Takes the available permit and waits for two more.
+    let mut big = spawn(sem.acquire_many(3));
+    assert_pending!(big.poll());
+    assert_eq!(sem.waiters(), 1);
+    assert_eq!(sem.queued_permits(), 2);
+
+    let mut small = spawn(sem.acquire());
+    assert_pending!(small.poll());
+    assert_eq!(sem.waiters(), 2);
+    assert_eq!(sem.queued_permits(), 3);
+
+    drop(small);
+    assert_eq!(sem.waiters(), 1);
+    assert_eq!(sem.queued_permits(), 2);
+
+    sem.add_permits(2);
+    assert_eq!(sem.waiters(), 0);
+    assert_eq!(sem.queued_permits(), 0);
+}
+
+#[test]
+fn unfair_serves_most_recent_waiter() {
+    use tokio_test::{assert_pending, assert_ready_ok, task::spawn};
+
+    let sem = Arc::new(Semaphore::new_unfair(0));
+    let mut first = spawn(sem.clone().acquire_owned());
+    let mut second = spawn(sem.clone().acquire_owned());
+    let mut third = spawn(sem.clone().acquire_owned());
+    assert_pending!(first.poll());
+    assert_pending!(second.poll());
+    assert_pending!(third.poll());
+
+    // The most recently queued waiter is served first.
+    sem.add_permits(1);
+    assert!(third.is_woken());
+    assert!(!first.is_woken());
+    assert!(!second.is_woken());
+    let _p3 = assert_ready_ok!(third.poll());
+
+    sem.add_permits(1);
+    assert!(second.is_woken());
+    assert!(!first.is_woken());
+    let _p2 = assert_ready_ok!(second.poll());
+
+    sem.add_permits(1);
+    assert!(first.is_woken());
+    let _p1 = assert_ready_ok!(first.poll());
+}
+
+#[test]
+fn unfair_new_waiter_jumps_queue() {
+    use tokio_test::{assert_pending, assert_ready_ok, task::spawn};
+
+    let sem = Arc::new(Semaphore::new_unfair(0));
+    let mut old = spawn(sem.clone().acquire_owned());
+    assert_pending!(old.poll());
+
+    let mut new = spawn(sem.clone().acquire_owned());
+    assert_pending!(new.poll());
+
+    // The newer waiter barges past the older one.
+    sem.add_permits(1);
+    assert!(new.is_woken());
+    assert!(!old.is_woken());
+    let _permit = assert_ready_ok!(new.poll());
+}
+
+#[test]
+fn capped_add_permits_saturates() {
+    let sem = Semaphore::new_with_max(2, 5);
+    assert_eq!(sem.max_permits(), 5);
+
+    sem.add_permits(100);
+    assert_eq!(sem.available_permits(), 5);
+}
+
+#[test]
+fn capped_dropped_permit_saturates() {
+    let sem = Semaphore::new_with_max(2, 2);
+    let permit = sem.try_acquire().unwrap();
+
+    // Replenish while the permit is held out...
+    sem.add_permits(2);
+    assert_eq!(sem.available_permits(), 2);
+
+    // ...so returning it has nowhere to go.
+    drop(permit);
+    assert_eq!(sem.available_permits(), 2);
+}
+
+#[test]
+fn add_permits_saturating_uncapped() {
+    let sem = Semaphore::new(1);
+    assert_eq!(sem.max_permits(), Semaphore::MAX_PERMITS);
+
+    sem.add_permits_saturating(Semaphore::MAX_PERMITS);
+    assert_eq!(sem.available_permits(), Semaphore::MAX_PERMITS);
+
+    // Already full; this would panic via `add_permits`.
+    sem.add_permits_saturating(1);
+    assert_eq!(sem.available_permits(), Semaphore::MAX_PERMITS);
+}
+
+#[test]
+fn capped_add_permits_still_wakes_waiters() {
+    use tokio_test::{assert_pending, assert_ready_ok, task::spawn};
+
+    let sem = Arc::new(Semaphore::new_with_max(0, 1));
+    let mut waiter = spawn(sem.clone().acquire_many_owned(2));
+    assert_pending!(waiter.poll());
+
+    // Permits assigned directly to waiters are not limited by the cap; only
+    // the idle count is.
+    sem.add_permits(2);
+    assert!(waiter.is_woken());
+    let _permit = assert_ready_ok!(waiter.poll());
+    assert_eq!(sem.available_permits(), 0);
+}
+
+#[test]
+fn closed_resolves_on_close() {
+    use tokio_test::{assert_pending, assert_ready, task::spawn};
+
+    let sem = Arc::new(Semaphore::new(1));
+    let closed_sem = sem.clone();
+    let mut closed = spawn(async move { closed_sem.closed().await });
+    assert_pending!(closed.poll());
+
+    sem.close();
+    assert!(closed.is_woken());
+    assert_ready!(closed.poll());
+}
+
+#[test]
+fn closed_ready_when_already_closed() {
+    use tokio_test::{assert_ready, task::spawn};
+
+    let sem = Semaphore::new(1);
+    sem.close();
+
+    let mut closed = spawn(sem.closed());
+    assert_ready!(closed.poll());
+}
+
+#[test]
+fn try_acquire_upto() {
+    use tokio::sync::TryAcquireError;
+
+    let sem = Semaphore::new(3);
+
+    // Fewer permits available than requested: take what is there.
+    let partial = sem.try_acquire_upto(5).unwrap();
+    assert_eq!(partial.num_permits(), 3);
+    assert_eq!(sem.available_permits(), 0);
+
+    // Nothing left at all.
+    assert_eq!(sem.try_acquire_upto(1).err(), Some(TryAcquireError::NoPermits));
+
+    drop(partial);
+    assert_eq!(sem.available_permits(), 3);
+
+    // More available than requested: take exactly `n`.
+    let exact = sem.try_acquire_upto(2).unwrap();
+    assert_eq!(exact.num_permits(), 2);
+    assert_eq!(sem.available_permits(), 1);
+
+    sem.close();
+    assert_eq!(sem.try_acquire_upto(1).err(), Some(TryAcquireError::Closed));
+}
+
+#[test]
+fn forget_many_keeps_remainder() {
+    let sem = Semaphore::new(5);
+    let mut permit = sem.try_acquire_many(4).unwrap();
+
+    assert_eq!(permit.forget_many(3), 3);
+    assert_eq!(permit.num_permits(), 1);
+
+    // Asking for more than is held forgets only what is there.
+    assert_eq!(permit.forget_many(10), 1);
+    assert_eq!(permit.num_permits(), 0);
+
+    drop(permit);
+    assert_eq!(sem.available_permits(), 1);
+}
@@ -104,3 +104,69 @@ async fn stresstest() {
     let _p5 = sem.clone().try_acquire_owned().unwrap();
     assert!(sem.try_acquire_owned().is_err());
 }
+
+#[tokio::test]
+async fn split_and_merge() {
+    let sem = Arc::new(Semaphore::new(3));
+    let mut permit = sem.clone().acquire_many_owned(3).await.unwrap();
+    assert_eq!(sem.available_permits(), 0);
+
+    let lent = permit.split(1).unwrap();
+    assert_eq!(permit.num_permits(), 2);
+    assert_eq!(lent.num_permits(), 1);
+
+    // Nothing was released by splitting.
+    assert_eq!(sem.available_permits(), 0);
+
+    permit.merge(lent);
+    assert_eq!(permit.num_permits(), 3);
+
+    drop(permit);
+    assert_eq!(sem.available_permits(), 3);
+}
+
+#[tokio::test]
+async fn split_insufficient_permits() {
+    let sem = Arc::new(Semaphore::new(1));
+    let mut permit = sem.clone().acquire_owned().await.unwrap();
+
+    assert!(permit.split(2).is_none());
+    assert_eq!(permit.num_permits(), 1);
+}
+
+#[tokio::test]
+async fn split_permit_released_independently() {
+    let sem = Arc::new(Semaphore::new(2));
+    let mut permit = sem.clone().acquire_many_owned(2).await.unwrap();
+
+    let lent = permit.split(1).unwrap();
+    drop(lent);
+    assert_eq!(sem.available_permits(), 1);
+
+    drop(permit);
+    assert_eq!(sem.available_permits(), 2);
+}
+
+#[tokio::test]
+#[should_panic = "merging permits from different semaphore instances"]
+async fn merge_different_semaphores() {
+    let sem1 = Arc::new(Semaphore::new(1));
+    let sem2 = Arc::new(Semaphore::new(1));
+
+    let mut permit = sem1.acquire_owned().await.unwrap();
+    let other = sem2.acquire_owned().await.unwrap();
+
+    permit.merge(other);
+}
+
+#[test]
+fn forget_many_keeps_remainder() {
+    let sem = Arc::new(Semaphore::new(5));
+    let mut permit = sem.clone().try_acquire_many_owned(4).unwrap();
+
+    assert_eq!(permit.forget_many(3), 3);
+    assert_eq!(permit.num_permits(), 1);
+
+    drop(permit);
+    assert_eq!(sem.available_permits(), 2);
+}
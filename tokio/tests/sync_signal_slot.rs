@@ -0,0 +1,81 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::sync::SignalSlot;
+use tokio_test::task::spawn;
+use tokio_test::{assert_pending, assert_ready_eq};
+
+#[test]
+fn wait_receives_notified_value() {
+    let slot = SignalSlot::new();
+
+    let mut wait = spawn(slot.wait());
+    assert_pending!(wait.poll());
+
+    assert!(slot.notify(1).is_none());
+    assert!(wait.is_woken());
+    assert_ready_eq!(wait.poll(), 1);
+}
+
+#[test]
+fn notified_before_wait() {
+    let slot = SignalSlot::new();
+
+    slot.notify("hello");
+
+    let mut wait = spawn(slot.wait());
+    assert_ready_eq!(wait.poll(), "hello");
+}
+
+#[test]
+fn last_write_wins() {
+    let slot = SignalSlot::new();
+
+    assert!(slot.notify(1).is_none());
+    assert_eq!(slot.notify(2), Some(1));
+
+    let mut wait = spawn(slot.wait());
+    assert_ready_eq!(wait.poll(), 2);
+
+    // The slot is empty again.
+    let mut wait = spawn(slot.wait());
+    assert_pending!(wait.poll());
+}
+
+#[test]
+fn try_take() {
+    let slot = SignalSlot::new();
+    assert_eq!(slot.try_take(), None);
+
+    slot.notify(7);
+    assert_eq!(slot.try_take(), Some(7));
+    assert_eq!(slot.try_take(), None);
+}
+
+#[tokio::test]
+async fn wakes_worker_across_tasks() {
+    use std::sync::Arc;
+
+    let slot = Arc::new(SignalSlot::new());
+    let slot2 = slot.clone();
+
+    let worker = tokio::spawn(async move {
+        let mut seen = Vec::new();
+        loop {
+            let value = slot2.wait().await;
+            let done = value == 0;
+            seen.push(value);
+            if done {
+                break;
+            }
+        }
+        seen
+    });
+
+    slot.notify(1);
+    tokio::task::yield_now().await;
+    slot.notify(0);
+
+    let seen = worker.await.unwrap();
+    assert_eq!(*seen.last().unwrap(), 0);
+}
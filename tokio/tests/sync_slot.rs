@@ -0,0 +1,144 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::sync::slot;
+use tokio::sync::slot::error::TryRecvError;
+use tokio_test::task::spawn;
+use tokio_test::{assert_pending, assert_ready};
+
+trait AssertSend: Send {}
+impl AssertSend for slot::Sender<i32> {}
+impl AssertSend for slot::Receiver<i32> {}
+
+#[test]
+fn send_recv() {
+    let (tx, mut rx) = slot::channel();
+
+    assert_eq!(None, tx.send(1).unwrap());
+    assert_eq!(Ok(1), rx.try_recv());
+}
+
+#[test]
+fn send_overwrites() {
+    let (tx, mut rx) = slot::channel();
+
+    assert_eq!(None, tx.send(1).unwrap());
+    assert_eq!(Some(1), tx.send(2).unwrap());
+
+    assert_eq!(Ok(2), rx.try_recv());
+    assert_eq!(Err(TryRecvError::Empty), rx.try_recv());
+}
+
+#[test]
+fn halves_are_reusable() {
+    let (tx, mut rx) = slot::channel();
+
+    for i in 0..10 {
+        assert_eq!(None, tx.send(i).unwrap());
+        assert_eq!(Ok(i), rx.try_recv());
+    }
+}
+
+#[test]
+fn recv_wakes_on_send() {
+    let (tx, mut rx) = slot::channel();
+
+    let mut recv = spawn(async { rx.recv().await });
+    assert_pending!(recv.poll());
+
+    assert_eq!(None, tx.send(1).unwrap());
+
+    assert!(recv.is_woken());
+    assert_eq!(Ok(1), assert_ready!(recv.poll()));
+}
+
+#[test]
+fn recv_wakes_on_sender_drop() {
+    let (tx, mut rx) = slot::channel::<i32>();
+
+    let mut recv = spawn(async { rx.recv().await });
+    assert_pending!(recv.poll());
+
+    drop(tx);
+
+    assert!(recv.is_woken());
+    assert!(assert_ready!(recv.poll()).is_err());
+}
+
+#[test]
+fn recv_value_sent_before_sender_drop() {
+    let (tx, mut rx) = slot::channel();
+
+    assert_eq!(None, tx.send(1).unwrap());
+    drop(tx);
+
+    assert_eq!(Ok(1), rx.try_recv());
+    assert_eq!(Err(TryRecvError::Closed), rx.try_recv());
+}
+
+#[test]
+fn send_to_dropped_receiver() {
+    let (tx, rx) = slot::channel();
+    drop(rx);
+
+    assert!(tx.is_closed());
+
+    let err = tx.send(1).unwrap_err();
+    assert_eq!(1, err.0);
+}
+
+#[test]
+fn notified_waits_for_recv() {
+    let (tx, mut rx) = slot::channel();
+
+    assert_eq!(None, tx.send(1).unwrap());
+
+    let mut notified = spawn(async { tx.notified().await });
+    assert_pending!(notified.poll());
+
+    assert_eq!(Ok(1), rx.try_recv());
+
+    assert!(notified.is_woken());
+    assert_ready!(notified.poll());
+}
+
+#[test]
+fn notified_ready_when_empty() {
+    let (tx, _rx) = slot::channel::<i32>();
+
+    let mut notified = spawn(async { tx.notified().await });
+    assert_ready!(notified.poll());
+}
+
+#[tokio::test]
+async fn ping_pong() {
+    let (tx, mut rx) = slot::channel();
+
+    tokio::spawn(async move {
+        for i in 0..5 {
+            tx.send(i).unwrap();
+            tx.notified().await;
+        }
+    });
+
+    for i in 0..5 {
+        assert_eq!(i, rx.recv().await.unwrap());
+    }
+
+    assert!(rx.recv().await.is_err());
+}
+
+#[tokio::test]
+async fn recv_is_cancel_safe() {
+    let (tx, mut rx) = slot::channel();
+
+    {
+        let mut recv = spawn(rx.recv());
+        assert_pending!(recv.poll());
+        // Dropped without completing.
+    }
+
+    tx.send(1).unwrap();
+    assert_eq!(Ok(1), rx.try_recv());
+    assert_eq!(None, tx.send(2).unwrap());
+}
@@ -0,0 +1,165 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::sync::spsc::{self, error::TryRecvError, error::TrySendError};
+
+use tokio_test::task::spawn;
+use tokio_test::{assert_pending, assert_ready};
+
+#[test]
+fn send_recv_in_order() {
+    let (mut tx, mut rx) = spsc::channel(4);
+
+    assert!(tx.try_send(1).is_ok());
+    assert!(tx.try_send(2).is_ok());
+
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Ok(2));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+}
+
+#[test]
+fn try_send_full() {
+    let (mut tx, mut rx) = spsc::channel(1);
+
+    assert!(tx.try_send(1).is_ok());
+    match tx.try_send(2) {
+        Err(TrySendError::Full(2)) => {}
+        other => panic!("expected Full, got {:?}", other),
+    }
+
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert!(tx.try_send(3).is_ok());
+}
+
+#[test]
+fn recv_waits_for_value() {
+    let (mut tx, mut rx) = spsc::channel(1);
+
+    let mut t = spawn(rx.recv());
+    assert_pending!(t.poll());
+
+    assert!(tx.try_send(7).is_ok());
+
+    assert!(t.is_woken());
+    assert_eq!(assert_ready!(t.poll()), Some(7));
+}
+
+#[test]
+fn send_waits_for_capacity() {
+    let (mut tx, mut rx) = spsc::channel(1);
+
+    assert!(tx.try_send(1).is_ok());
+
+    let mut t = spawn(tx.send(2));
+    assert_pending!(t.poll());
+
+    assert_eq!(rx.try_recv(), Ok(1));
+
+    assert!(t.is_woken());
+    assert!(assert_ready!(t.poll()).is_ok());
+    drop(t);
+
+    assert_eq!(rx.try_recv(), Ok(2));
+}
+
+#[test]
+fn recv_none_after_sender_drop() {
+    let (mut tx, mut rx) = spsc::channel(2);
+
+    assert!(tx.try_send(1).is_ok());
+    drop(tx);
+
+    // Buffered values are still delivered before the channel reports closed.
+    assert_eq!(rx.try_recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Err(TryRecvError::Closed));
+
+    let mut t = spawn(rx.recv());
+    assert_eq!(assert_ready!(t.poll()), None);
+}
+
+#[test]
+fn sender_drop_wakes_receiver() {
+    let (tx, mut rx) = spsc::channel::<i32>(1);
+
+    let mut t = spawn(rx.recv());
+    assert_pending!(t.poll());
+
+    drop(tx);
+
+    assert!(t.is_woken());
+    assert_eq!(assert_ready!(t.poll()), None);
+}
+
+#[test]
+fn send_errors_after_receiver_drop() {
+    let (mut tx, rx) = spsc::channel(1);
+
+    drop(rx);
+
+    match tx.try_send(1) {
+        Err(TrySendError::Closed(1)) => {}
+        other => panic!("expected Closed, got {:?}", other),
+    }
+    assert!(tx.is_closed());
+
+    let mut t = spawn(tx.send(2));
+    let err = assert_ready!(t.poll()).unwrap_err();
+    assert_eq!(err.0, 2);
+}
+
+#[test]
+fn receiver_drop_wakes_sender() {
+    let (mut tx, rx) = spsc::channel(1);
+
+    assert!(tx.try_send(1).is_ok());
+    let mut t = spawn(tx.send(2));
+    assert_pending!(t.poll());
+
+    drop(rx);
+
+    assert!(t.is_woken());
+    assert!(assert_ready!(t.poll()).is_err());
+}
+
+#[test]
+fn buffered_values_dropped_with_channel() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct Counted(Arc<AtomicUsize>);
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let drops = Arc::new(AtomicUsize::new(0));
+    let (mut tx, rx) = spsc::channel(4);
+
+    assert!(tx.try_send(Counted(drops.clone())).is_ok());
+    assert!(tx.try_send(Counted(drops.clone())).is_ok());
+
+    drop(rx);
+    drop(tx);
+
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn stress_send_recv() {
+    let (mut tx, mut rx) = spsc::channel(8);
+
+    let handle = tokio::spawn(async move {
+        for i in 0..1000u32 {
+            tx.send(i).await.unwrap();
+        }
+    });
+
+    for i in 0..1000u32 {
+        assert_eq!(rx.recv().await, Some(i));
+    }
+    assert_eq!(rx.recv().await, None);
+
+    handle.await.unwrap();
+}
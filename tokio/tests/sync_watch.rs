@@ -169,3 +169,246 @@ fn poll_close() {
 
     assert!(tx.send("two").is_err());
 }
+
+#[test]
+fn receiver_count() {
+    let (tx, rx1) = watch::channel("one");
+    assert_eq!(tx.receiver_count(), 1);
+
+    let rx2 = rx1.clone();
+    assert_eq!(tx.receiver_count(), 2);
+
+    drop(rx1);
+    drop(rx2);
+    assert_eq!(tx.receiver_count(), 0);
+    assert!(tx.is_closed());
+}
+
+#[test]
+fn reopened_after_subscribe() {
+    let (tx, rx) = watch::channel("one");
+    assert!(!tx.is_closed());
+
+    drop(rx);
+    assert!(tx.is_closed());
+
+    let rx = tx.subscribe();
+    assert!(!tx.is_closed());
+    assert_eq!(*rx.borrow(), "one");
+
+    drop(rx);
+    assert!(tx.is_closed());
+}
+
+#[test]
+fn subscribe_marks_current_value_seen() {
+    let (tx, _rx) = watch::channel("one");
+    tx.send("two").unwrap();
+
+    let mut rx2 = tx.subscribe();
+    assert_eq!(*rx2.borrow(), "two");
+
+    // The value present at subscription time is already seen.
+    let mut t = spawn(rx2.changed());
+    assert_pending!(t.poll());
+
+    tx.send("three").unwrap();
+    assert!(t.is_woken());
+    assert_ready_ok!(t.poll());
+    drop(t);
+    assert_eq!(*rx2.borrow(), "three");
+}
+
+#[test]
+fn history_changed_observes_every_transition() {
+    let (tx, mut rx) = watch::channel_with_history(0, 4);
+
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    tx.send(3).unwrap();
+
+    // One `changed` per buffered transition, none skipped.
+    for _ in 0..3 {
+        let mut t = spawn(rx.changed());
+        assert_ready_ok!(t.poll());
+    }
+
+    let mut t = spawn(rx.changed());
+    assert_pending!(t.poll());
+}
+
+#[test]
+fn history_recv_returns_intermediate_values() {
+    let (tx, mut rx) = watch::channel_with_history(0, 4);
+
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+
+    assert_eq!(assert_ready_ok!(spawn(rx.recv()).poll()), 1);
+    assert_eq!(assert_ready_ok!(spawn(rx.recv()).poll()), 2);
+
+    // `borrow` still returns the latest value.
+    assert_eq!(*rx.borrow(), 2);
+}
+
+#[test]
+fn history_lagged_receiver_resumes_at_oldest_retained() {
+    let (tx, mut rx) = watch::channel_with_history(0, 2);
+
+    for i in 1..=5 {
+        tx.send(i).unwrap();
+    }
+
+    // 1..=3 were evicted; only the last two transitions are retained.
+    assert_eq!(assert_ready_ok!(spawn(rx.recv()).poll()), 4);
+    assert_eq!(assert_ready_ok!(spawn(rx.recv()).poll()), 5);
+    assert_pending!(spawn(rx.recv()).poll());
+}
+
+#[test]
+fn history_subscribe_replays_buffer() {
+    let (tx, _rx) = watch::channel_with_history(0, 4);
+
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+
+    let mut rx2 = tx.subscribe();
+    assert_eq!(rx2.history(), vec![0, 1, 2]);
+
+    assert_eq!(assert_ready_ok!(spawn(rx2.recv()).poll()), 0);
+    assert_eq!(assert_ready_ok!(spawn(rx2.recv()).poll()), 1);
+    assert_eq!(assert_ready_ok!(spawn(rx2.recv()).poll()), 2);
+}
+
+#[test]
+fn history_plain_channel_unaffected() {
+    let (tx, mut rx) = watch::channel(0);
+
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+
+    // A plain channel still skips straight to the latest value.
+    assert_eq!(assert_ready_ok!(spawn(rx.recv()).poll()), 2);
+    assert_pending!(spawn(rx.changed()).poll());
+    assert_eq!(rx.history(), Vec::<i32>::new());
+}
+
+#[test]
+fn wait_for_initial_value_matches() {
+    let (_tx, mut rx) = watch::channel(10);
+
+    let mut t = spawn(rx.wait_for(|v| *v == 10));
+    let seen = assert_ready_ok!(t.poll());
+    assert_eq!(*seen, 10);
+}
+
+#[test]
+fn wait_for_resolves_on_matching_send() {
+    let (tx, mut rx) = watch::channel(0);
+
+    let mut t = spawn(rx.wait_for(|v| *v >= 2));
+    assert_pending!(t.poll());
+
+    tx.send(1).unwrap();
+    assert!(t.is_woken());
+    assert_pending!(t.poll());
+
+    tx.send(2).unwrap();
+    assert!(t.is_woken());
+    let seen = assert_ready_ok!(t.poll());
+    assert_eq!(*seen, 2);
+}
+
+#[test]
+fn wait_for_sender_dropped() {
+    let (tx, mut rx) = watch::channel(0);
+
+    let mut t = spawn(rx.wait_for(|v| *v == 1));
+    assert_pending!(t.poll());
+
+    drop(tx);
+    assert!(t.is_woken());
+    assert_ready_err!(t.poll());
+}
+
+#[test]
+fn wait_for_marks_value_seen() {
+    let (tx, mut rx) = watch::channel(0);
+
+    tx.send(1).unwrap();
+
+    {
+        let mut t = spawn(rx.wait_for(|v| *v == 1));
+        assert_ready_ok!(t.poll());
+    }
+
+    // The matching value counts as seen.
+    let mut t = spawn(rx.changed());
+    assert_pending!(t.poll());
+}
+
+#[test]
+fn send_modify_notifies() {
+    let (tx, mut rx) = watch::channel(0);
+
+    let mut t = spawn(rx.changed());
+    assert_pending!(t.poll());
+
+    tx.send_modify(|v| *v += 1);
+    assert!(t.is_woken());
+    assert_ready_ok!(t.poll());
+    drop(t);
+
+    assert_eq!(*rx.borrow(), 1);
+}
+
+#[test]
+fn send_if_modified_skips_notification() {
+    let (tx, mut rx) = watch::channel(0);
+
+    let mut t = spawn(rx.changed());
+    assert_pending!(t.poll());
+
+    // The mutation is applied, but receivers are not woken.
+    assert!(!tx.send_if_modified(|v| {
+        *v = 1;
+        false
+    }));
+    assert_pending!(t.poll());
+    drop(t);
+    assert_eq!(*rx.borrow(), 1);
+
+    let mut t = spawn(rx.changed());
+    assert_pending!(t.poll());
+    assert!(tx.send_if_modified(|v| {
+        *v = 2;
+        true
+    }));
+    assert!(t.is_woken());
+    assert_ready_ok!(t.poll());
+}
+
+#[test]
+fn send_modify_works_without_receivers() {
+    let (tx, rx) = watch::channel(0);
+    drop(rx);
+
+    tx.send_modify(|v| *v = 7);
+    assert_eq!(*tx.borrow(), 7);
+}
+
+#[test]
+fn send_modify_records_history() {
+    let (tx, mut rx) = watch::channel_with_history(0, 4);
+
+    tx.send_modify(|v| *v = 1);
+    assert!(!tx.send_if_modified(|v| {
+        *v = 2;
+        false
+    }));
+    tx.send_modify(|v| *v = 3);
+
+    // Un-notified mutations do not appear as transitions.
+    assert_eq!(assert_ready_ok!(spawn(rx.recv()).poll()), 1);
+    assert_eq!(assert_ready_ok!(spawn(rx.recv()).poll()), 3);
+}
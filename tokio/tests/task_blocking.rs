@@ -226,3 +226,52 @@ fn coop_disabled_in_block_in_place_in_block_on() {
 
     done_rx.recv().unwrap().unwrap();
 }
+
+#[tokio::test]
+async fn spawn_blocking_on_named_pool() {
+    let handle = runtime::Handle::current();
+
+    assert!(handle.create_blocking_pool("rocksdb", 2));
+    // Creating the same pool twice is a no-op.
+    assert!(!handle.create_blocking_pool("rocksdb", 8));
+
+    assert!(handle.has_blocking_pool("rocksdb"));
+    assert!(!handle.has_blocking_pool("unknown"));
+
+    let name = assert_ok!(
+        task::spawn_blocking_on("rocksdb", || {
+            thread::current().name().map(String::from)
+        })
+        .await
+    );
+
+    assert_eq!(name.as_deref(), Some("tokio-rocksdb-blocking"));
+}
+
+#[tokio::test]
+async fn spawn_blocking_on_unknown_pool_falls_back() {
+    // No pool registered under this name; the work must still run, on the
+    // default blocking pool.
+    let name = assert_ok!(
+        task::spawn_blocking_on("unregistered", || {
+            thread::current().name().map(String::from)
+        })
+        .await
+    );
+
+    assert_eq!(name.as_deref(), Some("tokio-runtime-worker"));
+}
+
+#[test]
+fn named_pool_shuts_down_with_runtime() {
+    let rt = runtime::Runtime::new().unwrap();
+
+    rt.handle().create_blocking_pool("shutdown-test", 1);
+
+    let done = rt.block_on(async {
+        assert_ok!(task::spawn_blocking_on("shutdown-test", || "done").await)
+    });
+    assert_eq!(done, "done");
+
+    rt.shutdown_timeout(Duration::from_secs(5));
+}
@@ -0,0 +1,55 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+#[tokio::test]
+async fn map_transforms_output() {
+    let out = tokio::spawn(async { 21 }).map(|n| n * 2).await.unwrap();
+    assert_eq!(out, 42);
+}
+
+#[tokio::test]
+async fn map_propagates_join_error() {
+    let err = tokio::spawn(async { panic!("boom") })
+        .map(|()| unreachable!())
+        .await
+        .unwrap_err();
+    assert!(err.is_panic());
+}
+
+#[tokio::test]
+async fn map_cancelled_task() {
+    let handle = tokio::spawn(std::future::pending::<u32>());
+    handle.abort();
+
+    let err = handle.map(|n| n + 1).await.unwrap_err();
+    assert!(err.is_cancelled());
+}
+
+#[tokio::test]
+async fn and_then_spawn_chains() {
+    let out = tokio::spawn(async { 6 })
+        .and_then_spawn(|n| async move { n * 7 })
+        .await
+        .unwrap();
+    assert_eq!(out, 42);
+}
+
+#[tokio::test]
+async fn and_then_spawn_propagates_continuation_panic() {
+    let err = tokio::spawn(async { 1 })
+        .and_then_spawn(|_| async move {
+            panic!("boom");
+        })
+        .await
+        .unwrap_err();
+    assert!(err.is_panic());
+}
+
+#[tokio::test]
+async fn and_then_spawn_propagates_first_error() {
+    let err = tokio::spawn(async { panic!("boom") })
+        .and_then_spawn(|()| async move { unreachable!() })
+        .await
+        .unwrap_err();
+    assert!(err.is_panic());
+}
@@ -0,0 +1,68 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{DualStackConfig, TcpListener, TcpStream};
+
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+#[tokio::test]
+async fn accepts_v4_and_v6() {
+    let listener = match TcpListener::bind_dual_stack(0, DualStackConfig::new()) {
+        Ok(listener) => listener,
+        // Environments without IPv6 support cannot run this test.
+        Err(_) => return,
+    };
+
+    let addrs = listener.local_addrs().unwrap();
+    assert!(!addrs.is_empty());
+    let port = addrs[0].port();
+    assert!(addrs.iter().all(|addr| addr.port() == port));
+
+    for addr in [
+        SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+        SocketAddr::from((Ipv6Addr::LOCALHOST, port)),
+    ]
+    .iter()
+    {
+        let mut client = match TcpStream::connect(*addr).await {
+            Ok(client) => client,
+            // Loopback for this family may be unavailable even if bind
+            // succeeded.
+            Err(_) => continue,
+        };
+
+        let (mut socket, _) = listener.accept().await.unwrap();
+        socket.write_all(b"pong").await.unwrap();
+        drop(socket);
+
+        let mut buf = Vec::new();
+        client.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, b"pong");
+    }
+}
+
+#[tokio::test]
+async fn two_socket_mode() {
+    let listener =
+        match TcpListener::bind_dual_stack(0, DualStackConfig::new().single_socket(false)) {
+            Ok(listener) => listener,
+            Err(_) => return,
+        };
+
+    // Two sockets bound, sharing a port number.
+    let addrs = listener.local_addrs().unwrap();
+    assert_eq!(addrs.len(), 2);
+    assert!(addrs[0].is_ipv4());
+    assert!(addrs[1].is_ipv6());
+    assert_eq!(addrs[0].port(), addrs[1].port());
+
+    let client = TcpStream::connect((Ipv4Addr::LOCALHOST, addrs[0].port()))
+        .await
+        .unwrap();
+
+    let (_socket, peer) = listener.accept().await.unwrap();
+    assert_eq!(peer, client.local_addr().unwrap());
+    // v4 peers keep un-mapped addresses in two-socket mode.
+    assert!(peer.is_ipv4());
+}
@@ -26,3 +26,94 @@ async fn shutdown() {
     let n = assert_ok!(io::copy(&mut rd, &mut wr).await);
     assert_eq!(n, 0);
 }
+
+#[tokio::test]
+async fn shutdown_write_half_close() {
+    let srv = assert_ok!(TcpListener::bind("127.0.0.1:0").await);
+    let addr = assert_ok!(srv.local_addr());
+
+    let t = tokio::spawn(async move {
+        let (mut stream, _) = assert_ok!(srv.accept().await);
+
+        // The client half-closed; reads see EOF but writes still work.
+        let mut buf = Vec::new();
+        assert_ok!(stream.read_to_end(&mut buf).await);
+        assert_eq!(buf, b"request");
+
+        assert_ok!(stream.write_all(b"response").await);
+    });
+
+    let mut stream = assert_ok!(TcpStream::connect(&addr).await);
+    assert_ok!(stream.write_all(b"request").await);
+
+    assert!(!stream.is_write_shutdown());
+    assert_ok!(stream.shutdown_write());
+    assert!(stream.is_write_shutdown());
+
+    // Shutting down again is a no-op.
+    assert_ok!(stream.shutdown_write());
+
+    // Writes now fail eagerly with a clear error.
+    let err = stream.write_all(b"more").await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+
+    // The read half is unaffected.
+    let mut buf = Vec::new();
+    assert_ok!(stream.read_to_end(&mut buf).await);
+    assert_eq!(buf, b"response");
+
+    assert_ok!(t.await);
+}
+
+#[tokio::test]
+async fn shutdown_read_errors_reads() {
+    let srv = assert_ok!(TcpListener::bind("127.0.0.1:0").await);
+    let addr = assert_ok!(srv.local_addr());
+
+    let t = tokio::spawn(async move {
+        let (mut stream, _) = assert_ok!(srv.accept().await);
+        let mut buf = [0; 16];
+        let n = assert_ok!(stream.read(&mut buf).await);
+        assert_eq!(&buf[..n], b"ping");
+    });
+
+    let mut stream = assert_ok!(TcpStream::connect(&addr).await);
+
+    assert!(!stream.is_read_shutdown());
+    assert_ok!(stream.shutdown_read());
+    assert!(stream.is_read_shutdown());
+
+    // Reads fail with an error instead of reporting end-of-file.
+    let mut buf = [0; 16];
+    let err = stream.read(&mut buf).await.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+
+    let err = stream.try_read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+
+    // The write half is unaffected.
+    assert_ok!(stream.write_all(b"ping").await);
+
+    assert_ok!(t.await);
+}
+
+#[tokio::test]
+async fn async_write_shutdown_is_tracked() {
+    let srv = assert_ok!(TcpListener::bind("127.0.0.1:0").await);
+    let addr = assert_ok!(srv.local_addr());
+
+    let t = tokio::spawn(async move {
+        let (mut stream, _) = assert_ok!(srv.accept().await);
+        let mut buf = Vec::new();
+        assert_ok!(stream.read_to_end(&mut buf).await);
+    });
+
+    let mut stream = assert_ok!(TcpStream::connect(&addr).await);
+    assert_ok!(stream.shutdown().await);
+
+    assert!(stream.is_write_shutdown());
+    let err = stream.try_write(b"data").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+
+    assert_ok!(t.await);
+}
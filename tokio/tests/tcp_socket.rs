@@ -58,3 +58,58 @@ async fn bind_before_connect() {
     // Accept
     let _ = assert_ok!(srv.accept().await);
 }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn attach_reuseport_cbpf() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{SockFilter, TcpStream};
+
+    let any_addr = assert_ok!("127.0.0.1:0".parse());
+    let srv = assert_ok!(TcpSocket::new_v4());
+    assert_ok!(srv.set_reuseport(true));
+
+    // Steer every connection to socket 0 of the group.
+    assert_ok!(srv.attach_reuseport_cbpf(&[
+        // A = 0
+        SockFilter::stmt(0x00, 0),
+        // return A
+        SockFilter::stmt(0x16, 0),
+    ]));
+
+    assert_ok!(srv.bind(any_addr));
+    let srv = assert_ok!(srv.listen(128));
+    let addr = srv.local_addr().unwrap();
+
+    let t = tokio::spawn(async move {
+        let (mut stream, _) = assert_ok!(srv.accept().await);
+        let mut buf = [0; 4];
+        assert_ok!(stream.read_exact(&mut buf).await);
+        assert_eq!(&buf, b"ping");
+    });
+
+    let mut cli = assert_ok!(TcpStream::connect(addr).await);
+    assert_ok!(cli.write_all(b"ping").await);
+
+    assert_ok!(t.await);
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn attach_reuseport_cbpf_rejects_empty_program() {
+    let srv = assert_ok!(TcpSocket::new_v4());
+    assert_ok!(srv.set_reuseport(true));
+
+    let err = srv.attach_reuseport_cbpf(&[]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn attach_reuseport_ebpf_invalid_fd() {
+    let srv = assert_ok!(TcpSocket::new_v4());
+    assert_ok!(srv.set_reuseport(true));
+
+    // No loaded eBPF program; the kernel rejects the bogus descriptor.
+    assert!(srv.attach_reuseport_ebpf(-1).is_err());
+}
@@ -36,7 +36,7 @@ async fn split() -> Result<()> {
     assert_eq!(peek_len1, read_len);
     assert_eq!(&read_buf[..read_len], MSG);
 
-    write_half.write(MSG).await?;
+    let _ = write_half.write(MSG).await?;
     handle.join().unwrap();
     Ok(())
 }
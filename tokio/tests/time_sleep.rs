@@ -370,3 +370,54 @@ async fn drop_from_wake() {
         }
     }
 }
+
+#[tokio::test]
+async fn sleep_precise_completes() {
+    let before = Instant::now();
+    let dur = Duration::from_micros(1500);
+
+    time::sleep_precise(dur).await;
+
+    assert!(before.elapsed() >= dur);
+}
+
+#[tokio::test]
+async fn sleep_precise_custom_precision() {
+    let before = Instant::now();
+    let dur = Duration::from_millis(3);
+
+    time::sleep_precise(dur)
+        .precision(Duration::from_micros(500))
+        .await;
+
+    assert!(before.elapsed() >= dur);
+}
+
+#[tokio::test]
+async fn sleep_precise_spin() {
+    let before = Instant::now();
+    let dur = Duration::from_micros(500);
+
+    time::sleep_precise(dur).spin(true).await;
+
+    assert!(before.elapsed() >= dur);
+}
+
+#[tokio::test(start_paused = true)]
+async fn sleep_precise_paused_clock() {
+    let before = Instant::now();
+    let dur = Duration::from_micros(1500);
+
+    // Completes through the timer wheel via auto-advance instead of
+    // re-checking a clock that never moves.
+    time::sleep_precise(dur).await;
+
+    assert!(before.elapsed() >= dur);
+}
+
+#[tokio::test]
+async fn sleep_precise_deadline() {
+    let sleep = time::sleep_precise(Duration::from_millis(5));
+    assert!(sleep.deadline() > Instant::now());
+    sleep.await;
+}
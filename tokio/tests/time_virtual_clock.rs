@@ -0,0 +1,110 @@
+#![warn(rust_2018_idioms)]
+#![cfg(feature = "full")]
+
+use tokio::runtime::Handle;
+use tokio::time::{Duration, Instant};
+use tokio_test::task;
+use tokio_test::{assert_pending, assert_ready_ok};
+
+#[tokio::test(start_paused = true)]
+async fn deadline_fires_at_instant() {
+    let clock = Handle::current().timer();
+    let when = clock.now() + Duration::from_millis(50);
+
+    let mut deadline = task::spawn(Box::pin(clock.deadline(when)));
+    assert_pending!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+    assert!(!deadline.is_elapsed());
+
+    tokio::time::sleep_until(when).await;
+
+    assert!(deadline.is_woken());
+    assert_ready_ok!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+    assert!(deadline.is_elapsed());
+}
+
+#[tokio::test(start_paused = true)]
+async fn deadline_in_the_past_is_ready() {
+    let clock = Handle::current().timer();
+
+    let mut deadline = task::spawn(Box::pin(clock.deadline(Instant::now())));
+    assert_ready_ok!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn reset_moves_the_wakeup() {
+    let clock = Handle::current().timer();
+    let start = clock.now();
+
+    let mut deadline = task::spawn(Box::pin(clock.deadline(start + Duration::from_millis(10))));
+    assert_pending!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+
+    // Push the deadline out before it fires.
+    deadline.enter(|_, d| d.get_mut().as_mut().reset(start + Duration::from_millis(100)));
+    assert_eq!(deadline.deadline(), start + Duration::from_millis(100));
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_pending!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(deadline.is_woken());
+    assert_ready_ok!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn reset_after_firing_rearms() {
+    let clock = Handle::current().timer();
+
+    let mut deadline = task::spawn(Box::pin(clock.deadline(Instant::now())));
+    assert_ready_ok!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+
+    let again = clock.now() + Duration::from_millis(20);
+    deadline.enter(|_, d| d.get_mut().as_mut().reset(again));
+    assert_pending!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+
+    tokio::time::sleep_until(again).await;
+    assert_ready_ok!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+}
+
+#[tokio::test(start_paused = true)]
+async fn cancel_deregisters() {
+    let clock = Handle::current().timer();
+    let when = clock.now() + Duration::from_millis(10);
+
+    let mut deadline = task::spawn(Box::pin(clock.deadline(when)));
+    assert_pending!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+
+    deadline.enter(|_, d| d.get_mut().as_mut().cancel());
+
+    tokio::time::sleep_until(when + Duration::from_millis(10)).await;
+    assert!(!deadline.is_woken());
+}
+
+#[test]
+fn clock_usable_off_runtime() {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .build()
+        .unwrap();
+
+    // The clock is created on the runtime but the deadline is registered and
+    // polled from a plain thread.
+    let clock = rt.handle().timer();
+
+    let th = std::thread::spawn(move || {
+        let mut deadline =
+            task::spawn(Box::pin(clock.deadline(clock.now() + Duration::from_millis(20))));
+        assert_pending!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+
+        while !deadline.is_woken() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        assert_ready_ok!(deadline.enter(|cx, d| d.get_mut().as_mut().poll_elapsed(cx)));
+    });
+
+    // Keep the driver running until the thread has observed the wakeup.
+    rt.block_on(async {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    });
+
+    th.join().unwrap();
+}
@@ -440,3 +440,128 @@ async fn try_recv_buf_from() {
         }
     }
 }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn recv_msg_ecn_and_pktinfo() -> std::io::Result<()> {
+    use tokio::net::{EcnCodepoint, SendMsg};
+
+    let sender = UdpSocket::bind("127.0.0.1:0").await?;
+    let receiver = UdpSocket::bind("127.0.0.1:0").await?;
+
+    receiver.set_recv_ecn(true)?;
+    receiver.set_recv_pktinfo(true)?;
+
+    let target = receiver.local_addr()?;
+    let sent = sender
+        .send_msg(MSG, &SendMsg::new().target(target).ecn(EcnCodepoint::Ect0))
+        .await?;
+    assert_eq!(sent, MSG_LEN);
+
+    let mut buf = [0u8; 32];
+    let msg = receiver.recv_msg(&mut buf).await?;
+
+    assert_eq!(msg.len, MSG_LEN);
+    assert!(!msg.truncated);
+    assert_eq!(&buf[..msg.len], MSG);
+    assert_eq!(msg.addr, sender.local_addr()?);
+    assert_eq!(msg.ecn, Some(EcnCodepoint::Ect0));
+    assert_eq!(msg.dst_ip, Some("127.0.0.1".parse().unwrap()));
+
+    Ok(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn recv_msg_timestamp() -> std::io::Result<()> {
+    use std::time::SystemTime;
+
+    let sender = UdpSocket::bind("127.0.0.1:0").await?;
+    let receiver = UdpSocket::bind("127.0.0.1:0").await?;
+
+    receiver.set_recv_timestamp(true)?;
+
+    let before = SystemTime::now();
+    sender.send_to(MSG, receiver.local_addr()?).await?;
+
+    let mut buf = [0u8; 32];
+    let msg = receiver.recv_msg(&mut buf).await?;
+
+    let timestamp = msg.timestamp.expect("timestamp not reported");
+    assert!(timestamp >= before);
+    assert!(timestamp <= SystemTime::now());
+
+    Ok(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn recv_msg_truncated() -> std::io::Result<()> {
+    let sender = UdpSocket::bind("127.0.0.1:0").await?;
+    let receiver = UdpSocket::bind("127.0.0.1:0").await?;
+
+    sender.send_to(b"hello world", receiver.local_addr()?).await?;
+
+    let mut buf = [0u8; 4];
+    let msg = receiver.recv_msg(&mut buf).await?;
+
+    assert_eq!(msg.len, 4);
+    assert!(msg.truncated);
+    assert_eq!(&buf[..], b"hell");
+
+    Ok(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn send_msg_connected() -> std::io::Result<()> {
+    use tokio::net::SendMsg;
+
+    let sender = UdpSocket::bind("127.0.0.1:0").await?;
+    let receiver = UdpSocket::bind("127.0.0.1:0").await?;
+
+    sender.connect(receiver.local_addr()?).await?;
+    sender.send_msg(MSG, &SendMsg::new()).await?;
+
+    let mut buf = [0u8; 32];
+    let msg = receiver.recv_msg(&mut buf).await?;
+
+    assert_eq!(msg.len, MSG_LEN);
+    assert_eq!(msg.addr, sender.local_addr()?);
+
+    Ok(())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[tokio::test]
+async fn attach_reuseport_cbpf() -> std::io::Result<()> {
+    use tokio::net::SockFilter;
+
+    let socket = socket2::Socket::new(
+        socket2::Domain::IPV4,
+        socket2::Type::DGRAM,
+        Some(socket2::Protocol::UDP),
+    )?;
+    socket.set_reuse_port(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&"127.0.0.1:0".parse::<std::net::SocketAddr>().unwrap().into())?;
+
+    let receiver = UdpSocket::from_socket2(socket)?;
+
+    // Steer every datagram to socket 0 of the group.
+    receiver.attach_reuseport_cbpf(&[
+        // A = 0
+        SockFilter::stmt(0x00, 0),
+        // return A
+        SockFilter::stmt(0x16, 0),
+    ])?;
+
+    let sender = UdpSocket::bind("127.0.0.1:0").await?;
+    sender.send_to(MSG, receiver.local_addr()?).await?;
+
+    let mut buf = [0u8; 32];
+    let (n, _) = receiver.recv_from(&mut buf).await?;
+    assert_eq!(&buf[..n], MSG);
+
+    Ok(())
+}